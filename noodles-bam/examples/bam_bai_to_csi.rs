@@ -0,0 +1,44 @@
+//! Converts a BAM index (BAI) to a coordinate-sorted index (CSI).
+//!
+//! The bins are rebuilt from the chunks of the BAI with the given minimum shift and depth, which
+//! default to 14 and 6, respectively. The associated BAM file is not read.
+//!
+//! This writes the output to stdout.
+
+use std::{env, io};
+
+use noodles_bam::bai;
+use noodles_csi as csi;
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+
+    let min_shift = args
+        .next()
+        .map(|s| {
+            s.parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+        })
+        .transpose()?
+        .unwrap_or(14);
+
+    let depth = args
+        .next()
+        .map(|s| {
+            s.parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+        })
+        .transpose()?
+        .unwrap_or(6);
+
+    let index = bai::fs::read(src)?;
+    let index = index.rebin(min_shift, depth)?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = csi::io::Writer::new(stdout);
+    writer.write_index(&index)?;
+
+    Ok(())
+}
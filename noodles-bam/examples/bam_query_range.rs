@@ -0,0 +1,43 @@
+//! Queries a BAM file with a given region, reading it through a range reader.
+//!
+//! This fetches the BAM in aligned, cached blocks using a `RangeSource`, as would be done when
+//! reading from object storage using range requests. Here, the source is a local file.
+//!
+//! The input BAM must have an index in the same directory.
+//!
+//! The result matches the output of `samtools view <src> <region>`.
+
+use std::{env, fs::File, io};
+
+use noodles_bam::{self as bam, bai};
+use noodles_bgzf as bgzf;
+use noodles_sam::{self as sam, alignment::io::Write};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = env::args();
+
+    let src = args.nth(1).expect("missing src");
+    let region = args.next().expect("missing region").parse()?;
+
+    let index = bai::fs::read(format!("{src}.bai"))?;
+    let source = File::open(src).map(bgzf::io::RangeReader::new)?;
+
+    let mut reader = bam::io::indexed_reader::Builder::default()
+        .set_index(index)
+        .build_from_reader(source)?;
+
+    let header = reader.read_header()?;
+    let query = reader.query(&header, &region)?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = sam::io::Writer::new(stdout);
+
+    for result in query.records() {
+        let record = result?;
+        writer.write_alignment_record(&header, &record)?;
+    }
+
+    writer.finish(&header)?;
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+//! Async indexed BAM reader.
+
+mod builder;
+
+use futures::Stream;
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_csi::BinningIndex;
+use noodles_sam::{self as sam, alignment::RecordBuf};
+use tokio::io::{self, AsyncRead, AsyncSeek};
+
+pub use self::builder::Builder;
+use super::{Reader, reader::Query};
+use crate::Record;
+
+/// An async indexed BAM reader.
+///
+/// This owns its associated index, which is used to query records that intersect a region.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use futures::TryStreamExt;
+/// use noodles_bam as bam;
+///
+/// let mut reader = bam::r#async::io::indexed_reader::Builder::default()
+///     .build_from_path("sample.bam")
+///     .await?;
+///
+/// let header = reader.read_header().await?;
+///
+/// let region = "sq0:8-13".parse()?;
+/// let mut query = reader.query(&header, &region)?.records();
+///
+/// while let Some(record) = query.try_next().await? {
+///     // ...
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct IndexedReader<R> {
+    inner: Reader<R>,
+    index: Box<dyn BinningIndex + Send + Sync>,
+}
+
+impl<R> IndexedReader<R> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner.get_mut()
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    /// Returns the associated index.
+    pub fn index(&self) -> &dyn BinningIndex {
+        &self.index
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Reads the SAM header.
+    pub async fn read_header(&mut self) -> io::Result<sam::Header> {
+        self.inner.read_header().await
+    }
+
+    /// Reads a record into an alignment record buffer.
+    pub async fn read_record_buf(
+        &mut self,
+        header: &sam::Header,
+        record: &mut RecordBuf,
+    ) -> io::Result<usize> {
+        self.inner.read_record_buf(header, record).await
+    }
+
+    /// Reads a record.
+    pub async fn read_record(&mut self, record: &mut Record) -> io::Result<usize> {
+        self.inner.read_record(record).await
+    }
+
+    /// Returns a stream over alignment record buffers.
+    pub fn record_bufs<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h sam::Header,
+    ) -> impl Stream<Item = io::Result<RecordBuf>> + use<'r, R> {
+        self.inner.record_bufs(header)
+    }
+
+    /// Returns a stream over records.
+    pub fn records(&mut self) -> impl Stream<Item = io::Result<Record>> {
+        self.inner.records()
+    }
+}
+
+impl<R> IndexedReader<bgzf::r#async::io::Reader<R>>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Creates an async indexed BAM reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam::{self as bam, bai};
+    /// let index = bai::Index::default();
+    /// let reader = bam::r#async::io::IndexedReader::new(tokio::io::empty(), index);
+    /// ```
+    pub fn new<I>(inner: R, index: I) -> Self
+    where
+        I: BinningIndex + Send + Sync + 'static,
+    {
+        Self {
+            inner: Reader::new(inner),
+            index: Box::new(index),
+        }
+    }
+}
+
+impl<R> IndexedReader<bgzf::r#async::io::Reader<R>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Returns a stream over records that intersect the given region.
+    pub fn query<'r>(
+        &'r mut self,
+        header: &sam::Header,
+        region: &Region,
+    ) -> io::Result<Query<'r, R>> {
+        self.inner.query(header, &self.index, region)
+    }
+
+    /// Returns a stream of unmapped records after querying for the unmapped region.
+    pub async fn query_unmapped(
+        &mut self,
+    ) -> io::Result<impl Stream<Item = io::Result<Record>> + use<'_, R>> {
+        self.inner.query_unmapped(&self.index).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+    use noodles_core::Position;
+    use noodles_sam::{
+        alignment::{
+            io::Write as _,
+            record::{
+                Flags,
+                cigar::{Op, op::Kind},
+            },
+        },
+        header::record::value::{Map, map::ReferenceSequence},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        use std::num::NonZero;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(const { NonZero::new(34).unwrap() }),
+            )
+            .build();
+
+        let mut writer = crate::io::IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for position in [2, 8, 21] {
+            let record = RecordBuf::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(position)?)
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                .build();
+
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        let index = writer.try_finish()?;
+        let data = writer.into_inner().into_inner();
+
+        let mut reader = IndexedReader::new(std::io::Cursor::new(data), index);
+        let header = reader.read_header().await?;
+
+        let region = "sq0:10-20".parse()?;
+        let records: Vec<_> = reader
+            .query(&header, &region)?
+            .records()
+            .try_collect()
+            .await?;
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].alignment_start().transpose()?, Position::new(8));
+
+        Ok(())
+    }
+}
@@ -108,8 +108,100 @@ where
         self.inner.query(header, &self.index, region)
     }
 
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// Each record is returned along with the indices of the regions it intersects.
+    pub fn query_regions<'r>(
+        &'r mut self,
+        header: &sam::Header,
+        regions: &[Region],
+    ) -> io::Result<impl Iterator<Item = io::Result<(Record, Vec<usize>)>> + use<'r, R>> {
+        self.inner.query_regions(header, &self.index, regions)
+    }
+
     /// Returns an iterator of unmapped records after querying for the unmapped region.
     pub fn query_unmapped(&mut self) -> io::Result<impl Iterator<Item = io::Result<Record>>> {
         self.inner.query_unmapped(&self.index)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use noodles_core::Position;
+    use noodles_sam::{
+        alignment::{
+            io::Write as _,
+            record::{
+                Flags,
+                cigar::{Op, op::Kind},
+            },
+        },
+        header::record::value::{Map, map::ReferenceSequence},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_query_regions() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(const { NonZero::new(34).unwrap() }),
+            )
+            .add_reference_sequence(
+                "sq1",
+                Map::<ReferenceSequence>::new(const { NonZero::new(13).unwrap() }),
+            )
+            .build();
+
+        let mut writer = crate::io::IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for (reference_sequence_id, position) in [(0, 2), (0, 8), (0, 21), (1, 5)] {
+            let record = RecordBuf::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(reference_sequence_id)
+                .set_alignment_start(Position::try_from(position)?)
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                .build();
+
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        let index = writer.try_finish()?;
+        let data = writer.into_inner().into_inner();
+
+        let mut reader = IndexedReader::new(io::Cursor::new(data), index);
+        let header = reader.read_header()?;
+
+        let regions = [
+            "sq0:1-9".parse()?,
+            "sq0:9-10".parse()?,
+            "sq1".parse()?,
+            "sq0:30-34".parse()?,
+        ];
+
+        let actual: Vec<_> = reader
+            .query_regions(&header, &regions)?
+            .map(|result| {
+                result.and_then(|(record, region_indices)| {
+                    let reference_sequence_id = record.reference_sequence_id().transpose()?;
+                    let alignment_start = record.alignment_start().transpose()?;
+                    Ok((reference_sequence_id, alignment_start, region_indices))
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (Some(0), Position::new(2), vec![0]),
+            (Some(0), Position::new(8), vec![0, 1]),
+            (Some(1), Position::new(5), vec![2]),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,379 @@
+//! Indexed BAM writer.
+
+use std::{
+    io::{self, Write},
+    mem,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+use noodles_csi::binning_index::{
+    Indexer,
+    index::reference_sequence::{bin::Chunk, index::LinearIndex},
+};
+use noodles_sam::{self as sam, alignment::io::Write as _};
+
+use super::Writer;
+use crate::{Record, bai};
+
+/// An indexed BAM writer.
+///
+/// This builds a BAM index (BAI) from the records as they are written, i.e., without a second
+/// pass over the output. Records must be coordinate-sorted, i.e., records without a reference
+/// sequence are written last.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bam as bam;
+/// use noodles_sam as sam;
+///
+/// let mut writer = bam::io::IndexedWriter::new(Vec::new());
+///
+/// let header = sam::Header::default();
+/// writer.write_header(&header)?;
+///
+/// let record = bam::Record::default();
+/// writer.write_record(&header, &record)?;
+///
+/// let index = writer.try_finish()?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub struct IndexedWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<bgzf::io::Writer<W>>,
+    indexer: Indexer<LinearIndex>,
+    reference_sequence_count: usize,
+    previous_alignment_context: Option<(usize, Position)>,
+    has_unplaced_records: bool,
+}
+
+impl<W> IndexedWriter<W>
+where
+    W: Write,
+{
+    /// Creates an indexed BAM writer with a default compression level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::io::IndexedWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self::from(Writer::new(inner))
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::io::IndexedWriter::new(Vec::new());
+    /// assert!(writer.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &bgzf::io::Writer<W> {
+        self.inner.get_ref()
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// let writer = bam::io::IndexedWriter::new(Vec::new());
+    /// assert!(writer.into_inner().get_ref().is_empty());
+    /// ```
+    pub fn into_inner(self) -> bgzf::io::Writer<W> {
+        self.inner.into_inner()
+    }
+
+    /// Writes a SAM header.
+    ///
+    /// The number of reference sequences in the header is used when building the index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = bam::io::IndexedWriter::new(Vec::new());
+    ///
+    /// let header = sam::Header::default();
+    /// writer.write_header(&header)?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn write_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.reference_sequence_count = header.reference_sequences().len();
+        self.inner.write_header(header)
+    }
+
+    /// Writes a BAM record and adds it to the index.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the record is not coordinate-sorted relative to the previously
+    /// written record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = bam::io::IndexedWriter::new(Vec::new());
+    ///
+    /// let header = sam::Header::default();
+    /// writer.write_header(&header)?;
+    ///
+    /// let record = bam::Record::default();
+    /// writer.write_record(&header, &record)?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn write_record(&mut self, header: &sam::Header, record: &Record) -> io::Result<()> {
+        self.write_alignment_record(header, record)
+    }
+
+    /// Attempts to finish the output stream and returns the built index.
+    ///
+    /// The index can be written using [`bai::fs::write`] or a [`bai::io::Writer`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bam as bam;
+    /// use noodles_csi::BinningIndex;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut writer = bam::io::IndexedWriter::new(Vec::new());
+    ///
+    /// let header = sam::Header::default();
+    /// writer.write_header(&header)?;
+    ///
+    /// let record = bam::Record::default();
+    /// writer.write_record(&header, &record)?;
+    ///
+    /// let index = writer.try_finish()?;
+    /// assert_eq!(index.unplaced_unmapped_record_count(), Some(1));
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn try_finish(&mut self) -> io::Result<bai::Index> {
+        self.inner.try_finish()?;
+        let indexer = mem::take(&mut self.indexer);
+        Ok(indexer.build(self.reference_sequence_count))
+    }
+}
+
+impl<W> From<Writer<bgzf::io::Writer<W>>> for IndexedWriter<W>
+where
+    W: Write,
+{
+    fn from(inner: Writer<bgzf::io::Writer<W>>) -> Self {
+        Self {
+            inner,
+            indexer: Indexer::default(),
+            reference_sequence_count: 0,
+            previous_alignment_context: None,
+            has_unplaced_records: false,
+        }
+    }
+}
+
+impl<W> sam::alignment::io::Write for IndexedWriter<W>
+where
+    W: Write,
+{
+    fn write_alignment_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.write_header(header)
+    }
+
+    fn write_alignment_record(
+        &mut self,
+        header: &sam::Header,
+        record: &dyn sam::alignment::Record,
+    ) -> io::Result<()> {
+        let reference_sequence_id = record.reference_sequence_id(header).transpose()?;
+
+        if reference_sequence_id.is_none() {
+            self.has_unplaced_records = true;
+        } else if self.has_unplaced_records {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "record is not coordinate-sorted",
+            ));
+        }
+
+        let alignment_context = match (
+            reference_sequence_id,
+            record.alignment_start().transpose()?,
+            record.alignment_end().transpose()?,
+        ) {
+            (Some(id), Some(start), Some(end)) => {
+                let is_mapped = !record.flags()?.is_unmapped();
+                Some((id, start, end, is_mapped))
+            }
+            _ => None,
+        };
+
+        if let Some((id, start, _, _)) = alignment_context {
+            if self
+                .previous_alignment_context
+                .is_some_and(|previous| (id, start) < previous)
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "record is not coordinate-sorted",
+                ));
+            }
+
+            self.previous_alignment_context = Some((id, start));
+        }
+
+        let start_position = self.inner.get_ref().virtual_position();
+        self.inner.write_alignment_record(header, record)?;
+        let end_position = self.inner.get_ref().virtual_position();
+
+        let chunk = Chunk::new(start_position, end_position);
+        self.indexer.add_record(alignment_context, chunk)
+    }
+
+    fn finish(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.inner.finish(header)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use bstr::BString;
+    use noodles_csi::{BinningIndex, binning_index::ReferenceSequence as _};
+    use noodles_sam::{
+        alignment::{RecordBuf, record::Flags},
+        header::record::value::{Map, map::ReferenceSequence},
+    };
+
+    use super::*;
+
+    fn build_header() -> sam::Header {
+        sam::Header::builder()
+            .set_reference_sequences(
+                [
+                    (
+                        BString::from("sq0"),
+                        Map::<ReferenceSequence>::new(const { NonZero::new(8).unwrap() }),
+                    ),
+                    (
+                        BString::from("sq1"),
+                        Map::<ReferenceSequence>::new(const { NonZero::new(13).unwrap() }),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_try_finish() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+
+        let mut writer = IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::default())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::MIN)
+            .build();
+
+        writer.write_alignment_record(&header, &record)?;
+        writer.write_record(&header, &Record::default())?;
+
+        let index = writer.try_finish()?;
+
+        assert_eq!(index.reference_sequences().len(), 2);
+        assert_eq!(index.unplaced_unmapped_record_count(), Some(1));
+
+        let sq0 = &index.reference_sequences()[0];
+        assert!(sq0.bins().get(&4681).is_some());
+        assert_eq!(
+            sq0.metadata().map(|metadata| (
+                metadata.mapped_record_count(),
+                metadata.unmapped_record_count()
+            )),
+            Some((1, 0))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_alignment_record_with_unsorted_records() -> io::Result<()> {
+        let header = build_header();
+
+        let mut writer = IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::default())
+            .set_reference_sequence_id(1)
+            .set_alignment_start(Position::MIN)
+            .build();
+
+        writer.write_alignment_record(&header, &record)?;
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::default())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::MIN)
+            .build();
+
+        assert!(matches!(
+            writer.write_alignment_record(&header, &record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_alignment_record_with_placed_record_after_unplaced_record() -> io::Result<()> {
+        let header = build_header();
+
+        let mut writer = IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        writer.write_alignment_record(&header, &RecordBuf::default())?;
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::default())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::MIN)
+            .build();
+
+        assert!(matches!(
+            writer.write_alignment_record(&header, &record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::UNMAPPED)
+            .set_reference_sequence_id(0)
+            .build();
+
+        assert!(matches!(
+            writer.write_alignment_record(&header, &record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        writer.write_alignment_record(&header, &RecordBuf::default())?;
+
+        Ok(())
+    }
+}
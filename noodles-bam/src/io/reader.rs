@@ -12,12 +12,13 @@ mod records;
 use std::{
     ffi::CStr,
     io::{self, Read},
+    iter,
 };
 
 use bstr::BString;
 use noodles_bgzf as bgzf;
 use noodles_core::Region;
-use noodles_csi::BinningIndex;
+use noodles_csi::{self as csi, BinningIndex, binning_index::RegionSet};
 use noodles_sam::{self as sam, alignment::RecordBuf, header::ReferenceSequences};
 
 pub use self::{builder::Builder, query::Query, record_bufs::RecordBufs, records::Records};
@@ -393,6 +394,67 @@ where
         ))
     }
 
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// The chunks of all regions are merged, so each block is read at most once, and each record
+    /// is returned once, along with the indices of the regions it intersects, in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bam::{self as bam, bai};
+    ///
+    /// let mut reader = File::open("sample.bam").map(bam::io::Reader::new)?;
+    /// let header = reader.read_header()?;
+    ///
+    /// let index = bai::fs::read("sample.bam.bai")?;
+    /// let regions = ["sq0:8-13".parse()?, "sq1:21-34".parse()?];
+    ///
+    /// for result in reader.query_regions(&header, &index, &regions)? {
+    ///     let (record, region_indices) = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_regions<'r, I>(
+        &'r mut self,
+        header: &sam::Header,
+        index: &I,
+        regions: &[Region],
+    ) -> io::Result<impl Iterator<Item = io::Result<(Record, Vec<usize>)>> + use<'r, I, R>>
+    where
+        I: BinningIndex,
+    {
+        let region_set = regions
+            .iter()
+            .map(|region| {
+                resolve_region(header.reference_sequences(), region)
+                    .map(|id| (id, region.interval()))
+            })
+            .collect::<io::Result<Vec<_>>>()
+            .map(RegionSet::new)?;
+
+        let chunks = region_set.query(index)?;
+        let mut reader = Reader::from(csi::io::Query::new(self.get_mut(), chunks));
+
+        Ok(iter::from_fn(move || {
+            loop {
+                let mut record = Record::default();
+
+                match reader.read_record(&mut record) {
+                    Ok(0) => return None,
+                    Ok(_) => match intersecting_regions(&region_set, &record) {
+                        Ok(region_indices) if region_indices.is_empty() => {}
+                        Ok(region_indices) => return Some(Ok((record, region_indices))),
+                        Err(e) => return Some(Err(e)),
+                    },
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+        }))
+    }
+
     /// Returns an iterator of unmapped records after querying for the unmapped region.
     ///
     /// # Examples
@@ -470,6 +532,21 @@ pub(crate) fn bytes_with_nul_to_bstring(buf: &[u8]) -> io::Result<BString> {
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
 }
 
+fn intersecting_regions(region_set: &RegionSet, record: &Record) -> io::Result<Vec<usize>> {
+    use sam::alignment::Record as _;
+
+    let (Some(reference_sequence_id), Some(start)) = (
+        record.reference_sequence_id().transpose()?,
+        record.alignment_start().transpose()?,
+    ) else {
+        return Ok(Vec::new());
+    };
+
+    let end = record.alignment_end().transpose()?.unwrap_or(start);
+
+    Ok(region_set.intersecting(reference_sequence_id, (start..=end).into()))
+}
+
 pub(crate) fn resolve_region(
     reference_sequences: &ReferenceSequences,
     region: &Region,
@@ -0,0 +1,104 @@
+//! BAM header validation.
+
+use std::io::{self, Read};
+
+use noodles_sam::{self as sam, validation::Diagnostic};
+
+use crate::io::reader::header::{magic_number, reference_sequences_eq};
+
+/// Reads and validates a BAM header.
+///
+/// This is the BAM counterpart of [`sam::validation::read_header`]. Duplicate reference sequence
+/// names and read group IDs in the raw SAM header are reported as diagnostics and left out of the
+/// returned header, i.e., the first definition is kept. Other malformed header lines and a
+/// mismatch between the SAM header and binary reference sequence dictionaries are still an error.
+///
+/// The position of the stream is expected to be at the start. Records can be read afterward.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::fs::File;
+/// use noodles_bam::{self as bam, validation};
+///
+/// let mut reader = File::open("sample.bam").map(bam::io::Reader::new)?;
+/// let (header, diagnostics) = validation::read_header(&mut reader)?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn read_header<R>(
+    reader: &mut crate::io::Reader<R>,
+) -> io::Result<(sam::Header, Vec<Diagnostic>)>
+where
+    R: Read,
+{
+    let mut header_reader = reader.header_reader();
+
+    header_reader
+        .read_magic_number()
+        .and_then(magic_number::validate)?;
+
+    let mut raw_sam_header_reader = header_reader.raw_sam_header_reader()?;
+    let mut src = Vec::new();
+    raw_sam_header_reader.read_to_end(&mut src)?;
+    raw_sam_header_reader.discard_to_end()?;
+
+    let (mut header, diagnostics) = sam::validation::parse_header(&src)?;
+
+    let reference_sequences = header_reader.read_reference_sequences()?;
+
+    if header.reference_sequences().is_empty() {
+        *header.reference_sequences_mut() = reference_sequences;
+    } else if !reference_sequences_eq(header.reference_sequences(), &reference_sequences) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "SAM header and binary reference sequence dictionaries mismatch",
+        ));
+    }
+
+    Ok((header, diagnostics))
+}
+
+#[cfg(test)]
+mod tests {
+    use sam::validation::{Kind, Location};
+
+    use super::*;
+    use crate::io::MAGIC_NUMBER;
+
+    fn put_u32_le(dst: &mut Vec<u8>, n: u32) {
+        dst.extend(n.to_le_bytes());
+    }
+
+    #[test]
+    fn test_read_header() -> io::Result<()> {
+        const TEXT: &[u8] = b"@HD\tVN:1.6\n@RG\tID:rg0\tSM:s0\n@RG\tID:rg0\tSM:s1\n";
+
+        let mut src = Vec::new();
+        src.extend(MAGIC_NUMBER); // magic
+        put_u32_le(&mut src, TEXT.len() as u32); // l_text
+        src.extend(TEXT); // text
+        put_u32_le(&mut src, 1); // n_ref
+        put_u32_le(&mut src, 4); // ref[0].l_name
+        src.extend(b"sq0\x00"); // ref[0].name
+        put_u32_le(&mut src, 8); // ref[0].l_ref
+
+        let mut reader = crate::io::Reader::from(&src[..]);
+        let (header, diagnostics) = read_header(&mut reader)?;
+
+        assert_eq!(header.reference_sequences().len(), 1);
+        assert!(header.reference_sequences().contains_key(&b"sq0"[..]));
+        assert_eq!(header.read_groups().len(), 1);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].location(),
+            Location::Header { line_number: 3 }
+        );
+        assert_eq!(
+            diagnostics[0].kind(),
+            &Kind::DuplicateReadGroupId("rg0".into())
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+//! Async indexed BCF reader.
+
+mod builder;
+
+use futures::Stream;
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_csi::BinningIndex;
+use noodles_vcf as vcf;
+use tokio::io::{self, AsyncRead, AsyncSeek};
+
+pub use self::builder::Builder;
+use super::{Reader, reader::Query};
+use crate::Record;
+
+/// An async indexed BCF reader.
+///
+/// This owns its associated index, which is used to query records that intersect a region.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use futures::TryStreamExt;
+/// use noodles_bcf as bcf;
+///
+/// let mut reader = bcf::r#async::io::indexed_reader::Builder::default()
+///     .build_from_path("sample.bcf")
+///     .await?;
+///
+/// let header = reader.read_header().await?;
+///
+/// let region = "sq0:8-13".parse()?;
+/// let mut query = reader.query(&header, &region)?.records();
+///
+/// while let Some(record) = query.try_next().await? {
+///     // ...
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct IndexedReader<R> {
+    inner: Reader<R>,
+    index: Box<dyn BinningIndex + Send + Sync>,
+}
+
+impl<R> IndexedReader<R> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner.get_mut()
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    /// Returns the associated index.
+    pub fn index(&self) -> &dyn BinningIndex {
+        &self.index
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Reads the VCF header.
+    pub async fn read_header(&mut self) -> io::Result<vcf::Header> {
+        self.inner.read_header().await
+    }
+
+    /// Reads a record.
+    pub async fn read_record(&mut self, record: &mut Record) -> io::Result<usize> {
+        self.inner.read_record(record).await
+    }
+
+    /// Returns a stream over records.
+    pub fn records(&mut self) -> impl Stream<Item = io::Result<Record>> + '_ {
+        self.inner.records()
+    }
+}
+
+impl<R> IndexedReader<bgzf::r#async::io::Reader<R>>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Creates an async indexed BCF reader.
+    ///
+    /// The given reader must be a raw BGZF stream, as the underlying reader wraps it in a decoder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// use noodles_csi as csi;
+    ///
+    /// let index = csi::Index::default();
+    /// let reader = bcf::r#async::io::IndexedReader::new(tokio::io::empty(), index);
+    /// ```
+    pub fn new<I>(inner: R, index: I) -> Self
+    where
+        I: BinningIndex + Send + Sync + 'static,
+    {
+        Self {
+            inner: Reader::new(inner),
+            index: Box::new(index),
+        }
+    }
+}
+
+impl<R> IndexedReader<bgzf::r#async::io::Reader<R>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Returns a stream over records that intersect the given region.
+    pub fn query<'r>(
+        &'r mut self,
+        header: &vcf::Header,
+        region: &Region,
+    ) -> io::Result<Query<'r, R>> {
+        self.inner.query(header, &self.index, region)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+    use noodles_core::Position;
+    use noodles_vcf::{
+        header::record::value::{Map, map::Contig},
+        variant::{RecordBuf, io::Write},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::builder()
+            .add_contig("sq0", Map::<Contig>::new())
+            .add_contig("sq1", Map::<Contig>::new())
+            .build();
+
+        let mut writer = crate::io::IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for (name, position) in [("sq0", 8), ("sq0", 13), ("sq1", 5)] {
+            let record = RecordBuf::builder()
+                .set_reference_sequence_name(name)
+                .set_variant_start(Position::try_from(position)?)
+                .set_reference_bases("A")
+                .build();
+
+            writer.write_variant_record(&header, &record)?;
+        }
+
+        let index = writer.try_finish()?;
+        let data = writer.into_inner().into_inner();
+
+        let mut reader = IndexedReader::new(std::io::Cursor::new(data), index);
+        let header = reader.read_header().await?;
+
+        let region = "sq0:10-20".parse()?;
+        let records: Vec<_> = reader
+            .query(&header, &region)?
+            .records()
+            .try_collect()
+            .await?;
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].variant_start().transpose()?, Position::new(13));
+
+        Ok(())
+    }
+}
@@ -2,12 +2,13 @@
 
 mod compression_method;
 pub mod indexed_reader;
+mod indexed_writer;
 pub mod reader;
 pub mod writer;
 
 pub use self::{
-    compression_method::CompressionMethod, indexed_reader::IndexedReader, reader::Reader,
-    writer::Writer,
+    compression_method::CompressionMethod, indexed_reader::IndexedReader,
+    indexed_writer::IndexedWriter, reader::Reader, writer::Writer,
 };
 
 pub(crate) const MAGIC_NUMBER: [u8; 3] = *b"BCF";
@@ -105,4 +105,81 @@ where
     ) -> io::Result<Query<'r, 'h, R>> {
         self.inner.query(header, &self.index, region)
     }
+
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// Each record is returned along with the indices of the regions it intersects.
+    pub fn query_regions<'r, 'h>(
+        &'r mut self,
+        header: &'h vcf::Header,
+        regions: &[Region],
+    ) -> io::Result<impl Iterator<Item = io::Result<(Record, Vec<usize>)>> + use<'r, 'h, R>> {
+        self.inner.query_regions(header, &self.index, regions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+    use noodles_vcf::{
+        header::record::value::{Map, map::Contig},
+        variant::io::Write as _,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_query_regions() -> Result<(), Box<dyn std::error::Error>> {
+        let header = vcf::Header::builder()
+            .add_contig("sq0", Map::<Contig>::new())
+            .add_contig("sq1", Map::<Contig>::new())
+            .build();
+
+        let mut writer = crate::io::IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for (name, position) in [("sq0", 8), ("sq0", 13), ("sq1", 5)] {
+            let record = RecordBuf::builder()
+                .set_reference_sequence_name(name)
+                .set_variant_start(Position::try_from(position)?)
+                .set_reference_bases("A")
+                .build();
+
+            writer.write_variant_record(&header, &record)?;
+        }
+
+        let index = writer.try_finish()?;
+        let data = writer.into_inner().into_inner();
+
+        let mut reader = IndexedReader::new(io::Cursor::new(data), index);
+        let header = reader.read_header()?;
+
+        let regions = [
+            "sq0:1-10".parse()?,
+            "sq0:8-13".parse()?,
+            "sq1:5-5".parse()?,
+            "sq1:6-10".parse()?,
+        ];
+
+        let actual: Vec<_> = reader
+            .query_regions(&header, &regions)?
+            .map(|result| {
+                result.and_then(|(record, region_indices)| {
+                    let reference_sequence_id = record.reference_sequence_id()?;
+                    let start = record.variant_start().transpose()?;
+                    Ok((reference_sequence_id, start, region_indices))
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (0, Position::new(8), vec![0, 1]),
+            (0, Position::new(13), vec![1]),
+            (1, Position::new(5), vec![2]),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,318 @@
+//! Indexed BCF writer.
+
+use std::{
+    io::{self, Write},
+    mem,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+use noodles_csi::{
+    self as csi,
+    binning_index::{Indexer, index::reference_sequence::bin::Chunk},
+};
+use noodles_vcf::{self as vcf, variant::io::Write as _};
+
+use super::Writer;
+use crate::Record;
+
+/// An indexed BCF writer.
+///
+/// This writes a BCF and builds a coordinate-sorted index (CSI) from the records as they are
+/// written, i.e., without a second pass over the output. Records must be coordinate-sorted.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bcf as bcf;
+/// use noodles_core::Position;
+/// use noodles_vcf::{
+///     self as vcf,
+///     header::record::value::{map::Contig, Map},
+///     variant::{io::Write, RecordBuf},
+/// };
+///
+/// let mut writer = bcf::io::IndexedWriter::new(Vec::new());
+///
+/// let header = vcf::Header::builder()
+///     .add_contig("sq0", Map::<Contig>::new())
+///     .build();
+///
+/// writer.write_header(&header)?;
+///
+/// let record = RecordBuf::builder()
+///     .set_reference_sequence_name("sq0")
+///     .set_variant_start(Position::MIN)
+///     .set_reference_bases("A")
+///     .build();
+///
+/// writer.write_variant_record(&header, &record)?;
+///
+/// let index = writer.try_finish()?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub struct IndexedWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<bgzf::io::Writer<W>>,
+    indexer: Indexer<csi::binning_index::index::reference_sequence::index::BinnedIndex>,
+    previous: Option<(usize, Position)>,
+}
+
+impl<W> IndexedWriter<W>
+where
+    W: Write,
+{
+    /// Creates an indexed BCF writer.
+    ///
+    /// The given stream is wrapped in a BGZF encoder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let writer = bcf::io::IndexedWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self::from(Writer::new(inner))
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let writer = bcf::io::IndexedWriter::new(Vec::new());
+    /// assert!(writer.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &bgzf::io::Writer<W> {
+        self.inner.get_ref()
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// let writer = bcf::io::IndexedWriter::new(Vec::new());
+    /// assert!(writer.into_inner().get_ref().is_empty());
+    /// ```
+    pub fn into_inner(self) -> bgzf::io::Writer<W> {
+        self.inner.into_inner()
+    }
+
+    /// Writes a VCF header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut writer = bcf::io::IndexedWriter::new(Vec::new());
+    ///
+    /// let header = vcf::Header::default();
+    /// writer.write_header(&header)?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn write_header(&mut self, header: &vcf::Header) -> io::Result<()> {
+        self.inner.write_header(header)
+    }
+
+    /// Writes a record and adds it to the index.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the record has no position, its reference sequence is not defined
+    /// in the header, or it is not coordinate-sorted relative to the previously written records.
+    pub fn write_record(&mut self, header: &vcf::Header, record: &Record) -> io::Result<()> {
+        self.write_variant_record(header, record)
+    }
+
+    /// Attempts to finish the output stream and returns the built index.
+    ///
+    /// The index can be written using [`csi::fs::write`] or a [`csi::io::Writer`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bcf as bcf;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut writer = bcf::io::IndexedWriter::new(Vec::new());
+    ///
+    /// let header = vcf::Header::default();
+    /// writer.write_header(&header)?;
+    ///
+    /// let index = writer.try_finish()?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn try_finish(&mut self) -> io::Result<csi::Index> {
+        self.inner.try_finish()?;
+
+        let reference_sequence_count = self.inner.string_maps().contigs().len();
+        let indexer = mem::take(&mut self.indexer);
+
+        Ok(indexer.build(reference_sequence_count))
+    }
+}
+
+impl<W> From<Writer<bgzf::io::Writer<W>>> for IndexedWriter<W>
+where
+    W: Write,
+{
+    fn from(inner: Writer<bgzf::io::Writer<W>>) -> Self {
+        Self {
+            inner,
+            indexer: Indexer::default(),
+            previous: None,
+        }
+    }
+}
+
+impl<W> vcf::variant::io::Write for IndexedWriter<W>
+where
+    W: Write,
+{
+    fn write_variant_header(&mut self, header: &vcf::Header) -> io::Result<()> {
+        self.write_header(header)
+    }
+
+    fn write_variant_record(
+        &mut self,
+        header: &vcf::Header,
+        record: &dyn vcf::variant::Record,
+    ) -> io::Result<()> {
+        let reference_sequence_name = record.reference_sequence_name(header)?;
+
+        let reference_sequence_id = self
+            .inner
+            .string_maps()
+            .contigs()
+            .get_index_of(reference_sequence_name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "reference sequence name not in header",
+                )
+            })?;
+
+        let start = record
+            .variant_start()
+            .transpose()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing position"))?;
+
+        let end = record.variant_end(header)?;
+
+        let context = (reference_sequence_id, start);
+
+        if self.previous.is_some_and(|previous| context < previous) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "record is not coordinate-sorted",
+            ));
+        }
+
+        let start_position = self.inner.get_ref().virtual_position();
+        self.inner.write_variant_record(header, record)?;
+        let end_position = self.inner.get_ref().virtual_position();
+
+        let chunk = Chunk::new(start_position, end_position);
+
+        self.indexer
+            .add_record(Some((reference_sequence_id, start, end, true)), chunk)?;
+
+        self.previous = Some(context);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Region;
+    use noodles_vcf::{
+        header::record::value::{Map, map::Contig},
+        variant::RecordBuf,
+    };
+
+    use super::*;
+
+    fn build_header() -> vcf::Header {
+        vcf::Header::builder()
+            .add_contig("sq0", Map::<Contig>::new())
+            .add_contig("sq1", Map::<Contig>::new())
+            .build()
+    }
+
+    fn build_record(reference_sequence_name: &str, position: usize) -> io::Result<RecordBuf> {
+        let variant_start = Position::try_from(position)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        Ok(RecordBuf::builder()
+            .set_reference_sequence_name(reference_sequence_name)
+            .set_variant_start(variant_start)
+            .set_reference_bases("A")
+            .build())
+    }
+
+    #[test]
+    fn test_try_finish() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+
+        let mut writer = IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for (name, position) in [("sq0", 8), ("sq0", 13), ("sq1", 5)] {
+            let record = build_record(name, position)?;
+            writer.write_variant_record(&header, &record)?;
+        }
+
+        let index = writer.try_finish()?;
+        let data = writer.into_inner().into_inner();
+
+        let mut reader = crate::io::IndexedReader::new(io::Cursor::new(data), index);
+        let header = reader.read_header()?;
+
+        let region: Region = "sq0:10-20".parse()?;
+        let records: Vec<_> = reader
+            .query(&header, &region)?
+            .records()
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].variant_start().transpose()?, Position::new(13));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_variant_record_with_invalid_records() -> io::Result<()> {
+        let header = build_header();
+
+        let mut writer = IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        writer.write_variant_record(&header, &build_record("sq1", 13)?)?;
+
+        assert!(matches!(
+            writer.write_variant_record(&header, &build_record("sq1", 8)?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            writer.write_variant_record(&header, &build_record("sq0", 21)?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            writer.write_variant_record(&header, &build_record("sq2", 21)?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
@@ -17,7 +17,7 @@ use std::{
 
 use noodles_bgzf as bgzf;
 use noodles_core::Region;
-use noodles_csi::BinningIndex;
+use noodles_csi::{self as csi, BinningIndex, binning_index::RegionSet};
 use noodles_vcf::{self as vcf, header::string_maps::ContigStringMap, variant::RecordBuf};
 
 use self::{header::read_header, record::read_record, record_buf::read_record_buf};
@@ -309,6 +309,68 @@ where
             region.interval(),
         ))
     }
+
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// The chunks of all regions are merged, so each block is read at most once, and each record
+    /// is returned once, along with the indices of the regions it intersects, in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bcf as bcf;
+    /// use noodles_csi as csi;
+    ///
+    /// let mut reader = File::open("sample.bcf").map(bcf::io::Reader::new)?;
+    /// let header = reader.read_header()?;
+    ///
+    /// let index = csi::fs::read("sample.bcf.csi")?;
+    /// let regions = ["sq0:8-13".parse()?, "sq1:21-34".parse()?];
+    ///
+    /// for result in reader.query_regions(&header, &index, &regions)? {
+    ///     let (record, region_indices) = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_regions<'r, 'h, I>(
+        &'r mut self,
+        header: &'h vcf::Header,
+        index: &I,
+        regions: &[Region],
+    ) -> io::Result<impl Iterator<Item = io::Result<(Record, Vec<usize>)>> + use<'r, 'h, I, R>>
+    where
+        I: BinningIndex,
+    {
+        let region_set = regions
+            .iter()
+            .map(|region| {
+                resolve_region(header.string_maps().contigs(), region)
+                    .map(|id| (id, region.interval()))
+            })
+            .collect::<io::Result<Vec<_>>>()
+            .map(RegionSet::new)?;
+
+        let chunks = region_set.query(index)?;
+        let mut reader = Reader::from(csi::io::Query::new(&mut self.inner, chunks));
+
+        Ok(iter::from_fn(move || {
+            loop {
+                let mut record = Record::default();
+
+                match reader.read_record(&mut record) {
+                    Ok(0) => return None,
+                    Ok(_) => match intersecting_regions(header, &region_set, &record) {
+                        Ok(region_indices) if region_indices.is_empty() => {}
+                        Ok(region_indices) => return Some(Ok((record, region_indices))),
+                        Err(e) => return Some(Err(e)),
+                    },
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+        }))
+    }
 }
 
 impl<R> From<R> for Reader<R> {
@@ -340,6 +402,24 @@ where
     }
 }
 
+fn intersecting_regions(
+    header: &vcf::Header,
+    region_set: &RegionSet,
+    record: &Record,
+) -> io::Result<Vec<usize>> {
+    use vcf::variant::Record as _;
+
+    let reference_sequence_id = record.reference_sequence_id()?;
+
+    let Some(start) = record.variant_start().transpose()? else {
+        return Ok(Vec::new());
+    };
+
+    let end = record.variant_end(header)?;
+
+    Ok(region_set.intersecting(reference_sequence_id, (start..=end).into()))
+}
+
 pub(crate) fn resolve_region(
     contig_string_map: &ContigStringMap,
     region: &Region,
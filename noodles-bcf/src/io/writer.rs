@@ -66,6 +66,10 @@ impl<W> Writer<W> {
     pub fn into_inner(self) -> W {
         self.inner
     }
+
+    pub(crate) fn string_maps(&self) -> &StringMaps {
+        &self.string_maps
+    }
 }
 
 impl<W> Writer<W>
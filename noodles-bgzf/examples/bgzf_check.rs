@@ -0,0 +1,45 @@
+//! Checks the integrity of a BGZF file.
+//!
+//! Each block is decompressed and verified. If `dst` is given, the intact blocks are copied to it,
+//! e.g., to recover a truncated file.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader, BufWriter},
+};
+
+use noodles_bgzf as bgzf;
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let dst = args.next();
+
+    let mut checker = File::open(src)
+        .map(BufReader::new)
+        .map(bgzf::io::Checker::new)?;
+
+    let report = match dst {
+        Some(dst) => {
+            let mut writer = File::create(dst).map(BufWriter::new)?;
+            checker.salvage(&mut writer)?
+        }
+        None => checker.check()?,
+    };
+
+    println!("blocks: {}", report.block_count());
+    println!("uncompressed size: {}", report.uncompressed_size());
+    println!("EOF marker: {}", report.has_eof_marker());
+
+    if let Some(block) = report.first_corrupt_block() {
+        println!(
+            "first corrupt block: {} ({:?})",
+            block.position(),
+            block.status()
+        );
+    }
+
+    Ok(())
+}
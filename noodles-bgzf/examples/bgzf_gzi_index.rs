@@ -0,0 +1,16 @@
+//! Builds a gzip index (GZI) from a BGZF file.
+//!
+//! The result is written to `<src>.gzi` and matches the output of `bgzip --reindex <src>`.
+
+use std::{env, io};
+
+use noodles_bgzf::gzi;
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let index = gzi::fs::index(&src)?;
+    gzi::fs::write(format!("{src}.gzi"), &index)?;
+
+    Ok(())
+}
@@ -1,9 +1,14 @@
 //! Async BGZF I/O.
 
+pub mod range_reader;
 pub mod reader;
 pub mod writer;
 
-pub use self::{reader::Reader, writer::Writer};
+pub use self::{
+    range_reader::{RangeReader, RangeSource},
+    reader::Reader,
+    writer::Writer,
+};
 
 #[cfg(test)]
 mod tests {
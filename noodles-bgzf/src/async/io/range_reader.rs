@@ -0,0 +1,277 @@
+//! Async range reader.
+
+mod builder;
+mod source;
+
+use std::{
+    io::{self, SeekFrom},
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncSeek, ReadBuf};
+
+pub use self::{builder::Builder, source::RangeSource};
+use crate::io::range_reader::Cache;
+
+/// An async reader over a random-access byte source.
+///
+/// This adapts a [`RangeSource`] to [`AsyncRead`], [`AsyncBufRead`], and [`AsyncSeek`], allowing,
+/// e.g., an async BGZF reader or an async indexed reader to read an object without downloading it
+/// entirely. Reads are fetched in aligned blocks and kept in a small least recently used cache.
+pub struct RangeReader<S> {
+    source: S,
+    block_size: u64,
+    cache: Cache,
+    position: u64,
+    size: Option<u64>,
+    pending_fetch: Option<(u64, BoxFuture<'static, io::Result<Bytes>>)>,
+    pending_seek: Option<PendingSeek>,
+}
+
+enum PendingSeek {
+    Position(u64),
+    End(i64, BoxFuture<'static, io::Result<u64>>),
+}
+
+impl<S> RangeReader<S>
+where
+    S: RangeSource,
+{
+    /// Creates an async range reader with the default block size and cache capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use noodles_bgzf::r#async::io::RangeReader;
+    /// let reader = RangeReader::new(Bytes::new());
+    /// ```
+    pub fn new(source: S) -> Self {
+        Builder::default().build_from_source(source)
+    }
+
+    /// Returns a reference to the underlying source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use noodles_bgzf::r#async::io::RangeReader;
+    /// let reader = RangeReader::new(Bytes::new());
+    /// assert!(reader.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns a mutable reference to the underlying source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use noodles_bgzf::r#async::io::RangeReader;
+    /// let mut reader = RangeReader::new(Bytes::new());
+    /// assert!(reader.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+
+    /// Unwraps and returns the underlying source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use noodles_bgzf::r#async::io::RangeReader;
+    /// let reader = RangeReader::new(Bytes::new());
+    /// assert!(reader.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    /// Returns the current position of the reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use noodles_bgzf::r#async::io::RangeReader;
+    /// let reader = RangeReader::new(Bytes::new());
+    /// assert_eq!(reader.position(), 0);
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+}
+
+impl<S> AsyncRead for RangeReader<S>
+where
+    S: RangeSource + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let src = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let amt = src.len().min(buf.remaining());
+        buf.put_slice(&src[..amt]);
+        self.consume(amt);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S> AsyncBufRead for RangeReader<S>
+where
+    S: RangeSource + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+        let i = this.position / this.block_size;
+
+        if this.cache.get(i).is_none() {
+            let fetch = match &mut this.pending_fetch {
+                Some((j, fetch)) if *j == i => fetch,
+                pending_fetch => {
+                    let start = i * this.block_size;
+                    let end = start.saturating_add(this.block_size);
+                    &mut pending_fetch.insert((i, this.source.fetch(start..end))).1
+                }
+            };
+
+            let result = ready!(fetch.as_mut().poll(cx));
+            this.pending_fetch = None;
+            this.cache.insert(i, result?);
+        }
+
+        let offset = (this.position % this.block_size) as usize;
+
+        let buf = this.cache.get(i).expect("missing block after insertion");
+
+        Poll::Ready(Ok(&buf[offset.min(buf.len())..]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.get_mut().position += amt as u64;
+    }
+}
+
+impl<S> AsyncSeek for RangeReader<S>
+where
+    S: RangeSource + Unpin,
+{
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> io::Result<()> {
+        let this = self.get_mut();
+
+        if this.pending_seek.is_some() {
+            return Err(io::Error::other("seek already in progress"));
+        }
+
+        let pending_seek = match position {
+            SeekFrom::Start(n) => PendingSeek::Position(n),
+            SeekFrom::End(n) => match this.size {
+                Some(size) => PendingSeek::Position(checked_add(size, n)?),
+                None => PendingSeek::End(n, this.source.size()),
+            },
+            SeekFrom::Current(n) => PendingSeek::Position(checked_add(this.position, n)?),
+        };
+
+        this.pending_seek = Some(pending_seek);
+
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<u64>> {
+        let this = self.get_mut();
+
+        let position = match &mut this.pending_seek {
+            None => return Poll::Ready(Ok(this.position)),
+            Some(PendingSeek::Position(n)) => Ok(*n),
+            Some(PendingSeek::End(n, size)) => {
+                let n = *n;
+                let result = ready!(size.as_mut().poll(cx));
+
+                result.and_then(|size| {
+                    this.size = Some(size);
+                    checked_add(size, n)
+                })
+            }
+        };
+
+        this.pending_seek = None;
+        this.position = position?;
+
+        Poll::Ready(Ok(this.position))
+    }
+}
+
+fn checked_add(position: u64, n: i64) -> io::Result<u64> {
+    position.checked_add_signed(n).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid seek to a negative or overflowing position",
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_and_seek() -> io::Result<()> {
+        let mut reader = Builder::default()
+            .set_block_size(NonZero::new(3).unwrap())
+            .set_cache_capacity(NonZero::new(1).unwrap())
+            .build_from_source(Bytes::from_static(b"noodles-bgzf"));
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await?;
+        assert_eq!(buf, b"noodles-bgzf");
+
+        let mut buf = [0; 4];
+
+        reader.seek(SeekFrom::End(-4)).await?;
+        reader.read_exact(&mut buf).await?;
+        assert_eq!(&buf, b"bgzf");
+
+        reader.seek(SeekFrom::Start(1)).await?;
+        reader.read_exact(&mut buf).await?;
+        assert_eq!(&buf, b"oodl");
+
+        assert_eq!(reader.seek(SeekFrom::Current(-2)).await?, 3);
+        assert!(reader.seek(SeekFrom::Current(-8)).await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_bgzf_reader() -> io::Result<()> {
+        let mut writer = crate::r#async::io::Writer::new(Vec::new());
+        writer.write_all(b"noodles").await?;
+        writer.shutdown().await?;
+        let data = Bytes::from(writer.into_inner());
+
+        let source = Builder::default()
+            .set_block_size(NonZero::new(16).unwrap())
+            .build_from_source(data);
+        let mut reader = crate::r#async::io::Reader::new(source);
+
+        reader.seek(crate::VirtualPosition::default()).await?;
+        let mut buf = String::new();
+        reader.read_to_string(&mut buf).await?;
+        assert_eq!(buf, "noodles");
+
+        Ok(())
+    }
+}
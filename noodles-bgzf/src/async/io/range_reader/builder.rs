@@ -0,0 +1,82 @@
+use std::num::NonZero;
+
+use super::{RangeReader, RangeSource};
+use crate::io::range_reader::Cache;
+
+const DEFAULT_BLOCK_SIZE: NonZero<u64> = NonZero::new(256 * 1024).unwrap();
+const DEFAULT_CACHE_CAPACITY: NonZero<usize> = NonZero::new(16).unwrap();
+
+/// An async range reader builder.
+#[derive(Debug)]
+pub struct Builder {
+    block_size: NonZero<u64>,
+    cache_capacity: NonZero<usize>,
+}
+
+impl Builder {
+    /// Sets the read-ahead block size.
+    ///
+    /// Reads are fetched from the source in ranges of this size, aligned to multiples of this
+    /// size. By default, the block size is 256 KiB.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZero;
+    /// use noodles_bgzf::r#async::io::range_reader::Builder;
+    /// let builder = Builder::default().set_block_size(NonZero::new(1 << 20).unwrap());
+    /// ```
+    pub fn set_block_size(mut self, block_size: NonZero<u64>) -> Self {
+        self.block_size = block_size;
+        self
+    }
+
+    /// Sets the maximum number of blocks kept in the cache.
+    ///
+    /// By default, the 16 most recently used blocks are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZero;
+    /// use noodles_bgzf::r#async::io::range_reader::Builder;
+    /// let builder = Builder::default().set_cache_capacity(NonZero::new(4).unwrap());
+    /// ```
+    pub fn set_cache_capacity(mut self, cache_capacity: NonZero<usize>) -> Self {
+        self.cache_capacity = cache_capacity;
+        self
+    }
+
+    /// Builds an async range reader from a range source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use noodles_bgzf::r#async::io::range_reader::Builder;
+    /// let reader = Builder::default().build_from_source(Bytes::new());
+    /// ```
+    pub fn build_from_source<S>(self, source: S) -> RangeReader<S>
+    where
+        S: RangeSource,
+    {
+        RangeReader {
+            source,
+            block_size: self.block_size.get(),
+            cache: Cache::new(self.cache_capacity.get()),
+            position: 0,
+            size: None,
+            pending_fetch: None,
+            pending_seek: None,
+        }
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            block_size: DEFAULT_BLOCK_SIZE,
+            cache_capacity: DEFAULT_CACHE_CAPACITY,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use std::{io, ops::Range};
+
+use bytes::Bytes;
+use futures::future::{self, BoxFuture};
+
+use crate::io::range_reader::source::clamp;
+
+/// An async random-access byte source.
+///
+/// A range source fetches arbitrary byte ranges of an underlying object, e.g., a file in object
+/// storage read using HTTP range requests. It is wrapped by a [`super::RangeReader`] to provide
+/// [`tokio::io::AsyncRead`] and [`tokio::io::AsyncSeek`].
+///
+/// The returned futures do not borrow the source, so implementations typically hold a cheaply
+/// cloneable client handle and clone it into each request.
+pub trait RangeSource {
+    /// Returns the size of the source, in bytes.
+    fn size(&self) -> BoxFuture<'static, io::Result<u64>>;
+
+    /// Fetches the bytes in the given range.
+    ///
+    /// The returned buffer may only be shorter than the requested range if the range extends past
+    /// the end of the source. An empty buffer is returned if the start of the range is at or past
+    /// the end of the source.
+    fn fetch(&self, range: Range<u64>) -> BoxFuture<'static, io::Result<Bytes>>;
+}
+
+impl RangeSource for Bytes {
+    fn size(&self) -> BoxFuture<'static, io::Result<u64>> {
+        Box::pin(future::ready(Ok(self.len() as u64)))
+    }
+
+    fn fetch(&self, range: Range<u64>) -> BoxFuture<'static, io::Result<Bytes>> {
+        let range = clamp(range, self.len());
+        Box::pin(future::ready(Ok(self.slice(range))))
+    }
+}
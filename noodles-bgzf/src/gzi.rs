@@ -9,6 +9,8 @@ pub mod r#async;
 
 pub mod fs;
 mod index;
+mod indexer;
 pub mod io;
 
 pub use self::index::Index;
+pub(crate) use self::indexer::Indexer;
@@ -1,11 +1,14 @@
 //! gzip index filesystem operations.
 
+mod index;
+
 use std::{
     fs::File,
     io::{self, BufReader, BufWriter},
     path::Path,
 };
 
+pub use self::index::index;
 use super::{
     Index,
     io::{Reader, Writer},
@@ -0,0 +1,88 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+use crate::{
+    gzi::{Index, Indexer},
+    io::reader::frame::{parse_frame_sizes, read_frame_into},
+};
+
+/// Indexes a BGZF file.
+///
+/// This scans the block headers and trailers of the given file without decompressing any data,
+/// similar to `bgzip --reindex`. See [`super::write`] to write the resulting index.
+///
+/// # Examples
+///
+/// ```no_run
+/// use noodles_bgzf::gzi;
+/// let index = gzi::fs::index("reference.fa.gz")?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn index<P>(src: P) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(BufReader::new)?;
+    index_inner(&mut reader)
+}
+
+fn index_inner<R>(reader: &mut R) -> io::Result<Index>
+where
+    R: Read,
+{
+    let mut indexer = Indexer::default();
+    let mut buf = Vec::new();
+
+    while read_frame_into(reader, &mut buf)?.is_some() {
+        let (block_size, uncompressed_size) = parse_frame_sizes(&buf)?;
+        indexer.add_block(block_size, uncompressed_size as u64);
+    }
+
+    Ok(indexer.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::io::{Writer, writer::Builder};
+
+    #[test]
+    fn test_index_inner() -> io::Result<()> {
+        let mut writer = Builder::default()
+            .set_gzi_indexing(true)
+            .build_from_writer(Vec::new());
+
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+        writer.flush()?;
+        writer.write_all(b"gzi")?;
+        writer.try_finish()?;
+
+        let expected = writer.gzi_index().cloned();
+        let data = writer.into_inner();
+
+        let actual = index_inner(&mut &data[..])?;
+        assert_eq!(Some(actual), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_inner_with_invalid_block() -> io::Result<()> {
+        let mut data = Writer::new(Vec::new()).finish()?;
+        data[0] = 0x00;
+
+        assert!(matches!(
+            index_inner(&mut &data[..]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -9,7 +9,7 @@ use crate::VirtualPosition;
 /// Like the physical index, this does _not_ include the position of the first block, which is
 /// implicitly at 0.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
-pub struct Index(Vec<(u64, u64)>);
+pub struct Index(pub(super) Vec<(u64, u64)>);
 
 impl Index {
     /// Returns the virtual position at the given uncompressed position.
@@ -0,0 +1,54 @@
+use super::Index;
+
+/// A gzip index (GZI) indexer.
+///
+/// This tracks the compressed and uncompressed positions of a BGZF stream as blocks are
+/// written or read and records the start of each data block after the first.
+#[derive(Debug, Default)]
+pub(crate) struct Indexer {
+    index: Index,
+    compressed_position: u64,
+    uncompressed_position: u64,
+}
+
+impl Indexer {
+    /// Adds a block with the given compressed (block) and uncompressed sizes.
+    ///
+    /// Empty blocks, e.g., the BGZF EOF marker, are skipped but still advance the compressed
+    /// position.
+    pub(crate) fn add_block(&mut self, block_size: u64, uncompressed_size: u64) {
+        if uncompressed_size > 0 && self.compressed_position > 0 {
+            self.index
+                .0
+                .push((self.compressed_position, self.uncompressed_position));
+        }
+
+        self.compressed_position += block_size;
+        self.uncompressed_position += uncompressed_size;
+    }
+
+    pub(crate) fn index(&self) -> &Index {
+        &self.index
+    }
+
+    pub(crate) fn build(self) -> Index {
+        self.index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_block() {
+        let mut indexer = Indexer::default();
+        indexer.add_block(21, 8);
+        indexer.add_block(34, 13);
+        indexer.add_block(28, 0);
+        indexer.add_block(55, 21);
+
+        let expected = Index::from(vec![(21, 8), (83, 21)]);
+        assert_eq!(indexer.build(), expected);
+    }
+}
@@ -2,9 +2,11 @@
 
 mod block;
 mod buf_read;
+pub mod checker;
 pub mod indexed_reader;
 mod multithreaded_reader;
 pub mod multithreaded_writer;
+pub mod range_reader;
 mod read;
 pub mod reader;
 mod seek;
@@ -12,8 +14,15 @@ pub mod writer;
 
 pub(crate) use self::block::Block;
 pub use self::{
-    buf_read::BufRead, indexed_reader::IndexedReader, multithreaded_reader::MultithreadedReader,
-    multithreaded_writer::MultithreadedWriter, read::Read, reader::Reader, seek::Seek,
+    buf_read::BufRead,
+    checker::Checker,
+    indexed_reader::IndexedReader,
+    multithreaded_reader::MultithreadedReader,
+    multithreaded_writer::MultithreadedWriter,
+    range_reader::{RangeReader, RangeSource},
+    read::Read,
+    reader::Reader,
+    seek::Seek,
     writer::Writer,
 };
 
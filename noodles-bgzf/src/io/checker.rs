@@ -0,0 +1,530 @@
+//! BGZF integrity checker.
+
+pub mod block;
+mod report;
+
+use std::io::{self, Read, Write};
+
+use flate2::Crc;
+
+use self::block::Status;
+pub use self::{block::Block, report::Report};
+use super::{
+    reader::frame::{MIN_FRAME_SIZE, is_valid_header, parse_trailer},
+    writer::BGZF_EOF,
+};
+use crate::{BGZF_HEADER_SIZE, BGZF_MAX_ISIZE, gz};
+
+/// A BGZF integrity checker.
+///
+/// This scans a BGZF stream block by block, verifying the frame of each block and the CRC32
+/// and uncompressed size (ISIZE) of its data.
+///
+/// A non-BGZF gzip member or a truncated block ends the scan since the position of the following
+/// block is unknown.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Write};
+/// use noodles_bgzf as bgzf;
+///
+/// let mut writer = bgzf::io::Writer::new(Vec::new());
+/// writer.write_all(b"noodles")?;
+/// let data = writer.finish()?;
+///
+/// let mut checker = bgzf::io::Checker::new(&data[..]);
+/// let report = checker.check()?;
+///
+/// assert!(report.is_ok());
+/// assert_eq!(report.block_count(), 2);
+/// assert_eq!(report.uncompressed_size(), 7);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct Checker<R> {
+    inner: R,
+    position: u64,
+    buf: Vec<u8>,
+    data_buf: Vec<u8>,
+    is_done: bool,
+}
+
+impl<R> Checker<R> {
+    /// Returns a reference to the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf as bgzf;
+    /// let checker = bgzf::io::Checker::new(io::empty());
+    /// let _inner = checker.get_ref();
+    /// ```
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf as bgzf;
+    /// let checker = bgzf::io::Checker::new(io::empty());
+    /// let _inner = checker.into_inner();
+    /// ```
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> Checker<R>
+where
+    R: Read,
+{
+    /// Creates a BGZF integrity checker.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf as bgzf;
+    /// let checker = bgzf::io::Checker::new(io::empty());
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            position: 0,
+            buf: Vec::new(),
+            data_buf: Vec::new(),
+            is_done: false,
+        }
+    }
+
+    /// Checks the next block.
+    ///
+    /// This returns `None` at the end of the stream or after a block with a fatal status (see
+    /// [`block::Status::is_fatal`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::{self as bgzf, io::checker::block::Status};
+    ///
+    /// let data = bgzf::io::Writer::new(Vec::new()).finish()?;
+    /// let mut checker = bgzf::io::Checker::new(&data[..]);
+    ///
+    /// let block = checker.check_block()?.expect("missing block");
+    /// assert_eq!(block.status(), Status::Ok);
+    ///
+    /// assert!(checker.check_block()?.is_none());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn check_block(&mut self) -> io::Result<Option<Block>> {
+        if self.is_done {
+            return Ok(None);
+        }
+
+        let position = self.position;
+
+        let (size, uncompressed_size, status) = match self.read_frame()? {
+            Some(Frame::Complete) => {
+                let (uncompressed_size, status) = self.check_frame()?;
+                (self.buf.len(), uncompressed_size, status)
+            }
+            Some(Frame::Incomplete(status)) => (self.buf.len(), 0, status),
+            None => {
+                self.is_done = true;
+                return Ok(None);
+            }
+        };
+
+        let size = size as u64;
+        self.position += size;
+
+        if status.is_fatal() {
+            self.is_done = true;
+        }
+
+        Ok(Some(Block {
+            position,
+            size,
+            uncompressed_size,
+            status,
+        }))
+    }
+
+    /// Checks the remaining blocks in the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut checker = bgzf::io::Checker::new(io::empty());
+    /// let report = checker.check()?;
+    ///
+    /// assert!(!report.is_ok());
+    /// assert!(!report.has_eof_marker());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn check(&mut self) -> io::Result<Report> {
+        let mut report = Report::default();
+
+        while let Some(block) = self.check_block()? {
+            let is_eof_marker = self.is_eof_marker();
+            report.add_block(block, is_eof_marker);
+        }
+
+        Ok(report)
+    }
+
+    /// Copies the intact blocks to the given writer.
+    ///
+    /// The blocks are copied as is, i.e., without being recompressed, and the output is terminated
+    /// with a BGZF EOF marker. This recovers, e.g., a truncated file.
+    ///
+    /// A block with a valid frame but corrupt data, e.g., a CRC32 mismatch, is skipped, and
+    /// copying continues with the following block. Copying stops at the first block with a fatal
+    /// status (see [`block::Status::is_fatal`]), e.g., a truncated block, since the position of
+    /// the following block is unknown. The returned report describes the input stream up to and
+    /// including that block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Read, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::io::Writer::new(Vec::new());
+    /// writer.write_all(b"noodles")?;
+    /// let mut data = writer.finish()?;
+    ///
+    /// // Truncate the EOF marker.
+    /// data.truncate(data.len() - 8);
+    ///
+    /// let mut checker = bgzf::io::Checker::new(&data[..]);
+    /// let mut dst = Vec::new();
+    /// let report = checker.salvage(&mut dst)?;
+    ///
+    /// assert!(!report.has_eof_marker());
+    ///
+    /// let mut reader = bgzf::io::Reader::new(&dst[..]);
+    /// let mut buf = Vec::new();
+    /// reader.read_to_end(&mut buf)?;
+    /// assert_eq!(buf, b"noodles");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn salvage<W>(&mut self, writer: &mut W) -> io::Result<Report>
+    where
+        W: Write,
+    {
+        let mut report = Report::default();
+        let mut has_eof_marker = false;
+
+        while let Some(block) = self.check_block()? {
+            let is_eof_marker = self.is_eof_marker();
+            report.add_block(block, is_eof_marker);
+
+            if block.status().is_ok() {
+                writer.write_all(&self.buf)?;
+                has_eof_marker = is_eof_marker;
+            }
+        }
+
+        if !has_eof_marker {
+            writer.write_all(&BGZF_EOF)?;
+        }
+
+        Ok(report)
+    }
+
+    fn read_frame(&mut self) -> io::Result<Option<Frame>> {
+        self.buf.resize(BGZF_HEADER_SIZE, 0);
+
+        let n = read_up_to(&mut self.inner, &mut self.buf)?;
+
+        if n == 0 {
+            return Ok(None);
+        } else if n < self.buf.len() {
+            self.buf.truncate(n);
+
+            let status = if is_gzip_header(&self.buf) {
+                Status::Truncated
+            } else {
+                Status::InvalidHeader
+            };
+
+            return Ok(Some(Frame::Incomplete(status)));
+        }
+
+        // SAFETY: `buf.len() == BGZF_HEADER_SIZE`.
+        let header = self.buf.first_chunk().unwrap();
+
+        if !is_gzip_header(header) {
+            return Ok(Some(Frame::Incomplete(Status::InvalidHeader)));
+        } else if !is_valid_header(header) {
+            return Ok(Some(Frame::Incomplete(Status::NotBgzf)));
+        }
+
+        // SAFETY: `buf.len() == BGZF_HEADER_SIZE >= mem::size_of::<u16>()`.
+        let bsize = self
+            .buf
+            .last_chunk()
+            .map(|b| u16::from_le_bytes(*b))
+            .unwrap();
+        let block_size = usize::from(bsize) + 1;
+
+        if block_size < MIN_FRAME_SIZE {
+            return Ok(Some(Frame::Incomplete(Status::InvalidHeader)));
+        }
+
+        self.buf.resize(block_size, 0);
+        let n = read_up_to(&mut self.inner, &mut self.buf[BGZF_HEADER_SIZE..])?;
+
+        if BGZF_HEADER_SIZE + n < block_size {
+            self.buf.truncate(BGZF_HEADER_SIZE + n);
+            return Ok(Some(Frame::Incomplete(Status::Truncated)));
+        }
+
+        Ok(Some(Frame::Complete))
+    }
+
+    fn check_frame(&mut self) -> io::Result<(u64, Status)> {
+        use crate::deflate;
+
+        // SAFETY: `buf.len() >= MIN_FRAME_SIZE`.
+        let (crc32, isize) = self.buf.last_chunk().map(parse_trailer).unwrap()?;
+        let uncompressed_size = isize as u64;
+
+        if isize > BGZF_MAX_ISIZE {
+            return Ok((uncompressed_size, Status::InvalidData));
+        }
+
+        let end = self.buf.len() - gz::TRAILER_SIZE;
+        let cdata = &self.buf[BGZF_HEADER_SIZE..end];
+
+        self.data_buf.resize(isize, 0);
+
+        if deflate::decode(cdata, &mut self.data_buf).is_err() {
+            return Ok((uncompressed_size, Status::InvalidData));
+        }
+
+        let mut crc = Crc::new();
+        crc.update(&self.data_buf);
+
+        let status = if crc.sum() == crc32 {
+            Status::Ok
+        } else {
+            Status::ChecksumMismatch
+        };
+
+        Ok((uncompressed_size, status))
+    }
+
+    fn is_eof_marker(&self) -> bool {
+        self.buf == BGZF_EOF
+    }
+}
+
+enum Frame {
+    Complete,
+    Incomplete(Status),
+}
+
+fn is_gzip_header(src: &[u8]) -> bool {
+    const CM_DEFLATE: u8 = 0x08;
+
+    let n = src.len().min(gz::MAGIC_NUMBER.len());
+
+    src[..n] == gz::MAGIC_NUMBER[..n] && src.get(2).is_none_or(|&cm| cm == CM_DEFLATE)
+}
+
+fn read_up_to<R>(reader: &mut R, mut buf: &mut [u8]) -> io::Result<usize>
+where
+    R: Read,
+{
+    let len = buf.len();
+
+    while !buf.is_empty() {
+        match reader.read(buf) {
+            Ok(0) => break,
+            Ok(n) => buf = &mut buf[n..],
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(len - buf.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Writer;
+
+    fn build_data() -> io::Result<(Vec<u8>, u64)> {
+        let mut writer = Writer::new(Vec::new());
+
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        let position = writer.position();
+
+        writer.write_all(b"bgzf")?;
+
+        writer.finish().map(|data| (data, position))
+    }
+
+    #[test]
+    fn test_check() -> io::Result<()> {
+        let (data, _) = build_data()?;
+
+        let report = Checker::new(&data[..]).check()?;
+
+        let expected = Report {
+            block_count: 3,
+            uncompressed_size: 11,
+            has_eof_marker: true,
+            first_corrupt_block: None,
+        };
+
+        assert_eq!(report, expected);
+        assert!(report.is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_with_checksum_mismatch() -> io::Result<()> {
+        let (mut data, position) = build_data()?;
+
+        // CRC32 of the first block
+        let i = usize::try_from(position).unwrap() - gz::TRAILER_SIZE;
+        data[i] ^= 0xff;
+
+        let report = Checker::new(&data[..]).check()?;
+
+        assert_eq!(report.block_count(), 2);
+        assert!(report.has_eof_marker());
+
+        let block = report.first_corrupt_block().expect("missing corrupt block");
+        assert_eq!(block.position(), 0);
+        assert_eq!(block.size(), position);
+        assert_eq!(block.status(), Status::ChecksumMismatch);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_with_truncated_block() -> io::Result<()> {
+        let (mut data, position) = build_data()?;
+        data.truncate(usize::try_from(position).unwrap() + 21);
+
+        let report = Checker::new(&data[..]).check()?;
+
+        assert_eq!(report.block_count(), 1);
+        assert!(!report.has_eof_marker());
+
+        let block = report.first_corrupt_block().expect("missing corrupt block");
+        assert_eq!(block.position(), position);
+        assert_eq!(block.size(), 21);
+        assert_eq!(block.status(), Status::Truncated);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_with_non_bgzf_member() -> io::Result<()> {
+        use flate2::{Compression, write::GzEncoder};
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"noodles")?;
+        let data = encoder.finish()?;
+
+        let mut checker = Checker::new(&data[..]);
+
+        let block = checker.check_block()?.expect("missing block");
+        assert_eq!(block.status(), Status::NotBgzf);
+
+        assert!(checker.check_block()?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_with_invalid_header() -> io::Result<()> {
+        let data = b"noodles";
+        let report = Checker::new(&data[..]).check()?;
+
+        let block = report.first_corrupt_block().expect("missing corrupt block");
+        assert_eq!(block.status(), Status::InvalidHeader);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_salvage() -> io::Result<()> {
+        let (data, position) = build_data()?;
+        let end = usize::try_from(position).unwrap();
+
+        let mut truncated_data = data.clone();
+        truncated_data.truncate(end + 21);
+
+        let mut dst = Vec::new();
+        let report = Checker::new(&truncated_data[..]).salvage(&mut dst)?;
+
+        assert_eq!(report.block_count(), 1);
+
+        let mut expected = data[..end].to_vec();
+        expected.extend(BGZF_EOF);
+        assert_eq!(dst, expected);
+
+        let mut dst = Vec::new();
+        Checker::new(&data[..]).salvage(&mut dst)?;
+        assert_eq!(dst, data);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_salvage_with_checksum_mismatch() -> io::Result<()> {
+        let mut writer = Writer::new(Vec::new());
+
+        let mut ends = Vec::new();
+
+        for buf in [&b"noodles"[..], b"bgzf", b"checker"] {
+            writer.write_all(buf)?;
+            writer.flush()?;
+            ends.push(usize::try_from(writer.position()).unwrap());
+        }
+
+        let data = writer.finish()?;
+
+        // CRC32 of the second block
+        let mut corrupt_data = data.clone();
+        corrupt_data[ends[1] - gz::TRAILER_SIZE] ^= 0xff;
+
+        let mut dst = Vec::new();
+        let report = Checker::new(&corrupt_data[..]).salvage(&mut dst)?;
+
+        assert_eq!(report.block_count(), 3);
+        assert!(report.has_eof_marker());
+
+        let block = report.first_corrupt_block().expect("missing corrupt block");
+        assert_eq!(block.position(), ends[0] as u64);
+        assert_eq!(block.status(), Status::ChecksumMismatch);
+
+        let mut expected = data[..ends[0]].to_vec();
+        expected.extend(&data[ends[1]..]);
+        assert_eq!(dst, expected);
+
+        let mut reader = crate::io::Reader::new(&dst[..]);
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"noodleschecker");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+//! Checked BGZF block.
+
+/// The status of a checked BGZF block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    /// The block is intact.
+    Ok,
+    /// The stream ended before the end of the block.
+    Truncated,
+    /// The block does not start with a gzip header.
+    InvalidHeader,
+    /// The block is a gzip member but not a BGZF block, i.e., it is missing the `BC` extra
+    /// subfield.
+    NotBgzf,
+    /// The compressed data does not decompress to the uncompressed size (ISIZE).
+    InvalidData,
+    /// The CRC32 of the uncompressed data does not match the block trailer.
+    ChecksumMismatch,
+}
+
+impl Status {
+    /// Returns whether the block is intact.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::io::checker::block::Status;
+    /// assert!(Status::Ok.is_ok());
+    /// assert!(!Status::Truncated.is_ok());
+    /// ```
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Self::Ok)
+    }
+
+    /// Returns whether the rest of the stream can no longer be read as BGZF blocks.
+    ///
+    /// A block with a valid frame but corrupt data is not fatal since the position of the
+    /// following block is still known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::io::checker::block::Status;
+    /// assert!(Status::Truncated.is_fatal());
+    /// assert!(!Status::ChecksumMismatch.is_fatal());
+    /// ```
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, Self::Truncated | Self::InvalidHeader | Self::NotBgzf)
+    }
+}
+
+/// A checked BGZF block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Block {
+    pub(super) position: u64,
+    pub(super) size: u64,
+    pub(super) uncompressed_size: u64,
+    pub(super) status: Status,
+}
+
+impl Block {
+    /// Returns the compressed position of the start of the block.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the number of bytes of the block that were read.
+    ///
+    /// For a truncated block, this is less than the block size given in its header.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the uncompressed size (ISIZE) given in the block trailer.
+    ///
+    /// This is 0 if the trailer could not be read.
+    pub fn uncompressed_size(&self) -> u64 {
+        self.uncompressed_size
+    }
+
+    /// Returns the status of the block.
+    pub fn status(&self) -> Status {
+        self.status
+    }
+}
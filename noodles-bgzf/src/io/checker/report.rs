@@ -0,0 +1,51 @@
+use super::Block;
+
+/// A BGZF stream check report.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Report {
+    pub(super) block_count: usize,
+    pub(super) uncompressed_size: u64,
+    pub(super) has_eof_marker: bool,
+    pub(super) first_corrupt_block: Option<Block>,
+}
+
+impl Report {
+    /// Returns the number of intact blocks.
+    pub fn block_count(&self) -> usize {
+        self.block_count
+    }
+
+    /// Returns the total uncompressed size of the intact blocks.
+    pub fn uncompressed_size(&self) -> u64 {
+        self.uncompressed_size
+    }
+
+    /// Returns whether the stream ends with the BGZF EOF marker.
+    pub fn has_eof_marker(&self) -> bool {
+        self.has_eof_marker
+    }
+
+    /// Returns the first block that is not intact, if any.
+    pub fn first_corrupt_block(&self) -> Option<&Block> {
+        self.first_corrupt_block.as_ref()
+    }
+
+    /// Returns whether all blocks are intact and the stream ends with the BGZF EOF marker.
+    pub fn is_ok(&self) -> bool {
+        self.first_corrupt_block.is_none() && self.has_eof_marker
+    }
+
+    pub(super) fn add_block(&mut self, block: Block, is_eof_marker: bool) {
+        if block.status().is_ok() {
+            self.block_count += 1;
+            self.uncompressed_size += block.uncompressed_size();
+            self.has_eof_marker = is_eof_marker;
+        } else {
+            self.has_eof_marker = false;
+
+            if self.first_corrupt_block.is_none() {
+                self.first_corrupt_block = Some(block);
+            }
+        }
+    }
+}
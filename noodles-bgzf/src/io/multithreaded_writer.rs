@@ -14,6 +14,7 @@ use crossbeam_channel::{Receiver, Sender};
 
 pub use self::builder::Builder;
 use super::writer::{CompressionLevelImpl, MAX_BUF_SIZE};
+use crate::gzi;
 
 type FrameParts = (Vec<u8>, u32, usize);
 type BufferedTx = Sender<io::Result<FrameParts>>;
@@ -22,10 +23,11 @@ type DeflateTx = Sender<(Bytes, BufferedTx)>;
 type DeflateRx = Receiver<(Bytes, BufferedTx)>;
 type WriteTx = Sender<BufferedRx>;
 type WriteRx = Receiver<BufferedRx>;
+type WriterResult<W> = io::Result<(W, Option<gzi::Index>)>;
 
 enum State<W> {
     Running {
-        writer_handle: JoinHandle<io::Result<W>>,
+        writer_handle: JoinHandle<WriterResult<W>>,
         deflater_handles: Vec<JoinHandle<()>>,
         write_tx: WriteTx,
         deflate_tx: DeflateTx,
@@ -42,6 +44,7 @@ where
 {
     state: State<W>,
     buf: BytesMut,
+    gzi_index: Option<gzi::Index>,
 }
 
 impl<W> MultithreadedWriter<W>
@@ -113,12 +116,40 @@ where
 
                 drop(write_tx);
 
-                writer_handle.join().unwrap()
+                let (inner, gzi_index) = writer_handle.join().unwrap()?;
+                self.gzi_index = gzi_index;
+
+                Ok(inner)
             }
             State::Done => panic!("invalid state"),
         }
     }
 
+    /// Returns the gzip index (GZI) of the written blocks.
+    ///
+    /// This is only available after the output stream is finished and when the writer is built
+    /// with [`Builder::set_gzi_indexing`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::io::multithreaded_writer::Builder::default()
+    ///     .set_gzi_indexing(true)
+    ///     .build_from_writer(io::sink());
+    ///
+    /// writer.write_all(b"noodles")?;
+    /// writer.finish()?;
+    ///
+    /// assert!(writer.gzi_index().is_some());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn gzi_index(&self) -> Option<&gzi::Index> {
+        self.gzi_index.as_ref()
+    }
+
     fn remaining(&self) -> usize {
         MAX_BUF_SIZE - self.buf.len()
     }
@@ -184,7 +215,11 @@ where
     }
 }
 
-fn spawn_writer<W>(mut writer: W, write_rx: WriteRx) -> JoinHandle<io::Result<W>>
+fn spawn_writer<W>(
+    mut writer: W,
+    mut gzi_indexer: Option<gzi::Indexer>,
+    write_rx: WriteRx,
+) -> JoinHandle<WriterResult<W>>
 where
     W: Write + Send + 'static,
 {
@@ -194,13 +229,18 @@ where
         while let Ok(buffered_rx) = write_rx.recv() {
             if let Ok(result) = buffered_rx.recv() {
                 let (compressed_data, crc32, uncompressed_size) = result?;
-                write_frame(&mut writer, &compressed_data, crc32, uncompressed_size)?;
+                let block_size =
+                    write_frame(&mut writer, &compressed_data, crc32, uncompressed_size)?;
+
+                if let Some(indexer) = gzi_indexer.as_mut() {
+                    indexer.add_block(block_size as u64, uncompressed_size as u64);
+                }
             }
         }
 
         writer.write_all(&BGZF_EOF)?;
 
-        Ok(writer)
+        Ok((writer, gzi_indexer.map(gzi::Indexer::build)))
     })
 }
 
@@ -235,3 +275,38 @@ fn compress(src: &[u8], compression_level: CompressionLevelImpl) -> io::Result<F
     let crc32 = deflate::encode(src, compression_level, &mut dst)?;
     Ok((dst, crc32, src.len()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gzi_index() -> io::Result<()> {
+        let mut writer = Builder::default()
+            .set_gzi_indexing(true)
+            .build_from_writer(Vec::new());
+
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        writer.write_all(b"bgzf")?;
+
+        let data = writer.finish()?;
+
+        let mut reader = crate::io::Reader::new(io::Cursor::new(data));
+        let mut buf = Vec::new();
+        io::Read::read_to_end(&mut reader, &mut buf)?;
+        assert_eq!(buf, b"noodlesbgzf");
+
+        let index = writer.gzi_index().expect("missing gzi index");
+        assert_eq!(index.as_ref().len(), 1);
+
+        let virtual_position = index.query(7)?;
+        reader.seek(virtual_position)?;
+
+        buf.clear();
+        io::Read::read_to_end(&mut reader, &mut buf)?;
+        assert_eq!(buf, b"bgzf");
+
+        Ok(())
+    }
+}
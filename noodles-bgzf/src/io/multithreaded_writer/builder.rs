@@ -3,12 +3,13 @@ use std::{io::Write, num::NonZero};
 use bytes::BytesMut;
 
 use super::MultithreadedWriter;
-use crate::io::writer::CompressionLevel;
+use crate::{gzi, io::writer::CompressionLevel};
 
 /// A multithreaded BGZF writer builder.
 pub struct Builder {
     compression_level: CompressionLevel,
     worker_count: NonZero<usize>,
+    gzi_indexing: bool,
 }
 
 impl Builder {
@@ -43,6 +44,22 @@ impl Builder {
         self
     }
 
+    /// Sets whether to build a gzip index (GZI) while writing.
+    ///
+    /// By default, no gzip index is built. When enabled, the index can be retrieved using
+    /// [`MultithreadedWriter::gzi_index`] after the output stream is finished.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf as bgzf;
+    /// let builder = bgzf::io::multithreaded_writer::Builder::default().set_gzi_indexing(true);
+    /// ```
+    pub fn set_gzi_indexing(mut self, gzi_indexing: bool) -> Self {
+        self.gzi_indexing = gzi_indexing;
+        self
+    }
+
     /// Builds a multithreaded BGZF writer from a writer.
     ///
     /// # Examples
@@ -65,7 +82,8 @@ impl Builder {
         let (write_tx, write_rx) = crossbeam_channel::bounded(worker_count);
         let (deflate_tx, deflate_rx) = crossbeam_channel::bounded(worker_count);
 
-        let writer_handle = spawn_writer(writer, write_rx);
+        let gzi_indexer = self.gzi_indexing.then(gzi::Indexer::default);
+        let writer_handle = spawn_writer(writer, gzi_indexer, write_rx);
         let deflater_handles =
             spawn_deflaters(self.compression_level, self.worker_count, deflate_rx);
 
@@ -77,6 +95,7 @@ impl Builder {
                 deflate_tx,
             },
             buf: BytesMut::new(),
+            gzi_index: None,
         }
     }
 }
@@ -86,6 +105,7 @@ impl Default for Builder {
         Self {
             compression_level: CompressionLevel::default(),
             worker_count: NonZero::<usize>::MIN,
+            gzi_indexing: false,
         }
     }
 }
@@ -0,0 +1,243 @@
+//! Range reader.
+
+mod builder;
+pub(crate) mod cache;
+pub(crate) mod source;
+
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
+
+pub(crate) use self::cache::Cache;
+pub use self::{builder::Builder, source::RangeSource};
+
+/// A reader over a random-access byte source.
+///
+/// This adapts a [`RangeSource`] to [`Read`], [`BufRead`], and [`Seek`], allowing, e.g., a BGZF
+/// reader or an indexed reader to read an object without downloading it entirely. Reads are
+/// fetched in aligned blocks and kept in a small least recently used cache, so small reads and
+/// nearby seeks do not each issue a request to the source.
+#[derive(Debug)]
+pub struct RangeReader<S> {
+    source: S,
+    block_size: u64,
+    cache: Cache,
+    position: u64,
+    size: Option<u64>,
+}
+
+impl<S> RangeReader<S>
+where
+    S: RangeSource,
+{
+    /// Creates a range reader with the default block size and cache capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::io::RangeReader;
+    /// let reader = RangeReader::new(Vec::new());
+    /// ```
+    pub fn new(source: S) -> Self {
+        Builder::default().build_from_source(source)
+    }
+
+    /// Returns a reference to the underlying source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::io::RangeReader;
+    /// let reader = RangeReader::new(Vec::new());
+    /// assert!(reader.get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns a mutable reference to the underlying source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::io::RangeReader;
+    /// let mut reader = RangeReader::new(Vec::new());
+    /// assert!(reader.get_mut().is_empty());
+    /// ```
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+
+    /// Unwraps and returns the underlying source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::io::RangeReader;
+    /// let reader = RangeReader::new(Vec::new());
+    /// assert!(reader.into_inner().is_empty());
+    /// ```
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    /// Returns the current position of the reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_bgzf::io::RangeReader;
+    /// let reader = RangeReader::new(Vec::new());
+    /// assert_eq!(reader.position(), 0);
+    /// ```
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    fn size(&mut self) -> io::Result<u64> {
+        if let Some(size) = self.size {
+            return Ok(size);
+        }
+
+        let size = self.source.size()?;
+        self.size = Some(size);
+
+        Ok(size)
+    }
+}
+
+impl<S> Read for RangeReader<S>
+where
+    S: RangeSource,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let src = self.fill_buf()?;
+        let amt = src.len().min(buf.len());
+        buf[..amt].copy_from_slice(&src[..amt]);
+        self.consume(amt);
+        Ok(amt)
+    }
+}
+
+impl<S> BufRead for RangeReader<S>
+where
+    S: RangeSource,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let i = self.position / self.block_size;
+
+        if self.cache.get(i).is_none() {
+            let start = i * self.block_size;
+            let end = start.saturating_add(self.block_size);
+            let buf = self.source.fetch(start..end)?;
+            self.cache.insert(i, buf);
+        }
+
+        let offset = (self.position % self.block_size) as usize;
+
+        let buf = self.cache.get(i).expect("missing block after insertion");
+
+        Ok(&buf[offset.min(buf.len())..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.position += amt as u64;
+    }
+}
+
+impl<S> Seek for RangeReader<S>
+where
+    S: RangeSource,
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::End(n) => self.size()?.checked_add_signed(n),
+            SeekFrom::Current(n) => self.position.checked_add_signed(n),
+        };
+
+        self.position = position.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        Ok(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use super::*;
+
+    #[test]
+    fn test_read() -> io::Result<()> {
+        let mut reader = Builder::default()
+            .set_block_size(NonZero::new(3).unwrap())
+            .build_from_source(&b"noodles-bgzf"[..]);
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        assert_eq!(buf, b"noodles-bgzf");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seek() -> io::Result<()> {
+        let mut reader = Builder::default()
+            .set_block_size(NonZero::new(3).unwrap())
+            .set_cache_capacity(NonZero::new(1).unwrap())
+            .build_from_source(&b"noodles-bgzf"[..]);
+
+        let mut buf = [0; 4];
+
+        reader.seek(SeekFrom::End(-4))?;
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"bgzf");
+
+        reader.seek(SeekFrom::Start(1))?;
+        reader.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"oodl");
+
+        assert_eq!(reader.seek(SeekFrom::Current(-2))?, 3);
+        assert!(reader.seek(SeekFrom::Current(-8)).is_err());
+
+        reader.seek(SeekFrom::Start(16))?;
+        assert_eq!(reader.read(&mut buf)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_bgzf_reader() -> io::Result<()> {
+        use std::io::Write;
+
+        use crate::VirtualPosition;
+
+        let mut writer = crate::io::Writer::new(Vec::new());
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        let virtual_position = writer.virtual_position();
+        writer.write_all(b"bgzf")?;
+        let data = writer.finish()?;
+
+        let source = Builder::default()
+            .set_block_size(NonZero::new(16).unwrap())
+            .build_from_source(data);
+        let mut reader = crate::io::Reader::new(source);
+
+        reader.seek(virtual_position)?;
+        let mut buf = String::new();
+        reader.read_to_string(&mut buf)?;
+        assert_eq!(buf, "bgzf");
+
+        reader.seek(VirtualPosition::default())?;
+        buf.clear();
+        reader.read_to_string(&mut buf)?;
+        assert_eq!(buf, "noodlesbgzf");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use std::collections::VecDeque;
+
+use bytes::Bytes;
+
+/// A least recently used cache of aligned blocks.
+#[derive(Debug)]
+pub(crate) struct Cache {
+    capacity: usize,
+    blocks: VecDeque<(u64, Bytes)>,
+}
+
+impl Cache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            blocks: VecDeque::new(),
+        }
+    }
+
+    /// Returns the block with the given index and marks it as the most recently used.
+    pub(crate) fn get(&mut self, i: u64) -> Option<&Bytes> {
+        let j = self.blocks.iter().position(|(k, _)| *k == i)?;
+
+        if j != self.blocks.len() - 1 {
+            let entry = self.blocks.remove(j)?;
+            self.blocks.push_back(entry);
+        }
+
+        self.blocks.back().map(|(_, buf)| buf)
+    }
+
+    pub(crate) fn insert(&mut self, i: u64, buf: Bytes) {
+        self.blocks.retain(|(k, _)| *k != i);
+
+        while self.blocks.len() >= self.capacity {
+            self.blocks.pop_front();
+        }
+
+        self.blocks.push_back((i, buf));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert() {
+        let mut cache = Cache::new(2);
+
+        cache.insert(0, Bytes::from_static(b"a"));
+        cache.insert(1, Bytes::from_static(b"b"));
+        assert!(cache.get(0).is_some());
+
+        cache.insert(2, Bytes::from_static(b"c"));
+        assert!(cache.get(1).is_none());
+        assert_eq!(cache.get(0), Some(&Bytes::from_static(b"a")));
+        assert_eq!(cache.get(2), Some(&Bytes::from_static(b"c")));
+    }
+}
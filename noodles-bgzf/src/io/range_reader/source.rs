@@ -0,0 +1,96 @@
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    ops::Range,
+};
+
+use bytes::Bytes;
+
+/// A random-access byte source.
+///
+/// A range source fetches arbitrary byte ranges of an underlying object, e.g., a file in object
+/// storage read using HTTP range requests. It is wrapped by a [`super::RangeReader`] to provide
+/// [`Read`] and [`Seek`].
+pub trait RangeSource {
+    /// Returns the size of the source, in bytes.
+    fn size(&mut self) -> io::Result<u64>;
+
+    /// Fetches the bytes in the given range.
+    ///
+    /// The returned buffer may only be shorter than the requested range if the range extends past
+    /// the end of the source. An empty buffer is returned if the start of the range is at or past
+    /// the end of the source.
+    fn fetch(&mut self, range: Range<u64>) -> io::Result<Bytes>;
+}
+
+impl RangeSource for Bytes {
+    fn size(&mut self) -> io::Result<u64> {
+        Ok(self.len() as u64)
+    }
+
+    fn fetch(&mut self, range: Range<u64>) -> io::Result<Bytes> {
+        let range = clamp(range, self.len());
+        Ok(self.slice(range))
+    }
+}
+
+impl RangeSource for Vec<u8> {
+    fn size(&mut self) -> io::Result<u64> {
+        Ok(self.len() as u64)
+    }
+
+    fn fetch(&mut self, range: Range<u64>) -> io::Result<Bytes> {
+        let range = clamp(range, self.len());
+        Ok(Bytes::copy_from_slice(&self[range]))
+    }
+}
+
+impl RangeSource for &[u8] {
+    fn size(&mut self) -> io::Result<u64> {
+        Ok(self.len() as u64)
+    }
+
+    fn fetch(&mut self, range: Range<u64>) -> io::Result<Bytes> {
+        let range = clamp(range, self.len());
+        Ok(Bytes::copy_from_slice(&self[range]))
+    }
+}
+
+impl RangeSource for File {
+    fn size(&mut self) -> io::Result<u64> {
+        self.metadata().map(|metadata| metadata.len())
+    }
+
+    fn fetch(&mut self, range: Range<u64>) -> io::Result<Bytes> {
+        self.seek(SeekFrom::Start(range.start))?;
+
+        let len = range.end.saturating_sub(range.start);
+        let mut buf = Vec::new();
+        self.take(len).read_to_end(&mut buf)?;
+
+        Ok(Bytes::from(buf))
+    }
+}
+
+pub(crate) fn clamp(range: Range<u64>, len: usize) -> Range<usize> {
+    let end = usize::try_from(range.end).unwrap_or(usize::MAX).min(len);
+    let start = usize::try_from(range.start).unwrap_or(usize::MAX).min(end);
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fetch() -> io::Result<()> {
+        let mut source: &[u8] = b"noodles";
+
+        assert_eq!(source.size()?, 7);
+        assert_eq!(source.fetch(1..4)?, &b"ood"[..]);
+        assert_eq!(source.fetch(4..16)?, &b"les"[..]);
+        assert!(source.fetch(8..16)?.is_empty());
+
+        Ok(())
+    }
+}
@@ -4,10 +4,10 @@ use flate2::Crc;
 
 use crate::{BGZF_HEADER_SIZE, gz, io::Block};
 
-const MIN_FRAME_SIZE: usize = BGZF_HEADER_SIZE + gz::TRAILER_SIZE;
+pub(crate) const MIN_FRAME_SIZE: usize = BGZF_HEADER_SIZE + gz::TRAILER_SIZE;
 
-type HeaderBuf = [u8; BGZF_HEADER_SIZE];
-type TrailerBuf = [u8; gz::TRAILER_SIZE];
+pub(crate) type HeaderBuf = [u8; BGZF_HEADER_SIZE];
+pub(crate) type TrailerBuf = [u8; gz::TRAILER_SIZE];
 
 pub(crate) fn read_frame_into<R>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<Option<()>>
 where
@@ -69,7 +69,7 @@ fn parse_header(src: &HeaderBuf) -> io::Result<()> {
     }
 }
 
-fn is_valid_header(src: &HeaderBuf) -> bool {
+pub(crate) fn is_valid_header(src: &HeaderBuf) -> bool {
     const BGZF_CM: u8 = 0x08; // DEFLATE
     const BGZF_FLG: u8 = 0x04; // FEXTRA
     const BGZF_XLEN: [u8; 2] = [0x06, 0x00];
@@ -84,7 +84,7 @@ fn is_valid_header(src: &HeaderBuf) -> bool {
         && src[14..16] == BGZF_SLEN
 }
 
-fn parse_trailer(src: &TrailerBuf) -> io::Result<(u32, usize)> {
+pub(crate) fn parse_trailer(src: &TrailerBuf) -> io::Result<(u32, usize)> {
     // SAFETY: `src.len() == 8`.
     let crc32 = u32::from_le_bytes(src[..4].try_into().unwrap());
 
@@ -114,6 +114,11 @@ pub(super) fn parse_block_into_buf(
     Ok(())
 }
 
+pub(crate) fn parse_frame_sizes(src: &[u8]) -> io::Result<(u64, usize)> {
+    let (block_size, _, _, isize) = parse_frame(src)?;
+    Ok((block_size, isize))
+}
+
 fn parse_frame(src: &[u8]) -> io::Result<(u64, &[u8], u32, usize)> {
     let (header, cdata, trailer) = split_frame(src)?;
 
@@ -9,7 +9,7 @@ pub use self::{builder::Builder, compression_level::CompressionLevel};
 use std::io::{self, Write};
 
 pub(crate) use self::frame::write_frame;
-use crate::{BGZF_HEADER_SIZE, BGZF_MAX_ISIZE, VirtualPosition, gz};
+use crate::{BGZF_HEADER_SIZE, BGZF_MAX_ISIZE, VirtualPosition, gz, gzi};
 
 // The max DEFLATE overhead for 65536 bytes of data at compression level 0.
 //
@@ -72,6 +72,7 @@ where
     staging_buf: Vec<u8>,
     compression_buf: Vec<u8>,
     compression_level: CompressionLevelImpl,
+    gzi_indexer: Option<gzi::Indexer>,
 }
 
 impl<W> Writer<W>
@@ -153,6 +154,32 @@ where
         VirtualPosition::try_from((self.position, uncompressed_position)).unwrap()
     }
 
+    /// Returns the gzip index (GZI) of the blocks written so far.
+    ///
+    /// This is only available when the writer is built with
+    /// [`Builder::set_gzi_indexing`]. The index is complete after the output stream is finished,
+    /// e.g., using [`Self::try_finish`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Write};
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let mut writer = bgzf::io::writer::Builder::default()
+    ///     .set_gzi_indexing(true)
+    ///     .build_from_writer(Vec::new());
+    ///
+    /// writer.write_all(b"noodles")?;
+    /// writer.try_finish()?;
+    ///
+    /// assert!(writer.gzi_index().is_some());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn gzi_index(&self) -> Option<&gzi::Index> {
+        self.gzi_indexer.as_ref().map(|indexer| indexer.index())
+    }
+
     fn flush_block(&mut self) -> io::Result<()> {
         use crate::deflate;
 
@@ -165,6 +192,10 @@ where
 
         self.position += block_size as u64;
 
+        if let Some(indexer) = self.gzi_indexer.as_mut() {
+            indexer.add_block(block_size as u64, uncompressed_size as u64);
+        }
+
         self.staging_buf.clear();
 
         Ok(())
@@ -290,6 +321,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_gzi_index() -> io::Result<()> {
+        let mut writer = Builder::default()
+            .set_gzi_indexing(true)
+            .build_from_writer(Vec::new());
+
+        writer.write_all(b"noodles")?;
+        writer.flush()?;
+        let compressed_position = writer.position();
+
+        writer.write_all(b"bgzf")?;
+        writer.try_finish()?;
+
+        let expected = gzi::Index::from(vec![(compressed_position, 7)]);
+        assert_eq!(writer.gzi_index(), Some(&expected));
+
+        let writer = Writer::new(Vec::new());
+        assert!(writer.gzi_index().is_none());
+
+        Ok(())
+    }
+
     #[test]
     fn test_finish() -> io::Result<()> {
         let mut writer = Writer::new(Vec::new());
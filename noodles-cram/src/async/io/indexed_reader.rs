@@ -0,0 +1,170 @@
+//! Async indexed CRAM reader.
+
+mod builder;
+
+use futures::Stream;
+use noodles_core::Region;
+use noodles_sam as sam;
+use tokio::io::{self, AsyncRead, AsyncSeek};
+
+pub use self::builder::Builder;
+use super::Reader;
+use crate::{FileDefinition, crai, io::reader::Container};
+
+/// An async indexed CRAM reader.
+///
+/// This owns its associated index, which is used to query records that intersect a region.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use futures::TryStreamExt;
+/// use noodles_cram as cram;
+///
+/// let mut reader = cram::r#async::io::indexed_reader::Builder::default()
+///     .build_from_path("sample.cram")
+///     .await?;
+///
+/// let header = reader.read_header().await?;
+///
+/// let region = "sq0:8-13".parse()?;
+/// let mut query = reader.query(&header, &region)?;
+///
+/// while let Some(record) = query.try_next().await? {
+///     // ...
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct IndexedReader<R> {
+    inner: Reader<R>,
+    index: crai::Index,
+}
+
+impl<R> IndexedReader<R> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner.get_mut()
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    /// Returns the associated index.
+    pub fn index(&self) -> &crai::Index {
+        &self.index
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Creates an async indexed CRAM reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::{self as cram, crai};
+    /// let index = crai::Index::default();
+    /// let reader = cram::r#async::io::IndexedReader::new(tokio::io::empty(), index);
+    /// ```
+    pub fn new(inner: R, index: crai::Index) -> Self {
+        Self {
+            inner: Reader::new(inner),
+            index,
+        }
+    }
+
+    /// Reads the CRAM file definition.
+    pub async fn read_file_definition(&mut self) -> io::Result<FileDefinition> {
+        self.inner.read_file_definition().await
+    }
+
+    /// Reads the SAM header in the CRAM file header container.
+    pub async fn read_file_header(&mut self) -> io::Result<sam::Header> {
+        self.inner.read_file_header().await
+    }
+
+    /// Reads the CRAM file definition and the SAM header.
+    pub async fn read_header(&mut self) -> io::Result<sam::Header> {
+        self.inner.read_header().await
+    }
+
+    /// Reads a container.
+    pub async fn read_container(&mut self, container: &mut Container) -> io::Result<usize> {
+        self.inner.read_container(container).await
+    }
+
+    /// Returns a stream over records.
+    pub fn records<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h sam::Header,
+    ) -> impl Stream<Item = io::Result<sam::alignment::RecordBuf>> + 'r {
+        self.inner.records(header)
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Returns a stream over records that intersect the given region.
+    pub fn query<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h sam::Header,
+        region: &Region,
+    ) -> io::Result<impl Stream<Item = io::Result<sam::alignment::RecordBuf>> + use<'r, 'h, R>>
+    {
+        self.inner.query(header, &self.index, region)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::io::reader::build_cram;
+
+    #[tokio::test]
+    async fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        let (_, repository, data, index) = build_cram(&[(0, 1), (0, 5), (0, 9), (1, 3)], &["u0"])?;
+
+        let mut reader = Builder::default()
+            .set_reference_sequence_repository(repository)
+            .set_index(index)
+            .build_from_reader(std::io::Cursor::new(data))?;
+
+        let header = reader.read_header().await?;
+
+        let region = "sq0:6-9".parse()?;
+        let names: Vec<_> = reader
+            .query(&header, &region)?
+            .map_ok(|record| record.name().map(|name| name.to_vec()))
+            .try_collect()
+            .await?;
+
+        assert_eq!(names, [Some(b"r1".to_vec()), Some(b"r2".to_vec())]);
+
+        let region = "sq1".parse()?;
+        let names: Vec<_> = reader
+            .query(&header, &region)?
+            .map_ok(|record| record.name().map(|name| name.to_vec()))
+            .try_collect()
+            .await?;
+
+        assert_eq!(names, [Some(b"r3".to_vec())]);
+
+        Ok(())
+    }
+}
@@ -18,6 +18,7 @@ struct Context<'r, 'h: 'r, 'i: 'r, R> {
     reference_sequence_id: usize,
     interval: Interval,
 
+    previous_offset: Option<u64>,
     records: vec::IntoIter<sam::alignment::RecordBuf>,
 }
 
@@ -41,6 +42,7 @@ where
         reference_sequence_id,
         interval,
 
+        previous_offset: None,
         records: Vec::new().into_iter(),
     };
 
@@ -48,6 +50,10 @@ where
         loop {
             match ctx.records.next() {
                 Some(r) => {
+                    if r.reference_sequence_id() != Some(ctx.reference_sequence_id) {
+                        continue;
+                    }
+
                     if let (Some(start), Some(end)) = (r.alignment_start(), r.alignment_end()) {
                         let alignment_interval = (start..=end).into();
 
@@ -72,10 +78,16 @@ where
 {
     let index_record = ctx.index.next()?;
 
-    if index_record.reference_sequence_id() != Some(ctx.reference_sequence_id) {
+    // A container with multiple slices has an index record for each slice, but its records are
+    // all read at once.
+    if index_record.reference_sequence_id() != Some(ctx.reference_sequence_id)
+        || ctx.previous_offset == Some(index_record.offset())
+    {
         return Some(Ok(()));
     }
 
+    ctx.previous_offset = Some(index_record.offset());
+
     if let Err(e) = ctx
         .reader
         .seek(SeekFrom::Start(index_record.offset()))
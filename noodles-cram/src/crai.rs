@@ -6,8 +6,10 @@ pub mod r#async;
 pub mod fs;
 pub mod io;
 pub mod record;
+pub mod shard;
+pub mod statistics;
 
-pub use self::record::Record;
+pub use self::{record::Record, statistics::Statistics};
 
 /// A CRAM index.
 pub type Index = Vec<Record>;
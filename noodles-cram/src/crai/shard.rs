@@ -0,0 +1,93 @@
+//! CRAM index sharding.
+
+use std::num::NonZero;
+
+use noodles_csi::binning_index::{Shard, shard::partition};
+
+use super::{Record, Statistics};
+
+// 16 KiB, the interval covered by the smallest bin of a BAI.
+const WINDOW_SIZE: NonZero<u64> = NonZero::new(1 << 14).unwrap();
+
+/// Plans shards of roughly equal compressed size using a CRAM index.
+///
+/// This partitions the given reference sequences into at most `shard_count` shards of
+/// [`Shard::Regions`] by the compressed size of the slices that overlap each 16 KiB window. See
+/// [`partition`] for details.
+///
+/// A [`Shard::Unmapped`] shard is always appended since it also owns records with a reference
+/// sequence but no alignment start, which are stored in slices with a reference sequence.
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZero;
+/// use noodles_cram::crai;
+///
+/// let index = crai::Index::default();
+/// let shards = crai::shard::plan(&index, [b"sq0"], NonZero::<usize>::MIN);
+/// assert_eq!(shards.len(), 2);
+/// ```
+pub fn plan<N>(
+    index: &[Record],
+    reference_sequence_names: impl IntoIterator<Item = N>,
+    shard_count: NonZero<usize>,
+) -> Vec<Shard>
+where
+    N: AsRef<[u8]>,
+{
+    let statistics = Statistics::new(index, WINDOW_SIZE);
+
+    let reference_sequences = reference_sequence_names
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let window_sizes = statistics
+                .reference_sequences()
+                .get(&i)
+                .map(|reference_sequence| reference_sequence.window_sizes().clone())
+                .unwrap_or_default();
+
+            (name, window_sizes)
+        });
+
+    let mut shards = partition(reference_sequences, WINDOW_SIZE, shard_count);
+    shards.push(Shard::Unmapped);
+    shards
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::{Position, Region};
+
+    use super::*;
+
+    #[test]
+    fn test_plan() -> Result<(), noodles_core::position::TryFromIntError> {
+        let index = [
+            Record::new(Some(0), Position::new(1), 16384, 100, 0, 80),
+            Record::new(Some(0), Position::new(16385), 16384, 100, 80, 60),
+            Record::new(Some(1), Position::new(1), 8, 200, 0, 8),
+            Record::new(None, None, 0, 300, 0, 13),
+        ];
+
+        let shards = plan(&index, [b"sq0", b"sq1"], NonZero::new(2).unwrap());
+
+        assert_eq!(
+            shards,
+            [
+                Shard::Regions(vec![Region::new(
+                    "sq0",
+                    Position::MIN..=Position::try_from(16384)?
+                )]),
+                Shard::Regions(vec![
+                    Region::new("sq0", Position::try_from(16385)?..),
+                    Region::new("sq1", Position::MIN..),
+                ]),
+                Shard::Unmapped,
+            ]
+        );
+
+        Ok(())
+    }
+}
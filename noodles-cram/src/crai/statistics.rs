@@ -0,0 +1,178 @@
+//! CRAM index statistics.
+
+mod reference_sequence;
+
+pub use self::reference_sequence::ReferenceSequence;
+
+use std::{collections::BTreeMap, num::NonZero};
+
+use super::Record;
+
+/// CRAM index statistics.
+///
+/// These are summaries computed only from an index, without reading the associated file. They
+/// are useful to plan how to partition a file into regions of similar work and to detect indexes
+/// that are inconsistent.
+///
+/// A CRAM index does not have record counts, so the size of the data in each window is the
+/// compressed size of the slices that overlap it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Statistics {
+    window_size: NonZero<u64>,
+    reference_sequences: BTreeMap<usize, ReferenceSequence>,
+    unplaced_unmapped_slice_count: usize,
+    unplaced_unmapped_compressed_size: u64,
+    is_sorted: bool,
+}
+
+impl Statistics {
+    /// Computes statistics of a CRAM index.
+    ///
+    /// Windows are consecutive intervals of the given size, starting at position 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZero;
+    /// use noodles_core::Position;
+    /// use noodles_cram::crai::{self, statistics::Statistics};
+    ///
+    /// let index = vec![crai::Record::new(Some(0), Position::new(8), 13, 21, 0, 34)];
+    /// let statistics = Statistics::new(&index, NonZero::new(16384).unwrap());
+    ///
+    /// assert_eq!(statistics.reference_sequences().len(), 1);
+    /// assert_eq!(statistics.reference_sequences()[&0].compressed_size(), 34);
+    /// ```
+    pub fn new(index: &[Record], window_size: NonZero<u64>) -> Self {
+        let mut reference_sequences: BTreeMap<usize, ReferenceSequence> = BTreeMap::new();
+        let mut unplaced_unmapped_slice_count = 0;
+        let mut unplaced_unmapped_compressed_size = 0;
+
+        for record in index {
+            match record.reference_sequence_id() {
+                Some(id) => reference_sequences
+                    .entry(id)
+                    .or_default()
+                    .add_record(window_size, record),
+                None => {
+                    unplaced_unmapped_slice_count += 1;
+                    unplaced_unmapped_compressed_size += record.slice_length();
+                }
+            }
+        }
+
+        let is_sorted = index
+            .windows(2)
+            .all(|w| (w[0].offset(), w[0].landmark()) <= (w[1].offset(), w[1].landmark()));
+
+        Self {
+            window_size,
+            reference_sequences,
+            unplaced_unmapped_slice_count,
+            unplaced_unmapped_compressed_size,
+            is_sorted,
+        }
+    }
+
+    /// Returns the size of a window.
+    pub fn window_size(&self) -> NonZero<u64> {
+        self.window_size
+    }
+
+    /// Returns the statistics of each reference sequence, keyed by reference sequence ID.
+    ///
+    /// Only reference sequences with index records are included.
+    pub fn reference_sequences(&self) -> &BTreeMap<usize, ReferenceSequence> {
+        &self.reference_sequences
+    }
+
+    /// Returns the number of slices with unplaced, unmapped records.
+    pub fn unplaced_unmapped_slice_count(&self) -> usize {
+        self.unplaced_unmapped_slice_count
+    }
+
+    /// Returns the compressed size of the slices with unplaced, unmapped records.
+    pub fn unplaced_unmapped_compressed_size(&self) -> u64 {
+        self.unplaced_unmapped_compressed_size
+    }
+
+    /// Returns whether the index records are sorted by container offset and slice landmark.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZero;
+    /// use noodles_cram::crai::statistics::Statistics;
+    /// let statistics = Statistics::new(&[], NonZero::new(16384).unwrap());
+    /// assert!(statistics.is_consistent());
+    /// ```
+    pub fn is_consistent(&self) -> bool {
+        self.is_sorted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        const WINDOW_SIZE: NonZero<u64> = NonZero::new(10).unwrap();
+
+        let index = [
+            Record::new(Some(0), Position::new(1), 10, 100, 0, 60),
+            Record::new(Some(0), Position::new(11), 20, 100, 60, 40),
+            Record::new(Some(2), Position::new(25), 1, 200, 0, 8),
+            Record::new(None, None, 0, 300, 0, 13),
+        ];
+
+        let statistics = Statistics::new(&index, WINDOW_SIZE);
+
+        assert_eq!(statistics.reference_sequences().len(), 2);
+        assert_eq!(statistics.unplaced_unmapped_slice_count(), 1);
+        assert_eq!(statistics.unplaced_unmapped_compressed_size(), 13);
+        assert!(statistics.is_consistent());
+
+        let reference_sequence = &statistics.reference_sequences()[&0];
+        assert_eq!(reference_sequence.slice_count(), 2);
+        assert_eq!(reference_sequence.container_count(), 1);
+        assert_eq!(reference_sequence.compressed_size(), 100);
+        assert_eq!(reference_sequence.alignment_start(), Position::new(1));
+        assert_eq!(reference_sequence.alignment_end(), Position::new(30));
+        assert_eq!(
+            reference_sequence.window_sizes().iter().collect::<Vec<_>>(),
+            [(&0, &60), (&1, &20), (&2, &20)]
+        );
+        assert_eq!(reference_sequence.window_count(), 3);
+        assert_eq!(reference_sequence.leaf_window_count(), 3);
+
+        assert!(!statistics.reference_sequences().contains_key(&1));
+
+        let reference_sequence = &statistics.reference_sequences()[&2];
+        assert_eq!(reference_sequence.window_count(), 3);
+        assert_eq!(reference_sequence.leaf_window_count(), 1);
+    }
+
+    #[test]
+    fn test_new_with_large_reference_sequence_id() {
+        const WINDOW_SIZE: NonZero<u64> = NonZero::new(10).unwrap();
+
+        let index = [Record::new(
+            Some(usize::MAX),
+            Position::new(1),
+            10,
+            100,
+            0,
+            60,
+        )];
+        let statistics = Statistics::new(&index, WINDOW_SIZE);
+
+        assert_eq!(statistics.reference_sequences().len(), 1);
+        assert_eq!(
+            statistics.reference_sequences()[&usize::MAX].compressed_size(),
+            60
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use std::{collections::BTreeMap, num::NonZero};
+
+use noodles_core::Position;
+
+use crate::crai::Record;
+
+/// CRAM index reference sequence statistics.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReferenceSequence {
+    slice_count: usize,
+    container_offsets: Vec<u64>,
+    compressed_size: u64,
+    alignment_start: Option<Position>,
+    alignment_end: Option<Position>,
+    window_sizes: BTreeMap<usize, u64>,
+}
+
+impl ReferenceSequence {
+    pub(super) fn add_record(&mut self, window_size: NonZero<u64>, record: &Record) {
+        self.slice_count += 1;
+
+        if self.container_offsets.last() != Some(&record.offset()) {
+            self.container_offsets.push(record.offset());
+        }
+
+        self.compressed_size += record.slice_length();
+
+        let Some(start) = record.alignment_start() else {
+            return;
+        };
+
+        let end = start
+            .checked_add(record.alignment_span().saturating_sub(1))
+            .unwrap_or(start);
+
+        self.alignment_start = Some(self.alignment_start.map_or(start, |s| s.min(start)));
+        self.alignment_end = self.alignment_end.max(Some(end));
+
+        // Distribute the slice size across the windows it overlaps.
+        let window_size = window_size.get();
+        let first_window = ((usize::from(start) - 1) as u64 / window_size) as usize;
+        let last_window = ((usize::from(end) - 1) as u64 / window_size) as usize;
+        let window_count = (last_window - first_window + 1) as u64;
+
+        for (i, window) in (first_window..=last_window).enumerate() {
+            let mut size = record.slice_length() / window_count;
+
+            if (i as u64) < record.slice_length() % window_count {
+                size += 1;
+            }
+
+            *self.window_sizes.entry(window).or_default() += size;
+        }
+    }
+
+    /// Returns the number of slices.
+    pub fn slice_count(&self) -> usize {
+        self.slice_count
+    }
+
+    /// Returns the number of containers with slices of this reference sequence.
+    pub fn container_count(&self) -> usize {
+        self.container_offsets.len()
+    }
+
+    /// Returns the compressed size of the slices.
+    pub fn compressed_size(&self) -> u64 {
+        self.compressed_size
+    }
+
+    /// Returns the start of the first slice.
+    pub fn alignment_start(&self) -> Option<Position> {
+        self.alignment_start
+    }
+
+    /// Returns the end of the last slice.
+    pub fn alignment_end(&self) -> Option<Position> {
+        self.alignment_end
+    }
+
+    /// Returns the estimated compressed size of the data in each window with data.
+    ///
+    /// The size of each slice is distributed evenly across the windows it overlaps.
+    pub fn window_sizes(&self) -> &BTreeMap<usize, u64> {
+        &self.window_sizes
+    }
+
+    /// Returns the number of windows from the start of the reference sequence to the last window
+    /// with data.
+    pub fn window_count(&self) -> usize {
+        self.window_sizes
+            .last_key_value()
+            .map(|(i, _)| i + 1)
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of windows with data.
+    ///
+    /// This is named after the equivalent binning index statistic, i.e., the number of windows
+    /// with a bin at the deepest level.
+    pub fn leaf_window_count(&self) -> usize {
+        self.window_sizes.len()
+    }
+}
@@ -3,3 +3,5 @@
 mod index;
 
 pub use self::index::index;
+#[cfg(test)]
+pub(crate) use self::index::index_reader;
@@ -1,4 +1,10 @@
-use std::{cmp, collections::HashMap, fs::File, io, path::Path};
+use std::{
+    cmp,
+    collections::HashMap,
+    fs::File,
+    io::{self, Read, Seek},
+    path::Path,
+};
 
 use noodles_core::Position;
 use noodles_fasta as fasta;
@@ -27,6 +33,13 @@ where
     P: AsRef<Path>,
 {
     let mut reader = File::open(src).map(Reader::new)?;
+    index_reader(&mut reader)
+}
+
+pub(crate) fn index_reader<R>(reader: &mut Reader<R>) -> io::Result<crai::Index>
+where
+    R: Read + Seek,
+{
     let header = reader.read_header()?;
 
     let mut index = Vec::new();
@@ -55,8 +68,8 @@ where
                 container_len - landmark
             };
 
-            push_index_records(
-                &mut index,
+            let records = build_index_records(
+                reader.reference_sequence_repository(),
                 &header,
                 &compression_header,
                 &slice,
@@ -64,6 +77,8 @@ where
                 landmark as u64,
                 slice_length as u64,
             )?;
+
+            index.extend(records);
         }
 
         container_position = reader.position()?;
@@ -72,18 +87,18 @@ where
     Ok(index)
 }
 
-fn push_index_records(
-    index: &mut crai::Index,
+fn build_index_records(
+    reference_sequence_repository: &fasta::Repository,
     header: &sam::Header,
     compression_header: &CompressionHeader,
     slice: &Slice,
     container_position: u64,
     landmark: u64,
     slice_length: u64,
-) -> io::Result<()> {
+) -> io::Result<Vec<crai::Record>> {
     if slice.header().reference_sequence_context().is_many() {
-        push_index_records_for_multi_reference_slice(
-            index,
+        build_index_records_for_multi_reference_slice(
+            reference_sequence_repository,
             header,
             compression_header,
             slice,
@@ -92,13 +107,14 @@ fn push_index_records(
             slice_length,
         )
     } else {
-        push_index_record_for_single_reference_slice(
-            index,
+        let record = build_index_record_for_single_reference_slice(
             slice.header(),
             container_position,
             landmark,
             slice_length,
-        )
+        );
+
+        Ok(vec![record])
     }
 }
 
@@ -117,15 +133,15 @@ impl Default for SliceReferenceSequenceAlignmentRangeInclusive {
     }
 }
 
-fn push_index_records_for_multi_reference_slice(
-    index: &mut crai::Index,
+fn build_index_records_for_multi_reference_slice(
+    reference_sequence_repository: &fasta::Repository,
     header: &sam::Header,
     compression_header: &CompressionHeader,
     slice: &Slice,
     container_position: u64,
     landmark: u64,
     slice_length: u64,
-) -> io::Result<()> {
+) -> io::Result<Vec<crai::Record>> {
     let mut reference_sequence_ids: HashMap<
         Option<usize>,
         SliceReferenceSequenceAlignmentRangeInclusive,
@@ -134,7 +150,7 @@ fn push_index_records_for_multi_reference_slice(
     let (core_data_src, external_data_srcs) = slice.decode_blocks()?;
 
     for record in slice.records(
-        fasta::Repository::default(), // TODO
+        reference_sequence_repository.clone(),
         header,
         compression_header,
         &core_data_src,
@@ -154,6 +170,8 @@ fn push_index_records_for_multi_reference_slice(
         reference_sequence_ids.keys().copied().collect();
     sorted_reference_sequence_ids.sort_unstable();
 
+    let mut records = Vec::with_capacity(sorted_reference_sequence_ids.len());
+
     for reference_sequence_id in sorted_reference_sequence_ids {
         let (alignment_start, alignment_span) = if reference_sequence_id.is_some() {
             let range = &reference_sequence_ids[&reference_sequence_id];
@@ -177,19 +195,18 @@ fn push_index_records_for_multi_reference_slice(
             slice_length,
         );
 
-        index.push(record);
+        records.push(record);
     }
 
-    Ok(())
+    Ok(records)
 }
 
-fn push_index_record_for_single_reference_slice(
-    index: &mut crai::Index,
+fn build_index_record_for_single_reference_slice(
     slice_header: &slice::Header,
     container_position: u64,
     landmark: u64,
     slice_length: u64,
-) -> io::Result<()> {
+) -> crai::Record {
     use crate::container::ReferenceSequenceContext;
 
     let (reference_sequence_id, alignment_start, alignment_span) =
@@ -204,16 +221,12 @@ fn push_index_record_for_single_reference_slice(
             ReferenceSequenceContext::Many => unreachable!(),
         };
 
-    let record = crai::Record::new(
+    crai::Record::new(
         reference_sequence_id,
         alignment_start,
         alignment_span,
         container_position,
         landmark,
         slice_length,
-    );
-
-    index.push(record);
-
-    Ok(())
+    )
 }
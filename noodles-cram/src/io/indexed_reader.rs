@@ -97,4 +97,25 @@ where
     ) -> io::Result<Query<'r, 'h, 'r, R>> {
         self.inner.query(header, &self.index, region)
     }
+
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// Each record is returned along with the indices of the regions it intersects.
+    pub fn query_regions<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h sam::Header,
+        regions: &[Region],
+    ) -> io::Result<
+        impl Iterator<Item = io::Result<(sam::alignment::RecordBuf, Vec<usize>)>> + use<'r, 'h, R>,
+    > {
+        self.inner.query_regions(header, &self.index, regions)
+    }
+
+    /// Returns an iterator over unplaced, unmapped records.
+    pub fn query_unmapped<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h sam::Header,
+    ) -> impl Iterator<Item = io::Result<sam::alignment::RecordBuf>> + use<'r, 'h, R> {
+        self.inner.query_unmapped(header, &self.index)
+    }
 }
@@ -14,6 +14,8 @@ use noodles_core::Region;
 use noodles_fasta as fasta;
 use noodles_sam as sam;
 
+#[cfg(test)]
+pub(crate) use self::query::tests::build_cram;
 pub use self::{builder::Builder, container::Container, query::Query, records::Records};
 use self::{container::read_container, header::read_header};
 use crate::{FileDefinition, crai};
@@ -336,6 +338,67 @@ where
             region.interval(),
         ))
     }
+
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// Each container is read at most once, and each record is returned once, along with the
+    /// indices of the regions it intersects, in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_cram::{self as cram, crai};
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::io::Reader::new)?;
+    /// let header = reader.read_header()?;
+    ///
+    /// let index = crai::fs::read("sample.cram.crai")?;
+    /// let regions = ["sq0:8-13".parse()?, "sq1:21-34".parse()?];
+    ///
+    /// for result in reader.query_regions(&header, &index, &regions)? {
+    ///     let (record, region_indices) = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_regions<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h sam::Header,
+        index: &crai::Index,
+        regions: &[Region],
+    ) -> io::Result<
+        impl Iterator<Item = io::Result<(sam::alignment::RecordBuf, Vec<usize>)>> + use<'r, 'h, R>,
+    > {
+        query::query_regions(self, header, index, regions)
+    }
+
+    /// Returns an iterator over unplaced, unmapped records.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_cram::{self as cram, crai};
+    ///
+    /// let mut reader = File::open("sample.cram").map(cram::io::Reader::new)?;
+    /// let header = reader.read_header()?;
+    ///
+    /// let index = crai::fs::read("sample.cram.crai")?;
+    ///
+    /// for result in reader.query_unmapped(&header, &index) {
+    ///     let record = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn query_unmapped<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h sam::Header,
+        index: &crai::Index,
+    ) -> impl Iterator<Item = io::Result<sam::alignment::RecordBuf>> + use<'r, 'h, R> {
+        query::query_unmapped(self, header, index)
+    }
 }
 
 impl<R> sam::alignment::io::Read<R> for Reader<R>
@@ -357,3 +420,47 @@ where
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_regions() -> Result<(), Box<dyn std::error::Error>> {
+        let (header, repository, data, index) =
+            build_cram(&[(0, 1), (0, 5), (0, 9), (1, 3)], &["u0"])?;
+
+        let mut reader = Builder::default()
+            .set_reference_sequence_repository(repository)
+            .build_from_reader(io::Cursor::new(data));
+
+        // Regions are out of order, and some overlap or are adjacent.
+        let regions = [
+            "sq1".parse()?,
+            "sq0:5-9".parse()?,
+            "sq0:1-5".parse()?,
+            "sq0:13-16".parse()?,
+            "sq0:12-12".parse()?,
+        ];
+
+        let actual: Vec<_> = reader
+            .query_regions(&header, &index, &regions)?
+            .map(|result| {
+                result.map(|(record, region_indices)| {
+                    (record.name().map(|name| name.to_vec()), region_indices)
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (Some(b"r0".to_vec()), vec![2]),
+            (Some(b"r1".to_vec()), vec![1, 2]),
+            (Some(b"r2".to_vec()), vec![1, 4]),
+            (Some(b"r3".to_vec()), vec![0]),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
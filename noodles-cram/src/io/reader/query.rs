@@ -1,9 +1,10 @@
 use std::{
     io::{self, Read, Seek, SeekFrom},
-    slice, vec,
+    iter, slice, vec,
 };
 
-use noodles_core::region::Interval;
+use noodles_core::{Region, region::Interval};
+use noodles_csi::binning_index::RegionSet;
 use noodles_sam as sam;
 
 use super::{Container, Reader};
@@ -25,6 +26,7 @@ where
     reference_sequence_id: usize,
     interval: Interval,
 
+    previous_offset: Option<u64>,
     records: vec::IntoIter<sam::alignment::RecordBuf>,
 }
 
@@ -49,6 +51,7 @@ where
             reference_sequence_id,
             interval,
 
+            previous_offset: None,
             records: Vec::new().into_iter(),
         }
     }
@@ -56,66 +59,24 @@ where
     fn read_next_container(&mut self) -> Option<io::Result<()>> {
         let index_record = self.index.next()?;
 
-        if index_record.reference_sequence_id() != Some(self.reference_sequence_id) {
+        // A container with multiple slices has an index record for each slice, but its records
+        // are all read at once.
+        if index_record.reference_sequence_id() != Some(self.reference_sequence_id)
+            || self.previous_offset == Some(index_record.offset())
+        {
             return Some(Ok(()));
         }
 
-        if let Err(e) = self.reader.seek(SeekFrom::Start(index_record.offset())) {
-            return Some(Err(e));
-        }
-
-        let mut container = Container::default();
-
-        match self.reader.read_container(&mut container) {
-            Ok(0) => return None,
-            Ok(_) => {}
-            Err(e) => return Some(Err(e)),
-        };
-
-        let compression_header = match container.compression_header() {
-            Ok(compression_header) => compression_header,
-            Err(e) => return Some(Err(e)),
-        };
+        self.previous_offset = Some(index_record.offset());
 
-        let records = container
-            .slices()
-            .map(|result| {
-                let slice = result?;
-
-                let (core_data_src, external_data_srcs) = slice.decode_blocks()?;
-
-                slice
-                    .records(
-                        self.reader.reference_sequence_repository.clone(),
-                        self.header,
-                        &compression_header,
-                        &core_data_src,
-                        &external_data_srcs,
-                    )
-                    .and_then(|records| {
-                        records
-                            .into_iter()
-                            .map(|record| {
-                                sam::alignment::RecordBuf::try_from_alignment_record(
-                                    self.header,
-                                    &record,
-                                )
-                            })
-                            .collect::<io::Result<Vec<_>>>()
-                    })
-            })
-            .collect::<Result<Vec<_>, _>>();
-
-        let records = match records {
-            Ok(records) => records,
+        let records = match read_container_records(self.reader, self.header, index_record.offset())
+        {
+            Ok(Some(records)) => records,
+            Ok(None) => return None,
             Err(e) => return Some(Err(e)),
         };
 
-        self.records = records
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>()
-            .into_iter();
+        self.records = records.into_iter();
 
         Some(Ok(()))
     }
@@ -131,7 +92,7 @@ where
         loop {
             match self.records.next() {
                 Some(record) => {
-                    if intersects(&record, self.interval) {
+                    if intersects(&record, self.reference_sequence_id, self.interval) {
                         return Some(Ok(record));
                     }
                 }
@@ -145,7 +106,203 @@ where
     }
 }
 
-fn intersects(record: &sam::alignment::RecordBuf, region_interval: Interval) -> bool {
+/// Returns records that intersect any of the given regions.
+///
+/// Each container is read at most once, even if it intersects multiple regions.
+pub(super) fn query_regions<'r, R>(
+    reader: &'r mut Reader<R>,
+    header: &'r sam::Header,
+    index: &crai::Index,
+    regions: &[Region],
+) -> io::Result<
+    impl Iterator<Item = io::Result<(sam::alignment::RecordBuf, Vec<usize>)>> + use<'r, R>,
+>
+where
+    R: Read + Seek,
+{
+    let region_set = regions
+        .iter()
+        .map(|region| {
+            header
+                .reference_sequences()
+                .get_index_of(region.name())
+                .map(|id| (id, region.interval()))
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "invalid reference sequence name",
+                    )
+                })
+        })
+        .collect::<io::Result<Vec<_>>>()
+        .map(RegionSet::new)?;
+
+    let mut offsets = container_offsets(index, &region_set).into_iter();
+    let mut records = Vec::new().into_iter();
+
+    Ok(iter::from_fn(move || {
+        loop {
+            match records.next() {
+                Some(record) => {
+                    let region_indices = intersecting_regions(&region_set, &record);
+
+                    if !region_indices.is_empty() {
+                        return Some(Ok((record, region_indices)));
+                    }
+                }
+                None => {
+                    let offset = offsets.next()?;
+
+                    match read_container_records(reader, header, offset) {
+                        Ok(Some(rs)) => records = rs.into_iter(),
+                        Ok(None) => return None,
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+            }
+        }
+    }))
+}
+
+/// Returns unplaced, unmapped records.
+///
+/// These are read from the containers with slices that have no reference sequence.
+pub(super) fn query_unmapped<'r, R>(
+    reader: &'r mut Reader<R>,
+    header: &'r sam::Header,
+    index: &crai::Index,
+) -> impl Iterator<Item = io::Result<sam::alignment::RecordBuf>> + use<'r, R>
+where
+    R: Read + Seek,
+{
+    let mut offsets: Vec<_> = index
+        .iter()
+        .filter(|index_record| index_record.reference_sequence_id().is_none())
+        .map(|index_record| index_record.offset())
+        .collect();
+
+    offsets.sort_unstable();
+    offsets.dedup();
+
+    let mut offsets = offsets.into_iter();
+    let mut records = Vec::<sam::alignment::RecordBuf>::new().into_iter();
+
+    iter::from_fn(move || {
+        loop {
+            match records.next() {
+                Some(record) => {
+                    if record.reference_sequence_id().is_none() {
+                        return Some(Ok(record));
+                    }
+                }
+                None => {
+                    let offset = offsets.next()?;
+
+                    match read_container_records(reader, header, offset) {
+                        Ok(Some(rs)) => records = rs.into_iter(),
+                        Ok(None) => return None,
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+            }
+        }
+    })
+}
+
+// Returns the sorted, unique offsets of the containers that have a slice intersecting any region.
+fn container_offsets(index: &crai::Index, region_set: &RegionSet) -> Vec<u64> {
+    let mut offsets: Vec<_> = index
+        .iter()
+        .filter(|index_record| {
+            let Some(reference_sequence_id) = index_record.reference_sequence_id() else {
+                return false;
+            };
+
+            let interval = match index_record.alignment_start() {
+                Some(start) => {
+                    let end = start
+                        .checked_add(index_record.alignment_span().saturating_sub(1))
+                        .unwrap_or(start);
+
+                    (start..=end).into()
+                }
+                None => Interval::from(..),
+            };
+
+            !region_set
+                .intersecting(reference_sequence_id, interval)
+                .is_empty()
+        })
+        .map(|index_record| index_record.offset())
+        .collect();
+
+    offsets.sort_unstable();
+    offsets.dedup();
+
+    offsets
+}
+
+fn read_container_records<R>(
+    reader: &mut Reader<R>,
+    header: &sam::Header,
+    offset: u64,
+) -> io::Result<Option<Vec<sam::alignment::RecordBuf>>>
+where
+    R: Read + Seek,
+{
+    reader.seek(SeekFrom::Start(offset))?;
+
+    let mut container = Container::default();
+
+    if reader.read_container(&mut container)? == 0 {
+        return Ok(None);
+    }
+
+    let compression_header = container.compression_header()?;
+
+    let mut records = Vec::new();
+
+    for result in container.slices() {
+        let slice = result?;
+
+        let (core_data_src, external_data_srcs) = slice.decode_blocks()?;
+
+        for record in slice.records(
+            reader.reference_sequence_repository.clone(),
+            header,
+            &compression_header,
+            &core_data_src,
+            &external_data_srcs,
+        )? {
+            let record = sam::alignment::RecordBuf::try_from_alignment_record(header, &record)?;
+            records.push(record);
+        }
+    }
+
+    Ok(Some(records))
+}
+
+fn intersecting_regions(region_set: &RegionSet, record: &sam::alignment::RecordBuf) -> Vec<usize> {
+    let (Some(reference_sequence_id), Some(start)) =
+        (record.reference_sequence_id(), record.alignment_start())
+    else {
+        return Vec::new();
+    };
+
+    let end = record.alignment_end().map_or(start, |end| end.max(start));
+
+    region_set.intersecting(reference_sequence_id, (start..=end).into())
+}
+
+fn intersects(
+    record: &sam::alignment::RecordBuf,
+    reference_sequence_id: usize,
+    region_interval: Interval,
+) -> bool {
+    if record.reference_sequence_id() != Some(reference_sequence_id) {
+        return false;
+    }
+
     match (record.alignment_start(), record.alignment_end()) {
         (Some(start), Some(end)) => {
             let alignment_interval = (start..=end).into();
@@ -154,3 +311,167 @@ fn intersects(record: &sam::alignment::RecordBuf, region_interval: Interval) ->
         _ => false,
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::num::NonZero;
+
+    use noodles_core::Position;
+    use noodles_fasta as fasta;
+    use noodles_sam::{
+        alignment::{
+            RecordBuf,
+            io::Write as _,
+            record::{
+                Flags,
+                cigar::{Op, op::Kind},
+            },
+            record_buf::{QualityScores, Sequence},
+        },
+        header::record::value::{Map, map::ReferenceSequence},
+    };
+
+    use super::*;
+
+    type IndexedCram = (sam::Header, fasta::Repository, Vec<u8>, crai::Index);
+
+    // Builds a CRAM file with mapped records on `sq0` and `sq1` at the given 1-based starts
+    // (4M), followed by unplaced, unmapped records with the given names.
+    //
+    // This returns the header, the reference sequence repository, the file, and its index.
+    pub(crate) fn build_cram(
+        mapped_records: &[(usize, usize)],
+        unmapped_record_names: &[&str],
+    ) -> Result<IndexedCram, Box<dyn std::error::Error>> {
+        let repository = fasta::Repository::new(vec![
+            fasta::Record::new(
+                fasta::record::Definition::new("sq0", None),
+                fasta::record::Sequence::from(b"TTCACCCAGATCTTAC".to_vec()),
+            ),
+            fasta::Record::new(
+                fasta::record::Definition::new("sq1", None),
+                fasta::record::Sequence::from(b"GATCTTACTTTTT".to_vec()),
+            ),
+        ]);
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(const { NonZero::new(16).unwrap() }),
+            )
+            .add_reference_sequence(
+                "sq1",
+                Map::<ReferenceSequence>::new(const { NonZero::new(13).unwrap() }),
+            )
+            .build();
+
+        let mut writer = crate::io::writer::Builder::default()
+            .set_reference_sequence_repository(repository.clone())
+            .build_from_writer(Vec::new());
+
+        writer.write_header(&header)?;
+
+        for (i, &(reference_sequence_id, start)) in mapped_records.iter().enumerate() {
+            let record = RecordBuf::builder()
+                .set_name(format!("r{i}"))
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(reference_sequence_id)
+                .set_alignment_start(Position::try_from(start)?)
+                .set_mapping_quality(sam::alignment::record::MappingQuality::MIN)
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                .set_sequence(Sequence::from(b"ACGT"))
+                .set_quality_scores(QualityScores::from(vec![45; 4]))
+                .build();
+
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        for name in unmapped_record_names {
+            let record = RecordBuf::builder()
+                .set_name(*name)
+                .set_flags(Flags::UNMAPPED)
+                .set_sequence(Sequence::from(b"ACGT"))
+                .set_quality_scores(QualityScores::from(vec![45; 4]))
+                .build();
+
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        writer.try_finish(&header)?;
+        let data = writer.into_inner();
+
+        let mut reader = crate::io::reader::Builder::default()
+            .set_reference_sequence_repository(repository.clone())
+            .build_from_reader(io::Cursor::new(&data));
+
+        let index = crate::fs::index_reader(&mut reader)?;
+
+        Ok((header, repository, data, index))
+    }
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        let (header, repository, data, index) =
+            build_cram(&[(0, 1), (0, 5), (0, 9), (1, 3)], &["u0"])?;
+
+        let mut reader = crate::io::reader::Builder::default()
+            .set_reference_sequence_repository(repository)
+            .build_from_reader(io::Cursor::new(data));
+
+        let reference_sequence_id = 0;
+        let interval = (Position::try_from(6)?..=Position::try_from(9)?).into();
+
+        let names: Vec<_> = Query::new(
+            &mut reader,
+            &header,
+            &index,
+            reference_sequence_id,
+            interval,
+        )
+        .map(|result| result.map(|record| record.name().map(|name| name.to_vec())))
+        .collect::<io::Result<_>>()?;
+
+        assert_eq!(names, [Some(b"r1".to_vec()), Some(b"r2".to_vec())]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_unmapped() -> Result<(), Box<dyn std::error::Error>> {
+        let (header, repository, data, index) =
+            build_cram(&[(0, 1), (0, 5), (1, 3)], &["u0", "u1"])?;
+
+        let mut reader = crate::io::reader::Builder::default()
+            .set_reference_sequence_repository(repository)
+            .build_from_reader(io::Cursor::new(data));
+
+        let names: Vec<_> = query_unmapped(&mut reader, &header, &index)
+            .map(|result| result.map(|record| record.name().map(|name| name.to_vec())))
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(names, [Some(b"u0".to_vec()), Some(b"u1".to_vec())]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_container_offsets() -> Result<(), noodles_core::position::TryFromIntError> {
+        let index = vec![
+            crai::Record::new(Some(0), Position::new(1), 10, 100, 0, 8),
+            crai::Record::new(Some(0), Position::new(11), 10, 100, 8, 8),
+            crai::Record::new(Some(0), Position::new(100), 10, 200, 0, 8),
+            crai::Record::new(Some(1), Position::new(1), 10, 300, 0, 8),
+            crai::Record::new(None, None, 0, 400, 0, 8),
+        ];
+
+        let region_set = RegionSet::new([
+            (0, (Position::try_from(5)?..=Position::try_from(8)?).into()),
+            (0, (Position::try_from(9)?..=Position::try_from(12)?).into()),
+            (1, Interval::from(..)),
+        ]);
+
+        assert_eq!(container_offsets(&index, &region_set), [100, 300]);
+
+        Ok(())
+    }
+}
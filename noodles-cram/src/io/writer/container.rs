@@ -114,9 +114,8 @@ fn build_container(
 
         blocks.extend(slice.external_data_blocks);
 
-        let last_landmark = landmarks.last().copied().unwrap_or(0);
-        let landmark = last_landmark + slice_size;
-        landmarks.push(landmark);
+        // A landmark is the start of a slice relative to the start of the container data.
+        landmarks.push(container_size);
 
         container_size += slice_size;
     }
@@ -0,0 +1,37 @@
+//! Prints statistics of each reference sequence in a coordinate-sorted index (CSI).
+//!
+//! The columns are the reference sequence ID, the number of mapped records, the number of
+//! unmapped records, the number of chunks, the number of windows with a bin at the deepest level,
+//! the total number of windows, and the compressed size.
+
+use std::{env, io};
+
+use noodles_csi::{self as csi, binning_index::Statistics};
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let index = csi::fs::read(src)?;
+    let statistics = Statistics::new(&index);
+
+    for (i, reference_sequence) in statistics.reference_sequences().iter().enumerate() {
+        let mapped_record_count = reference_sequence.mapped_record_count().unwrap_or_default();
+        let unmapped_record_count = reference_sequence
+            .unmapped_record_count()
+            .unwrap_or_default();
+
+        println!(
+            "{i}\t{mapped_record_count}\t{unmapped_record_count}\t{}\t{}\t{}\t{}",
+            reference_sequence.chunk_count(),
+            reference_sequence.leaf_window_count(),
+            reference_sequence.window_count(),
+            reference_sequence.compressed_size(),
+        );
+    }
+
+    if !statistics.is_consistent() {
+        eprintln!("warning: index has invalid bins or chunks");
+    }
+
+    Ok(())
+}
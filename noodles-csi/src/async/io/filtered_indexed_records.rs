@@ -3,7 +3,7 @@ use noodles_core::Region;
 use tokio::io::{self, AsyncBufRead, AsyncBufReadExt, Lines};
 
 use crate::{
-    binning_index::index::{Header, header::format::CoordinateSystem},
+    binning_index::index::{Header, header::Format},
     io::{
         IndexedRecord,
         indexed_records::{Record, parse_record},
@@ -16,7 +16,7 @@ struct Context<'r, R> {
     reference_sequence_name_index: usize,
     start_position_index: usize,
     end_position_index: Option<usize>,
-    coordinate_system: CoordinateSystem,
+    format: Format,
     region: &'r Region,
 }
 
@@ -34,7 +34,7 @@ where
         reference_sequence_name_index: header.reference_sequence_name_index(),
         start_position_index: header.start_position_index(),
         end_position_index: header.end_position_index(),
-        coordinate_system: header.format().coordinate_system(),
+        format: header.format(),
         region,
     };
 
@@ -54,7 +54,7 @@ where
                 ctx.reference_sequence_name_index,
                 ctx.start_position_index,
                 ctx.end_position_index,
-                ctx.coordinate_system,
+                ctx.format,
             ) {
                 Ok(record) => record,
                 Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
@@ -3,6 +3,9 @@
 pub mod index;
 mod indexer;
 mod reference_sequence;
+mod region_set;
+pub mod shard;
+pub mod statistics;
 
 use std::io;
 
@@ -10,7 +13,10 @@ use noodles_bgzf as bgzf;
 use noodles_core::region::Interval;
 
 use self::index::{Header, reference_sequence::bin::Chunk};
-pub use self::{index::Index, indexer::Indexer, reference_sequence::ReferenceSequence};
+pub use self::{
+    index::Index, indexer::Indexer, reference_sequence::ReferenceSequence, region_set::RegionSet,
+    shard::Shard, statistics::Statistics,
+};
 
 /// A binning index.
 pub trait BinningIndex {
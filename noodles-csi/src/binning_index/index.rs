@@ -2,6 +2,7 @@
 
 mod builder;
 pub mod header;
+mod rebin;
 pub mod reference_sequence;
 
 pub use self::{builder::Builder, header::Header, reference_sequence::ReferenceSequence};
@@ -11,6 +12,7 @@ use std::io;
 use noodles_bgzf as bgzf;
 use noodles_core::{Position, region::Interval};
 
+use self::reference_sequence::index::{BinnedIndex, LinearIndex};
 use super::{BinningIndex, index::reference_sequence::bin::Chunk};
 
 /// A binning index.
@@ -51,6 +53,62 @@ where
     pub fn reference_sequences(&self) -> &[ReferenceSequence<I>] {
         &self.reference_sequences
     }
+
+    /// Converts the index to a coordinate-sorted index (CSI) with the given binning scheme.
+    ///
+    /// The bins and linear index are rebuilt from the existing chunks, without reading the
+    /// associated file. This can be used to convert a BAI or tabix index to a CSI, e.g., to
+    /// index reference sequences longer than 2^29 - 1 bases, or to change the binning scheme of a
+    /// CSI.
+    ///
+    /// The positions of records within a bin are not known, so each chunk is placed in the
+    /// smallest bin that covers its original bin. Queries on the new index return every record
+    /// they would have on the original, but they may read more data.
+    ///
+    /// This fails if the binning scheme is invalid or if any records may be past its max
+    /// position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, BinningIndex};
+    ///
+    /// let index = csi::Index::default();
+    /// let index = index.rebin(12, 7)?;
+    ///
+    /// assert_eq!(index.min_shift(), 12);
+    /// assert_eq!(index.depth(), 7);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn rebin(&self, min_shift: u8, depth: u8) -> io::Result<Index<BinnedIndex>> {
+        rebin::rebin(self, min_shift, depth)
+    }
+
+    /// Converts the index to one with a linear index, i.e., a BAI or tabix index.
+    ///
+    /// This is the same as [`Self::rebin`] using the binning scheme of a BAI or tabix index
+    /// (`min_shift` = 14, `depth` = 5). This fails if any records may be past position 2^29 - 1.
+    ///
+    /// The header is kept, which is required to write a tabix index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, BinningIndex};
+    ///
+    /// let index = csi::Index::builder().set_min_shift(12).set_depth(7).build();
+    /// let index = index.rebin_linear()?;
+    ///
+    /// assert_eq!(index.min_shift(), 14);
+    /// assert_eq!(index.depth(), 5);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn rebin_linear(&self) -> io::Result<Index<LinearIndex>> {
+        const MIN_SHIFT: u8 = 14;
+        const DEPTH: u8 = 5;
+
+        rebin::rebin(self, MIN_SHIFT, DEPTH)
+    }
 }
 
 impl<I> Default for Index<I>
@@ -0,0 +1,296 @@
+use std::io;
+
+use indexmap::IndexMap;
+use noodles_core::Position;
+
+use super::{
+    Index, ReferenceSequence, max_position,
+    reference_sequence::{self, Bin, reg2bin},
+};
+use crate::binning_index::{
+    ReferenceSequence as _,
+    statistics::{first_id, level},
+};
+
+// The maximum depth supported by bin IDs.
+const MAX_DEPTH: u8 = 10;
+
+pub(super) fn rebin<I, J>(src: &Index<I>, min_shift: u8, depth: u8) -> io::Result<Index<J>>
+where
+    I: reference_sequence::Index,
+    J: reference_sequence::Index + Default,
+{
+    if min_shift == 0
+        || depth > MAX_DEPTH
+        || u32::from(min_shift) + 3 * u32::from(depth) >= usize::BITS
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid binning scheme",
+        ));
+    }
+
+    let max_position = max_position(min_shift, depth)?;
+
+    let reference_sequences = src
+        .reference_sequences
+        .iter()
+        .map(|reference_sequence| {
+            rebin_reference_sequence(
+                src.min_shift,
+                src.depth,
+                reference_sequence,
+                min_shift,
+                depth,
+                max_position,
+            )
+        })
+        .collect::<io::Result<_>>()?;
+
+    let mut builder = Index::builder()
+        .set_min_shift(min_shift)
+        .set_depth(depth)
+        .set_reference_sequences(reference_sequences);
+
+    if let Some(header) = src.header.clone() {
+        builder = builder.set_header(header);
+    }
+
+    if let Some(n) = src.unplaced_unmapped_record_count {
+        builder = builder.set_unplaced_unmapped_record_count(n);
+    }
+
+    Ok(builder.build())
+}
+
+fn rebin_reference_sequence<I, J>(
+    src_min_shift: u8,
+    src_depth: u8,
+    src: &ReferenceSequence<I>,
+    min_shift: u8,
+    depth: u8,
+    max_position: Position,
+) -> io::Result<ReferenceSequence<J>>
+where
+    I: reference_sequence::Index,
+    J: reference_sequence::Index + Default,
+{
+    let mut entries = Vec::new();
+
+    for (&id, bin) in src.bins() {
+        let (start, end) = bin_interval(src_min_shift, src_depth, id, max_position)?;
+
+        for &chunk in bin.chunks() {
+            entries.push((chunk, start, end));
+        }
+    }
+
+    // The linear index expects chunks in file order.
+    entries.sort_by_key(|(chunk, _, _)| chunk.start());
+
+    let mut bins: IndexMap<usize, Bin> = IndexMap::new();
+    let mut index = J::default();
+
+    for (chunk, start, end) in entries {
+        let id = reg2bin(start, end, min_shift, depth);
+
+        bins.entry(id)
+            .or_insert_with(|| Bin::new(Vec::new()))
+            .add_chunk(chunk);
+
+        index.update(min_shift, depth, start, end, chunk);
+    }
+
+    Ok(ReferenceSequence::new(bins, index, src.metadata().cloned()))
+}
+
+// Returns the interval covered by a bin, clamped to the given max position.
+//
+// The positions of the records in a bin are unknown, so the whole bin interval is used. This fails
+// if the records in the bin must be past the max position: records in a bin that is not at the
+// deepest level overlap the start of its second child, as they would otherwise be in a child.
+fn bin_interval(
+    min_shift: u8,
+    depth: u8,
+    id: usize,
+    max_position: Position,
+) -> io::Result<(Position, Position)> {
+    if id >= Bin::max_id(depth) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid bin ID: {id}"),
+        ));
+    }
+
+    let l = level(depth, id);
+    let shift = u32::from(min_shift) + 3 * u32::from(depth - l);
+
+    // 0-based
+    let bin_start = (id - first_id(l))
+        .checked_shl(shift)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid bin ID"))?;
+
+    let min_end = if l < depth {
+        bin_start + (1 << (shift - 3))
+    } else {
+        bin_start
+    };
+
+    if min_end >= usize::from(max_position) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "bin is past the max position of the binning scheme",
+        ));
+    }
+
+    let bin_end = bin_start.saturating_add((1 << shift) - 1);
+
+    let start = Position::try_from(bin_start + 1)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let end = Position::try_from(bin_end + 1)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+        .min(max_position);
+
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf as bgzf;
+
+    use super::*;
+    use crate::{
+        BinningIndex,
+        binning_index::{
+            Indexer,
+            index::reference_sequence::{
+                bin::Chunk,
+                index::{BinnedIndex, LinearIndex},
+            },
+        },
+    };
+
+    fn contains_record(chunks: &[Chunk], i: u64) -> bool {
+        let offset = bgzf::VirtualPosition::from(i * 8);
+
+        chunks
+            .iter()
+            .any(|chunk| chunk.start() <= offset && offset < chunk.end())
+    }
+
+    fn build_index() -> Result<Index<LinearIndex>, Box<dyn std::error::Error>> {
+        let mut indexer = Indexer::<LinearIndex>::default();
+
+        for (i, (start, end)) in [(8, 13), (16380, 16390), (21, 40000), (100000, 100100)]
+            .into_iter()
+            .enumerate()
+        {
+            let chunk = Chunk::new(
+                bgzf::VirtualPosition::from(i as u64 * 8),
+                bgzf::VirtualPosition::from((i as u64 + 1) * 8),
+            );
+
+            indexer.add_record(
+                Some((
+                    0,
+                    Position::try_from(start)?,
+                    Position::try_from(end)?,
+                    true,
+                )),
+                chunk,
+            )?;
+        }
+
+        Ok(indexer.build(1))
+    }
+
+    #[test]
+    fn test_rebin() -> Result<(), Box<dyn std::error::Error>> {
+        let src = build_index()?;
+        let dst: Index<BinnedIndex> = rebin(&src, 12, 6)?;
+
+        assert_eq!(dst.min_shift(), 12);
+        assert_eq!(dst.depth(), 6);
+        assert_eq!(dst.reference_sequences().len(), 1);
+        assert_eq!(
+            dst.reference_sequences()[0].metadata(),
+            src.reference_sequences()[0].metadata()
+        );
+
+        // Every record that intersects a query is still in the returned chunks.
+        for (start, end, expected) in [
+            (1, 8, vec![0]),
+            (16385, 16385, vec![1, 2]),
+            (100050, 100050, vec![3]),
+        ] {
+            let interval = (Position::try_from(start)?..=Position::try_from(end)?).into();
+            let chunks = dst.query(0, interval)?;
+
+            for i in expected {
+                assert!(contains_record(&chunks, i));
+            }
+        }
+
+        let dst: Index<LinearIndex> = rebin(&dst, 14, 5)?;
+        assert_eq!(dst.min_shift(), 14);
+        assert_eq!(dst.depth(), 5);
+
+        let chunks = dst.query(0, (Position::try_from(100050)?..).into())?;
+        assert!(contains_record(&chunks, 3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rebin_with_invalid_binning_scheme() -> Result<(), Box<dyn std::error::Error>> {
+        let src = build_index()?;
+
+        assert!(matches!(
+            rebin::<_, BinnedIndex>(&src, 0, 5),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            rebin::<_, BinnedIndex>(&src, 14, 11),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bin_interval() -> Result<(), Box<dyn std::error::Error>> {
+        const MIN_SHIFT: u8 = 14;
+        const DEPTH: u8 = 6;
+
+        let max_position = max_position(MIN_SHIFT, DEPTH)?;
+
+        assert_eq!(
+            bin_interval(MIN_SHIFT, DEPTH, 37449, max_position)?,
+            (Position::MIN, Position::try_from(16384)?)
+        );
+
+        assert_eq!(
+            bin_interval(MIN_SHIFT, DEPTH, 1, max_position)?,
+            (Position::MIN, Position::try_from(536870912)?)
+        );
+
+        let bai_max_position = super::max_position(14, 5)?;
+
+        // The second bin at level 1 starts at 2^29.
+        assert!(bin_interval(MIN_SHIFT, DEPTH, 2, bai_max_position).is_err());
+
+        // Records in the root bin overlap 2^29.
+        assert!(bin_interval(MIN_SHIFT, DEPTH, 0, bai_max_position).is_err());
+
+        assert_eq!(
+            bin_interval(MIN_SHIFT, DEPTH, 1, bai_max_position)?,
+            (Position::MIN, bai_max_position)
+        );
+
+        assert!(bin_interval(MIN_SHIFT, DEPTH, Bin::max_id(DEPTH), max_position).is_err());
+
+        Ok(())
+    }
+}
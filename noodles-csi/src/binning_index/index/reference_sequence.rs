@@ -234,9 +234,17 @@ impl<I> binning_index::ReferenceSequence for ReferenceSequence<I>
 where
     I: Index,
 {
+    fn bins(&self) -> Box<dyn Iterator<Item = (usize, &Bin)> + '_> {
+        Box::new(self.bins.iter().map(|(&id, bin)| (id, bin)))
+    }
+
     fn metadata(&self) -> Option<&Metadata> {
         self.metadata.as_ref()
     }
+
+    fn linear_index(&self) -> Option<&[bgzf::VirtualPosition]> {
+        self.index.as_linear_index()
+    }
 }
 
 const M: usize = NonZero::new(8).unwrap().get();
@@ -247,7 +255,7 @@ pub(crate) fn parent_id(id: usize) -> Option<usize> {
 }
 
 // `CSIv1.pdf` (2020-07-21)
-fn reg2bin(start: Position, end: Position, min_shift: u8, depth: u8) -> usize {
+pub(crate) fn reg2bin(start: Position, end: Position, min_shift: u8, depth: u8) -> usize {
     // [beg, end), 0-based
     let beg = usize::from(start) - 1;
     let end = usize::from(end);
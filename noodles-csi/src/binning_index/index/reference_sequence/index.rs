@@ -20,4 +20,11 @@ pub trait Index {
 
     /// Adds a record to the index.
     fn update(&mut self, min_shift: u8, depth: u8, start: Position, end: Position, chunk: Chunk);
+
+    /// Returns the index as a linear index, if it is one.
+    ///
+    /// The default implementation returns `None`.
+    fn as_linear_index(&self) -> Option<&[bgzf::VirtualPosition]> {
+        None
+    }
 }
@@ -29,6 +29,10 @@ impl Index for LinearIndex {
             self.resize(new_len, chunk.start());
         }
     }
+
+    fn as_linear_index(&self) -> Option<&[bgzf::VirtualPosition]> {
+        Some(self)
+    }
 }
 
 #[cfg(test)]
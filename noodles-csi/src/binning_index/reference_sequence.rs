@@ -1,7 +1,27 @@
-use super::index::reference_sequence::Metadata;
+use std::iter;
+
+use noodles_bgzf as bgzf;
+
+use super::index::reference_sequence::{Bin, Metadata};
 
 /// A binning index reference sequence.
 pub trait ReferenceSequence {
+    /// Returns the bins in the reference sequence as (ID, bin) pairs.
+    ///
+    /// This does not include the metadata pseudo-bin. The default implementation returns no
+    /// bins.
+    fn bins(&self) -> Box<dyn Iterator<Item = (usize, &Bin)> + '_> {
+        Box::new(iter::empty())
+    }
+
     /// Returns the optional metadata for the reference sequence.
     fn metadata(&self) -> Option<&Metadata>;
+
+    /// Returns the linear index, if the reference sequence has one.
+    ///
+    /// Each entry is the start position of the first record that overlaps a 16 KiB interval. The
+    /// default implementation returns `None`.
+    fn linear_index(&self) -> Option<&[bgzf::VirtualPosition]> {
+        None
+    }
 }
@@ -0,0 +1,277 @@
+use std::{collections::BTreeMap, io};
+
+use noodles_core::{Position, region::Interval};
+
+use super::{BinningIndex, index::reference_sequence::bin::Chunk, merge_chunks};
+
+/// A set of regions resolved to reference sequence IDs.
+///
+/// A region set is used to query many regions at once. [`Self::query`] merges the chunks of all
+/// regions so that each block is read at most once, and [`Self::intersecting`] tags a record with
+/// the regions it overlaps.
+///
+/// Regions are identified by their position in the input.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RegionSet {
+    reference_sequences: BTreeMap<usize, Entries>,
+    len: usize,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct Entries {
+    // (start, end, interval, region index), sorted by start.
+    entries: Vec<(Position, Position, Interval, usize)>,
+    // The running maximum end of `entries`.
+    max_ends: Vec<Position>,
+}
+
+impl RegionSet {
+    /// Creates a region set from a list of reference sequence IDs and intervals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_csi::binning_index::RegionSet;
+    ///
+    /// let regions = RegionSet::new([
+    ///     (0, (Position::try_from(8)?..=Position::try_from(13)?).into()),
+    ///     (1, (Position::try_from(5)?..).into()),
+    /// ]);
+    ///
+    /// assert_eq!(regions.len(), 2);
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn new<I>(regions: I) -> Self
+    where
+        I: IntoIterator<Item = (usize, Interval)>,
+    {
+        let mut reference_sequences: BTreeMap<usize, Entries> = BTreeMap::new();
+        let mut len = 0;
+
+        for (i, (reference_sequence_id, interval)) in regions.into_iter().enumerate() {
+            let (start, end) = bounds(interval);
+
+            reference_sequences
+                .entry(reference_sequence_id)
+                .or_default()
+                .entries
+                .push((start, end, interval, i));
+
+            len += 1;
+        }
+
+        for entries in reference_sequences.values_mut() {
+            entries.entries.sort_by_key(|(start, ..)| *start);
+
+            let mut max_end = Position::MIN;
+
+            entries.max_ends = entries
+                .entries
+                .iter()
+                .map(|(_, end, ..)| {
+                    max_end = max_end.max(*end);
+                    max_end
+                })
+                .collect();
+        }
+
+        Self {
+            reference_sequences,
+            len,
+        }
+    }
+
+    /// Returns the number of regions in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::binning_index::RegionSet;
+    /// let regions = RegionSet::default();
+    /// assert_eq!(regions.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the set has no regions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::binning_index::RegionSet;
+    /// let regions = RegionSet::default();
+    /// assert!(regions.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the merged chunks of all regions in the set.
+    ///
+    /// The chunks of each region are queried from the given index and merged into a sorted list of
+    /// non-overlapping chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_csi::{
+    ///     self as csi,
+    ///     binning_index::{RegionSet, index::ReferenceSequence},
+    /// };
+    ///
+    /// let reference_sequence = ReferenceSequence::new(Default::default(), Default::default(), None);
+    /// let index = csi::Index::builder()
+    ///     .set_reference_sequences(vec![reference_sequence])
+    ///     .build();
+    ///
+    /// let regions = RegionSet::new([(0, (Position::try_from(8)?..=Position::try_from(13)?).into())]);
+    /// let chunks = regions.query(&index)?;
+    /// assert!(chunks.is_empty());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query<I>(&self, index: &I) -> io::Result<Vec<Chunk>>
+    where
+        I: BinningIndex + ?Sized,
+    {
+        let mut chunks = Vec::new();
+
+        for (&reference_sequence_id, entries) in &self.reference_sequences {
+            for &(_, _, interval, _) in &entries.entries {
+                chunks.extend(index.query(reference_sequence_id, interval)?);
+            }
+        }
+
+        Ok(merge_chunks(&chunks))
+    }
+
+    /// Returns the indices of the regions that intersect the given interval.
+    ///
+    /// The indices are in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_csi::binning_index::RegionSet;
+    ///
+    /// let regions = RegionSet::new([
+    ///     (0, (Position::try_from(8)?..=Position::try_from(13)?).into()),
+    ///     (0, (Position::try_from(13)?..=Position::try_from(21)?).into()),
+    /// ]);
+    ///
+    /// let interval = (Position::try_from(5)?..=Position::try_from(13)?).into();
+    /// assert_eq!(regions.intersecting(0, interval), [0, 1]);
+    /// assert!(regions.intersecting(1, interval).is_empty());
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn intersecting(&self, reference_sequence_id: usize, interval: Interval) -> Vec<usize> {
+        let Some(entries) = self.reference_sequences.get(&reference_sequence_id) else {
+            return Vec::new();
+        };
+
+        let (start, end) = bounds(interval);
+
+        // Only entries that start at or before the end of the interval can intersect it.
+        let n = entries.entries.partition_point(|(s, ..)| *s <= end);
+
+        let mut indices: Vec<_> = entries.entries[..n]
+            .iter()
+            .zip(&entries.max_ends[..n])
+            .rev()
+            .take_while(|(_, max_end)| **max_end >= start)
+            .filter(|((_, e, ..), _)| *e >= start)
+            .map(|((.., i), _)| *i)
+            .collect();
+
+        indices.sort_unstable();
+
+        indices
+    }
+}
+
+fn bounds(interval: Interval) -> (Position, Position) {
+    (
+        interval.start().unwrap_or(Position::MIN),
+        interval.end().unwrap_or(Position::MAX),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf as bgzf;
+
+    use super::*;
+
+    #[test]
+    fn test_intersecting() -> Result<(), noodles_core::position::TryFromIntError> {
+        let regions = RegionSet::new([
+            (
+                0,
+                (Position::try_from(21)?..=Position::try_from(34)?).into(),
+            ),
+            (0, (Position::try_from(1)?..=Position::try_from(55)?).into()),
+            (0, (Position::try_from(8)?..=Position::try_from(13)?).into()),
+            (1, Interval::from(..)),
+        ]);
+
+        let interval = (Position::try_from(15)?..=Position::try_from(20)?).into();
+        assert_eq!(regions.intersecting(0, interval), [1]);
+
+        let interval = (Position::try_from(13)?..=Position::try_from(21)?).into();
+        assert_eq!(regions.intersecting(0, interval), [0, 1, 2]);
+
+        let interval = (Position::try_from(56)?..=Position::try_from(89)?).into();
+        assert!(regions.intersecting(0, interval).is_empty());
+
+        assert_eq!(regions.intersecting(1, interval), [3]);
+        assert!(regions.intersecting(2, interval).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::binning_index::{Indexer, index::reference_sequence::index::BinnedIndex};
+
+        let mut indexer = Indexer::<BinnedIndex>::default();
+
+        for (start, end, chunk_start, chunk_end) in
+            [(8, 13, 0, 9), (20000, 20010, 9, 21), (40000, 40010, 21, 34)]
+        {
+            indexer.add_record(
+                Some((
+                    0,
+                    Position::try_from(start)?,
+                    Position::try_from(end)?,
+                    true,
+                )),
+                Chunk::new(
+                    bgzf::VirtualPosition::from(chunk_start),
+                    bgzf::VirtualPosition::from(chunk_end),
+                ),
+            )?;
+        }
+
+        let index = indexer.build(1);
+
+        let regions = RegionSet::new([
+            (
+                0,
+                (Position::try_from(20000)?..=Position::try_from(20005)?).into(),
+            ),
+            (0, (Position::try_from(1)?..=Position::try_from(8)?).into()),
+        ]);
+
+        let actual = regions.query(&index)?;
+        let expected = [Chunk::new(
+            bgzf::VirtualPosition::from(0),
+            bgzf::VirtualPosition::from(21),
+        )];
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,358 @@
+//! Binning index sharding.
+
+use std::{collections::BTreeMap, num::NonZero};
+
+use bstr::BStr;
+use noodles_core::{Position, Region};
+
+use super::{
+    BinningIndex, ReferenceSequence,
+    index::reference_sequence::Bin,
+    statistics::{chunk_size, first_id, level},
+};
+
+/// A partition of the records in an indexed file.
+///
+/// Each record is owned by exactly one shard of a plan, determined by its reference sequence and
+/// start position. Readers should use [`Self::contains`] to discard records that overlap a shard
+/// but start in another.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Shard {
+    /// Records that start in any of the given regions.
+    Regions(Vec<Region>),
+    /// Unplaced, unmapped records, i.e., records without a reference sequence or start position.
+    ///
+    /// This includes records that have a reference sequence but no start position.
+    Unmapped,
+}
+
+impl Shard {
+    /// Returns whether a record with the given reference sequence name and start position is
+    /// owned by this shard.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::{Position, Region};
+    /// use noodles_csi::binning_index::Shard;
+    ///
+    /// let start = Position::try_from(8)?;
+    /// let end = Position::try_from(13)?;
+    /// let shard = Shard::Regions(vec![Region::new("sq0", start..=end)]);
+    ///
+    /// assert!(shard.contains(Some(b"sq0".as_slice()), Some(start)));
+    /// assert!(!shard.contains(Some(b"sq0".as_slice()), Position::new(5)));
+    /// assert!(!shard.contains(Some(b"sq1".as_slice()), Some(start)));
+    /// assert!(!shard.contains(None, None));
+    ///
+    /// assert!(Shard::Unmapped.contains(None, None));
+    /// # Ok::<_, noodles_core::position::TryFromIntError>(())
+    /// ```
+    pub fn contains(
+        &self,
+        reference_sequence_name: Option<&[u8]>,
+        start: Option<Position>,
+    ) -> bool {
+        match (self, reference_sequence_name, start) {
+            (Self::Regions(regions), Some(name), Some(start)) => {
+                let name = BStr::new(name);
+
+                regions
+                    .iter()
+                    .any(|region| region.name() == name && region.interval().contains(start))
+            }
+            (Self::Regions(_), _, _) => false,
+            (Self::Unmapped, name, start) => name.is_none() || start.is_none(),
+        }
+    }
+}
+
+/// Plans shards of roughly equal compressed size using a binning index.
+///
+/// This partitions the given reference sequences into at most `shard_count` shards of
+/// [`Shard::Regions`]. The data in each bin is distributed evenly across the windows it covers,
+/// and shards are cut only at window boundaries. Together, the regions cover every position of
+/// every reference sequence.
+///
+/// A [`Shard::Unmapped`] shard is always appended. Records with a reference sequence but no start
+/// position are owned by it, and an index does not necessarily count them as unplaced, unmapped
+/// records.
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZero;
+/// use noodles_csi::binning_index::{self, Shard};
+///
+/// let index = noodles_csi::Index::default();
+/// let shards = binning_index::shard::plan(&index, [b"sq0"], NonZero::<usize>::MIN);
+///
+/// assert_eq!(shards.len(), 2);
+/// assert_eq!(shards[1], Shard::Unmapped);
+/// ```
+pub fn plan<I, N>(
+    index: &I,
+    reference_sequence_names: impl IntoIterator<Item = N>,
+    shard_count: NonZero<usize>,
+) -> Vec<Shard>
+where
+    I: BinningIndex + ?Sized,
+    N: AsRef<[u8]>,
+{
+    let depth = index.depth();
+    let window_size = NonZero::new(1 << index.min_shift()).unwrap_or(NonZero::<u64>::MIN);
+
+    let mut index_reference_sequences = index.reference_sequences();
+
+    let reference_sequences = reference_sequence_names.into_iter().map(|name| {
+        let window_sizes = index_reference_sequences
+            .next()
+            .map(|reference_sequence| window_sizes(depth, reference_sequence))
+            .unwrap_or_default();
+
+        (name, window_sizes)
+    });
+
+    let mut shards = partition(reference_sequences, window_size, shard_count);
+    shards.push(Shard::Unmapped);
+    shards
+}
+
+/// Partitions reference sequences into at most `shard_count` shards of roughly equal size.
+///
+/// Each reference sequence is given with the sizes of its windows, keyed by window index, where
+/// window `i` covers the positions `[i * window_size + 1, (i + 1) * window_size]`. Shards are cut
+/// at window boundaries when the cumulative size reaches the next equal share of the total size.
+/// Reference sequences without data are kept whole.
+///
+/// This only returns shards of [`Shard::Regions`].
+pub fn partition<I, N>(
+    reference_sequences: I,
+    window_size: NonZero<u64>,
+    shard_count: NonZero<usize>,
+) -> Vec<Shard>
+where
+    I: IntoIterator<Item = (N, BTreeMap<usize, u64>)>,
+    N: AsRef<[u8]>,
+{
+    let reference_sequences: Vec<_> = reference_sequences.into_iter().collect();
+
+    let total_size: u128 = reference_sequences
+        .iter()
+        .flat_map(|(_, window_sizes)| window_sizes.values())
+        .map(|&size| u128::from(size))
+        .sum();
+
+    let shard_count = shard_count.get();
+    let threshold = |i: usize| total_size * (i as u128 + 1) / shard_count as u128;
+
+    let mut shards = Vec::new();
+    let mut regions = Vec::new();
+    let mut size = 0;
+
+    for (name, window_sizes) in reference_sequences {
+        let name = name.as_ref();
+        let mut start = Position::MIN;
+
+        for (&i, &window_size_in_bytes) in &window_sizes {
+            size += u128::from(window_size_in_bytes);
+
+            if shards.len() + 1 >= shard_count || size < threshold(shards.len()) {
+                continue;
+            }
+
+            let Some(end) = window_end(i, window_size) else {
+                continue;
+            };
+
+            if end < start {
+                continue;
+            }
+
+            regions.push(Region::new(name, start..=end));
+            shards.push(Shard::Regions(regions.split_off(0)));
+
+            while shards.len() + 1 < shard_count && size >= threshold(shards.len()) {
+                // A window can exceed multiple shares. These empty shards are removed below.
+                shards.push(Shard::Regions(Vec::new()));
+            }
+
+            match end.checked_add(1) {
+                Some(position) => start = position,
+                None => break,
+            }
+        }
+
+        regions.push(Region::new(name, start..));
+    }
+
+    if !regions.is_empty() {
+        shards.push(Shard::Regions(regions));
+    }
+
+    shards.retain(|shard| !matches!(shard, Shard::Regions(regions) if regions.is_empty()));
+
+    shards
+}
+
+fn window_end(i: usize, window_size: NonZero<u64>) -> Option<Position> {
+    u64::try_from(i)
+        .ok()
+        .and_then(|i| i.checked_add(1))
+        .and_then(|n| n.checked_mul(window_size.get()))
+        .and_then(|n| usize::try_from(n).ok())
+        .and_then(Position::new)
+}
+
+// Returns the size of the data in each window.
+//
+// The size of a bin's chunks is distributed evenly across the windows the bin covers, up to the
+// last window that starts a bin.
+fn window_sizes(depth: u8, reference_sequence: &dyn ReferenceSequence) -> BTreeMap<usize, u64> {
+    let max_id = Bin::max_id(depth);
+
+    let spans: Vec<_> = reference_sequence
+        .bins()
+        .filter(|&(id, _)| id < max_id)
+        .map(|(id, bin)| {
+            let size: u64 = bin.chunks().iter().map(|&chunk| chunk_size(chunk)).sum();
+            let (start, len) = bin_windows(depth, id);
+            (start, len, size)
+        })
+        .collect();
+
+    let Some(window_count) = spans.iter().map(|(start, _, _)| start + 1).max() else {
+        return BTreeMap::new();
+    };
+
+    let mut window_sizes = BTreeMap::new();
+
+    for (start, len, size) in spans {
+        let end = start.saturating_add(len).min(window_count.max(start + 1));
+        let n = (end - start) as u64;
+
+        for (j, i) in (start..end).enumerate() {
+            let mut share = size / n;
+
+            if (j as u64) < size % n {
+                share += 1;
+            }
+
+            *window_sizes.entry(i).or_default() += share;
+        }
+    }
+
+    window_sizes
+}
+
+// Returns the index of the first window covered by a bin and the number of windows it covers.
+fn bin_windows(depth: u8, id: usize) -> (usize, usize) {
+    let l = level(depth, id);
+    let len = 1 << (3 * usize::from(depth - l));
+    let start = (id - first_id(l)) * len;
+    (start, len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains() -> Result<(), noodles_core::position::TryFromIntError> {
+        let shard = Shard::Regions(vec![
+            Region::new("sq0", Position::try_from(5)?..),
+            Region::new("sq1", ..=Position::try_from(8)?),
+        ]);
+
+        assert!(shard.contains(Some(b"sq0".as_slice()), Position::new(5)));
+        assert!(!shard.contains(Some(b"sq0".as_slice()), Position::new(4)));
+        assert!(shard.contains(Some(b"sq1".as_slice()), Position::new(8)));
+        assert!(!shard.contains(Some(b"sq1".as_slice()), Position::new(9)));
+        assert!(!shard.contains(Some(b"sq0".as_slice()), None));
+        assert!(!shard.contains(None, None));
+
+        assert!(Shard::Unmapped.contains(Some(b"sq0".as_slice()), None));
+        assert!(Shard::Unmapped.contains(None, Position::new(5)));
+        assert!(!Shard::Unmapped.contains(Some(b"sq0".as_slice()), Position::new(5)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_partition() -> Result<(), noodles_core::position::TryFromIntError> {
+        const WINDOW_SIZE: NonZero<u64> = match NonZero::new(10) {
+            Some(n) => n,
+            None => unreachable!(),
+        };
+
+        let reference_sequences = [
+            ("sq0", [(0, 10), (1, 10), (3, 10)].into_iter().collect()),
+            ("sq1", BTreeMap::new()),
+            ("sq2", [(0, 10)].into_iter().collect()),
+        ];
+
+        let shards = partition(
+            reference_sequences.clone(),
+            WINDOW_SIZE,
+            NonZero::<usize>::MIN,
+        );
+
+        assert_eq!(
+            shards,
+            [Shard::Regions(vec![
+                Region::new("sq0", Position::MIN..),
+                Region::new("sq1", Position::MIN..),
+                Region::new("sq2", Position::MIN..),
+            ])]
+        );
+
+        let shards = partition(
+            reference_sequences.clone(),
+            WINDOW_SIZE,
+            NonZero::new(2).unwrap(),
+        );
+
+        assert_eq!(
+            shards,
+            [
+                Shard::Regions(vec![Region::new(
+                    "sq0",
+                    Position::MIN..=Position::try_from(20)?
+                )]),
+                Shard::Regions(vec![
+                    Region::new("sq0", Position::try_from(21)?..),
+                    Region::new("sq1", Position::MIN..),
+                    Region::new("sq2", Position::MIN..),
+                ]),
+            ]
+        );
+
+        // Shares smaller than a window result in fewer shards.
+        let shards = partition(reference_sequences, WINDOW_SIZE, NonZero::new(8).unwrap());
+        assert_eq!(shards.len(), 5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_partition_with_no_data() {
+        let shards = partition(
+            [("sq0", BTreeMap::new())],
+            NonZero::<u64>::MIN,
+            NonZero::new(4).unwrap(),
+        );
+
+        assert_eq!(
+            shards,
+            [Shard::Regions(vec![Region::new("sq0", Position::MIN..)])]
+        );
+    }
+
+    #[test]
+    fn test_bin_windows() {
+        assert_eq!(bin_windows(5, 0), (0, 32768));
+        assert_eq!(bin_windows(5, 2), (4096, 4096));
+        assert_eq!(bin_windows(5, 4681), (0, 1));
+        assert_eq!(bin_windows(5, 4683), (2, 1));
+    }
+}
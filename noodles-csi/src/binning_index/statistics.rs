@@ -0,0 +1,213 @@
+//! Binning index statistics.
+
+mod reference_sequence;
+
+pub use self::reference_sequence::ReferenceSequence;
+pub(crate) use self::reference_sequence::{chunk_size, first_id, level};
+
+use super::BinningIndex;
+
+/// Binning index statistics.
+///
+/// These are summaries computed only from an index, without reading the associated file. They
+/// are useful to plan how to partition a file into regions of similar work and to detect indexes
+/// that are inconsistent.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statistics {
+    min_shift: u8,
+    depth: u8,
+    reference_sequences: Vec<ReferenceSequence>,
+    unplaced_unmapped_record_count: Option<u64>,
+}
+
+impl Statistics {
+    /// Computes statistics of a binning index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, binning_index::Statistics};
+    ///
+    /// let index = csi::Index::default();
+    /// let statistics = Statistics::new(&index);
+    /// assert!(statistics.reference_sequences().is_empty());
+    /// ```
+    pub fn new<I>(index: &I) -> Self
+    where
+        I: BinningIndex + ?Sized,
+    {
+        let min_shift = index.min_shift();
+        let depth = index.depth();
+
+        let reference_sequences = index
+            .reference_sequences()
+            .map(|reference_sequence| ReferenceSequence::new(depth, reference_sequence))
+            .collect();
+
+        Self {
+            min_shift,
+            depth,
+            reference_sequences,
+            unplaced_unmapped_record_count: index.unplaced_unmapped_record_count(),
+        }
+    }
+
+    /// Returns the number of bits for the minimum interval.
+    pub fn min_shift(&self) -> u8 {
+        self.min_shift
+    }
+
+    /// Returns the depth of the binning index.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the size of a window, i.e., the interval covered by a bin at the deepest level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, binning_index::Statistics};
+    /// let statistics = Statistics::new(&csi::Index::default());
+    /// assert_eq!(statistics.window_size(), 16384);
+    /// ```
+    pub fn window_size(&self) -> u64 {
+        1 << self.min_shift
+    }
+
+    /// Returns the statistics of each reference sequence.
+    pub fn reference_sequences(&self) -> &[ReferenceSequence] {
+        &self.reference_sequences
+    }
+
+    /// Returns the number of unplaced, unmapped records, if available.
+    pub fn unplaced_unmapped_record_count(&self) -> Option<u64> {
+        self.unplaced_unmapped_record_count
+    }
+
+    /// Returns the total number of mapped records.
+    ///
+    /// This is the sum of the mapped record counts of the reference sequences with metadata. A
+    /// reference sequence without metadata has no records. This is only available if at least one
+    /// reference sequence has metadata.
+    pub fn mapped_record_count(&self) -> Option<u64> {
+        self.reference_sequences
+            .iter()
+            .filter_map(|reference_sequence| reference_sequence.mapped_record_count())
+            .reduce(|a, b| a + b)
+    }
+
+    /// Returns whether the index has no invalid bins or chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi::{self as csi, binning_index::Statistics};
+    /// let statistics = Statistics::new(&csi::Index::default());
+    /// assert!(statistics.is_consistent());
+    /// ```
+    pub fn is_consistent(&self) -> bool {
+        self.reference_sequences
+            .iter()
+            .all(|reference_sequence| reference_sequence.is_consistent())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_bgzf as bgzf;
+    use noodles_core::Position;
+
+    use super::*;
+    use crate::binning_index::{
+        Indexer,
+        index::reference_sequence::{bin::Chunk, index::LinearIndex},
+    };
+
+    #[test]
+    fn test_new() -> Result<(), Box<dyn std::error::Error>> {
+        let mut indexer = Indexer::<LinearIndex>::default();
+
+        for (reference_sequence_id, start, end, is_mapped, chunk_start, chunk_end) in [
+            (0, 8, 13, true, 0, 10),
+            (0, 16390, 16400, true, 10, 40),
+            (0, 16391, 16401, false, 40, 50),
+            (1, 100000, 100010, true, 50, 60),
+        ] {
+            indexer.add_record(
+                Some((
+                    reference_sequence_id,
+                    Position::try_from(start)?,
+                    Position::try_from(end)?,
+                    is_mapped,
+                )),
+                Chunk::new(
+                    bgzf::VirtualPosition::from(chunk_start),
+                    bgzf::VirtualPosition::from(chunk_end),
+                ),
+            )?;
+        }
+
+        indexer.add_record(
+            None,
+            Chunk::new(
+                bgzf::VirtualPosition::from(60),
+                bgzf::VirtualPosition::from(70),
+            ),
+        )?;
+
+        let index = indexer.build(3);
+        let statistics = Statistics::new(&index);
+
+        assert_eq!(statistics.reference_sequences().len(), 3);
+        assert_eq!(statistics.unplaced_unmapped_record_count(), Some(1));
+        assert_eq!(statistics.mapped_record_count(), Some(3));
+        assert!(statistics.is_consistent());
+
+        let reference_sequence = &statistics.reference_sequences()[0];
+        assert_eq!(reference_sequence.mapped_record_count(), Some(2));
+        assert_eq!(reference_sequence.unmapped_record_count(), Some(1));
+        assert_eq!(reference_sequence.bin_counts(), [0, 0, 0, 0, 0, 2]);
+        assert_eq!(reference_sequence.window_count(), 2);
+        assert_eq!(reference_sequence.leaf_window_count(), 2);
+        assert_eq!(reference_sequence.linear_index_interval_count(), Some(2));
+        assert_eq!(
+            reference_sequence.estimated_record_density(),
+            [(0, 0.4), (1, 1.6)]
+        );
+
+        let reference_sequence = &statistics.reference_sequences()[1];
+        assert_eq!(reference_sequence.window_count(), 7);
+        assert_eq!(reference_sequence.leaf_window_count(), 1);
+        assert_eq!(reference_sequence.linear_index_interval_count(), Some(7));
+
+        let reference_sequence = &statistics.reference_sequences()[2];
+        assert_eq!(reference_sequence.mapped_record_count(), None);
+        assert_eq!(reference_sequence.linear_index_interval_count(), Some(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_with_binned_index() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::binning_index::index::reference_sequence::index::BinnedIndex;
+
+        let mut indexer = Indexer::<BinnedIndex>::default();
+
+        indexer.add_record(
+            Some((0, Position::try_from(8)?, Position::try_from(13)?, true)),
+            Chunk::new(
+                bgzf::VirtualPosition::from(0),
+                bgzf::VirtualPosition::from(10),
+            ),
+        )?;
+
+        let index = indexer.build(1);
+        let statistics = Statistics::new(&index);
+
+        let reference_sequence = &statistics.reference_sequences()[0];
+        assert_eq!(reference_sequence.linear_index_interval_count(), None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+
+use crate::binning_index::{
+    self,
+    index::reference_sequence::{Bin, bin::Chunk},
+    merge_chunks,
+};
+
+/// Binning index reference sequence statistics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReferenceSequence {
+    mapped_record_count: Option<u64>,
+    unmapped_record_count: Option<u64>,
+    bin_counts: Vec<usize>,
+    chunk_count: usize,
+    invalid_bin_count: usize,
+    invalid_chunk_count: usize,
+    compressed_size: u64,
+    window_sizes: BTreeMap<usize, u64>,
+    linear_index_interval_count: Option<usize>,
+}
+
+impl ReferenceSequence {
+    pub(super) fn new(
+        depth: u8,
+        reference_sequence: &dyn binning_index::ReferenceSequence,
+    ) -> Self {
+        let metadata = reference_sequence.metadata();
+        let max_id = Bin::max_id(depth);
+        let first_leaf_id = first_id(depth);
+
+        let mut bin_counts = vec![0; usize::from(depth) + 1];
+        let mut chunk_count = 0;
+        let mut invalid_bin_count = 0;
+        let mut invalid_chunk_count = 0;
+        let mut window_sizes = BTreeMap::new();
+        let mut chunks = Vec::new();
+
+        for (id, bin) in reference_sequence.bins() {
+            if id < max_id {
+                bin_counts[usize::from(level(depth, id))] += 1;
+            } else {
+                invalid_bin_count += 1;
+            }
+
+            for chunk in bin.chunks() {
+                chunk_count += 1;
+
+                if chunk.start() > chunk.end() {
+                    invalid_chunk_count += 1;
+                    continue;
+                }
+
+                chunks.push(*chunk);
+            }
+
+            if (first_leaf_id..max_id).contains(&id) {
+                let size = bin.chunks().iter().map(|chunk| chunk_size(*chunk)).sum();
+                window_sizes.insert(id - first_leaf_id, size);
+            }
+        }
+
+        let compressed_size = merge_chunks(&chunks)
+            .iter()
+            .map(|chunk| chunk.end().compressed() - chunk.start().compressed())
+            .sum();
+
+        Self {
+            mapped_record_count: metadata.map(|m| m.mapped_record_count()),
+            unmapped_record_count: metadata.map(|m| m.unmapped_record_count()),
+            bin_counts,
+            chunk_count,
+            invalid_bin_count,
+            invalid_chunk_count,
+            compressed_size,
+            window_sizes,
+            linear_index_interval_count: reference_sequence
+                .linear_index()
+                .map(|linear_index| linear_index.len()),
+        }
+    }
+
+    /// Returns the number of mapped records, if available.
+    ///
+    /// This is read from the metadata pseudo-bin.
+    pub fn mapped_record_count(&self) -> Option<u64> {
+        self.mapped_record_count
+    }
+
+    /// Returns the number of unmapped records, if available.
+    ///
+    /// This is read from the metadata pseudo-bin.
+    pub fn unmapped_record_count(&self) -> Option<u64> {
+        self.unmapped_record_count
+    }
+
+    /// Returns the number of occupied bins at each level.
+    ///
+    /// The first element is the root level, and the last element is the deepest level.
+    pub fn bin_counts(&self) -> &[usize] {
+        &self.bin_counts
+    }
+
+    /// Returns the total number of chunks in all bins.
+    pub fn chunk_count(&self) -> usize {
+        self.chunk_count
+    }
+
+    /// Returns the number of bins with an ID that is out of range for the index depth.
+    pub fn invalid_bin_count(&self) -> usize {
+        self.invalid_bin_count
+    }
+
+    /// Returns the number of chunks that end before they start.
+    pub fn invalid_chunk_count(&self) -> usize {
+        self.invalid_chunk_count
+    }
+
+    /// Returns whether the reference sequence has no invalid bins or chunks.
+    pub fn is_consistent(&self) -> bool {
+        self.invalid_bin_count == 0 && self.invalid_chunk_count == 0
+    }
+
+    /// Returns the number of compressed bytes spanned by the chunks of all bins.
+    pub fn compressed_size(&self) -> u64 {
+        self.compressed_size
+    }
+
+    /// Returns the number of windows from the start of the reference sequence to the last window
+    /// with records.
+    ///
+    /// A window is the interval covered by a bin at the deepest level.
+    pub fn window_count(&self) -> usize {
+        self.window_sizes
+            .last_key_value()
+            .map(|(i, _)| i + 1)
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of windows with a bin at the deepest level.
+    ///
+    /// This is a lower bound of the number of windows with records. Only the bins at the deepest
+    /// level are read: a record that spans a window boundary is stored in a bin at a higher level
+    /// and is not counted, and the linear index, if any, is not used.
+    pub fn leaf_window_count(&self) -> usize {
+        self.window_sizes.len()
+    }
+
+    /// Returns the number of 16 KiB intervals in the linear index, if the index has one.
+    ///
+    /// This is the span, in intervals, from the start of the reference sequence to the end of the
+    /// last record. Unlike [`Self::window_count`], this includes records that span window
+    /// boundaries. CSI indexes do not have a linear index.
+    pub fn linear_index_interval_count(&self) -> Option<usize> {
+        self.linear_index_interval_count
+    }
+
+    /// Returns the estimated number of records in each window with records.
+    ///
+    /// The mapped record count is distributed across windows in proportion to the size of the data
+    /// in each window. This returns an empty list if the mapped record count is not available.
+    pub fn estimated_record_density(&self) -> Vec<(usize, f64)> {
+        let Some(mapped_record_count) = self.mapped_record_count else {
+            return Vec::new();
+        };
+
+        let total_size: u64 = self.window_sizes.values().sum();
+
+        if total_size == 0 {
+            return Vec::new();
+        }
+
+        self.window_sizes
+            .iter()
+            .map(|(&i, &size)| {
+                let density = mapped_record_count as f64 * (size as f64 / total_size as f64);
+                (i, density)
+            })
+            .collect()
+    }
+}
+
+// The first bin ID at the given level.
+pub(crate) fn first_id(level: u8) -> usize {
+    ((1 << (3 * usize::from(level))) - 1) / 7
+}
+
+pub(crate) fn level(depth: u8, id: usize) -> u8 {
+    (0..=depth).rev().find(|&l| id >= first_id(l)).unwrap_or(0)
+}
+
+// Returns the distance between the start and end of a chunk.
+//
+// This is the difference between the virtual positions, i.e., the compressed distance, in bytes,
+// scaled by 2^16, plus the uncompressed distance.
+pub(crate) fn chunk_size(chunk: Chunk) -> u64 {
+    u64::from(chunk.end()).saturating_sub(u64::from(chunk.start()))
+}
@@ -3,8 +3,8 @@ use std::io::{self, Read, Seek};
 use noodles_bgzf as bgzf;
 use noodles_core::Region;
 
-use super::{Query, indexed_records::Record};
-use crate::BinningIndex;
+use super::{IndexedRecord, Query, indexed_records::Record};
+use crate::{BinningIndex, binning_index::RegionSet};
 
 /// An indexed reader.
 pub struct IndexedReader<R, I> {
@@ -150,4 +150,158 @@ where
             .indexed_records(header)
             .filter_by_region(region))
     }
+
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// The chunks of all regions are merged, so each block is read at most once, and each record
+    /// is returned once, along with the indices of the regions it intersects, in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_csi as csi;
+    ///
+    /// let index = csi::fs::read("sample.bed.gz.csi")?;
+    /// let mut reader = File::open("sample.bed.gz")
+    ///     .map(|f| csi::io::IndexedReader::new(f, index))?;
+    ///
+    /// let regions = ["sq0:8-13".parse()?, "sq1:21-34".parse()?];
+    ///
+    /// for result in reader.query_regions(&regions)? {
+    ///     let (record, region_indices) = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_regions<'r>(
+        &'r mut self,
+        regions: &[Region],
+    ) -> io::Result<impl Iterator<Item = io::Result<(Record, Vec<usize>)>> + 'r> {
+        let header = self
+            .index
+            .header()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing index header"))?;
+
+        let reference_sequence_names = header.reference_sequence_names();
+
+        let region_set = regions
+            .iter()
+            .map(|region| {
+                reference_sequence_names
+                    .get_index_of(region.name())
+                    .map(|id| (id, region.interval()))
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "missing reference sequence name",
+                        )
+                    })
+            })
+            .collect::<io::Result<Vec<_>>>()
+            .map(RegionSet::new)?;
+
+        let chunks = region_set.query(&self.index)?;
+
+        Ok(Query::new(&mut self.inner, chunks)
+            .indexed_records(header)
+            .filter_map(move |result| {
+                result
+                    .and_then(|record| {
+                        let id = reference_sequence_names
+                            .get_index_of(record.indexed_reference_sequence_name().as_bytes())
+                            .ok_or_else(|| {
+                                io::Error::new(
+                                    io::ErrorKind::InvalidData,
+                                    "missing reference sequence name",
+                                )
+                            })?;
+
+                        let region_indices = region_set.intersecting(id, record.indexed_interval());
+                        Ok((!region_indices.is_empty()).then_some((record, region_indices)))
+                    })
+                    .transpose()
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use noodles_core::Position;
+
+    use super::*;
+    use crate::binning_index::{
+        Indexer,
+        index::{
+            header,
+            reference_sequence::{bin::Chunk, index::BinnedIndex},
+        },
+    };
+
+    #[test]
+    fn test_query_regions() -> Result<(), Box<dyn std::error::Error>> {
+        const LINES: [(usize, &str, usize, usize); 4] = [
+            (0, "sq0", 1, 5),
+            (0, "sq0", 7, 13),
+            (0, "sq0", 20, 30),
+            (1, "sq1", 4, 8),
+        ];
+
+        let header = header::Builder::bed()
+            .set_reference_sequence_names(["sq0", "sq1"].into_iter().map(Into::into).collect())
+            .build();
+
+        let mut indexer = Indexer::<BinnedIndex>::default().set_header(header);
+        let mut writer = bgzf::io::Writer::new(Vec::new());
+
+        for (reference_sequence_id, name, start, end) in LINES {
+            let start_position = writer.virtual_position();
+            writeln!(writer, "{name}\t{start}\t{end}")?;
+            let end_position = writer.virtual_position();
+
+            indexer.add_record(
+                Some((
+                    reference_sequence_id,
+                    Position::try_from(start + 1)?,
+                    Position::try_from(end)?,
+                    true,
+                )),
+                Chunk::new(start_position, end_position),
+            )?;
+        }
+
+        let index = indexer.build(2);
+        let data = writer.finish()?;
+
+        let mut reader = IndexedReader::new(io::Cursor::new(data), index);
+
+        // Regions are out of order, and some overlap or are adjacent.
+        let regions = [
+            "sq1".parse()?,
+            "sq0:8-13".parse()?,
+            "sq0:1-8".parse()?,
+            "sq0:14-20".parse()?,
+            "sq0:21-21".parse()?,
+        ];
+
+        let actual: Vec<_> = reader
+            .query_regions(&regions)?
+            .map(|result| {
+                result.map(|(record, region_indices)| (record.as_ref().to_string(), region_indices))
+            })
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (String::from("sq0\t1\t5"), vec![2]),
+            (String::from("sq0\t7\t13"), vec![1, 2]),
+            (String::from("sq0\t20\t30"), vec![4]),
+            (String::from("sq1\t4\t8"), vec![0]),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
 }
@@ -9,7 +9,7 @@ use noodles_core::Region;
 pub use self::record::Record;
 pub(crate) use self::record::parse_record;
 use super::FilterByRegion;
-use crate::binning_index::index::{Header, header::format::CoordinateSystem};
+use crate::binning_index::index::{Header, header::Format};
 
 /// An iterator over indexed records.
 pub struct IndexedRecords<R> {
@@ -18,7 +18,7 @@ pub struct IndexedRecords<R> {
     reference_sequence_name_index: usize,
     start_position_index: usize,
     end_position_index: Option<usize>,
-    coordinate_system: CoordinateSystem,
+    format: Format,
 }
 
 impl<R> IndexedRecords<R>
@@ -33,7 +33,7 @@ where
             reference_sequence_name_index: header.reference_sequence_name_index(),
             start_position_index: header.start_position_index(),
             end_position_index: header.end_position_index(),
-            coordinate_system: header.format().coordinate_system(),
+            format: header.format(),
         }
     }
 
@@ -65,7 +65,7 @@ where
                 self.reference_sequence_name_index,
                 self.start_position_index,
                 self.end_position_index,
-                self.coordinate_system,
+                self.format,
             )
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
 
@@ -79,6 +79,7 @@ mod tests {
     use bstr::BString;
 
     use super::*;
+    use crate::io::IndexedRecord;
 
     #[test]
     fn test_next() -> Result<(), Box<dyn std::error::Error>> {
@@ -103,4 +104,53 @@ sq0\t21\t34
 
         Ok(())
     }
+
+    #[test]
+    fn test_filter_by_region_with_sam_format() -> Result<(), Box<dyn std::error::Error>> {
+        let data = b"@HD\tVN:1.6
+r0\t0\tsq0\t2\t255\t4M\t*\t0\t0\tACGT\t*
+r1\t0\tsq0\t5\t255\t2M8D2M\t*\t0\t0\tACGT\t*
+r2\t0\tsq0\t21\t255\t4M\t*\t0\t0\tACGT\t*
+";
+
+        let header = crate::binning_index::index::header::Builder::sam().build();
+        let region = "sq0:13-20".parse()?;
+
+        let records: Vec<_> = IndexedRecords::new(&data[..], &header)
+            .filter_by_region(&region)
+            .collect::<io::Result<_>>()?;
+
+        // r1 starts before the region but ends in it.
+        assert_eq!(records.len(), 1);
+        assert!(records[0].as_ref().starts_with("r1\t"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_by_region_with_vcf_format() -> Result<(), Box<dyn std::error::Error>> {
+        let data = b"##fileformat=VCFv4.5
+sq0\t2\t.\tA\tC\t.\tPASS\t.
+sq0\t5\t.\tACGTACGTA\tA\t.\tPASS\t.
+sq0\t8\t.\tA\t<DEL>\t.\tPASS\tSVTYPE=DEL;END=21
+sq0\t34\t.\tA\tC\t.\tPASS\t.
+";
+
+        let header = crate::binning_index::index::header::Builder::vcf().build();
+        let region = "sq0:13-20".parse()?;
+
+        let records: Vec<_> = IndexedRecords::new(&data[..], &header)
+            .filter_by_region(&region)
+            .collect::<io::Result<_>>()?;
+
+        // The records at 5 and 8 start before the region but end in or after it.
+        let starts: Vec<_> = records
+            .iter()
+            .map(|record| usize::from(record.indexed_start_position()))
+            .collect();
+
+        assert_eq!(starts, [5, 8]);
+
+        Ok(())
+    }
 }
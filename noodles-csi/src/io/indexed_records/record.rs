@@ -1,12 +1,13 @@
 mod position;
 
-use std::{error, fmt, ops::Range};
+use std::{error, fmt, io, ops::Range};
 
 use noodles_core::Position;
 
 use self::position::parse_start_position;
 use crate::{
-    binning_index::index::header::format::coordinate_system::CoordinateSystem, io::IndexedRecord,
+    binning_index::index::{Header, header::format::Format},
+    io::IndexedRecord,
 };
 
 /// An indexed record.
@@ -17,6 +18,42 @@ pub struct Record {
     end_position: Position,
 }
 
+impl Record {
+    /// Parses a line using the columns and format of the given index header.
+    ///
+    /// The end position is read from the end position column, if set. Otherwise, it is
+    /// calculated from the CIGAR for the SAM format and from the reference bases or `END` info
+    /// field for the VCF format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_csi::{
+    ///     binning_index::index::header,
+    ///     io::{IndexedRecord, indexed_records::Record},
+    /// };
+    ///
+    /// let header = header::Builder::vcf().build();
+    /// let record = Record::parse(String::from("sq0\t8\t.\tACGT\tA"), &header)?;
+    ///
+    /// assert_eq!(record.indexed_reference_sequence_name(), "sq0");
+    /// assert_eq!(record.indexed_start_position(), Position::try_from(8)?);
+    /// assert_eq!(record.indexed_end_position(), Position::try_from(11)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn parse(s: String, header: &Header) -> io::Result<Self> {
+        parse_record(
+            s,
+            header.reference_sequence_name_index(),
+            header.start_position_index(),
+            header.end_position_index(),
+            header.format(),
+        )
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
 impl IndexedRecord for Record {
     fn indexed_reference_sequence_name(&self) -> &str {
         &self.buf[self.reference_sequence_name_bounds.clone()]
@@ -51,6 +88,8 @@ pub enum ParseError {
     MissingEndPosition,
     /// The end position is invalid.
     InvalidEndPosition(position::ParseError),
+    /// The CIGAR is invalid.
+    InvalidCigar,
 }
 
 impl error::Error for ParseError {
@@ -72,6 +111,7 @@ impl fmt::Display for ParseError {
             Self::InvalidStartPosition(_) => write!(f, "invalid start position"),
             Self::MissingEndPosition => write!(f, "missing end position"),
             Self::InvalidEndPosition(_) => write!(f, "invalid end position"),
+            Self::InvalidCigar => write!(f, "invalid CIGAR"),
         }
     }
 }
@@ -81,7 +121,7 @@ pub(crate) fn parse_record(
     reference_sequence_name_index: usize,
     start_position_index: usize,
     end_position_index: Option<usize>,
-    coordinate_system: CoordinateSystem,
+    format: Format,
 ) -> Result<Record, ParseError> {
     const DELIMITER: char = '\t';
 
@@ -98,7 +138,7 @@ pub(crate) fn parse_record(
         .get(start_position_index)
         .ok_or(ParseError::MissingStartPosition)?;
 
-    let start_position = parse_start_position(raw_start, coordinate_system)
+    let start_position = parse_start_position(raw_start, format.coordinate_system())
         .map_err(ParseError::InvalidStartPosition)?;
 
     let end_position = if let Some(i) = end_position_index {
@@ -111,7 +151,11 @@ pub(crate) fn parse_record(
                     .map_err(ParseError::InvalidEndPosition)
             })?
     } else {
-        start_position
+        match format {
+            Format::Generic(_) => start_position,
+            Format::Sam => calculate_sam_end_position(&fields, start_position)?,
+            Format::Vcf => calculate_vcf_end_position(&fields, start_position),
+        }
     };
 
     Ok(Record {
@@ -141,57 +185,213 @@ fn calculate_reference_sequence_name_bounds(
     Ok(start..end)
 }
 
+// The end position is calculated from the reference length of the CIGAR, i.e., the sum of the
+// lengths of the alignment match (`M`), deletion (`D`), skip (`N`), sequence match (`=`), and
+// sequence mismatch (`X`) operations.
+fn calculate_sam_end_position(fields: &[&str], start: Position) -> Result<Position, ParseError> {
+    const CIGAR_INDEX: usize = 5;
+    const MISSING: &str = "*";
+
+    let cigar = match fields.get(CIGAR_INDEX) {
+        None | Some(&MISSING) => return Ok(start),
+        Some(s) => s,
+    };
+
+    let mut len: usize = 0;
+    let mut reference_len = 0;
+
+    for b in cigar.bytes() {
+        match b {
+            b'0'..=b'9' => {
+                len = len
+                    .checked_mul(10)
+                    .and_then(|n| n.checked_add(usize::from(b - b'0')))
+                    .ok_or(ParseError::InvalidCigar)?;
+            }
+            b'M' | b'D' | b'N' | b'=' | b'X' => {
+                reference_len += len;
+                len = 0;
+            }
+            b'I' | b'S' | b'H' | b'P' => len = 0,
+            _ => return Err(ParseError::InvalidCigar),
+        }
+    }
+
+    Ok(reference_len
+        .checked_sub(1)
+        .and_then(|n| start.checked_add(n))
+        .unwrap_or(start))
+}
+
+// The end position is the start position plus the length of the reference bases, or, if present,
+// the `END` info field.
+fn calculate_vcf_end_position(fields: &[&str], start: Position) -> Position {
+    const REFERENCE_BASES_INDEX: usize = 3;
+    const INFO_INDEX: usize = 7;
+
+    let info_end = fields
+        .get(INFO_INDEX)
+        .and_then(|s| s.split(';').find_map(|field| field.strip_prefix("END=")))
+        .and_then(|s| s.parse::<Position>().ok())
+        .filter(|&end| end >= start);
+
+    if let Some(end) = info_end {
+        return end;
+    }
+
+    fields
+        .get(REFERENCE_BASES_INDEX)
+        .and_then(|s| s.len().checked_sub(1))
+        .and_then(|n| start.checked_add(n))
+        .unwrap_or(start)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::binning_index::index::header::format::CoordinateSystem;
 
     #[test]
     fn test_parse_record() -> Result<(), ParseError> {
-        let record = parse_record("sq0\t7\t13".into(), 0, 1, Some(2), CoordinateSystem::Bed)?;
+        let record = parse_record(
+            "sq0\t7\t13".into(),
+            0,
+            1,
+            Some(2),
+            Format::Generic(CoordinateSystem::Bed),
+        )?;
         assert_eq!(record.reference_sequence_name_bounds, 0..3);
         assert_eq!(record.start_position, const { Position::new(8).unwrap() });
         assert_eq!(record.end_position, const { Position::new(13).unwrap() });
 
-        let record = parse_record("sq0\t8\t13".into(), 0, 1, Some(2), CoordinateSystem::Gff)?;
+        let record = parse_record(
+            "sq0\t8\t13".into(),
+            0,
+            1,
+            Some(2),
+            Format::Generic(CoordinateSystem::Gff),
+        )?;
         assert_eq!(record.reference_sequence_name_bounds, 0..3);
         assert_eq!(record.start_position, const { Position::new(8).unwrap() });
         assert_eq!(record.end_position, const { Position::new(13).unwrap() });
 
-        let record = parse_record("sq0\t8".into(), 0, 1, None, CoordinateSystem::Gff)?;
+        let record = parse_record(
+            "sq0\t8".into(),
+            0,
+            1,
+            None,
+            Format::Generic(CoordinateSystem::Gff),
+        )?;
         assert_eq!(record.reference_sequence_name_bounds, 0..3);
         assert_eq!(record.start_position, const { Position::new(8).unwrap() });
         assert_eq!(record.end_position, const { Position::new(8).unwrap() });
 
         assert!(matches!(
-            parse_record("".into(), 0, 1, Some(2), CoordinateSystem::Gff),
+            parse_record(
+                "".into(),
+                0,
+                1,
+                Some(2),
+                Format::Generic(CoordinateSystem::Gff)
+            ),
             Err(ParseError::Empty)
         ));
 
         assert!(matches!(
-            parse_record("8".into(), 1, 0, Some(2), CoordinateSystem::Gff),
+            parse_record(
+                "8".into(),
+                1,
+                0,
+                Some(2),
+                Format::Generic(CoordinateSystem::Gff)
+            ),
             Err(ParseError::MissingReferenceSequenceName)
         ));
 
         assert!(matches!(
-            parse_record("sq0".into(), 0, 1, Some(2), CoordinateSystem::Gff),
+            parse_record(
+                "sq0".into(),
+                0,
+                1,
+                Some(2),
+                Format::Generic(CoordinateSystem::Gff)
+            ),
             Err(ParseError::MissingStartPosition)
         ));
 
         assert!(matches!(
-            parse_record("sq0\tn".into(), 0, 1, Some(2), CoordinateSystem::Gff),
+            parse_record(
+                "sq0\tn".into(),
+                0,
+                1,
+                Some(2),
+                Format::Generic(CoordinateSystem::Gff)
+            ),
             Err(ParseError::InvalidStartPosition(_))
         ));
 
         assert!(matches!(
-            parse_record("sq0\t8".into(), 0, 1, Some(2), CoordinateSystem::Gff),
+            parse_record(
+                "sq0\t8".into(),
+                0,
+                1,
+                Some(2),
+                Format::Generic(CoordinateSystem::Gff)
+            ),
             Err(ParseError::MissingEndPosition)
         ));
 
         assert!(matches!(
-            parse_record("sq0\t8\tn".into(), 0, 1, Some(2), CoordinateSystem::Gff),
+            parse_record(
+                "sq0\t8\tn".into(),
+                0,
+                1,
+                Some(2),
+                Format::Generic(CoordinateSystem::Gff)
+            ),
             Err(ParseError::InvalidEndPosition(_))
         ));
 
         Ok(())
     }
+
+    #[test]
+    fn test_parse_record_with_sam_format() -> Result<(), ParseError> {
+        let record = parse_record(
+            "r0\t0\tsq0\t8\t255\t2S4M1D2I3N\t*\t0\t0\tACGTACGT\t*".into(),
+            2,
+            3,
+            None,
+            Format::Sam,
+        )?;
+        assert_eq!(record.start_position, const { Position::new(8).unwrap() });
+        assert_eq!(record.end_position, const { Position::new(15).unwrap() });
+
+        let record = parse_record("r0\t4\tsq0\t8\t255\t*".into(), 2, 3, None, Format::Sam)?;
+        assert_eq!(record.end_position, const { Position::new(8).unwrap() });
+
+        assert!(matches!(
+            parse_record("r0\t0\tsq0\t8\t255\t4Z".into(), 2, 3, None, Format::Sam),
+            Err(ParseError::InvalidCigar)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_record_with_vcf_format() -> Result<(), ParseError> {
+        let record = parse_record("sq0\t8\t.\tACGT\tA".into(), 0, 1, None, Format::Vcf)?;
+        assert_eq!(record.end_position, const { Position::new(11).unwrap() });
+
+        let record = parse_record(
+            "sq0\t8\t.\tA\t<DEL>\t.\tPASS\tSVTYPE=DEL;END=21".into(),
+            0,
+            1,
+            None,
+            Format::Vcf,
+        )?;
+        assert_eq!(record.end_position, const { Position::new(21).unwrap() });
+
+        Ok(())
+    }
 }
@@ -1,6 +1,7 @@
 //! Feature record.
 
+pub mod hierarchy;
 pub mod record;
 pub mod record_buf;
 
-pub use self::{record::Record, record_buf::RecordBuf};
+pub use self::{hierarchy::Hierarchy, record::Record, record_buf::RecordBuf};
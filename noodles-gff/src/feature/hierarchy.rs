@@ -0,0 +1,410 @@
+//! GFF feature hierarchy.
+//!
+//! A feature hierarchy is a graph of features linked by their `ID` and `Parent` attributes.
+
+mod builder;
+mod feature;
+
+use bstr::BString;
+
+pub use self::{builder::Builder, feature::Feature};
+use super::RecordBuf;
+
+/// A problem found while building a GFF feature hierarchy.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Diagnostic {
+    /// A feature references a parent ID that is not defined in the same forward reference block.
+    DanglingParent {
+        /// The index of the feature.
+        feature: usize,
+        /// The parent ID.
+        parent_id: BString,
+    },
+    /// The parents of the given features form a cycle.
+    Cycle(Vec<usize>),
+}
+
+/// A GFF feature hierarchy.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Hierarchy {
+    features: Vec<Feature>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Hierarchy {
+    /// Returns a builder to create a hierarchy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::feature::Hierarchy;
+    /// let builder = Hierarchy::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the features.
+    ///
+    /// Features are in the order they were first seen. The parents and children of a feature are
+    /// indices into this list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::feature::Hierarchy;
+    /// let hierarchy = Hierarchy::default();
+    /// assert!(hierarchy.features().is_empty());
+    /// ```
+    pub fn features(&self) -> &[Feature] {
+        &self.features
+    }
+
+    /// Returns the problems found while building the hierarchy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::feature::Hierarchy;
+    /// let hierarchy = Hierarchy::default();
+    /// assert!(hierarchy.diagnostics().is_empty());
+    /// ```
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Returns the features without parents as (index, feature) pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::feature::Hierarchy;
+    /// let hierarchy = Hierarchy::default();
+    /// assert_eq!(hierarchy.roots().count(), 0);
+    /// ```
+    pub fn roots(&self) -> impl Iterator<Item = (usize, &Feature)> {
+        self.features
+            .iter()
+            .enumerate()
+            .filter(|(_, feature)| feature.parents().is_empty())
+    }
+
+    /// Returns the children of the feature at the given index as (index, feature) pairs.
+    ///
+    /// This is empty if the index is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::feature::Hierarchy;
+    /// let hierarchy = Hierarchy::default();
+    /// assert_eq!(hierarchy.children(0).count(), 0);
+    /// ```
+    pub fn children(&self, i: usize) -> impl Iterator<Item = (usize, &Feature)> {
+        self.related(i, Feature::children)
+    }
+
+    /// Returns the parents of the feature at the given index as (index, feature) pairs.
+    ///
+    /// This is empty if the index is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::feature::Hierarchy;
+    /// let hierarchy = Hierarchy::default();
+    /// assert_eq!(hierarchy.parents(0).count(), 0);
+    /// ```
+    pub fn parents(&self, i: usize) -> impl Iterator<Item = (usize, &Feature)> {
+        self.related(i, Feature::parents)
+    }
+
+    /// Returns the genes as (index, feature) pairs.
+    ///
+    /// A gene is a feature with the type `gene`, `pseudogene`, or a type that ends with `_gene`,
+    /// e.g., `ncRNA_gene`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::feature::Hierarchy;
+    /// let hierarchy = Hierarchy::default();
+    /// assert_eq!(hierarchy.genes().count(), 0);
+    /// ```
+    pub fn genes(&self) -> impl Iterator<Item = (usize, &Feature)> {
+        self.features
+            .iter()
+            .enumerate()
+            .filter(|(_, feature)| is_gene(feature))
+    }
+
+    /// Returns the transcripts of the gene at the given index as (index, feature) pairs.
+    ///
+    /// A transcript is a child with the type `transcript`, a type that ends with `_transcript`, or
+    /// a type that ends with `RNA`, e.g., `mRNA`.
+    pub fn transcripts(&self, gene: usize) -> impl Iterator<Item = (usize, &Feature)> {
+        self.children(gene)
+            .filter(|(_, feature)| is_transcript(feature))
+    }
+
+    /// Returns the exons of the transcript at the given index, sorted by start position.
+    pub fn exons(&self, transcript: usize) -> Vec<&RecordBuf> {
+        self.child_records(transcript, b"exon")
+    }
+
+    /// Returns the CDS segments of the transcript at the given index, sorted by start position.
+    ///
+    /// The segments of a discontinuous CDS are each returned.
+    pub fn cds_segments(&self, transcript: usize) -> Vec<&RecordBuf> {
+        self.child_records(transcript, b"CDS")
+    }
+
+    fn related(
+        &self,
+        i: usize,
+        f: fn(&Feature) -> &[usize],
+    ) -> impl Iterator<Item = (usize, &Feature)> {
+        let indices = self.features.get(i).map(f).unwrap_or_default();
+        indices.iter().map(|&j| (j, &self.features[j]))
+    }
+
+    fn child_records(&self, i: usize, ty: &[u8]) -> Vec<&RecordBuf> {
+        let mut records: Vec<_> = self
+            .children(i)
+            .flat_map(|(_, child)| child.records())
+            .filter(|record| record.ty() == ty)
+            .collect();
+
+        records.sort_by_key(|record| (record.start(), record.end()));
+
+        records
+    }
+}
+
+fn is_gene(feature: &Feature) -> bool {
+    let ty = feature.ty();
+    ty == "gene" || ty == "pseudogene" || ty.ends_with(b"_gene")
+}
+
+fn is_transcript(feature: &Feature) -> bool {
+    let ty = feature.ty();
+    ty == "transcript" || ty.ends_with(b"_transcript") || ty.ends_with(b"RNA")
+}
+
+#[cfg(test)]
+mod tests {
+    use bstr::{BStr, BString};
+    use noodles_core::Position;
+
+    use super::*;
+    use crate::{
+        DirectiveBuf, LineBuf,
+        directive_buf::key,
+        feature::record_buf::{Attributes, attributes::field::Value},
+    };
+
+    fn build_record(
+        ty: &str,
+        start: usize,
+        end: usize,
+        attributes: &[(&str, &[&str])],
+    ) -> Result<RecordBuf, noodles_core::position::TryFromIntError> {
+        let attributes: Attributes = attributes
+            .iter()
+            .map(|&(tag, values)| {
+                let value = if let [value] = values {
+                    Value::from(*value)
+                } else {
+                    Value::from(values.iter().map(|&v| BString::from(v)).collect::<Vec<_>>())
+                };
+
+                (BString::from(tag), value)
+            })
+            .collect();
+
+        Ok(RecordBuf::builder()
+            .set_reference_sequence_name("sq0")
+            .set_type(ty)
+            .set_start(Position::try_from(start)?)
+            .set_end(Position::try_from(end)?)
+            .set_attributes(attributes)
+            .build())
+    }
+
+    #[test]
+    fn test_build() -> Result<(), Box<dyn std::error::Error>> {
+        let mut builder = Hierarchy::builder();
+
+        // Children can precede their parents.
+        builder.add_record(build_record("exon", 1, 5, &[("Parent", &["t0", "t1"])])?);
+        builder.add_record(build_record("gene", 1, 20, &[("ID", &["g0"])])?);
+        builder.add_record(build_record(
+            "mRNA",
+            1,
+            20,
+            &[("ID", &["t0"]), ("Parent", &["g0"])],
+        )?);
+        builder.add_record(build_record(
+            "mRNA",
+            1,
+            15,
+            &[("ID", &["t1"]), ("Parent", &["g0"])],
+        )?);
+        builder.add_record(build_record(
+            "CDS",
+            13,
+            15,
+            &[("ID", &["c0"]), ("Parent", &["t0"])],
+        )?);
+        builder.add_record(build_record(
+            "CDS",
+            3,
+            5,
+            &[("ID", &["c0"]), ("Parent", &["t0"])],
+        )?);
+        builder.add_record(build_record("exon", 13, 20, &[("Parent", &["t0"])])?);
+
+        let hierarchy = builder.build();
+
+        assert!(hierarchy.diagnostics().is_empty());
+        assert_eq!(hierarchy.features().len(), 6);
+
+        let roots: Vec<_> = hierarchy.roots().map(|(_, feature)| feature.id()).collect();
+        assert_eq!(roots, [Some(BStr::new("g0"))]);
+
+        let genes: Vec<_> = hierarchy.genes().map(|(i, _)| i).collect();
+        assert_eq!(genes, [1]);
+
+        let transcripts: Vec<_> = hierarchy.transcripts(genes[0]).map(|(i, _)| i).collect();
+        assert_eq!(transcripts, [2, 3]);
+
+        let t0 = transcripts[0];
+        assert_eq!(hierarchy.features()[t0].id(), Some(BStr::new("t0")));
+
+        let parents: Vec<_> = hierarchy.parents(t0).map(|(i, _)| i).collect();
+        assert_eq!(parents, [1]);
+
+        let exons: Vec<_> = hierarchy
+            .exons(t0)
+            .into_iter()
+            .map(|record| usize::from(record.start()))
+            .collect();
+        assert_eq!(exons, [1, 13]);
+
+        let cds_segments: Vec<_> = hierarchy
+            .cds_segments(t0)
+            .into_iter()
+            .map(|record| usize::from(record.start()))
+            .collect();
+        assert_eq!(cds_segments, [3, 13]);
+
+        let c0 = &hierarchy.features()[4];
+        assert_eq!(c0.records().len(), 2);
+        assert_eq!(c0.start(), Position::try_from(3)?);
+        assert_eq!(c0.end(), Position::try_from(15)?);
+        assert_eq!(c0.parents(), [2]);
+
+        // The first exon has multiple parents.
+        assert_eq!(hierarchy.features()[0].parents(), [2, 3]);
+        assert_eq!(hierarchy.exons(transcripts[1]).len(), 1);
+
+        // An index that is out of range has no relatives.
+        assert_eq!(hierarchy.children(hierarchy.features().len()).count(), 0);
+        assert!(hierarchy.exons(hierarchy.features().len()).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_forward_references_are_resolved_directive()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let mut builder = Hierarchy::builder();
+
+        builder.add_line(LineBuf::Record(build_record(
+            "gene",
+            1,
+            20,
+            &[("ID", &["g0"])],
+        )?));
+        builder.add_line(LineBuf::Directive(DirectiveBuf::new(
+            key::FORWARD_REFERENCES_ARE_RESOLVED,
+            None,
+        )));
+        builder.add_line(LineBuf::Record(build_record(
+            "mRNA",
+            1,
+            20,
+            &[("ID", &["t0"]), ("Parent", &["g0"])],
+        )?));
+        // A repeated ID after the directive is a new feature.
+        builder.add_line(LineBuf::Record(build_record(
+            "gene",
+            30,
+            40,
+            &[("ID", &["g0"])],
+        )?));
+
+        let hierarchy = builder.build();
+
+        assert_eq!(hierarchy.features().len(), 3);
+        assert!(hierarchy.features()[0].children().is_empty());
+        assert_eq!(hierarchy.features()[1].parents(), [2]);
+        assert!(hierarchy.diagnostics().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_dangling_parent() -> Result<(), Box<dyn std::error::Error>> {
+        let mut builder = Hierarchy::builder();
+        builder.add_record(build_record("exon", 1, 5, &[("Parent", &["t0"])])?);
+        let hierarchy = builder.build();
+
+        assert_eq!(
+            hierarchy.diagnostics(),
+            [Diagnostic::DanglingParent {
+                feature: 0,
+                parent_id: BString::from("t0"),
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_cycle() -> Result<(), Box<dyn std::error::Error>> {
+        let mut builder = Hierarchy::builder();
+        builder.add_record(build_record(
+            "gene",
+            1,
+            5,
+            &[("ID", &["a"]), ("Parent", &["c"])],
+        )?);
+        builder.add_record(build_record(
+            "gene",
+            1,
+            5,
+            &[("ID", &["b"]), ("Parent", &["a"])],
+        )?);
+        builder.add_record(build_record(
+            "gene",
+            1,
+            5,
+            &[("ID", &["c"]), ("Parent", &["b"])],
+        )?);
+        builder.add_record(build_record(
+            "gene",
+            1,
+            5,
+            &[("ID", &["d"]), ("Parent", &["d"])],
+        )?);
+        let hierarchy = builder.build();
+
+        assert_eq!(
+            hierarchy.diagnostics(),
+            [Diagnostic::Cycle(vec![0, 2, 1]), Diagnostic::Cycle(vec![3])]
+        );
+
+        Ok(())
+    }
+}
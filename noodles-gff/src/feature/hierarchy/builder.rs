@@ -0,0 +1,199 @@
+use std::{collections::HashMap, mem};
+
+use bstr::BString;
+
+use super::{Diagnostic, Feature, Hierarchy};
+use crate::{LineBuf, directive_buf::key, feature::RecordBuf};
+
+const ID: &[u8] = b"ID";
+const PARENT: &[u8] = b"Parent";
+
+/// A GFF feature hierarchy builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    features: Vec<Feature>,
+    ids: HashMap<BString, usize>,
+    parent_references: Vec<(usize, BString)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Builder {
+    /// Adds a line.
+    ///
+    /// Records are added using [`Self::add_record`], and forward references are resolved at a
+    /// `###` directive. Other lines are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::{self as gff, feature::hierarchy};
+    ///
+    /// let mut builder = hierarchy::Builder::default();
+    /// builder.add_line(gff::LineBuf::Record(gff::feature::RecordBuf::default()));
+    /// ```
+    pub fn add_line(&mut self, line: LineBuf) {
+        match line {
+            LineBuf::Record(record) => self.add_record(record),
+            LineBuf::Directive(directive)
+                if directive.key() == key::FORWARD_REFERENCES_ARE_RESOLVED =>
+            {
+                self.resolve_forward_references();
+            }
+            _ => {}
+        }
+    }
+
+    /// Adds a record.
+    ///
+    /// A record with an ID that was already added since the last resolution of forward
+    /// references is added to that feature. Otherwise, it creates a new feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::feature::{RecordBuf, hierarchy};
+    ///
+    /// let mut builder = hierarchy::Builder::default();
+    /// builder.add_record(RecordBuf::default());
+    /// ```
+    pub fn add_record(&mut self, record: RecordBuf) {
+        let attributes = record.attributes();
+
+        let id: Option<BString> = attributes
+            .get(ID)
+            .and_then(|value| value.iter().next())
+            .cloned();
+
+        let parent_ids: Vec<BString> = attributes
+            .get(PARENT)
+            .map(|value| value.iter().cloned().collect())
+            .unwrap_or_default();
+
+        let i = match id.as_ref().and_then(|id| self.ids.get(id)) {
+            Some(&i) => {
+                self.features[i].records.push(record);
+                i
+            }
+            None => {
+                let i = self.features.len();
+
+                if let Some(id) = id.clone() {
+                    self.ids.insert(id, i);
+                }
+
+                self.features.push(Feature::new(id, record));
+
+                i
+            }
+        };
+
+        self.parent_references
+            .extend(parent_ids.into_iter().map(|parent_id| (i, parent_id)));
+    }
+
+    /// Resolves all parent references.
+    ///
+    /// This is the equivalent of a `###` directive. Parent references to IDs that have not been
+    /// seen are reported as dangling. Subsequent records can no longer reference or extend the
+    /// features added before this.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::feature::hierarchy;
+    /// let mut builder = hierarchy::Builder::default();
+    /// builder.resolve_forward_references();
+    /// ```
+    pub fn resolve_forward_references(&mut self) {
+        for (i, parent_id) in mem::take(&mut self.parent_references) {
+            match self.ids.get(&parent_id) {
+                Some(&j) => {
+                    if !self.features[i].parents.contains(&j) {
+                        self.features[i].parents.push(j);
+                        self.features[j].children.push(i);
+                    }
+                }
+                None => self.diagnostics.push(Diagnostic::DanglingParent {
+                    feature: i,
+                    parent_id,
+                }),
+            }
+        }
+
+        self.ids.clear();
+    }
+
+    /// Builds a GFF feature hierarchy.
+    ///
+    /// This resolves any remaining forward references and reports cycles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_gff::feature::hierarchy;
+    /// let hierarchy = hierarchy::Builder::default().build();
+    /// ```
+    pub fn build(mut self) -> Hierarchy {
+        self.resolve_forward_references();
+
+        let cycles = find_cycles(&self.features);
+        self.diagnostics
+            .extend(cycles.into_iter().map(Diagnostic::Cycle));
+
+        Hierarchy {
+            features: self.features,
+            diagnostics: self.diagnostics,
+        }
+    }
+}
+
+// Returns the features in each cycle of parent references.
+fn find_cycles(features: &[Feature]) -> Vec<Vec<usize>> {
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    enum State {
+        Unvisited,
+        Visiting,
+        Visited,
+    }
+
+    let mut states = vec![State::Unvisited; features.len()];
+    let mut cycles = Vec::new();
+
+    for i in 0..features.len() {
+        if states[i] != State::Unvisited {
+            continue;
+        }
+
+        states[i] = State::Visiting;
+
+        // (feature index, next parent index)
+        let mut stack = vec![(i, 0)];
+
+        while let Some((j, k)) = stack.last_mut() {
+            let Some(&parent) = features[*j].parents.get(*k) else {
+                states[*j] = State::Visited;
+                stack.pop();
+                continue;
+            };
+
+            *k += 1;
+
+            match states[parent] {
+                State::Unvisited => {
+                    states[parent] = State::Visiting;
+                    stack.push((parent, 0));
+                }
+                State::Visiting => {
+                    let start = stack
+                        .iter()
+                        .position(|&(j, _)| j == parent)
+                        .expect("a feature being visited is on the stack");
+                    cycles.push(stack[start..].iter().map(|&(j, _)| j).collect());
+                }
+                State::Visited => {}
+            }
+        }
+    }
+
+    cycles
+}
@@ -0,0 +1,88 @@
+use bstr::{BStr, BString};
+use noodles_core::Position;
+
+use crate::feature::{RecordBuf, record::Strand};
+
+/// A feature in a GFF feature hierarchy.
+///
+/// A feature is one or more records. Records that share an ID are parts of the same discontinuous
+/// feature, e.g., the segments of a CDS.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Feature {
+    pub(super) id: Option<BString>,
+    pub(super) records: Vec<RecordBuf>,
+    pub(super) parents: Vec<usize>,
+    pub(super) children: Vec<usize>,
+}
+
+impl Feature {
+    pub(super) fn new(id: Option<BString>, record: RecordBuf) -> Self {
+        Self {
+            id,
+            records: vec![record],
+            parents: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Returns the ID of the feature.
+    pub fn id(&self) -> Option<&BStr> {
+        self.id.as_ref().map(|id| id.as_ref())
+    }
+
+    /// Returns the records of the feature.
+    ///
+    /// This is never empty.
+    pub fn records(&self) -> &[RecordBuf] {
+        &self.records
+    }
+
+    /// Returns the type of the feature.
+    pub fn ty(&self) -> &BStr {
+        self.first_record().ty()
+    }
+
+    /// Returns the reference sequence name of the feature.
+    pub fn reference_sequence_name(&self) -> &BStr {
+        self.first_record().reference_sequence_name()
+    }
+
+    /// Returns the start position of the feature, i.e., the smallest start position of its
+    /// records.
+    pub fn start(&self) -> Position {
+        self.records
+            .iter()
+            .map(|record| record.start())
+            .min()
+            .unwrap_or(Position::MIN)
+    }
+
+    /// Returns the end position of the feature, i.e., the largest end position of its records.
+    pub fn end(&self) -> Position {
+        self.records
+            .iter()
+            .map(|record| record.end())
+            .max()
+            .unwrap_or(Position::MIN)
+    }
+
+    /// Returns the strand of the feature.
+    pub fn strand(&self) -> Strand {
+        self.first_record().strand()
+    }
+
+    /// Returns the indices of the parents of the feature.
+    pub fn parents(&self) -> &[usize] {
+        &self.parents
+    }
+
+    /// Returns the indices of the children of the feature.
+    pub fn children(&self) -> &[usize] {
+        &self.children
+    }
+
+    fn first_record(&self) -> &RecordBuf {
+        // SAFETY: `self.records` is non-empty.
+        &self.records[0]
+    }
+}
@@ -0,0 +1,42 @@
+//! Validates a SAM file.
+//!
+//! Each diagnostic, including duplicate reference sequence names and read group IDs in the header,
+//! is printed to stdout. The result is similar to the output of `picard ValidateSamFile --MODE
+//! VERBOSE`.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader},
+};
+
+use noodles_sam::{
+    self as sam,
+    validation::{self, Validator},
+};
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let mut reader = File::open(src)
+        .map(BufReader::new)
+        .map(sam::io::Reader::new)?;
+
+    let (header, diagnostics) = validation::read_header(&mut reader)?;
+
+    for diagnostic in diagnostics {
+        println!("{diagnostic}");
+    }
+
+    let mut validator = Validator::new(&header);
+
+    for result in reader.records() {
+        let record = result?;
+
+        for diagnostic in validator.validate_record(&record) {
+            println!("{diagnostic}");
+        }
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 //! Async SAM I/O.
 
+pub mod indexed_reader;
 mod reader;
 mod writer;
 
-pub use self::{reader::Reader, writer::Writer};
+pub use self::{indexed_reader::IndexedReader, reader::Reader, writer::Writer};
@@ -0,0 +1,207 @@
+//! Async indexed SAM reader.
+
+mod builder;
+
+use futures::Stream;
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_csi::BinningIndex;
+use tokio::io::{self, AsyncRead, AsyncSeek};
+
+pub use self::builder::Builder;
+use super::{Reader, reader::Query};
+use crate::{Header, Record, alignment::RecordBuf};
+
+/// An async indexed SAM reader.
+///
+/// This owns its associated index, which is used to query records that intersect a region.
+///
+/// # Examples
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use futures::TryStreamExt;
+/// use noodles_sam as sam;
+///
+/// let mut reader = sam::r#async::io::indexed_reader::Builder::default()
+///     .build_from_path("sample.sam.gz")
+///     .await?;
+///
+/// let header = reader.read_header().await?;
+///
+/// let region = "sq0:8-13".parse()?;
+/// let mut query = reader.query(&header, &region)?.records();
+///
+/// while let Some(record) = query.try_next().await? {
+///     // ...
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct IndexedReader<R>
+where
+    R: AsyncRead,
+{
+    inner: Reader<bgzf::r#async::io::Reader<R>>,
+    index: Box<dyn BinningIndex + Send + Sync>,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: AsyncRead,
+{
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &bgzf::r#async::io::Reader<R> {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut bgzf::r#async::io::Reader<R> {
+        self.inner.get_mut()
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> bgzf::r#async::io::Reader<R> {
+        self.inner.into_inner()
+    }
+
+    /// Returns the associated index.
+    pub fn index(&self) -> &dyn BinningIndex {
+        &self.index
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Creates an async indexed SAM reader.
+    ///
+    /// The given reader must be a raw BGZF stream, as the underlying reader wraps it in a decoder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// use noodles_sam as sam;
+    ///
+    /// let index = csi::Index::default();
+    /// let reader = sam::r#async::io::IndexedReader::new(tokio::io::empty(), index);
+    /// ```
+    pub fn new<I>(inner: R, index: I) -> Self
+    where
+        I: BinningIndex + Send + Sync + 'static,
+    {
+        Self {
+            inner: Reader::new(bgzf::r#async::io::Reader::new(inner)),
+            index: Box::new(index),
+        }
+    }
+
+    /// Reads the SAM header.
+    pub async fn read_header(&mut self) -> io::Result<Header> {
+        self.inner.read_header().await
+    }
+
+    /// Reads a record into an alignment record buffer.
+    pub async fn read_record_buf(
+        &mut self,
+        header: &Header,
+        record: &mut RecordBuf,
+    ) -> io::Result<usize> {
+        self.inner.read_record_buf(header, record).await
+    }
+
+    /// Reads a record.
+    pub async fn read_record(&mut self, record: &mut Record) -> io::Result<usize> {
+        self.inner.read_record(record).await
+    }
+
+    /// Returns a stream over alignment record buffers.
+    pub fn record_bufs<'a>(
+        &'a mut self,
+        header: &'a Header,
+    ) -> impl Stream<Item = io::Result<RecordBuf>> + 'a {
+        self.inner.record_bufs(header)
+    }
+
+    /// Returns a stream over records.
+    pub fn records(&mut self) -> impl Stream<Item = io::Result<Record>> + '_ {
+        self.inner.records()
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Returns a stream over records that intersect the given region.
+    pub fn query<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h Header,
+        region: &Region,
+    ) -> io::Result<Query<'r, 'h, R>> {
+        self.inner.query(header, &self.index, region)
+    }
+
+    /// Returns a stream of unmapped records after querying for the unmapped region.
+    pub async fn query_unmapped(
+        &mut self,
+    ) -> io::Result<impl Stream<Item = io::Result<Record>> + use<'_, R>> {
+        self.inner.query_unmapped(&self.index).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write as _;
+
+    use futures::TryStreamExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        const DATA: &[u8] = b"@HD\tVN:1.6\tSO:coordinate
+@SQ\tSN:sq0\tLN:34
+@SQ\tSN:sq1\tLN:13
+r0\t0\tsq0\t2\t60\t4M\t*\t0\t0\t*\t*
+r1\t0\tsq0\t8\t60\t4M\t*\t0\t0\t*\t*
+r2\t0\tsq0\t21\t60\t4M\t*\t0\t0\t*\t*
+r3\t0\tsq1\t5\t60\t4M\t*\t0\t0\t*\t*
+";
+
+        let mut writer = bgzf::io::Writer::new(Vec::new());
+        writer.write_all(DATA)?;
+        let data = writer.finish()?;
+
+        let mut reader = crate::io::Reader::new(bgzf::io::Reader::new(&data[..]));
+        let index = crate::fs::index_inner(&mut reader)?;
+
+        let mut reader = IndexedReader::new(std::io::Cursor::new(data), index);
+        let header = reader.read_header().await?;
+
+        let region = "sq0:8-21".parse()?;
+        let names: Vec<_> = reader
+            .query(&header, &region)?
+            .records()
+            .map_ok(|record| record.name().map(|name| name.to_vec()))
+            .try_collect()
+            .await?;
+
+        assert_eq!(names, [Some(b"r1".to_vec()), Some(b"r2".to_vec())]);
+
+        let region = "sq1".parse()?;
+        let names: Vec<_> = reader
+            .query(&header, &region)?
+            .records()
+            .map_ok(|record| record.name().map(|name| name.to_vec()))
+            .try_collect()
+            .await?;
+
+        assert_eq!(names, [Some(b"r3".to_vec())]);
+
+        Ok(())
+    }
+}
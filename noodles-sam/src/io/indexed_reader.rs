@@ -105,6 +105,17 @@ where
         self.inner.query(header, &self.index, region)
     }
 
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// Each record is returned along with the indices of the regions it intersects.
+    pub fn query_regions<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h Header,
+        regions: &[Region],
+    ) -> io::Result<impl Iterator<Item = io::Result<(Record, Vec<usize>)>> + use<'r, 'h, R>> {
+        self.inner.query_regions(header, &self.index, regions)
+    }
+
     /// Returns an iterator of unmapped records after querying for the unmapped region.
     pub fn query_unmapped<'r>(
         &'r mut self,
@@ -14,7 +14,7 @@ use std::{
 
 use noodles_bgzf as bgzf;
 use noodles_core::Region;
-use noodles_csi::BinningIndex;
+use noodles_csi::{self as csi, BinningIndex, binning_index::RegionSet};
 
 pub(crate) use self::record::read_record;
 pub use self::{builder::Builder, query::Query, record_bufs::RecordBufs};
@@ -370,6 +370,72 @@ where
         ))
     }
 
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// The chunks of all regions are merged, so each block is read at most once, and each record
+    /// is returned once, along with the indices of the regions it intersects, in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_csi as csi;
+    /// use noodles_sam as sam;
+    ///
+    /// let mut reader = File::open("sample.sam.gz")
+    ///     .map(bgzf::io::Reader::new)
+    ///     .map(sam::io::Reader::new)?;
+    ///
+    /// let header = reader.read_header()?;
+    ///
+    /// let index = csi::fs::read("sample.sam.gz.csi")?;
+    /// let regions = ["sq0:8-13".parse()?, "sq1:21-34".parse()?];
+    ///
+    /// for result in reader.query_regions(&header, &index, &regions)? {
+    ///     let (record, region_indices) = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_regions<'r, 'h: 'r, I>(
+        &'r mut self,
+        header: &'h Header,
+        index: &I,
+        regions: &[Region],
+    ) -> io::Result<impl Iterator<Item = io::Result<(Record, Vec<usize>)>> + use<'r, 'h, I, R>>
+    where
+        I: BinningIndex,
+    {
+        let region_set = regions
+            .iter()
+            .map(|region| {
+                resolve_region(header.reference_sequences(), region)
+                    .map(|id| (id, region.interval()))
+            })
+            .collect::<io::Result<Vec<_>>>()
+            .map(RegionSet::new)?;
+
+        let chunks = region_set.query(index)?;
+        let mut reader = Reader::from(csi::io::Query::new(self.get_mut(), chunks));
+
+        Ok(iter::from_fn(move || {
+            loop {
+                let mut record = Record::default();
+
+                match reader.read_record(&mut record) {
+                    Ok(0) => return None,
+                    Ok(_) => match intersecting_regions(header, &region_set, &record) {
+                        Ok(region_indices) if region_indices.is_empty() => {}
+                        Ok(region_indices) => return Some(Ok((record, region_indices))),
+                        Err(e) => return Some(Err(e)),
+                    },
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+        }))
+    }
+
     /// Returns an iterator of unmapped records after querying for the unmapped region.
     ///
     /// ```no_run
@@ -481,6 +547,28 @@ where
     }
 }
 
+fn intersecting_regions(
+    header: &Header,
+    region_set: &RegionSet,
+    record: &Record,
+) -> io::Result<Vec<usize>> {
+    use crate::alignment::Record as _;
+
+    let (Some(reference_sequence_id), Some(start)) = (
+        record.reference_sequence_id(header).transpose()?,
+        record.alignment_start().transpose()?,
+    ) else {
+        return Ok(Vec::new());
+    };
+
+    let end = record
+        .alignment_end()
+        .transpose()?
+        .map_or(start, |end| end.max(start));
+
+    Ok(region_set.intersecting(reference_sequence_id, (start..=end).into()))
+}
+
 pub(crate) fn resolve_region(
     reference_sequences: &ReferenceSequences,
     region: &Region,
@@ -36,5 +36,6 @@ pub mod fs;
 pub mod header;
 pub mod io;
 pub mod record;
+pub mod validation;
 
 pub use self::{header::Header, record::Record};
@@ -0,0 +1,846 @@
+//! SAM header and alignment record validation.
+//!
+//! Readers only check that fields are well-formed. This checks the semantic rules in the SAM
+//! specification, similar to Picard `ValidateSamFile`, and reports each problem as a
+//! [`Diagnostic`] rather than stopping at the first error.
+//!
+//! # Examples
+//!
+//! ```
+//! use noodles_sam::{self as sam, alignment::RecordBuf, validation::Validator};
+//!
+//! let header = sam::Header::default();
+//! let mut validator = Validator::new(&header);
+//!
+//! let record = RecordBuf::default();
+//! let diagnostics = validator.validate_record(&record);
+//! assert!(diagnostics.is_empty());
+//! ```
+
+mod data;
+mod diagnostic;
+
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    io::{self, Read},
+};
+
+use bstr::{BString, ByteSlice};
+use noodles_core::Position;
+
+pub use self::diagnostic::{Diagnostic, Kind, Location};
+use crate::{
+    Header,
+    alignment::{
+        Record,
+        record::{Flags, data::field::Tag},
+    },
+    header::{
+        self,
+        record::value::map::header::{
+            sort_order::{COORDINATE, QUERY_NAME},
+            tag::{SORT_ORDER, SUBSORT_ORDER},
+        },
+    },
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum SortOrder {
+    Coordinate,
+    QueryName(QueryNameOrder),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum QueryNameOrder {
+    Natural,
+    Lexicographical,
+}
+
+#[derive(Debug)]
+enum SortKey {
+    Coordinate(Option<usize>, Option<Position>),
+    QueryName(QueryNameOrder, BString),
+}
+
+/// A SAM alignment record validator.
+///
+/// A validator tracks the number of records it has seen and, when the header declares a
+/// `coordinate` or `queryname` sort order (`@HD SO`), the previous record's sort key.
+///
+/// Query names are compared in natural order (e.g., `r2` < `r10`), as written by `samtools sort
+/// -n`, unless the header subsort order (`@HD SS`) is `queryname:lexicographical`.
+pub struct Validator<'h> {
+    header: &'h Header,
+    sort_order: Option<SortOrder>,
+    record_number: u64,
+    previous_sort_key: Option<SortKey>,
+}
+
+impl<'h> Validator<'h> {
+    /// Creates a validator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, validation::Validator};
+    /// let header = sam::Header::default();
+    /// let validator = Validator::new(&header);
+    /// ```
+    pub fn new(header: &'h Header) -> Self {
+        let other_fields = header.header().map(|hdr| hdr.other_fields());
+
+        let sort_order = other_fields
+            .and_then(|fields| fields.get(&SORT_ORDER))
+            .and_then(|sort_order| match sort_order.as_ref() {
+                COORDINATE => Some(SortOrder::Coordinate),
+                QUERY_NAME => {
+                    let subsort_order = other_fields.and_then(|fields| fields.get(&SUBSORT_ORDER));
+
+                    let order = match subsort_order.map(|order| order.as_slice()) {
+                        Some(b"queryname:lexicographical") => QueryNameOrder::Lexicographical,
+                        _ => QueryNameOrder::Natural,
+                    };
+
+                    Some(SortOrder::QueryName(order))
+                }
+                _ => None,
+            });
+
+        Self {
+            header,
+            sort_order,
+            record_number: 0,
+            previous_sort_key: None,
+        }
+    }
+
+    /// Validates an alignment record.
+    ///
+    /// Records must be given in input order for sort order validation and record numbering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     alignment::{RecordBuf, record::Flags},
+    ///     validation::{Kind, Validator},
+    /// };
+    ///
+    /// let header = sam::Header::default();
+    /// let mut validator = Validator::new(&header);
+    ///
+    /// let record = RecordBuf::builder()
+    ///     .set_flags(Flags::MATE_UNMAPPED | Flags::UNMAPPED)
+    ///     .build();
+    ///
+    /// let diagnostics = validator.validate_record(&record);
+    /// assert_eq!(diagnostics.len(), 1);
+    /// assert_eq!(diagnostics[0].kind(), &Kind::MateFlagsOnUnsegmentedRecord);
+    /// ```
+    pub fn validate_record<R>(&mut self, record: &R) -> Vec<Diagnostic>
+    where
+        R: Record + ?Sized,
+    {
+        self.record_number += 1;
+
+        let location = Location::Record {
+            record_number: self.record_number,
+        };
+
+        let mut kinds = Vec::new();
+
+        match record.flags() {
+            Ok(flags) => {
+                validate_flags(self.header, record, flags, &mut kinds);
+                self.validate_sort_order(record, flags, &mut kinds);
+            }
+            Err(e) => kinds.push(invalid_field("flags", e)),
+        }
+
+        validate_lengths(record, &mut kinds);
+        validate_positions(self.header, record, &mut kinds);
+        validate_data(self.header, record, &mut kinds);
+
+        kinds
+            .into_iter()
+            .map(|kind| Diagnostic::new(location, kind))
+            .collect()
+    }
+
+    fn validate_sort_order<R>(&mut self, record: &R, flags: Flags, kinds: &mut Vec<Kind>)
+    where
+        R: Record + ?Sized,
+    {
+        let sort_key = match self.sort_order {
+            Some(SortOrder::Coordinate) => {
+                let (Ok(reference_sequence_id), Ok(alignment_start)) = (
+                    record.reference_sequence_id(self.header).transpose(),
+                    record.alignment_start().transpose(),
+                ) else {
+                    return;
+                };
+
+                // Unplaced unmapped records sort last, regardless of alignment start.
+                if flags.is_unmapped() && reference_sequence_id.is_none() {
+                    SortKey::Coordinate(None, None)
+                } else {
+                    SortKey::Coordinate(reference_sequence_id, alignment_start)
+                }
+            }
+            Some(SortOrder::QueryName(order)) => {
+                SortKey::QueryName(order, record.name().map(BString::from).unwrap_or_default())
+            }
+            None => return,
+        };
+
+        if let Some(previous_sort_key) = &self.previous_sort_key
+            && cmp_sort_keys(previous_sort_key, &sort_key) == Ordering::Greater
+        {
+            kinds.push(Kind::InvalidSortOrder);
+        }
+
+        self.previous_sort_key = Some(sort_key);
+    }
+}
+
+/// Validates raw SAM header text.
+///
+/// A parsed [`Header`] cannot hold duplicate reference sequence names or read group IDs, so this
+/// checks the raw header text for them instead.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::validation::{self, Kind};
+///
+/// let src = b"@HD\tVN:1.6\n@RG\tID:rg0\n@RG\tID:rg0\n";
+/// let diagnostics = validation::validate_raw_header(src);
+///
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].kind(), &Kind::DuplicateReadGroupId("rg0".into()));
+/// ```
+pub fn validate_raw_header(src: &[u8]) -> Vec<Diagnostic> {
+    let mut reference_sequence_names = HashSet::new();
+    let mut read_group_ids = HashSet::new();
+    let mut diagnostics = Vec::new();
+
+    for (i, line) in src.lines().enumerate() {
+        let location = Location::Header { line_number: i + 1 };
+        let mut fields = line.split_str("\t");
+
+        let (key, ids, kind): (_, _, fn(BString) -> Kind) = match fields.next() {
+            Some(b"@SQ") => (
+                &b"SN:"[..],
+                &mut reference_sequence_names,
+                Kind::DuplicateReferenceSequenceName,
+            ),
+            Some(b"@RG") => (&b"ID:"[..], &mut read_group_ids, Kind::DuplicateReadGroupId),
+            _ => continue,
+        };
+
+        if let Some(id) = fields.find_map(|field| field.strip_prefix(key))
+            && !ids.insert(BString::from(id))
+        {
+            diagnostics.push(Diagnostic::new(location, kind(id.into())));
+        }
+    }
+
+    diagnostics
+}
+
+/// Reads and validates a SAM header.
+///
+/// Unlike [`crate::io::Reader::read_header`], this does not fail on duplicate reference sequence
+/// names or read group IDs. Each duplicate is reported as a diagnostic (see
+/// [`validate_raw_header`]) and left out of the returned header, i.e., the first definition is
+/// kept. Other malformed header lines are still an error.
+///
+/// The position of the stream is expected to be at the start. Records can be read afterward.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{self as sam, validation::{self, Kind}};
+///
+/// let data = b"@HD\tVN:1.6\n@RG\tID:rg0\n@RG\tID:rg0\n";
+/// let mut reader = sam::io::Reader::new(&data[..]);
+///
+/// let (header, diagnostics) = validation::read_header(&mut reader)?;
+///
+/// assert_eq!(header.read_groups().len(), 1);
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].kind(), &Kind::DuplicateReadGroupId("rg0".into()));
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn read_header<R>(reader: &mut crate::io::Reader<R>) -> io::Result<(Header, Vec<Diagnostic>)>
+where
+    R: io::BufRead,
+{
+    let mut src = Vec::new();
+    reader.header_reader().read_to_end(&mut src)?;
+    parse_header(&src)
+}
+
+/// Parses and validates raw SAM header text.
+///
+/// This is the parser used by [`read_header`] and is useful for header text read from other
+/// formats, e.g., BAM or CRAM. Duplicate reference sequence names and read group IDs are reported
+/// as diagnostics and left out of the returned header.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::validation::{self, Kind};
+///
+/// let src = b"@HD\tVN:1.6\n@SQ\tSN:sq0\tLN:8\n@SQ\tSN:sq0\tLN:13\n";
+/// let (header, diagnostics) = validation::parse_header(src)?;
+///
+/// assert_eq!(header.reference_sequences().len(), 1);
+/// assert_eq!(diagnostics[0].kind(), &Kind::DuplicateReferenceSequenceName("sq0".into()));
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn parse_header(src: &[u8]) -> io::Result<(Header, Vec<Diagnostic>)> {
+    let diagnostics = validate_raw_header(src);
+
+    let duplicate_line_numbers: HashSet<_> = diagnostics
+        .iter()
+        .filter_map(|diagnostic| match diagnostic.location() {
+            Location::Header { line_number } => Some(line_number),
+            _ => None,
+        })
+        .collect();
+
+    let mut parser = header::Parser::default();
+
+    for (i, line) in src.lines().enumerate() {
+        if !duplicate_line_numbers.contains(&(i + 1)) {
+            parser
+                .parse_partial(line)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        }
+    }
+
+    Ok((parser.finish(), diagnostics))
+}
+
+fn invalid_field(field: &'static str, e: io::Error) -> Kind {
+    Kind::InvalidField {
+        field,
+        message: e.to_string(),
+    }
+}
+
+fn validate_flags<R>(header: &Header, record: &R, flags: Flags, kinds: &mut Vec<Kind>)
+where
+    R: Record + ?Sized,
+{
+    const MATE_FLAGS: Flags = Flags::PROPERLY_SEGMENTED
+        .union(Flags::MATE_UNMAPPED)
+        .union(Flags::MATE_REVERSE_COMPLEMENTED)
+        .union(Flags::FIRST_SEGMENT)
+        .union(Flags::LAST_SEGMENT);
+
+    if flags.is_unmapped() {
+        match record.mapping_quality().transpose() {
+            Ok(Some(mapping_quality)) => {
+                let n = u8::from(mapping_quality);
+
+                if n != 0 {
+                    kinds.push(Kind::UnmappedWithMappingQuality(n));
+                }
+            }
+            Ok(None) => {}
+            Err(e) => kinds.push(invalid_field("mapping quality", e)),
+        }
+    } else {
+        let is_placed = matches!(record.reference_sequence_id(header), Some(Ok(_)))
+            && matches!(record.alignment_start(), Some(Ok(_)));
+
+        if !is_placed || record.cigar().is_empty() {
+            kinds.push(Kind::IncompleteMappedRecord);
+        }
+    }
+
+    if flags.is_segmented() {
+        if !flags.is_mate_unmapped() {
+            let is_mate_placed = matches!(record.mate_reference_sequence_id(header), Some(Ok(_)))
+                && matches!(record.mate_alignment_start(), Some(Ok(_)));
+
+            if !is_mate_placed {
+                kinds.push(Kind::MissingMatePosition);
+            }
+        }
+    } else if flags.intersects(MATE_FLAGS) {
+        kinds.push(Kind::MateFlagsOnUnsegmentedRecord);
+    }
+}
+
+fn validate_lengths<R>(record: &R, kinds: &mut Vec<Kind>)
+where
+    R: Record + ?Sized,
+{
+    let sequence_length = record.sequence().len();
+
+    if sequence_length == 0 {
+        return;
+    }
+
+    let cigar = record.cigar();
+
+    if !cigar.is_empty() {
+        match cigar.read_length() {
+            Ok(cigar_read_length) => {
+                if cigar_read_length != sequence_length {
+                    kinds.push(Kind::CigarSequenceLengthMismatch {
+                        cigar_read_length,
+                        sequence_length,
+                    });
+                }
+            }
+            Err(e) => kinds.push(invalid_field("CIGAR", e)),
+        }
+    }
+
+    let quality_scores_length = record.quality_scores().len();
+
+    if quality_scores_length != 0 && quality_scores_length != sequence_length {
+        kinds.push(Kind::QualityScoresLengthMismatch {
+            quality_scores_length,
+            sequence_length,
+        });
+    }
+}
+
+fn validate_positions<R>(header: &Header, record: &R, kinds: &mut Vec<Kind>)
+where
+    R: Record + ?Sized,
+{
+    let reference_sequences = header.reference_sequences();
+
+    let reference_sequence_length = |id: usize, kinds: &mut Vec<Kind>| {
+        let len = reference_sequences
+            .get_index(id)
+            .map(|(_, reference_sequence)| reference_sequence.length().get());
+
+        if len.is_none() {
+            kinds.push(Kind::InvalidReferenceSequenceId(id));
+        }
+
+        len
+    };
+
+    match record.reference_sequence_id(header).transpose() {
+        Ok(Some(id)) => {
+            if let Some(len) = reference_sequence_length(id, kinds) {
+                match record.alignment_end().transpose() {
+                    Ok(Some(end)) => {
+                        let position = usize::from(end);
+
+                        if position > len {
+                            kinds.push(Kind::PositionOutOfBounds {
+                                field: "alignment end",
+                                position,
+                                reference_sequence_length: len,
+                            });
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => kinds.push(invalid_field("alignment end", e)),
+                }
+            }
+        }
+        Ok(None) => {}
+        Err(e) => kinds.push(invalid_field("reference sequence ID", e)),
+    }
+
+    match record.mate_reference_sequence_id(header).transpose() {
+        Ok(Some(id)) => {
+            if let Some(len) = reference_sequence_length(id, kinds) {
+                match record.mate_alignment_start().transpose() {
+                    Ok(Some(start)) => {
+                        let position = usize::from(start);
+
+                        if position > len {
+                            kinds.push(Kind::PositionOutOfBounds {
+                                field: "mate alignment start",
+                                position,
+                                reference_sequence_length: len,
+                            });
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => kinds.push(invalid_field("mate alignment start", e)),
+                }
+            }
+        }
+        Ok(None) => {}
+        Err(e) => kinds.push(invalid_field("mate reference sequence ID", e)),
+    }
+}
+
+fn validate_data<R>(header: &Header, record: &R, kinds: &mut Vec<Kind>)
+where
+    R: Record + ?Sized,
+{
+    use crate::alignment::record::data::field::Value;
+
+    for result in record.data().iter() {
+        let (tag, value) = match result {
+            Ok(field) => field,
+            Err(e) => {
+                kinds.push(invalid_field("data", e));
+                return;
+            }
+        };
+
+        let actual = value.ty();
+
+        if !data::is_valid_type(tag, actual) {
+            kinds.push(Kind::InvalidDataFieldType { tag, actual });
+            continue;
+        }
+
+        if tag == Tag::READ_GROUP
+            && let Value::String(id) = value
+            && !header.read_groups().contains_key(id)
+        {
+            kinds.push(Kind::MissingReadGroup(id.into()));
+        }
+    }
+}
+
+fn cmp_sort_keys(a: &SortKey, b: &SortKey) -> Ordering {
+    match (a, b) {
+        (SortKey::Coordinate(a_id, a_start), SortKey::Coordinate(b_id, b_start)) => {
+            // Records without a reference sequence sort last.
+            match (a_id, b_id) {
+                (Some(a_id), Some(b_id)) => a_id.cmp(b_id).then(a_start.cmp(b_start)),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        }
+        (SortKey::QueryName(order, a), SortKey::QueryName(_, b)) => match order {
+            QueryNameOrder::Natural => cmp_natural(a, b),
+            QueryNameOrder::Lexicographical => a.cmp(b),
+        },
+        _ => Ordering::Equal,
+    }
+}
+
+// Compares names in natural order, i.e., runs of digits are compared by numeric value.
+fn cmp_natural(a: &[u8], b: &[u8]) -> Ordering {
+    fn split_digits(src: &[u8]) -> (&[u8], &[u8]) {
+        let i = src
+            .iter()
+            .position(|b| !b.is_ascii_digit())
+            .unwrap_or(src.len());
+
+        src.split_at(i)
+    }
+
+    fn trim_leading_zeros(src: &[u8]) -> &[u8] {
+        let i = src.iter().position(|&b| b != b'0').unwrap_or(src.len());
+        &src[i..]
+    }
+
+    let (mut a, mut b) = (a, b);
+
+    loop {
+        match (a.first(), b.first()) {
+            (Some(c), Some(d)) if c.is_ascii_digit() && d.is_ascii_digit() => {
+                let (a_digits, a_rest) = split_digits(a);
+                let (b_digits, b_rest) = split_digits(b);
+
+                let (a_n, b_n) = (trim_leading_zeros(a_digits), trim_leading_zeros(b_digits));
+
+                let ordering = a_n
+                    .len()
+                    .cmp(&b_n.len())
+                    .then_with(|| a_n.cmp(b_n))
+                    .then_with(|| a_digits.len().cmp(&b_digits.len()));
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+
+                (a, b) = (a_rest, b_rest);
+            }
+            (Some(c), Some(d)) => {
+                if c != d {
+                    return c.cmp(d);
+                }
+
+                (a, b) = (&a[1..], &b[1..]);
+            }
+            (Some(_), None) => return Ordering::Greater,
+            (None, Some(_)) => return Ordering::Less,
+            (None, None) => return Ordering::Equal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use super::*;
+    use crate::{
+        alignment::{
+            RecordBuf,
+            record::{
+                MappingQuality,
+                cigar::{Op, op::Kind as OpKind},
+            },
+            record_buf::{QualityScores, Sequence, data::field::Value},
+        },
+        header::record::value::{
+            Map,
+            map::{self, ReferenceSequence},
+        },
+    };
+
+    fn build_header(sort_order: &[u8]) -> Result<Header, Box<dyn std::error::Error>> {
+        Ok(Header::builder()
+            .set_header(
+                Map::<map::Header>::builder()
+                    .insert(SORT_ORDER, sort_order)
+                    .build()?,
+            )
+            .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(NonZero::try_from(8)?))
+            .build())
+    }
+
+    fn kinds(diagnostics: Vec<Diagnostic>) -> Vec<Kind> {
+        diagnostics
+            .into_iter()
+            .map(|diagnostic| diagnostic.kind().clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_record() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header(COORDINATE)?;
+        let mut validator = Validator::new(&header);
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(1)?)
+            .set_cigar([Op::new(OpKind::Match, 4)].into_iter().collect())
+            .set_sequence(Sequence::from(b"ACGT"))
+            .set_quality_scores(QualityScores::from(vec![45, 35, 43, 50]))
+            .build();
+
+        assert!(validator.validate_record(&record).is_empty());
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(6)?)
+            .set_cigar([Op::new(OpKind::Match, 4)].into_iter().collect())
+            .set_sequence(Sequence::from(b"ACG"))
+            .set_quality_scores(QualityScores::from(vec![45, 35]))
+            .build();
+
+        let diagnostics = validator.validate_record(&record);
+        assert!(
+            diagnostics
+                .iter()
+                .all(|d| d.location() == Location::Record { record_number: 2 })
+        );
+        assert_eq!(
+            kinds(diagnostics),
+            [
+                Kind::CigarSequenceLengthMismatch {
+                    cigar_read_length: 4,
+                    sequence_length: 3,
+                },
+                Kind::QualityScoresLengthMismatch {
+                    quality_scores_length: 2,
+                    sequence_length: 3,
+                },
+                Kind::PositionOutOfBounds {
+                    field: "alignment end",
+                    position: 9,
+                    reference_sequence_length: 8,
+                },
+            ]
+        );
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::UNMAPPED | Flags::SEGMENTED)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(2)?)
+            .set_mapping_quality(MappingQuality::try_from(8)?)
+            .set_data(
+                [(Tag::READ_GROUP, Value::from("rg0"))]
+                    .into_iter()
+                    .collect(),
+            )
+            .build();
+
+        assert_eq!(
+            kinds(validator.validate_record(&record)),
+            [
+                Kind::UnmappedWithMappingQuality(8),
+                Kind::MissingMatePosition,
+                Kind::InvalidSortOrder,
+                Kind::MissingReadGroup("rg0".into()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_record_with_invalid_data_field_type() {
+        let header = Header::default();
+        let mut validator = Validator::new(&header);
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::UNMAPPED)
+            .set_data(
+                [(Tag::EDIT_DISTANCE, Value::from("0"))]
+                    .into_iter()
+                    .collect(),
+            )
+            .build();
+
+        assert_eq!(
+            kinds(validator.validate_record(&record)),
+            [Kind::InvalidDataFieldType {
+                tag: Tag::EDIT_DISTANCE,
+                actual: crate::alignment::record::data::field::Type::String,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validate_record_with_query_name_sort_order() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header(QUERY_NAME)?;
+        let mut validator = Validator::new(&header);
+
+        for (name, expected) in [("r1", true), ("r1", true), ("r2", true), ("r0", false)] {
+            let record = RecordBuf::builder()
+                .set_name(name)
+                .set_flags(Flags::UNMAPPED)
+                .build();
+
+            assert_eq!(validator.validate_record(&record).is_empty(), expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_record_with_query_name_subsort_order() -> Result<(), Box<dyn std::error::Error>>
+    {
+        fn validate_names(header: &Header, names: &[&str]) -> Vec<bool> {
+            let mut validator = Validator::new(header);
+
+            names
+                .iter()
+                .map(|name| {
+                    let record = RecordBuf::builder()
+                        .set_name(*name)
+                        .set_flags(Flags::UNMAPPED)
+                        .build();
+
+                    validator.validate_record(&record).is_empty()
+                })
+                .collect()
+        }
+
+        fn build_header_with_subsort_order(
+            subsort_order: &[u8],
+        ) -> Result<Header, Box<dyn std::error::Error>> {
+            Ok(Header::builder()
+                .set_header(
+                    Map::<map::Header>::builder()
+                        .insert(SORT_ORDER, QUERY_NAME)
+                        .insert(SUBSORT_ORDER, subsort_order)
+                        .build()?,
+                )
+                .build())
+        }
+
+        let header = build_header(QUERY_NAME)?;
+        assert_eq!(validate_names(&header, &["r2", "r10"]), [true, true]);
+        assert_eq!(validate_names(&header, &["r10", "r2"]), [true, false]);
+
+        let header = build_header_with_subsort_order(b"queryname:natural")?;
+        assert_eq!(validate_names(&header, &["r2", "r10"]), [true, true]);
+
+        let header = build_header_with_subsort_order(b"queryname:lexicographical")?;
+        assert_eq!(validate_names(&header, &["r10", "r2"]), [true, true]);
+        assert_eq!(validate_names(&header, &["r2", "r10"]), [true, false]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cmp_natural() {
+        assert_eq!(cmp_natural(b"r2", b"r10"), Ordering::Less);
+        assert_eq!(cmp_natural(b"r10", b"r10"), Ordering::Equal);
+        assert_eq!(cmp_natural(b"r10", b"r2"), Ordering::Greater);
+        assert_eq!(cmp_natural(b"r1:2", b"r1:10"), Ordering::Less);
+        assert_eq!(cmp_natural(b"r01", b"r1"), Ordering::Greater);
+        assert_eq!(cmp_natural(b"r1", b"r1a"), Ordering::Less);
+        assert_eq!(cmp_natural(b"a9", b"b1"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_read_header() -> Result<(), Box<dyn std::error::Error>> {
+        let data = b"@HD\tVN:1.6\tSO:coordinate
+@SQ\tSN:sq0\tLN:8
+@RG\tID:rg0\tSM:s0
+@RG\tID:rg0\tSM:s1
+r0\t4\t*\t0\t0\t*\t*\t0\t0\t*\t*\tRG:Z:rg0
+";
+
+        let mut reader = crate::io::Reader::new(&data[..]);
+        let (header, diagnostics) = read_header(&mut reader)?;
+
+        assert_eq!(header.reference_sequences().len(), 1);
+        assert_eq!(header.read_groups().len(), 1);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].location(),
+            Location::Header { line_number: 4 }
+        );
+        assert_eq!(
+            diagnostics[0].kind(),
+            &Kind::DuplicateReadGroupId(BString::from("rg0"))
+        );
+
+        let mut validator = Validator::new(&header);
+        let mut n = 0;
+
+        for result in reader.records() {
+            let record = result?;
+            assert!(validator.validate_record(&record).is_empty());
+            n += 1;
+        }
+
+        assert_eq!(n, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_raw_header() {
+        let src = b"@HD\tVN:1.6\n@SQ\tSN:sq0\tLN:8\n@SQ\tSN:sq0\tLN:13\n@RG\tID:rg0\n";
+
+        assert_eq!(
+            validate_raw_header(src),
+            [Diagnostic::new(
+                Location::Header { line_number: 3 },
+                Kind::DuplicateReferenceSequenceName("sq0".into())
+            )]
+        );
+    }
+}
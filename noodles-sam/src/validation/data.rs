@@ -0,0 +1,112 @@
+use crate::alignment::record::data::field::{Tag, Type};
+
+#[derive(Clone, Copy)]
+enum ExpectedType {
+    Character,
+    Integer,
+    String,
+    Array,
+}
+
+impl ExpectedType {
+    fn matches(self, ty: Type) -> bool {
+        match self {
+            Self::Character => ty == Type::Character,
+            Self::Integer => matches!(
+                ty,
+                Type::Int8 | Type::UInt8 | Type::Int16 | Type::UInt16 | Type::Int32 | Type::UInt32
+            ),
+            Self::String => ty == Type::String,
+            Self::Array => ty == Type::Array,
+        }
+    }
+}
+
+/// Returns whether the type is valid for the given tag.
+///
+/// Tags that are not reserved (or whose types are unspecified) accept any type.
+pub(super) fn is_valid_type(tag: Tag, ty: Type) -> bool {
+    expected_type(tag).is_none_or(|expected_type| expected_type.matches(ty))
+}
+
+fn expected_type(tag: Tag) -> Option<ExpectedType> {
+    use ExpectedType::{Array, Character, Integer, String};
+
+    let expected_type = match tag {
+        Tag::MIN_MAPPING_QUALITY => Integer,
+        Tag::ALIGNMENT_SCORE => Integer,
+        Tag::SAMPLE_BARCODE_SEQUENCE => String,
+        Tag::BASE_ALIGNMENT_QUALITY_OFFSETS => String,
+        Tag::ORIGINAL_UMI_QUALITY_SCORES => String,
+        Tag::CELL_BARCODE_ID => String,
+        Tag::NEXT_HIT_REFERENCE_SEQUENCE_NAME => String,
+        Tag::CIGAR => Array,
+        Tag::COLOR_EDIT_DISTANCE => Integer,
+        Tag::COMMENT => String,
+        Tag::NEXT_HIT_POSITION => Integer,
+        Tag::COLOR_QUALITY_SCORES => String,
+        Tag::CELL_BARCODE_SEQUENCE => String,
+        Tag::COLOR_SEQUENCE => String,
+        Tag::COMPLETE_READ_ANNOTATIONS => String,
+        Tag::CELL_BARCODE_QUALITY_SCORES => String,
+        Tag::NEXT_HIT_SEQUENCE => String,
+        Tag::SEGMENT_INDEX => Integer,
+        Tag::SEGMENT_SUFFIX => String,
+        Tag::ALTERNATIVE_SEQUENCE => Array,
+        Tag::PERFECT_HIT_COUNT => Integer,
+        Tag::ONE_DIFFERENCE_HIT_COUNT => Integer,
+        Tag::TWO_DIFFERENCE_HIT_COUNT => Integer,
+        Tag::HIT_INDEX => Integer,
+        Tag::TOTAL_HIT_COUNT => Integer,
+        Tag::LIBRARY => String,
+        Tag::MATE_CIGAR => String,
+        Tag::MISMATCHED_POSITIONS => String,
+        Tag::UMI_ID => String,
+        Tag::BASE_MODIFICATION_PROBABILITIES => Array,
+        Tag::BASE_MODIFICATIONS => String,
+        Tag::BASE_MODIFICATION_SEQUENCE_LENGTH => Integer,
+        Tag::MATE_MAPPING_QUALITY => Integer,
+        Tag::ALIGNMENT_HIT_COUNT => Integer,
+        Tag::EDIT_DISTANCE => Integer,
+        Tag::ORIGINAL_ALIGNMENT => String,
+        Tag::ORIGINAL_CIGAR => String,
+        Tag::ORIGINAL_POSITION => Integer,
+        Tag::ORIGINAL_QUALITY_SCORES => String,
+        Tag::ORIGINAL_UMI_BARCODE_SEQUENCE => String,
+        Tag::PROGRAM => String,
+        Tag::TEMPLATE_LIKELIHOOD => Integer,
+        Tag::PADDED_READ_ANNOTATIONS => String,
+        Tag::PLATFORM_UNIT => String,
+        Tag::MATE_QUALITY_SCORES => String,
+        Tag::SAMPLE_BARCODE_QUALITY_SCORES => String,
+        Tag::UMI_QUALITY_SCORES => String,
+        Tag::MATE_SEQUENCE => String,
+        Tag::READ_GROUP => String,
+        Tag::UMI_SEQUENCE => String,
+        Tag::OTHER_ALIGNMENTS => String,
+        Tag::TEMPLATE_MAPPING_QUALITY => Integer,
+        Tag::SEGMENT_COUNT => Integer,
+        Tag::TRANSCRIPT_STRAND => Character,
+        Tag::NEXT_HIT_QUALITY_SCORES => String,
+        Tag::SEGMENT_LIKELIHOOD => Integer,
+        _ => return None,
+    };
+
+    Some(expected_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_type() {
+        assert!(is_valid_type(Tag::EDIT_DISTANCE, Type::UInt8));
+        assert!(is_valid_type(Tag::EDIT_DISTANCE, Type::Int32));
+        assert!(!is_valid_type(Tag::EDIT_DISTANCE, Type::String));
+        assert!(is_valid_type(Tag::READ_GROUP, Type::String));
+        assert!(!is_valid_type(Tag::READ_GROUP, Type::Character));
+        assert!(is_valid_type(Tag::TRANSCRIPT_STRAND, Type::Character));
+        assert!(is_valid_type(Tag::new(b'X', b'Y'), Type::Float));
+    }
+}
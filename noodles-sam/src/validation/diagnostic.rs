@@ -0,0 +1,185 @@
+use std::fmt;
+
+use bstr::BString;
+
+use crate::alignment::record::data::field::{Tag, Type};
+
+/// The location of a validation diagnostic.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Location {
+    /// A header line.
+    ///
+    /// The line number is 1-based.
+    Header {
+        /// The line number.
+        line_number: usize,
+    },
+    /// A record.
+    ///
+    /// The record number is 1-based and counts records in the order they were validated.
+    Record {
+        /// The record number.
+        record_number: u64,
+    },
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Header { line_number } => write!(f, "header line {line_number}"),
+            Self::Record { record_number } => write!(f, "record {record_number}"),
+        }
+    }
+}
+
+/// A validation diagnostic kind.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Kind {
+    /// A read group ID is defined more than once.
+    DuplicateReadGroupId(BString),
+    /// A reference sequence name is defined more than once.
+    DuplicateReferenceSequenceName(BString),
+    /// A field could not be decoded.
+    InvalidField {
+        /// The field name.
+        field: &'static str,
+        /// The decoding error message.
+        message: String,
+    },
+    /// The read length implied by the CIGAR operations does not match the sequence length.
+    CigarSequenceLengthMismatch {
+        /// The read length implied by the CIGAR operations.
+        cigar_read_length: usize,
+        /// The sequence length.
+        sequence_length: usize,
+    },
+    /// The number of quality scores does not match the sequence length.
+    QualityScoresLengthMismatch {
+        /// The number of quality scores.
+        quality_scores_length: usize,
+        /// The sequence length.
+        sequence_length: usize,
+    },
+    /// A mapped record is missing its reference sequence, alignment start, or CIGAR operations.
+    IncompleteMappedRecord,
+    /// An unmapped record has a nonzero mapping quality.
+    UnmappedWithMappingQuality(u8),
+    /// A record that is not segmented has mate flags set.
+    MateFlagsOnUnsegmentedRecord,
+    /// A segmented record with a mapped mate is missing the mate reference sequence or mate
+    /// alignment start.
+    MissingMatePosition,
+    /// A reference sequence ID is not defined in the header.
+    InvalidReferenceSequenceId(usize),
+    /// A position is past the end of its reference sequence.
+    PositionOutOfBounds {
+        /// The field name.
+        field: &'static str,
+        /// The position.
+        position: usize,
+        /// The reference sequence length.
+        reference_sequence_length: usize,
+    },
+    /// A read group referenced by a record is not defined in the header.
+    MissingReadGroup(BString),
+    /// A record is out of order with respect to the header sort order.
+    InvalidSortOrder,
+    /// A reserved data field has an unexpected type.
+    InvalidDataFieldType {
+        /// The data field tag.
+        tag: Tag,
+        /// The actual type.
+        actual: Type,
+    },
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateReadGroupId(id) => write!(f, "duplicate read group ID: {id}"),
+            Self::DuplicateReferenceSequenceName(name) => {
+                write!(f, "duplicate reference sequence name: {name}")
+            }
+            Self::InvalidField { field, message } => write!(f, "invalid {field}: {message}"),
+            Self::CigarSequenceLengthMismatch {
+                cigar_read_length,
+                sequence_length,
+            } => write!(
+                f,
+                "CIGAR read length ({cigar_read_length}) does not match sequence length ({sequence_length})"
+            ),
+            Self::QualityScoresLengthMismatch {
+                quality_scores_length,
+                sequence_length,
+            } => write!(
+                f,
+                "quality scores length ({quality_scores_length}) does not match sequence length ({sequence_length})"
+            ),
+            Self::IncompleteMappedRecord => write!(
+                f,
+                "mapped record is missing reference sequence, alignment start, or CIGAR"
+            ),
+            Self::UnmappedWithMappingQuality(n) => {
+                write!(f, "unmapped record has nonzero mapping quality: {n}")
+            }
+            Self::MateFlagsOnUnsegmentedRecord => {
+                write!(f, "unsegmented record has mate flags set")
+            }
+            Self::MissingMatePosition => write!(
+                f,
+                "segmented record with mapped mate is missing mate reference sequence or position"
+            ),
+            Self::InvalidReferenceSequenceId(id) => {
+                write!(f, "invalid reference sequence ID: {id}")
+            }
+            Self::PositionOutOfBounds {
+                field,
+                position,
+                reference_sequence_length,
+            } => write!(
+                f,
+                "{field} ({position}) is past the end of the reference sequence ({reference_sequence_length})"
+            ),
+            Self::MissingReadGroup(id) => write!(f, "read group not in header: {id}"),
+            Self::InvalidSortOrder => write!(f, "record is out of order"),
+            Self::InvalidDataFieldType { tag, actual } => {
+                let [a, b] = tag.as_ref();
+                write!(
+                    f,
+                    "invalid data field type for {}{}: {actual:?}",
+                    char::from(*a),
+                    char::from(*b)
+                )
+            }
+        }
+    }
+}
+
+/// A validation diagnostic.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    location: Location,
+    kind: Kind,
+}
+
+impl Diagnostic {
+    pub(super) fn new(location: Location, kind: Kind) -> Self {
+        Self { location, kind }
+    }
+
+    /// Returns where the problem was found.
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    /// Returns the problem that was found.
+    pub fn kind(&self) -> &Kind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.kind)
+    }
+}
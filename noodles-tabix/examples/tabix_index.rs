@@ -0,0 +1,39 @@
+//! Builds and writes a tabix index from a bgzip-compressed, tab-delimited text file.
+//!
+//! The preset is one of `gff`, `bed`, `sam`, or `vcf`.
+//!
+//! This writes the output to stdout rather than `<src>.tbi`.
+//!
+//! The output is similar to the output of `tabix --preset <preset> <src>`.
+
+use std::{env, io};
+
+use noodles_csi::binning_index::index::header;
+use noodles_tabix as tabix;
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+    let src = args.next().expect("missing src");
+    let preset = args.next().expect("missing preset");
+
+    let builder = match preset.as_str() {
+        "gff" => header::Builder::gff(),
+        "bed" => header::Builder::bed(),
+        "sam" => header::Builder::sam(),
+        "vcf" => header::Builder::vcf(),
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid preset: {preset}"),
+            ));
+        }
+    };
+
+    let index = tabix::fs::index(src, builder.build())?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = tabix::io::Writer::new(stdout);
+    writer.write_index(&index)?;
+
+    Ok(())
+}
@@ -1,7 +1,10 @@
 //! tabix filesystem operations.
 
+mod index;
+
 use std::{fs::File, io, path::Path};
 
+pub use self::index::index;
 use super::{
     Index,
     io::{Reader, Writer},
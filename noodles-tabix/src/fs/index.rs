@@ -0,0 +1,235 @@
+use std::{
+    fs::File,
+    io::{self, BufRead},
+    path::Path,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+use noodles_csi::{
+    binning_index::index::{Header, header::Format, reference_sequence::bin::Chunk},
+    io::{IndexedRecord, indexed_records::Record},
+};
+
+use crate::{Index, index::Indexer};
+
+/// Indexes a bgzip-compressed, tab-delimited text file.
+///
+/// The columns, coordinate system, comment prefix, and number of lines to skip are read from the
+/// given index header. Use a preset, e.g., [`header::Builder::gff`], [`header::Builder::bed`],
+/// [`header::Builder::sam`], or [`header::Builder::vcf`], or set custom columns, which is the
+/// equivalent of `tabix --preset` or `tabix --sequence --begin --end --zero-based --comment
+/// --skip-lines`, respectively.
+///
+/// Lines that start with the comment prefix and empty lines are skipped. For the SAM format,
+/// unplaced records are skipped. The input must be grouped by reference sequence name and sorted
+/// by start position.
+///
+/// See also [`crate::fs::write`] to write the resulting [`Index`] to a file.
+///
+/// [`header::Builder::gff`]: noodles_csi::binning_index::index::header::Builder::gff
+/// [`header::Builder::bed`]: noodles_csi::binning_index::index::header::Builder::bed
+/// [`header::Builder::sam`]: noodles_csi::binning_index::index::header::Builder::sam
+/// [`header::Builder::vcf`]: noodles_csi::binning_index::index::header::Builder::vcf
+///
+/// # Examples
+///
+/// ```no_run
+/// use noodles_csi::binning_index::index::header;
+/// use noodles_tabix as tabix;
+///
+/// let header = header::Builder::gff().build();
+/// let index = tabix::fs::index("annotations.gff3.gz", header)?;
+///
+/// let header = header::Builder::bed()
+///     .set_end_position_index(None)
+///     .set_line_skip_count(1)
+///     .build();
+/// let index = tabix::fs::index("sample.tsv.gz", header)?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn index<P>(src: P, header: Header) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src).map(bgzf::io::Reader::new)?;
+    index_inner(&mut reader, header)
+}
+
+fn index_inner<R>(reader: &mut bgzf::io::Reader<R>, header: Header) -> io::Result<Index>
+where
+    R: io::Read,
+{
+    const MISSING: &str = "*";
+
+    let mut indexer = Indexer::default();
+
+    let mut buf = Vec::new();
+    let mut line_count = 0;
+    let mut last: Option<(String, Position)> = None;
+
+    loop {
+        let start_position = reader.virtual_position();
+
+        buf.clear();
+
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+
+        let end_position = reader.virtual_position();
+
+        line_count += 1;
+
+        if line_count <= header.line_skip_count()
+            || buf.first() == Some(&header.line_comment_prefix())
+        {
+            continue;
+        }
+
+        let line = String::from_utf8(chomp(&buf).to_vec())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if header.format() == Format::Sam
+            && line.split('\t').nth(header.reference_sequence_name_index()) == Some(MISSING)
+        {
+            continue;
+        }
+
+        let record = Record::parse(line, &header)?;
+
+        let reference_sequence_name = record.indexed_reference_sequence_name();
+        let start = record.indexed_start_position();
+        let end = record.indexed_end_position().max(start);
+
+        match &mut last {
+            Some((name, last_start)) if name == reference_sequence_name => {
+                if start < *last_start {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "unsorted positions",
+                    ));
+                }
+
+                *last_start = start;
+            }
+            _ => last = Some((reference_sequence_name.into(), start)),
+        }
+
+        let chunk = Chunk::new(start_position, end_position);
+        indexer.add_record(reference_sequence_name, start, end, chunk)?;
+    }
+
+    indexer.set_header(header);
+
+    Ok(indexer.build())
+}
+
+fn chomp(buf: &[u8]) -> &[u8] {
+    let buf = buf.strip_suffix(b"\n").unwrap_or(buf);
+    buf.strip_suffix(b"\r").unwrap_or(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use noodles_core::Region;
+    use noodles_csi::{BinningIndex, binning_index::index::header};
+
+    use super::*;
+
+    fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut writer = bgzf::io::Writer::new(Vec::new());
+        writer.write_all(data)?;
+        writer.finish()
+    }
+
+    fn query(
+        data: &[u8],
+        index: Index,
+        region: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let region: Region = region.parse()?;
+        let mut reader = noodles_csi::io::IndexedReader::new(io::Cursor::new(data), index);
+
+        let lines = reader
+            .query(&region)?
+            .map(|result| result.map(|record| record.as_ref().to_string()))
+            .collect::<io::Result<_>>()?;
+
+        Ok(lines)
+    }
+
+    #[test]
+    fn test_index_inner() -> Result<(), Box<dyn std::error::Error>> {
+        let data = compress(
+            b"chrom\tstart\tend
+#noodles
+sq0\t7\t13
+sq0\t21\t34
+sq1\t0\t5
+",
+        )?;
+
+        let header = header::Builder::bed().set_line_skip_count(1).build();
+        let mut reader = bgzf::io::Reader::new(&data[..]);
+        let index = index_inner(&mut reader, header)?;
+
+        let header = index.header().expect("missing header");
+        assert_eq!(
+            header.reference_sequence_names().iter().collect::<Vec<_>>(),
+            ["sq0", "sq1"]
+        );
+        assert_eq!(index.reference_sequences().len(), 2);
+
+        assert_eq!(query(&data, index.clone(), "sq0:10-21")?, ["sq0\t7\t13"]);
+        assert_eq!(
+            query(&data, index.clone(), "sq0:14-21")?,
+            Vec::<String>::new()
+        );
+        assert_eq!(query(&data, index.clone(), "sq0:22-22")?, ["sq0\t21\t34"]);
+        assert_eq!(query(&data, index, "sq1")?, ["sq1\t0\t5"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_inner_with_vcf_format() -> Result<(), Box<dyn std::error::Error>> {
+        let data = compress(
+            b"##fileformat=VCFv4.5
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t8\t.\tACGT\tA\t.\tPASS\t.
+sq0\t13\t.\tA\t<DEL>\t.\tPASS\tEND=21
+",
+        )?;
+
+        let mut reader = bgzf::io::Reader::new(&data[..]);
+        let index = index_inner(&mut reader, header::Builder::vcf().build())?;
+
+        let lines = query(&data, index.clone(), "sq0:10-10")?;
+        assert_eq!(lines, ["sq0\t8\t.\tACGT\tA\t.\tPASS\t."]);
+
+        let lines = query(&data, index, "sq0:20-20")?;
+        assert_eq!(lines, ["sq0\t13\t.\tA\t<DEL>\t.\tPASS\tEND=21"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_index_inner_with_unsorted_positions() -> io::Result<()> {
+        let data = compress(b"sq0\t21\t34\nsq0\t7\t13\n")?;
+        let mut reader = bgzf::io::Reader::new(&data[..]);
+
+        assert!(matches!(
+            index_inner(&mut reader, header::Builder::bed().build()),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
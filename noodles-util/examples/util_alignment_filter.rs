@@ -0,0 +1,39 @@
+//! Prints the records of an alignment file that pass a filter expression in the SAM format.
+//!
+//! E.g., `util_alignment_filter sample.bam 'flag & 0x4 == 0 && [NM] < 5'`.
+
+use std::{
+    env,
+    io::{self, BufWriter},
+};
+
+use noodles_sam::{self as sam, alignment::io::Write};
+use noodles_util::alignment::{self, filter::Filter};
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let expr = args.next().expect("missing expr");
+
+    let mut reader = alignment::io::reader::Builder::default().build_from_path(src)?;
+    let header = reader.read_header()?;
+
+    let filter =
+        Filter::new(&header, &expr).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = sam::io::Writer::new(BufWriter::new(stdout));
+
+    writer.write_header(&header)?;
+
+    for result in reader.records(&header) {
+        let record = result?;
+
+        if filter.evaluate(&record)? {
+            writer.write_alignment_record(&header, &record)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! Fixes mate information in a queryname-grouped alignment file.
+//!
+//! The output format is determined from the extension of the destination.
+
+use std::{env, io};
+
+use noodles_sam::alignment::RecordBuf;
+use noodles_util::alignment::{self, iter::Templates};
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let dst = args.next().expect("missing dst");
+
+    let mut reader = alignment::io::reader::Builder::default().build_from_path(src)?;
+    let header = reader.read_header()?;
+
+    let mut writer = alignment::io::writer::Builder::default().build_from_path(dst)?;
+    writer.write_header(&header)?;
+
+    let records = reader.records(&header).map(|result| {
+        result.and_then(|record| RecordBuf::try_from_alignment_record(&header, &record))
+    });
+
+    for result in Templates::new(records) {
+        let mut template = result?;
+        template.fix_mates()?;
+
+        for record in template.records() {
+            writer.write_record(&header, record)?;
+        }
+    }
+
+    writer.finish(&header)?;
+
+    Ok(())
+}
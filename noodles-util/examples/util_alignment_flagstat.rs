@@ -0,0 +1,19 @@
+//! Prints flag statistics of an alignment file.
+//!
+//! The result matches the output of `samtools flagstat <src>`.
+
+use std::{env, io};
+
+use noodles_util::alignment::{self, flagstat};
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let mut reader = alignment::io::reader::Builder::default().build_from_path(src)?;
+    let header = reader.read_header()?;
+
+    let stats = flagstat::count(&header, reader.records(&header))?;
+    print!("{stats}");
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+//! Prints index statistics of an indexed alignment file.
+//!
+//! The result matches the output of `samtools idxstats <src>`.
+
+use std::{env, io};
+
+use noodles_util::alignment::{self, idxstats};
+
+fn main() -> io::Result<()> {
+    let src = env::args().nth(1).expect("missing src");
+
+    let mut reader = alignment::io::indexed_reader::Builder::default().build_from_path(src)?;
+    let header = reader.read_header()?;
+
+    let stats = idxstats::read(&mut reader, &header)?;
+    print!("{stats}");
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//! Marks duplicate records in an alignment file.
+//!
+//! The source is read twice: first to find duplicates and then to write the marked records. Only a
+//! small read end per record is held in memory. The output format is determined from the extension
+//! of the destination. Duplication metrics are printed to stderr.
+
+use std::{env, io};
+
+use noodles_sam::alignment::{RecordBuf, record::Flags};
+use noodles_util::alignment::{self, duplicates::Marker};
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let dst = args.next().expect("missing dst");
+
+    let mut reader = alignment::io::reader::Builder::default().build_from_path(&src)?;
+    let header = reader.read_header()?;
+
+    let duplicates = Marker::default().find_duplicates(&header, reader.records(&header))?;
+
+    let mut reader = alignment::io::reader::Builder::default().build_from_path(&src)?;
+    reader.read_header()?;
+
+    let mut writer = alignment::io::writer::Builder::default().build_from_path(dst)?;
+
+    writer.write_header(&header)?;
+
+    for (i, result) in reader.records(&header).enumerate() {
+        let record = result?;
+        let mut record = RecordBuf::try_from_alignment_record(&header, &record)?;
+
+        record
+            .flags_mut()
+            .set(Flags::DUPLICATE, duplicates.is_duplicate(i));
+
+        writer.write_record(&header, &record)?;
+    }
+
+    writer.finish(&header)?;
+
+    let metrics = duplicates.metrics();
+
+    for (library, library_metrics) in metrics.iter() {
+        eprintln!(
+            "{library}\t{}\t{}\t{}\t{}\t{:?}",
+            library_metrics.unpaired_reads_examined(),
+            library_metrics.read_pairs_examined(),
+            library_metrics.unpaired_read_duplicates(),
+            library_metrics.read_pair_duplicates(),
+            library_metrics.percent_duplication(),
+        );
+    }
+
+    Ok(())
+}
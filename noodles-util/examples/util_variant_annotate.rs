@@ -0,0 +1,66 @@
+//! Annotates a variant file using an indexed variant file and prints the result in the VCF format.
+//!
+//! Columns are either `ID` or `INFO/<key>`, e.g., `util_variant_annotate sample.vcf.gz
+//! annotations.vcf.gz ID INFO/AF`. Annotations are matched by position and alleles.
+
+use std::{
+    env,
+    io::{self, BufWriter},
+};
+
+use noodles_util::variant::{
+    self,
+    annotate::{self, Column},
+};
+use noodles_vcf::{
+    self as vcf,
+    variant::{RecordBuf, io::Write},
+};
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let annotations_src = args.next().expect("missing annotations src");
+
+    let mut builder = annotate::Builder::default();
+
+    for arg in args {
+        let column = match arg.strip_prefix("INFO/") {
+            Some(key) => Column::Info(key.into()),
+            None if arg == "ID" => Column::Id,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid column: {arg}"),
+                ));
+            }
+        };
+
+        builder = builder.add_column(column);
+    }
+
+    let annotations_reader =
+        variant::io::indexed_reader::Builder::default().build_from_path(annotations_src)?;
+    let mut annotator = builder.build_from_variant_reader(annotations_reader)?;
+
+    let mut reader = variant::io::reader::Builder::default().build_from_path(src)?;
+    let mut header = reader.read_header()?;
+    annotator.annotate_header(&mut header)?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = vcf::io::Writer::new(BufWriter::new(stdout));
+
+    writer.write_header(&header)?;
+
+    for result in reader.records(&header) {
+        let record = result?;
+        let mut record = RecordBuf::try_from_variant_record(&header, record.as_ref())?;
+
+        annotator.annotate(&header, &mut record)?;
+
+        writer.write_variant_record(&header, &record)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+//! Prints the records of a variant file that pass a filter expression in the VCF format.
+//!
+//! E.g., `util_variant_filter sample.vcf.gz 'QUAL > 30 && FMT/GQ[*] > 20'`.
+
+use std::{
+    env,
+    io::{self, BufWriter},
+};
+
+use noodles_util::variant::{self, filter::Filter};
+use noodles_vcf::{self as vcf, variant::io::Write};
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let src = args.next().expect("missing src");
+    let expr = args.next().expect("missing expr");
+
+    let mut reader = variant::io::reader::Builder::default().build_from_path(src)?;
+    let header = reader.read_header()?;
+
+    let filter =
+        Filter::new(&header, &expr).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let stdout = io::stdout().lock();
+    let mut writer = vcf::io::Writer::new(BufWriter::new(stdout));
+
+    writer.write_header(&header)?;
+
+    for result in reader.records(&header) {
+        let record = result?;
+
+        if filter.evaluate(record.as_ref())? {
+            writer.write_variant_record(&header, record.as_ref())?;
+        }
+    }
+
+    Ok(())
+}
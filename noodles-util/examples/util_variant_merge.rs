@@ -0,0 +1,47 @@
+//! Merges sorted variant files.
+//!
+//! The first argument is the destination, and the rest are sources. Set `MODE=merge` to merge
+//! sources with different samples; otherwise, sources are concatenated.
+//!
+//! The output format is determined from the extension of the destination.
+
+use std::{env, io};
+
+use noodles_util::variant::{
+    self,
+    merge::{self, Mode},
+};
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+
+    let dst = args.next().expect("missing dst");
+    let srcs: Vec<_> = args.collect();
+
+    let mode = match env::var("MODE").as_deref() {
+        Ok("merge") => Mode::Merge,
+        _ => Mode::Concat,
+    };
+
+    let readers = srcs
+        .iter()
+        .map(|src| variant::io::reader::Builder::default().build_from_path(src))
+        .collect::<io::Result<_>>()?;
+
+    let mut merger = merge::Builder::default()
+        .set_mode(mode)
+        .build_from_readers(readers)?;
+
+    let header = merger.header().clone();
+
+    let mut writer = variant::io::writer::Builder::default().build_from_path(dst)?;
+
+    writer.write_header(&header)?;
+
+    for result in merger.records() {
+        let record = result?;
+        writer.write_record(&header, &record)?;
+    }
+
+    Ok(())
+}
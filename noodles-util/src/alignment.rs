@@ -3,8 +3,13 @@
 #[cfg(feature = "async")]
 pub mod r#async;
 
+pub mod duplicates;
+pub mod filter;
+pub mod flagstat;
+pub mod idxstats;
 pub mod io;
 pub mod iter;
 mod record;
+pub mod template;
 
-pub use self::record::Record;
+pub use self::{record::Record, template::Template};
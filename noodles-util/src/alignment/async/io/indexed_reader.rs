@@ -0,0 +1,238 @@
+//! Async indexed alignment reader.
+
+mod builder;
+
+use std::pin::Pin;
+
+use futures::{Stream, StreamExt};
+use noodles_bam as bam;
+use noodles_bgzf as bgzf;
+use noodles_core::Region;
+use noodles_cram as cram;
+use noodles_sam::{self as sam, alignment::Record};
+use tokio::io::{self, AsyncRead, AsyncSeek};
+
+pub use self::builder::Builder;
+
+type RecordStream<'r> = Pin<Box<dyn Stream<Item = io::Result<Box<dyn Record>>> + 'r>>;
+
+/// An async indexed alignment reader.
+pub enum IndexedReader<R>
+where
+    R: AsyncRead,
+{
+    /// SAM.
+    Sam(sam::r#async::io::IndexedReader<R>),
+    /// BAM.
+    Bam(bam::r#async::io::IndexedReader<bgzf::r#async::io::Reader<R>>),
+    /// CRAM.
+    Cram(cram::r#async::io::IndexedReader<R>),
+}
+
+impl<R> IndexedReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Reads the SAM header.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> tokio::io::Result<()> {
+    /// use noodles_util::alignment::r#async::io::indexed_reader::Builder;
+    ///
+    /// let mut reader = Builder::default().build_from_path("sample.bam").await?;
+    /// let _header = reader.read_header().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_header(&mut self) -> io::Result<sam::Header> {
+        match self {
+            Self::Sam(reader) => reader.read_header().await,
+            Self::Bam(reader) => reader.read_header().await,
+            Self::Cram(reader) => reader.read_header().await,
+        }
+    }
+
+    /// Returns a stream over records starting from the current stream position.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> tokio::io::Result<()> {
+    /// use futures::TryStreamExt;
+    /// use noodles_util::alignment::r#async::io::indexed_reader::Builder;
+    ///
+    /// let mut reader = Builder::default().build_from_path("sample.bam").await?;
+    /// let header = reader.read_header().await?;
+    ///
+    /// let mut records = reader.records(&header);
+    ///
+    /// while let Some(record) = records.try_next().await? {
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn records<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h sam::Header,
+    ) -> impl Stream<Item = io::Result<Box<dyn Record>>> + 'r {
+        let records: RecordStream<'r> = match self {
+            Self::Sam(reader) => Box::pin(
+                reader
+                    .records()
+                    .map(|result| result.map(|record| Box::new(record) as Box<dyn Record>)),
+            ),
+            Self::Bam(reader) => Box::pin(
+                reader
+                    .records()
+                    .map(|result| result.map(|record| Box::new(record) as Box<dyn Record>)),
+            ),
+            Self::Cram(reader) => Box::pin(
+                reader
+                    .records(header)
+                    .map(|result| result.map(|record| Box::new(record) as Box<dyn Record>)),
+            ),
+        };
+
+        records
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Returns a stream over records that intersect the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::TryStreamExt;
+    /// use noodles_util::alignment::r#async::io::indexed_reader::Builder;
+    ///
+    /// let mut reader = Builder::default().build_from_path("sample.bam").await?;
+    /// let header = reader.read_header().await?;
+    ///
+    /// let region = "sq0:8-13".parse()?;
+    /// let mut query = reader.query(&header, &region)?;
+    ///
+    /// while let Some(record) = query.try_next().await? {
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn query<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h sam::Header,
+        region: &Region,
+    ) -> io::Result<impl Stream<Item = io::Result<Box<dyn Record>>> + use<'r, 'h, R>> {
+        let records: RecordStream<'r> = match self {
+            Self::Sam(reader) => Box::pin(
+                reader
+                    .query(header, region)?
+                    .records()
+                    .map(|result| result.map(|record| Box::new(record) as Box<dyn Record>)),
+            ),
+            Self::Bam(reader) => Box::pin(
+                reader
+                    .query(header, region)?
+                    .records()
+                    .map(|result| result.map(|record| Box::new(record) as Box<dyn Record>)),
+            ),
+            Self::Cram(reader) => Box::pin(
+                reader
+                    .query(header, region)?
+                    .map(|result| result.map(|record| Box::new(record) as Box<dyn Record>)),
+            ),
+        };
+
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use futures::TryStreamExt;
+    use noodles_core::Position;
+    use noodles_sam::{
+        alignment::{
+            RecordBuf,
+            io::Write as _,
+            record::{
+                Flags,
+                cigar::{Op, op::Kind},
+            },
+        },
+        header::record::value::{Map, map::ReferenceSequence},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(const { NonZero::new(34).unwrap() }),
+            )
+            .add_reference_sequence(
+                "sq1",
+                Map::<ReferenceSequence>::new(const { NonZero::new(13).unwrap() }),
+            )
+            .build();
+
+        let mut writer = bam::io::IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for (reference_sequence_id, position) in [(0, 2), (0, 8), (0, 21), (1, 5)] {
+            let record = RecordBuf::builder()
+                .set_flags(Flags::empty())
+                .set_reference_sequence_id(reference_sequence_id)
+                .set_alignment_start(Position::try_from(position)?)
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+                .build();
+
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        let index = writer.try_finish()?;
+        let data = writer.into_inner().into_inner();
+
+        let mut reader = Builder::default()
+            .set_index(index)
+            .build_from_reader(std::io::Cursor::new(data))
+            .await?;
+
+        assert!(matches!(reader, IndexedReader::Bam(_)));
+
+        let header = reader.read_header().await?;
+
+        let region = "sq0:8-21".parse()?;
+        let records: Vec<_> = reader.query(&header, &region)?.try_collect().await?;
+
+        let actual = records
+            .iter()
+            .map(|record| {
+                let reference_sequence_id = record.reference_sequence_id(&header).transpose()?;
+                let alignment_start = record.alignment_start().transpose()?;
+                Ok((reference_sequence_id, alignment_start))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        assert_eq!(
+            actual,
+            [(Some(0), Position::new(8)), (Some(0), Position::new(21))]
+        );
+
+        Ok(())
+    }
+}
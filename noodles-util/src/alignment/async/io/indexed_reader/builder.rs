@@ -0,0 +1,322 @@
+use std::path::Path;
+
+use noodles_bam as bam;
+use noodles_cram::{self as cram, crai};
+use noodles_csi::{
+    self as csi, BinningIndex,
+    binning_index::index::reference_sequence::index::{BinnedIndex, LinearIndex},
+};
+use noodles_fasta as fasta;
+use noodles_sam as sam;
+use tokio::{
+    fs::File,
+    io::{self, AsyncBufReadExt, AsyncRead, BufReader},
+};
+
+use super::IndexedReader;
+use crate::alignment::io::{
+    CompressionMethod, Format,
+    reader::builder::{detect_compression_method, detect_format},
+};
+
+/// An async alignment index.
+pub enum Index {
+    /// CSI.
+    Csi(Box<dyn BinningIndex + Send + Sync>),
+    /// CRAI.
+    Crai(crai::Index),
+}
+
+impl From<csi::binning_index::Index<BinnedIndex>> for Index {
+    fn from(index: csi::binning_index::Index<BinnedIndex>) -> Self {
+        Self::Csi(Box::new(index))
+    }
+}
+
+impl From<csi::binning_index::Index<LinearIndex>> for Index {
+    fn from(index: csi::binning_index::Index<LinearIndex>) -> Self {
+        Self::Csi(Box::new(index))
+    }
+}
+
+impl From<crai::Index> for Index {
+    fn from(index: crai::Index) -> Self {
+        Self::Crai(index)
+    }
+}
+
+/// An async indexed alignment reader builder.
+#[derive(Default)]
+pub struct Builder {
+    compression_method: Option<Option<CompressionMethod>>,
+    format: Option<Format>,
+    reference_sequence_repository: fasta::Repository,
+    index: Option<Index>,
+}
+
+impl Builder {
+    /// Sets the compression method of the input.
+    ///
+    /// By default, the compression method is autodetected on build.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::{
+    ///     r#async::io::indexed_reader::Builder,
+    ///     io::CompressionMethod,
+    /// };
+    ///
+    /// let builder = Builder::default().set_compression_method(Some(CompressionMethod::Bgzf));
+    /// ```
+    pub fn set_compression_method(mut self, compression_method: Option<CompressionMethod>) -> Self {
+        self.compression_method = Some(compression_method);
+        self
+    }
+
+    /// Sets the format of the input.
+    ///
+    /// By default, the format is autodetected on build.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::{r#async::io::indexed_reader::Builder, io::Format};
+    /// let builder = Builder::default().set_format(Format::Sam);
+    /// ```
+    pub fn set_format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Sets the reference sequence repository.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta as fasta;
+    /// use noodles_util::alignment::r#async::io::indexed_reader::Builder;
+    ///
+    /// let repository = fasta::Repository::default();
+    /// let builder = Builder::default().set_reference_sequence_repository(repository);
+    /// ```
+    pub fn set_reference_sequence_repository(
+        mut self,
+        reference_sequence_repository: fasta::Repository,
+    ) -> Self {
+        self.reference_sequence_repository = reference_sequence_repository;
+        self
+    }
+
+    /// Sets an index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// use noodles_util::alignment::r#async::io::indexed_reader::Builder;
+    ///
+    /// let index = csi::Index::default();
+    /// let builder = Builder::default().set_index(index);
+    /// ```
+    pub fn set_index<I>(mut self, index: I) -> Self
+    where
+        I: Into<Index>,
+    {
+        self.index = Some(index.into());
+        self
+    }
+
+    /// Builds an async indexed alignment reader from a path.
+    ///
+    /// By default, the format and compression method will be autodetected. This can be overridden
+    /// by using [`Self::set_format`] and [`Self::set_compression_method`].
+    ///
+    /// If no index is set, this will attempt to read an associated index, e.g., `<src>.bai`,
+    /// `<src>.csi`, or `<src>.crai`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> tokio::io::Result<()> {
+    /// use noodles_util::alignment::r#async::io::indexed_reader::Builder;
+    /// let reader = Builder::default().build_from_path("sample.bam").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn build_from_path<P>(self, src: P) -> io::Result<IndexedReader<File>>
+    where
+        P: AsRef<Path>,
+    {
+        let src = src.as_ref();
+
+        let mut reader = File::open(src).await.map(BufReader::new)?;
+        let (format, compression_method) = self.detect(&mut reader).await?;
+
+        match (format, compression_method) {
+            (Format::Sam | Format::Bam, None) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "source not bgzip-compressed",
+            )),
+            (Format::Sam, Some(CompressionMethod::Bgzf)) => {
+                let mut builder = sam::r#async::io::indexed_reader::Builder::default();
+
+                if let Some(Index::Csi(index)) = self.index {
+                    builder = builder.set_index(index);
+                }
+
+                builder.build_from_path(src).await.map(IndexedReader::Sam)
+            }
+            (Format::Bam, Some(CompressionMethod::Bgzf)) => {
+                let mut builder = bam::r#async::io::indexed_reader::Builder::default();
+
+                if let Some(Index::Csi(index)) = self.index {
+                    builder = builder.set_index(index);
+                }
+
+                builder.build_from_path(src).await.map(IndexedReader::Bam)
+            }
+            (Format::Cram, None) => {
+                let mut builder = cram::r#async::io::indexed_reader::Builder::default()
+                    .set_reference_sequence_repository(self.reference_sequence_repository);
+
+                if let Some(Index::Crai(index)) = self.index {
+                    builder = builder.set_index(index);
+                }
+
+                builder.build_from_path(src).await.map(IndexedReader::Cram)
+            }
+            (Format::Cram, Some(CompressionMethod::Bgzf)) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "CRAM cannot be bgzip-compressed",
+            )),
+        }
+    }
+
+    /// Builds an async indexed alignment reader from a reader.
+    ///
+    /// An index must be set using [`Self::set_index`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() -> tokio::io::Result<()> {
+    /// use noodles_csi as csi;
+    /// use noodles_util::alignment::{self, r#async::io::indexed_reader::Builder};
+    ///
+    /// let index = csi::Index::default();
+    ///
+    /// let reader = Builder::default()
+    ///     .set_format(alignment::io::Format::Bam)
+    ///     .set_compression_method(Some(alignment::io::CompressionMethod::Bgzf))
+    ///     .set_index(index)
+    ///     .build_from_reader(tokio::io::empty())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn build_from_reader<R>(self, reader: R) -> io::Result<IndexedReader<BufReader<R>>>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut reader = BufReader::new(reader);
+        let (format, compression_method) = self.detect(&mut reader).await?;
+
+        match (format, compression_method) {
+            (Format::Sam | Format::Bam, None) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "source not bgzip-compressed",
+            )),
+            (Format::Sam, Some(CompressionMethod::Bgzf)) => {
+                let mut builder = sam::r#async::io::indexed_reader::Builder::default();
+
+                if let Some(Index::Csi(index)) = self.index {
+                    builder = builder.set_index(index);
+                }
+
+                builder.build_from_reader(reader).map(IndexedReader::Sam)
+            }
+            (Format::Bam, Some(CompressionMethod::Bgzf)) => {
+                let mut builder = bam::r#async::io::indexed_reader::Builder::default();
+
+                if let Some(Index::Csi(index)) = self.index {
+                    builder = builder.set_index(index);
+                }
+
+                builder.build_from_reader(reader).map(IndexedReader::Bam)
+            }
+            (Format::Cram, None) => {
+                let mut builder = cram::r#async::io::indexed_reader::Builder::default()
+                    .set_reference_sequence_repository(self.reference_sequence_repository);
+
+                if let Some(Index::Crai(index)) = self.index {
+                    builder = builder.set_index(index);
+                }
+
+                builder.build_from_reader(reader).map(IndexedReader::Cram)
+            }
+            (Format::Cram, Some(CompressionMethod::Bgzf)) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "CRAM cannot be bgzip-compressed",
+            )),
+        }
+    }
+
+    async fn detect<R>(
+        &self,
+        reader: &mut BufReader<R>,
+    ) -> io::Result<(Format, Option<CompressionMethod>)>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let compression_method = match self.compression_method {
+            Some(compression_method) => compression_method,
+            None => {
+                let mut src = reader.fill_buf().await?;
+                detect_compression_method(&mut src)?
+            }
+        };
+
+        let format = match self.format {
+            Some(format) => format,
+            None => {
+                let mut src = reader.fill_buf().await?;
+                detect_format(&mut src, compression_method)?
+            }
+        };
+
+        Ok((format, compression_method))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use noodles_bgzf as bgzf;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_build_from_reader() -> io::Result<()> {
+        let mut writer = bgzf::io::Writer::new(Vec::new());
+        writer.write_all(b"@HD\tVN:1.6\tSO:coordinate\n")?;
+        let data = writer.finish()?;
+
+        let reader = Builder::default()
+            .set_index(csi::Index::default())
+            .build_from_reader(Cursor::new(data.clone()))
+            .await?;
+
+        assert!(matches!(reader, IndexedReader::Sam(_)));
+
+        assert!(matches!(
+            Builder::default().build_from_reader(Cursor::new(data)).await,
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
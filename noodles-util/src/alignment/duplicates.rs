@@ -0,0 +1,734 @@
+//! Alignment record duplicate marking.
+//!
+//! Duplicates are identified by the unclipped 5' positions and orientations of each template's
+//! primary alignments, per library (`@RG LB`) and, optionally, per UMI. Within a set of
+//! duplicates, the template with the greatest sum of base quality scores is kept.
+
+mod metrics;
+
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+};
+
+use bstr::{BStr, BString};
+use noodles_sam::{
+    self as sam,
+    alignment::{
+        Record, RecordBuf,
+        record::{
+            Flags,
+            cigar::{Op, op::Kind},
+            data::field::{Tag, Value},
+        },
+    },
+    header::record::value::map::read_group::tag as read_group_tag,
+};
+
+pub use self::metrics::{LibraryMetrics, Metrics};
+
+const UNKNOWN_LIBRARY: &[u8] = b"Unknown Library";
+
+/// A duplicate marker.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{self as sam, alignment::RecordBuf};
+/// use noodles_util::alignment::duplicates::Marker;
+///
+/// let header = sam::Header::default();
+/// let mut records = vec![RecordBuf::default()];
+///
+/// let metrics = Marker::default().mark(&header, &mut records)?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct Marker {
+    umi_tag: Option<Tag>,
+    remove_duplicates: bool,
+}
+
+impl Marker {
+    /// Sets the data field tag used to group templates by UMI, e.g., `RX` or `MI`.
+    ///
+    /// By default, UMIs are not considered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::record::data::field::Tag;
+    /// use noodles_util::alignment::duplicates::Marker;
+    /// let marker = Marker::default().set_umi_tag(Tag::UMI_SEQUENCE);
+    /// ```
+    pub fn set_umi_tag(mut self, umi_tag: Tag) -> Self {
+        self.umi_tag = Some(umi_tag);
+        self
+    }
+
+    /// Sets whether duplicates are removed rather than flagged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::duplicates::Marker;
+    /// let marker = Marker::default().set_remove_duplicates(true);
+    /// ```
+    pub fn set_remove_duplicates(mut self, remove_duplicates: bool) -> Self {
+        self.remove_duplicates = remove_duplicates;
+        self
+    }
+
+    /// Marks duplicate records.
+    ///
+    /// Existing `DUPLICATE` flags are cleared, and the flag is set on every record of a
+    /// duplicate template, including its secondary and supplementary alignments. If duplicate
+    /// removal is enabled, these records are removed instead. The order of the remaining records
+    /// is preserved, i.e., coordinate-sorted input stays coordinate-sorted.
+    ///
+    /// This requires all records to be in memory. For inputs that do not fit in memory, use
+    /// [`Self::find_duplicates`] instead.
+    pub fn mark(&self, header: &sam::Header, records: &mut Vec<RecordBuf>) -> io::Result<Metrics> {
+        let mut metrics = Metrics::default();
+
+        let entries = records
+            .iter()
+            .map(|record| self.read_entry(header, &mut metrics, record))
+            .collect::<io::Result<_>>()?;
+
+        let duplicates = find_duplicates(entries, metrics);
+
+        for (i, record) in records.iter_mut().enumerate() {
+            record
+                .flags_mut()
+                .set(Flags::DUPLICATE, duplicates.is_duplicate(i));
+        }
+
+        if self.remove_duplicates {
+            let mut i = 0;
+
+            records.retain(|_| {
+                let is_duplicate = duplicates.is_duplicate(i);
+                i += 1;
+                !is_duplicate
+            });
+        }
+
+        Ok(duplicates.metrics)
+    }
+
+    /// Finds duplicate records.
+    ///
+    /// This is the first pass of marking duplicates in an input that does not fit in memory,
+    /// e.g., a coordinate-sorted BAM file. Records are read from the given iterator, and only a
+    /// small read end is kept for each: its name, flags, library, UMI, unclipped 5' position, and
+    /// sum of base quality scores. Memory use is linear in the number of records but does not
+    /// depend on their sequences, quality scores, or data fields.
+    ///
+    /// Records are identified by their 0-based index in the input. In the second pass, read the
+    /// same records in the same order, e.g., by reopening the file, and set the `DUPLICATE` flag
+    /// using [`Duplicates::is_duplicate`]. The duplicate removal setting is not applied here.
+    ///
+    /// Records can be any alignment record, e.g., those read by
+    /// [`crate::alignment::io::Reader::records`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, alignment::RecordBuf};
+    /// use noodles_util::alignment::duplicates::Marker;
+    ///
+    /// let header = sam::Header::default();
+    /// let records = [RecordBuf::default()];
+    ///
+    /// let duplicates = Marker::default().find_duplicates(&header, records.into_iter().map(Ok))?;
+    /// assert!(!duplicates.is_duplicate(0));
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn find_duplicates<I, R>(&self, header: &sam::Header, records: I) -> io::Result<Duplicates>
+    where
+        I: IntoIterator<Item = io::Result<R>>,
+        R: Record,
+    {
+        let mut metrics = Metrics::default();
+        let mut entries = Vec::new();
+
+        for result in records {
+            let record = result?;
+            let entry = self.read_entry(header, &mut metrics, &record)?;
+            entries.push(entry);
+        }
+
+        Ok(find_duplicates(entries, metrics))
+    }
+
+    fn read_entry<'h>(
+        &self,
+        header: &'h sam::Header,
+        metrics: &mut Metrics,
+        record: &dyn Record,
+    ) -> io::Result<Entry<'h>> {
+        let flags = record.flags()?;
+        let library = library(header, record)?;
+        let entry = metrics.entry(library);
+
+        let end = if flags.is_secondary() || flags.is_supplementary() {
+            entry.secondary_or_supplementary_reads += 1;
+            None
+        } else if flags.is_unmapped() {
+            entry.unmapped_reads += 1;
+            None
+        } else {
+            let umi = self.umi(record)?;
+            Some(ReadEnd::try_from_record(
+                header, library, umi, flags, record,
+            )?)
+        };
+
+        let score = if end.is_some() {
+            sum_quality_scores(record)?
+        } else {
+            0
+        };
+
+        Ok(Entry {
+            name: record.name().map(BString::from),
+            flags,
+            score,
+            end,
+        })
+    }
+
+    fn umi(&self, record: &dyn Record) -> io::Result<Option<BString>> {
+        let Some(tag) = self.umi_tag else {
+            return Ok(None);
+        };
+
+        match record.data().get(&tag).transpose()? {
+            Some(Value::String(s)) => Ok(Some(s.into())),
+            Some(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid UMI data field value type",
+            )),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Duplicate records found by [`Marker::find_duplicates`].
+#[derive(Debug)]
+pub struct Duplicates {
+    is_duplicate: Vec<bool>,
+    metrics: Metrics,
+}
+
+impl Duplicates {
+    /// Returns whether the record at the given 0-based index in the input is a duplicate.
+    ///
+    /// This returns `false` for indices past the end of the input.
+    pub fn is_duplicate(&self, i: usize) -> bool {
+        self.is_duplicate.get(i).copied().unwrap_or(false)
+    }
+
+    /// Returns the duplication metrics.
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+}
+
+// A record in the input, without its sequence, quality scores, or data fields.
+#[derive(Debug)]
+struct Entry<'h> {
+    name: Option<BString>,
+    flags: Flags,
+    score: u64,
+    end: Option<ReadEnd<'h>>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+struct Position {
+    reference_sequence_id: usize,
+    unclipped_five_prime_position: i64,
+    is_reverse_complemented: bool,
+}
+
+#[derive(Debug, Eq, Hash, PartialEq)]
+struct ReadEnd<'a> {
+    library: &'a BStr,
+    umi: Option<BString>,
+    position: Position,
+}
+
+impl<'a> ReadEnd<'a> {
+    fn try_from_record(
+        header: &sam::Header,
+        library: &'a BStr,
+        umi: Option<BString>,
+        flags: Flags,
+        record: &dyn Record,
+    ) -> io::Result<Self> {
+        let reference_sequence_id = record
+            .reference_sequence_id(header)
+            .transpose()?
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "missing reference sequence ID for mapped record",
+                )
+            })?;
+
+        let unclipped_five_prime_position = unclipped_five_prime_position(flags, record)?;
+
+        Ok(Self {
+            library,
+            umi,
+            position: Position {
+                reference_sequence_id,
+                unclipped_five_prime_position,
+                is_reverse_complemented: flags.is_reverse_complemented(),
+            },
+        })
+    }
+}
+
+#[derive(Debug, Eq, Hash, PartialEq)]
+struct PairKey<'a> {
+    library: &'a BStr,
+    umi: Option<&'a BStr>,
+    positions: (Position, Position),
+}
+
+impl<'a> PairKey<'a> {
+    fn new(a: &'a ReadEnd<'a>, b: &ReadEnd<'a>) -> Self {
+        let positions = if a.position <= b.position {
+            (a.position, b.position)
+        } else {
+            (b.position, a.position)
+        };
+
+        Self {
+            library: a.library,
+            umi: a.umi.as_ref().map(|umi| umi.as_ref()),
+            positions,
+        }
+    }
+}
+
+fn find_duplicates(entries: Vec<Entry<'_>>, mut metrics: Metrics) -> Duplicates {
+    let mates = find_mates(&entries);
+
+    let mut pairs: HashMap<PairKey<'_>, Vec<(u64, usize, usize)>> = HashMap::new();
+    let mut fragments: HashMap<&ReadEnd<'_>, Vec<(u64, usize)>> = HashMap::new();
+    let mut paired_ends = HashSet::new();
+
+    for (i, entry) in entries.iter().enumerate() {
+        let Some(end) = &entry.end else {
+            continue;
+        };
+
+        match mates.get(&i) {
+            Some(&j) => {
+                paired_ends.insert(end);
+
+                if i < j {
+                    let mate = &entries[j];
+                    // SAFETY: `find_mates` only pairs records with read ends.
+                    let mate_end = mate.end.as_ref().unwrap();
+                    let key = PairKey::new(end, mate_end);
+
+                    pairs
+                        .entry(key)
+                        .or_default()
+                        .push((entry.score + mate.score, i, j));
+
+                    metrics.entry(end.library).read_pairs_examined += 1;
+                }
+            }
+            None => {
+                fragments.entry(end).or_default().push((entry.score, i));
+                metrics.entry(end.library).unpaired_reads_examined += 1;
+            }
+        }
+    }
+
+    let mut is_duplicate = vec![false; entries.len()];
+
+    for candidates in pairs.into_values() {
+        let best = best_index(candidates.iter().map(|(score, _, _)| *score));
+
+        for (k, &(_, i, j)) in candidates.iter().enumerate() {
+            if k != best {
+                is_duplicate[i] = true;
+                is_duplicate[j] = true;
+
+                // SAFETY: Pairs are only built from records with read ends.
+                let library = entries[i].end.as_ref().unwrap().library;
+                metrics.entry(library).read_pair_duplicates += 1;
+            }
+        }
+    }
+
+    for (end, candidates) in fragments {
+        let best = if paired_ends.contains(end) {
+            None
+        } else {
+            Some(best_index(candidates.iter().map(|(score, _)| *score)))
+        };
+
+        for (k, &(_, i)) in candidates.iter().enumerate() {
+            if Some(k) != best {
+                is_duplicate[i] = true;
+                metrics.entry(end.library).unpaired_read_duplicates += 1;
+            }
+        }
+    }
+
+    mark_other_alignments(&entries, &mut is_duplicate);
+
+    Duplicates {
+        is_duplicate,
+        metrics,
+    }
+}
+
+fn library<'h>(header: &'h sam::Header, record: &dyn Record) -> io::Result<&'h BStr> {
+    let data = record.data();
+
+    let read_group_id = match data.get(&Tag::READ_GROUP).transpose()? {
+        Some(Value::String(id)) => id,
+        Some(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid read group data field value type",
+            ));
+        }
+        None => return Ok(BStr::new(UNKNOWN_LIBRARY)),
+    };
+
+    let read_group = header.read_groups().get(read_group_id).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("missing read group in header: {read_group_id}"),
+        )
+    })?;
+
+    Ok(read_group
+        .other_fields()
+        .get(&read_group_tag::LIBRARY)
+        .map(|library| library.as_ref())
+        .unwrap_or(BStr::new(UNKNOWN_LIBRARY)))
+}
+
+fn unclipped_five_prime_position(flags: Flags, record: &dyn Record) -> io::Result<i64> {
+    fn is_clip(op: &Op) -> bool {
+        matches!(op.kind(), Kind::SoftClip | Kind::HardClip)
+    }
+
+    let ops: Vec<_> = record.cigar().iter().collect::<io::Result<_>>()?;
+
+    if flags.is_reverse_complemented() {
+        let end = record
+            .alignment_end()
+            .transpose()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing alignment end"))?;
+
+        let clip_len: usize = ops
+            .iter()
+            .rev()
+            .take_while(|op| is_clip(op))
+            .map(|op| op.len())
+            .sum();
+
+        Ok(to_i64(usize::from(end))? + to_i64(clip_len)?)
+    } else {
+        let start = record
+            .alignment_start()
+            .transpose()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing alignment start"))?;
+
+        let clip_len: usize = ops
+            .iter()
+            .take_while(|op| is_clip(op))
+            .map(|op| op.len())
+            .sum();
+
+        Ok(to_i64(usize::from(start))? - to_i64(clip_len)?)
+    }
+}
+
+fn to_i64(n: usize) -> io::Result<i64> {
+    i64::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn sum_quality_scores(record: &dyn Record) -> io::Result<u64> {
+    record
+        .quality_scores()
+        .iter()
+        .map(|result| result.map(u64::from))
+        .sum()
+}
+
+fn best_index<I>(scores: I) -> usize
+where
+    I: Iterator<Item = u64>,
+{
+    let mut best = (0, 0);
+
+    for (i, score) in scores.enumerate() {
+        if i == 0 || score > best.1 {
+            best = (i, score);
+        }
+    }
+
+    best.0
+}
+
+fn find_mates(entries: &[Entry<'_>]) -> HashMap<usize, usize> {
+    let mut candidates: HashMap<&[u8], Vec<usize>> = HashMap::new();
+
+    for (i, entry) in entries.iter().enumerate() {
+        let flags = entry.flags;
+
+        if entry.end.is_none() || !flags.is_segmented() || flags.is_mate_unmapped() {
+            continue;
+        }
+
+        if let Some(name) = &entry.name {
+            candidates.entry(name.as_ref()).or_default().push(i);
+        }
+    }
+
+    let mut mates = HashMap::new();
+
+    for indices in candidates.into_values() {
+        if let [i, j] = indices[..] {
+            mates.insert(i, j);
+            mates.insert(j, i);
+        }
+    }
+
+    mates
+}
+
+fn segment_flags(flags: Flags) -> Flags {
+    flags.intersection(Flags::FIRST_SEGMENT | Flags::LAST_SEGMENT)
+}
+
+fn mark_other_alignments(entries: &[Entry<'_>], is_duplicate: &mut [bool]) {
+    let duplicate_segments: HashSet<(&[u8], u16)> = entries
+        .iter()
+        .zip(is_duplicate.iter())
+        .filter(|(entry, is_duplicate)| entry.end.is_some() && **is_duplicate)
+        .filter_map(|(entry, _)| {
+            entry
+                .name
+                .as_ref()
+                .map(|name| (name.as_ref(), segment_flags(entry.flags).bits()))
+        })
+        .collect();
+
+    if duplicate_segments.is_empty() {
+        return;
+    }
+
+    for (i, entry) in entries.iter().enumerate() {
+        let flags = entry.flags;
+
+        if !flags.is_secondary() && !flags.is_supplementary() {
+            continue;
+        }
+
+        if let Some(name) = &entry.name {
+            let key = (name.as_ref(), segment_flags(flags).bits());
+
+            if duplicate_segments.contains(&key) {
+                is_duplicate[i] = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use noodles_core::Position as CorePosition;
+    use sam::{
+        alignment::record_buf::{QualityScores, data::field::Value as ValueBuf},
+        header::record::value::{
+            Map,
+            map::{ReadGroup, ReferenceSequence},
+        },
+    };
+
+    use super::*;
+
+    fn build_header() -> sam::Header {
+        sam::Header::builder()
+            .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(NonZero::<usize>::MAX))
+            .add_read_group(
+                "rg0",
+                Map::<ReadGroup>::builder()
+                    .insert(read_group_tag::LIBRARY, "lib0")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+    }
+
+    fn build_record(
+        name: &str,
+        flags: Flags,
+        alignment_start: usize,
+        cigar: &[Op],
+        quality_score: u8,
+    ) -> Result<RecordBuf, Box<dyn std::error::Error>> {
+        let read_length = cigar
+            .iter()
+            .filter(|op| op.kind().consumes_read())
+            .map(|op| op.len())
+            .sum();
+
+        Ok(RecordBuf::builder()
+            .set_name(name)
+            .set_flags(flags)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(CorePosition::try_from(alignment_start)?)
+            .set_cigar(cigar.iter().copied().collect())
+            .set_quality_scores(QualityScores::from(vec![quality_score; read_length]))
+            .set_data(
+                [(Tag::READ_GROUP, ValueBuf::from("rg0"))]
+                    .into_iter()
+                    .collect(),
+            )
+            .build())
+    }
+
+    #[test]
+    fn test_mark() -> Result<(), Box<dyn std::error::Error>> {
+        const R1: Flags = Flags::SEGMENTED.union(Flags::FIRST_SEGMENT);
+        const R2: Flags = Flags::SEGMENTED
+            .union(Flags::LAST_SEGMENT)
+            .union(Flags::REVERSE_COMPLEMENTED);
+
+        let header = build_header();
+
+        let m4 = [Op::new(Kind::Match, 4)];
+        let s1m3 = [Op::new(Kind::SoftClip, 1), Op::new(Kind::Match, 3)];
+
+        let mut records = vec![
+            build_record("r0", R1, 1, &m4, 30)?,
+            // same unclipped 5' position as r0
+            build_record("r1", R1, 2, &s1m3, 20)?,
+            build_record("r2", Flags::empty(), 1, &m4, 40)?,
+            build_record("r1", R1 | Flags::SUPPLEMENTARY, 5, &m4, 20)?,
+            build_record("r0", R2, 8, &m4, 30)?,
+            build_record("r1", R2, 8, &m4, 20)?,
+            build_record("r3", Flags::empty(), 16, &m4, 10)?,
+            build_record("r4", Flags::empty(), 16, &m4, 20)?,
+            build_record("r5", Flags::UNMAPPED, 16, &m4, 20)?,
+        ];
+
+        let metrics = Marker::default().mark(&header, &mut records)?;
+
+        let actual: Vec<_> = records
+            .iter()
+            .map(|record| record.flags().is_duplicate())
+            .collect();
+
+        let expected = [false, true, true, true, false, true, true, false, false];
+        assert_eq!(actual, expected);
+
+        let library_metrics = metrics.get("lib0").unwrap();
+        assert_eq!(library_metrics.read_pairs_examined(), 2);
+        assert_eq!(library_metrics.read_pair_duplicates(), 1);
+        assert_eq!(library_metrics.unpaired_reads_examined(), 3);
+        assert_eq!(library_metrics.unpaired_read_duplicates(), 2);
+        assert_eq!(library_metrics.secondary_or_supplementary_reads(), 1);
+        assert_eq!(library_metrics.unmapped_reads(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_duplicates() -> Result<(), Box<dyn std::error::Error>> {
+        const R1: Flags = Flags::SEGMENTED.union(Flags::FIRST_SEGMENT);
+        const R2: Flags = Flags::SEGMENTED
+            .union(Flags::LAST_SEGMENT)
+            .union(Flags::REVERSE_COMPLEMENTED);
+
+        let header = build_header();
+        let m4 = [Op::new(Kind::Match, 4)];
+
+        let records = [
+            build_record("r0", R1, 1, &m4, 30)?,
+            build_record("r1", R1, 1, &m4, 20)?,
+            build_record("r0", R2, 8, &m4, 30)?,
+            build_record("r1", R2 | Flags::SUPPLEMENTARY, 13, &m4, 20)?,
+            build_record("r1", R2, 8, &m4, 20)?,
+        ];
+
+        // Records are given as boxed alignment records, as when read from a file.
+        let records = records
+            .into_iter()
+            .map(|record| Ok(Box::new(record) as Box<dyn Record>));
+
+        let duplicates = Marker::default().find_duplicates(&header, records)?;
+
+        let actual: Vec<_> = (0..6).map(|i| duplicates.is_duplicate(i)).collect();
+        assert_eq!(actual, [false, true, false, true, true, false]);
+
+        let library_metrics = duplicates.metrics().get("lib0").unwrap();
+        assert_eq!(library_metrics.read_pair_duplicates(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mark_with_umi_tag() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+        let m4 = [Op::new(Kind::Match, 4)];
+
+        let mut records = vec![
+            build_record("r0", Flags::empty(), 1, &m4, 30)?,
+            build_record("r1", Flags::empty(), 1, &m4, 20)?,
+            build_record("r2", Flags::empty(), 1, &m4, 10)?,
+        ];
+
+        for (record, umi) in records.iter_mut().zip(["ACGT", "TGCA", "ACGT"]) {
+            record
+                .data_mut()
+                .insert(Tag::UMI_SEQUENCE, ValueBuf::from(umi));
+        }
+
+        Marker::default()
+            .set_umi_tag(Tag::UMI_SEQUENCE)
+            .set_remove_duplicates(true)
+            .mark(&header, &mut records)?;
+
+        let actual: Vec<_> = records.iter().filter_map(|record| record.name()).collect();
+        assert_eq!(actual, ["r0", "r1"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unclipped_five_prime_position() -> Result<(), Box<dyn std::error::Error>> {
+        let cigar = [
+            Op::new(Kind::HardClip, 2),
+            Op::new(Kind::SoftClip, 3),
+            Op::new(Kind::Match, 4),
+            Op::new(Kind::SoftClip, 5),
+        ];
+
+        let flags = Flags::empty();
+        let record = build_record("r0", flags, 8, &cigar, 0)?;
+        assert_eq!(unclipped_five_prime_position(flags, &record)?, 3);
+
+        let flags = Flags::REVERSE_COMPLEMENTED;
+        let record = build_record("r0", flags, 8, &cigar, 0)?;
+        assert_eq!(unclipped_five_prime_position(flags, &record)?, 16);
+
+        Ok(())
+    }
+}
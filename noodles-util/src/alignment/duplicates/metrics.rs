@@ -0,0 +1,176 @@
+use bstr::{BStr, BString};
+use indexmap::IndexMap;
+
+/// Duplication metrics for a single library.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LibraryMetrics {
+    pub(super) unpaired_reads_examined: u64,
+    pub(super) read_pairs_examined: u64,
+    pub(super) secondary_or_supplementary_reads: u64,
+    pub(super) unmapped_reads: u64,
+    pub(super) unpaired_read_duplicates: u64,
+    pub(super) read_pair_duplicates: u64,
+}
+
+impl LibraryMetrics {
+    /// Returns the number of mapped primary reads examined that were not part of a mapped pair.
+    pub fn unpaired_reads_examined(&self) -> u64 {
+        self.unpaired_reads_examined
+    }
+
+    /// Returns the number of mapped read pairs examined.
+    pub fn read_pairs_examined(&self) -> u64 {
+        self.read_pairs_examined
+    }
+
+    /// Returns the number of secondary and supplementary records.
+    pub fn secondary_or_supplementary_reads(&self) -> u64 {
+        self.secondary_or_supplementary_reads
+    }
+
+    /// Returns the number of unmapped reads.
+    pub fn unmapped_reads(&self) -> u64 {
+        self.unmapped_reads
+    }
+
+    /// Returns the number of unpaired reads marked as duplicates.
+    pub fn unpaired_read_duplicates(&self) -> u64 {
+        self.unpaired_read_duplicates
+    }
+
+    /// Returns the number of read pairs marked as duplicates.
+    pub fn read_pair_duplicates(&self) -> u64 {
+        self.read_pair_duplicates
+    }
+
+    /// Returns the fraction of examined reads marked as duplicates.
+    ///
+    /// This counts each read of a pair individually. If no reads were examined, this returns
+    /// `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::alignment::duplicates::LibraryMetrics;
+    /// let metrics = LibraryMetrics::default();
+    /// assert!(metrics.percent_duplication().is_none());
+    /// ```
+    pub fn percent_duplication(&self) -> Option<f64> {
+        let examined = self.unpaired_reads_examined + 2 * self.read_pairs_examined;
+
+        if examined == 0 {
+            None
+        } else {
+            let duplicates = self.unpaired_read_duplicates + 2 * self.read_pair_duplicates;
+            Some(duplicates as f64 / examined as f64)
+        }
+    }
+
+    /// Estimates the number of unique molecules in the library.
+    ///
+    /// This uses the Lander-Waterman equation over read pairs, as in Picard `MarkDuplicates`. If
+    /// there are no read pairs or no read pair duplicates, this returns `None`.
+    pub fn estimated_library_size(&self) -> Option<u64> {
+        estimate_library_size(
+            self.read_pairs_examined,
+            self.read_pairs_examined - self.read_pair_duplicates,
+        )
+    }
+}
+
+/// Duplication metrics.
+///
+/// Metrics are keyed by library name, in the order libraries were first encountered.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Metrics(IndexMap<BString, LibraryMetrics>);
+
+impl Metrics {
+    /// Returns the metrics for the given library.
+    pub fn get<L>(&self, library: L) -> Option<&LibraryMetrics>
+    where
+        L: AsRef<[u8]>,
+    {
+        self.0.get(BStr::new(library.as_ref()))
+    }
+
+    /// Returns an iterator over library names and their metrics.
+    pub fn iter(&self) -> impl Iterator<Item = (&BStr, &LibraryMetrics)> {
+        self.0
+            .iter()
+            .map(|(name, metrics)| (name.as_ref(), metrics))
+    }
+
+    pub(super) fn entry(&mut self, library: &BStr) -> &mut LibraryMetrics {
+        if !self.0.contains_key(library) {
+            self.0.insert(library.into(), LibraryMetrics::default());
+        }
+
+        // SAFETY: The entry was inserted above.
+        self.0.get_mut(library).unwrap()
+    }
+}
+
+fn estimate_library_size(read_pairs: u64, unique_read_pairs: u64) -> Option<u64> {
+    fn f(x: f64, c: f64, n: f64) -> f64 {
+        c / x - 1.0 + (-n / x).exp()
+    }
+
+    if read_pairs == 0 || unique_read_pairs >= read_pairs {
+        return None;
+    }
+
+    let n = read_pairs as f64;
+    let c = unique_read_pairs as f64;
+
+    let mut m = 1.0;
+    let mut big_m = 100.0;
+
+    if c == 0.0 || f(m * c, c, n) < 0.0 {
+        return None;
+    }
+
+    while f(big_m * c, c, n) > 0.0 {
+        big_m *= 10.0;
+    }
+
+    for _ in 0..40 {
+        let r = (m + big_m) / 2.0;
+        let u = f(r * c, c, n);
+
+        if u == 0.0 {
+            break;
+        } else if u > 0.0 {
+            m = r;
+        } else {
+            big_m = r;
+        }
+    }
+
+    Some((c * (m + big_m) / 2.0) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_duplication() {
+        let metrics = LibraryMetrics {
+            unpaired_reads_examined: 2,
+            read_pairs_examined: 4,
+            unpaired_read_duplicates: 1,
+            read_pair_duplicates: 1,
+            ..Default::default()
+        };
+
+        assert_eq!(metrics.percent_duplication(), Some(0.3));
+    }
+
+    #[test]
+    fn test_estimate_library_size() {
+        assert!(estimate_library_size(0, 0).is_none());
+        assert!(estimate_library_size(8, 8).is_none());
+
+        assert_eq!(estimate_library_size(1000, 900), Some(4660));
+    }
+}
@@ -0,0 +1,301 @@
+//! Alignment record filtering.
+
+use std::io;
+
+use noodles_sam::{
+    self as sam,
+    alignment::{
+        Record,
+        record::data::field::{Tag, Value as DataValue, value::Array},
+    },
+};
+
+use crate::filter::{Expression, ParseError, Subscript, Value};
+
+/// An alignment record field.
+#[derive(Clone, Debug, PartialEq)]
+enum Field {
+    Name,
+    Flags,
+    ReferenceSequenceName,
+    AlignmentStart,
+    AlignmentEnd,
+    MappingQuality,
+    MateReferenceSequenceName,
+    MateAlignmentStart,
+    TemplateLength,
+    SequenceLength,
+    Data(Tag, Option<usize>),
+}
+
+/// An alignment record filter.
+///
+/// A filter is compiled from a [filter expression](crate::filter) and a SAM header. The fields
+/// are
+///
+///   * `qname`, `flag`, `rname`, `pos`, `endpos`, `mapq`, `mrname`, `mpos`, `tlen`, and `qlen`
+///     (the sequence length); and
+///   * `[<tag>]`, a data field, e.g., `[NM]`. An array value can be indexed, e.g., `[ZC][0]`.
+///
+/// Missing values, e.g., an unmapped record's `pos`, are missing (`.`).
+pub struct Filter<'h> {
+    header: &'h sam::Header,
+    expression: Expression<Field>,
+}
+
+impl<'h> Filter<'h> {
+    /// Compiles an alignment record filter.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the expression or a field is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam as sam;
+    /// use noodles_util::alignment::filter::Filter;
+    ///
+    /// let header = sam::Header::default();
+    /// let filter = Filter::new(&header, "flag & 0x4 == 0 && [NM] < 5")?;
+    /// # Ok::<_, noodles_util::filter::ParseError>(())
+    /// ```
+    pub fn new(header: &'h sam::Header, s: &str) -> Result<Self, ParseError> {
+        let expression = Expression::parse(s, resolve_field)?;
+        Ok(Self { header, expression })
+    }
+
+    /// Returns whether the given record passes the filter.
+    ///
+    /// Only the fields referenced by the expression are read.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if a referenced field fails to be read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, alignment::{record::Flags, RecordBuf}};
+    /// use noodles_util::alignment::filter::Filter;
+    ///
+    /// let header = sam::Header::default();
+    /// let filter = Filter::new(&header, "flag & 0x4 == 0")?;
+    ///
+    /// let record = RecordBuf::default();
+    /// assert!(!filter.evaluate(&record)?);
+    ///
+    /// let record = RecordBuf::builder().set_flags(Flags::empty()).build();
+    /// assert!(filter.evaluate(&record)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn evaluate<R>(&self, record: &R) -> io::Result<bool>
+    where
+        R: Record + ?Sized,
+    {
+        self.expression
+            .matches(&mut |field| get_field(self.header, record, field))
+    }
+}
+
+fn resolve_field(name: &str, subscript: Option<Subscript>) -> Result<Field, ParseError> {
+    if let Some(raw_tag) = name.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+        let tag = match raw_tag.as_bytes() {
+            &[a, b] if a.is_ascii_alphabetic() && b.is_ascii_alphanumeric() => Tag::new(a, b),
+            _ => return Err(ParseError::InvalidField(name.into())),
+        };
+
+        let index = match subscript {
+            Some(Subscript::Index(i)) => Some(i),
+            Some(Subscript::All) | None => None,
+        };
+
+        return Ok(Field::Data(tag, index));
+    }
+
+    if subscript.is_some() {
+        return Err(ParseError::InvalidSubscript(name.into()));
+    }
+
+    match name {
+        "qname" => Ok(Field::Name),
+        "flag" => Ok(Field::Flags),
+        "rname" => Ok(Field::ReferenceSequenceName),
+        "pos" => Ok(Field::AlignmentStart),
+        "endpos" => Ok(Field::AlignmentEnd),
+        "mapq" => Ok(Field::MappingQuality),
+        "mrname" => Ok(Field::MateReferenceSequenceName),
+        "mpos" => Ok(Field::MateAlignmentStart),
+        "tlen" => Ok(Field::TemplateLength),
+        "qlen" => Ok(Field::SequenceLength),
+        _ => Err(ParseError::InvalidField(name.into())),
+    }
+}
+
+fn get_field<R>(header: &sam::Header, record: &R, field: &Field) -> io::Result<Value>
+where
+    R: Record + ?Sized,
+{
+    match field {
+        Field::Name => Ok(record
+            .name()
+            .map_or(Value::Missing, |name| Value::String(name.to_string()))),
+        Field::Flags => record
+            .flags()
+            .map(|flags| Value::Integer(i64::from(u16::from(flags)))),
+        Field::ReferenceSequenceName => {
+            record
+                .reference_sequence(header)
+                .transpose()
+                .map(|reference_sequence| {
+                    reference_sequence
+                        .map_or(Value::Missing, |(name, _)| Value::String(name.to_string()))
+                })
+        }
+        Field::AlignmentStart => position(record.alignment_start()),
+        Field::AlignmentEnd => position(record.alignment_end()),
+        Field::MappingQuality => record.mapping_quality().transpose().map(|mapping_quality| {
+            mapping_quality.map_or(Value::Missing, |mapq| {
+                Value::Integer(i64::from(u8::from(mapq)))
+            })
+        }),
+        Field::MateReferenceSequenceName => {
+            record
+                .mate_reference_sequence(header)
+                .transpose()
+                .map(|reference_sequence| {
+                    reference_sequence
+                        .map_or(Value::Missing, |(name, _)| Value::String(name.to_string()))
+                })
+        }
+        Field::MateAlignmentStart => position(record.mate_alignment_start()),
+        Field::TemplateLength => record
+            .template_length()
+            .map(|n| Value::Integer(i64::from(n))),
+        Field::SequenceLength => {
+            Ok(i64::try_from(record.sequence().len()).map_or(Value::Missing, Value::Integer))
+        }
+        Field::Data(tag, index) => {
+            let value = match record.data().get(tag).transpose()? {
+                Some(value) => data_value(value)?,
+                None => Value::Missing,
+            };
+
+            Ok(match (value, index) {
+                (Value::Array(mut values), Some(i)) if *i < values.len() => values.swap_remove(*i),
+                (_, Some(_)) => Value::Missing,
+                (value, None) => value,
+            })
+        }
+    }
+}
+
+fn position(position: Option<io::Result<noodles_core::Position>>) -> io::Result<Value> {
+    position.transpose().map(|position| {
+        position
+            .and_then(|p| i64::try_from(usize::from(p)).ok())
+            .map_or(Value::Missing, Value::Integer)
+    })
+}
+
+fn collect_values<N>(values: Box<dyn Iterator<Item = io::Result<N>> + '_>) -> io::Result<Value>
+where
+    i64: From<N>,
+{
+    values
+        .map(|result| result.map(|n| Value::Integer(i64::from(n))))
+        .collect::<io::Result<_>>()
+        .map(Value::from_values)
+}
+
+fn data_value(value: DataValue<'_>) -> io::Result<Value> {
+    match value {
+        DataValue::Character(c) => Ok(Value::String(char::from(c).into())),
+        DataValue::Float(n) => Ok(Value::Float(f64::from(n))),
+        DataValue::String(s) | DataValue::Hex(s) => Ok(Value::String(s.to_string())),
+        DataValue::Array(Array::Int8(values)) => collect_values(values.iter()),
+        DataValue::Array(Array::UInt8(values)) => collect_values(values.iter()),
+        DataValue::Array(Array::Int16(values)) => collect_values(values.iter()),
+        DataValue::Array(Array::UInt16(values)) => collect_values(values.iter()),
+        DataValue::Array(Array::Int32(values)) => collect_values(values.iter()),
+        DataValue::Array(Array::UInt32(values)) => collect_values(values.iter()),
+        DataValue::Array(Array::Float(values)) => values
+            .iter()
+            .map(|result| result.map(|n| Value::Float(f64::from(n))))
+            .collect::<io::Result<_>>()
+            .map(Value::from_values),
+        value => value
+            .as_int()
+            .map(Value::Integer)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid data value")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRC: &[u8] = b"@SQ\tSN:sq0\tLN:8
+@SQ\tSN:sq1\tLN:13
+r0\t99\tsq0\t1\t34\t4M\t=\t5\t8\tACGT\tNDLS\tNM:i:1\tZC:B:c,5,-8
+r1\t4\t*\t0\t255\t*\t*\t0\t0\tACG\tNDL
+r2\t0\tsq1\t2\t8\t2M1D2M\t*\t0\t0\tACGT\tNDLS\tNM:i:6\tRG:Z:rg0
+";
+
+    #[test]
+    fn test_evaluate() -> Result<(), Box<dyn std::error::Error>> {
+        let mut reader = sam::io::Reader::new(SRC);
+        let header = reader.read_header()?;
+        let records: Vec<_> = reader.records().collect::<io::Result<_>>()?;
+
+        let cases = [
+            ("flag & 0x4 == 0 && [NM] < 5", [true, false, false]),
+            ("flag & 0x4", [false, true, false]),
+            ("mapq >= 30", [true, false, false]),
+            ("mapq == .", [false, true, false]),
+            (
+                "rname == \"sq1\" && pos == 2 && endpos == 6",
+                [false, false, true],
+            ),
+            (
+                "mrname == \"sq0\" && mpos == 5 && tlen == 8",
+                [true, false, false],
+            ),
+            ("qname == \"r1\" || qlen > 3", [true, true, true]),
+            ("[RG] == \"rg0\"", [false, false, true]),
+            ("[ZC] < 0", [true, false, false]),
+            ("[ZC][0] < 0", [false, false, false]),
+            ("[NM] == .", [false, true, false]),
+        ];
+
+        for (s, expected) in cases {
+            let filter = Filter::new(&header, s)?;
+
+            for (record, expected) in records.iter().zip(expected) {
+                assert_eq!(filter.evaluate(record)?, expected, "{s}");
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_with_invalid_fields() {
+        let header = sam::Header::default();
+
+        assert!(matches!(
+            Filter::new(&header, "[NMX] > 1"),
+            Err(ParseError::InvalidField(name)) if name == "[NMX]"
+        ));
+
+        assert!(matches!(
+            Filter::new(&header, "flag[0] > 1"),
+            Err(ParseError::InvalidSubscript(name)) if name == "flag"
+        ));
+
+        assert!(matches!(
+            Filter::new(&header, "seq == \"ACGT\""),
+            Err(ParseError::InvalidField(name)) if name == "seq"
+        ));
+    }
+}
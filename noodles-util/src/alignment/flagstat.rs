@@ -0,0 +1,418 @@
+//! Alignment record flag statistics.
+//!
+//! This calculates the same statistics as `samtools flagstat`. Records are split by whether they
+//! passed quality control, i.e., whether the `QC_FAIL` flag is unset.
+
+use std::{fmt, io};
+
+use noodles_sam::{
+    self as sam,
+    alignment::{Record, record::MappingQuality},
+};
+
+/// The minimum mapping quality of a record to be considered high quality.
+pub const MIN_HQ_MAPPING_QUALITY: MappingQuality = MappingQuality::new(5).unwrap();
+
+/// Flag statistics counts for a set of records.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Counts {
+    read: u64,
+    primary: u64,
+    secondary: u64,
+    supplementary: u64,
+    duplicate: u64,
+    primary_duplicate: u64,
+    mapped: u64,
+    primary_mapped: u64,
+    paired: u64,
+    read_1: u64,
+    read_2: u64,
+    proper_pair: u64,
+    mate_mapped: u64,
+    singleton: u64,
+    mate_reference_sequence_id_mismatch: u64,
+    mate_reference_sequence_id_mismatch_hq: u64,
+}
+
+impl Counts {
+    /// Returns the total number of records.
+    pub fn read(&self) -> u64 {
+        self.read
+    }
+
+    /// Returns the number of primary records.
+    pub fn primary(&self) -> u64 {
+        self.primary
+    }
+
+    /// Returns the number of secondary records.
+    pub fn secondary(&self) -> u64 {
+        self.secondary
+    }
+
+    /// Returns the number of supplementary records.
+    pub fn supplementary(&self) -> u64 {
+        self.supplementary
+    }
+
+    /// Returns the number of duplicate records.
+    pub fn duplicate(&self) -> u64 {
+        self.duplicate
+    }
+
+    /// Returns the number of primary duplicate records.
+    pub fn primary_duplicate(&self) -> u64 {
+        self.primary_duplicate
+    }
+
+    /// Returns the number of mapped records.
+    pub fn mapped(&self) -> u64 {
+        self.mapped
+    }
+
+    /// Returns the number of primary mapped records.
+    pub fn primary_mapped(&self) -> u64 {
+        self.primary_mapped
+    }
+
+    /// Returns the number of primary records that are segmented ("paired in sequencing").
+    pub fn paired(&self) -> u64 {
+        self.paired
+    }
+
+    /// Returns the number of primary records that are the first segment.
+    pub fn read_1(&self) -> u64 {
+        self.read_1
+    }
+
+    /// Returns the number of primary records that are the last segment.
+    pub fn read_2(&self) -> u64 {
+        self.read_2
+    }
+
+    /// Returns the number of primary mapped records that are properly segmented.
+    pub fn proper_pair(&self) -> u64 {
+        self.proper_pair
+    }
+
+    /// Returns the number of primary mapped records with a mapped mate.
+    pub fn mate_mapped(&self) -> u64 {
+        self.mate_mapped
+    }
+
+    /// Returns the number of primary mapped records with an unmapped mate.
+    pub fn singleton(&self) -> u64 {
+        self.singleton
+    }
+
+    /// Returns the number of primary mapped records with a mate mapped to a different reference
+    /// sequence.
+    pub fn mate_reference_sequence_id_mismatch(&self) -> u64 {
+        self.mate_reference_sequence_id_mismatch
+    }
+
+    /// Returns the number of primary mapped records with a mate mapped to a different reference
+    /// sequence and a mapping quality of at least [`MIN_HQ_MAPPING_QUALITY`].
+    pub fn mate_reference_sequence_id_mismatch_hq(&self) -> u64 {
+        self.mate_reference_sequence_id_mismatch_hq
+    }
+
+    fn add<R>(&mut self, header: &sam::Header, record: &R) -> io::Result<()>
+    where
+        R: Record + ?Sized,
+    {
+        let flags = record.flags()?;
+
+        self.read += 1;
+
+        if !flags.is_unmapped() {
+            self.mapped += 1;
+        }
+
+        if flags.is_duplicate() {
+            self.duplicate += 1;
+        }
+
+        if flags.is_secondary() {
+            self.secondary += 1;
+            return Ok(());
+        } else if flags.is_supplementary() {
+            self.supplementary += 1;
+            return Ok(());
+        }
+
+        self.primary += 1;
+
+        if !flags.is_unmapped() {
+            self.primary_mapped += 1;
+        }
+
+        if flags.is_duplicate() {
+            self.primary_duplicate += 1;
+        }
+
+        if !flags.is_segmented() {
+            return Ok(());
+        }
+
+        self.paired += 1;
+
+        if flags.is_first_segment() {
+            self.read_1 += 1;
+        }
+
+        if flags.is_last_segment() {
+            self.read_2 += 1;
+        }
+
+        if flags.is_unmapped() {
+            return Ok(());
+        }
+
+        if flags.is_properly_segmented() {
+            self.proper_pair += 1;
+        }
+
+        if flags.is_mate_unmapped() {
+            self.singleton += 1;
+            return Ok(());
+        }
+
+        self.mate_mapped += 1;
+
+        let reference_sequence_id = record.reference_sequence_id(header).transpose()?;
+        let mate_reference_sequence_id = record.mate_reference_sequence_id(header).transpose()?;
+
+        if mate_reference_sequence_id != reference_sequence_id {
+            self.mate_reference_sequence_id_mismatch += 1;
+
+            let is_hq = record
+                .mapping_quality()
+                .transpose()?
+                .map(|mapping_quality| mapping_quality >= MIN_HQ_MAPPING_QUALITY)
+                .unwrap_or(true);
+
+            if is_hq {
+                self.mate_reference_sequence_id_mismatch_hq += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Flag statistics.
+///
+/// The [`fmt::Display`] implementation writes the same report as `samtools flagstat`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FlagStat {
+    qc_pass: Counts,
+    qc_fail: Counts,
+}
+
+impl FlagStat {
+    /// Returns the counts for records that passed quality control.
+    pub fn qc_pass(&self) -> &Counts {
+        &self.qc_pass
+    }
+
+    /// Returns the counts for records that failed quality control.
+    pub fn qc_fail(&self) -> &Counts {
+        &self.qc_fail
+    }
+
+    /// Adds a record to the statistics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, alignment::RecordBuf};
+    /// use noodles_util::alignment::flagstat::FlagStat;
+    ///
+    /// let header = sam::Header::default();
+    /// let mut flagstat = FlagStat::default();
+    /// flagstat.add(&header, &RecordBuf::default())?;
+    ///
+    /// assert_eq!(flagstat.qc_pass().read(), 1);
+    /// assert_eq!(flagstat.qc_fail().read(), 0);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn add<R>(&mut self, header: &sam::Header, record: &R) -> io::Result<()>
+    where
+        R: Record + ?Sized,
+    {
+        if record.flags()?.is_qc_fail() {
+            self.qc_fail.add(header, record)
+        } else {
+            self.qc_pass.add(header, record)
+        }
+    }
+}
+
+impl fmt::Display for FlagStat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (pass, fail) = (&self.qc_pass, &self.qc_fail);
+
+        writeln!(
+            f,
+            "{} + {} in total (QC-passed reads + QC-failed reads)",
+            pass.read, fail.read
+        )?;
+        writeln!(f, "{} + {} primary", pass.primary, fail.primary)?;
+        writeln!(f, "{} + {} secondary", pass.secondary, fail.secondary)?;
+        writeln!(
+            f,
+            "{} + {} supplementary",
+            pass.supplementary, fail.supplementary
+        )?;
+        writeln!(f, "{} + {} duplicates", pass.duplicate, fail.duplicate)?;
+        writeln!(
+            f,
+            "{} + {} primary duplicates",
+            pass.primary_duplicate, fail.primary_duplicate
+        )?;
+        writeln!(
+            f,
+            "{} + {} mapped ({} : {})",
+            pass.mapped,
+            fail.mapped,
+            Percentage(pass.mapped, pass.read),
+            Percentage(fail.mapped, fail.read)
+        )?;
+        writeln!(
+            f,
+            "{} + {} primary mapped ({} : {})",
+            pass.primary_mapped,
+            fail.primary_mapped,
+            Percentage(pass.primary_mapped, pass.primary),
+            Percentage(fail.primary_mapped, fail.primary)
+        )?;
+        writeln!(f, "{} + {} paired in sequencing", pass.paired, fail.paired)?;
+        writeln!(f, "{} + {} read1", pass.read_1, fail.read_1)?;
+        writeln!(f, "{} + {} read2", pass.read_2, fail.read_2)?;
+        writeln!(
+            f,
+            "{} + {} properly paired ({} : {})",
+            pass.proper_pair,
+            fail.proper_pair,
+            Percentage(pass.proper_pair, pass.paired),
+            Percentage(fail.proper_pair, fail.paired)
+        )?;
+        writeln!(
+            f,
+            "{} + {} with itself and mate mapped",
+            pass.mate_mapped, fail.mate_mapped
+        )?;
+        writeln!(
+            f,
+            "{} + {} singletons ({} : {})",
+            pass.singleton,
+            fail.singleton,
+            Percentage(pass.singleton, pass.paired),
+            Percentage(fail.singleton, fail.paired)
+        )?;
+        writeln!(
+            f,
+            "{} + {} with mate mapped to a different chr",
+            pass.mate_reference_sequence_id_mismatch, fail.mate_reference_sequence_id_mismatch
+        )?;
+        writeln!(
+            f,
+            "{} + {} with mate mapped to a different chr (mapQ>={})",
+            pass.mate_reference_sequence_id_mismatch_hq,
+            fail.mate_reference_sequence_id_mismatch_hq,
+            MIN_HQ_MAPPING_QUALITY.get()
+        )
+    }
+}
+
+struct Percentage(u64, u64);
+
+impl fmt::Display for Percentage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.1 == 0 {
+            f.write_str("N/A")
+        } else {
+            let (a, b) = (self.0 as f64, self.1 as f64);
+            write!(f, "{:.2}%", a / b * 100.0)
+        }
+    }
+}
+
+/// Calculates flag statistics from an iterator of records.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+/// use noodles_sam::{self as sam, alignment::RecordBuf};
+/// use noodles_util::alignment::flagstat;
+///
+/// let header = sam::Header::default();
+/// let records = [RecordBuf::default()].into_iter().map(Ok::<_, io::Error>);
+///
+/// let flagstat = flagstat::count(&header, records)?;
+/// assert_eq!(flagstat.qc_pass().read(), 1);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn count<I, R>(header: &sam::Header, records: I) -> io::Result<FlagStat>
+where
+    I: IntoIterator<Item = io::Result<R>>,
+    R: Record,
+{
+    let mut flagstat = FlagStat::default();
+
+    for result in records {
+        let record = result?;
+        flagstat.add(header, &record)?;
+    }
+
+    Ok(flagstat)
+}
+
+#[cfg(test)]
+mod tests {
+    use sam::alignment::{RecordBuf, record::Flags};
+
+    use super::*;
+
+    #[test]
+    fn test_count() -> io::Result<()> {
+        let header = sam::Header::default();
+
+        let records = [
+            Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::MATE_UNMAPPED,
+            Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::UNMAPPED,
+            Flags::SECONDARY | Flags::DUPLICATE,
+            Flags::UNMAPPED | Flags::QC_FAIL,
+        ]
+        .into_iter()
+        .map(|flags| Ok(RecordBuf::builder().set_flags(flags).build()));
+
+        let flagstat = count(&header, records)?;
+
+        let qc_pass = flagstat.qc_pass();
+        assert_eq!(qc_pass.read(), 3);
+        assert_eq!(qc_pass.primary(), 2);
+        assert_eq!(qc_pass.secondary(), 1);
+        assert_eq!(qc_pass.duplicate(), 1);
+        assert_eq!(qc_pass.primary_duplicate(), 0);
+        assert_eq!(qc_pass.mapped(), 2);
+        assert_eq!(qc_pass.primary_mapped(), 1);
+        assert_eq!(qc_pass.paired(), 2);
+        assert_eq!(qc_pass.read_1(), 1);
+        assert_eq!(qc_pass.read_2(), 1);
+        assert_eq!(qc_pass.singleton(), 1);
+        assert_eq!(qc_pass.mate_mapped(), 0);
+
+        let qc_fail = flagstat.qc_fail();
+        assert_eq!(qc_fail.read(), 1);
+        assert_eq!(qc_fail.mapped(), 0);
+
+        let report = flagstat.to_string();
+        assert!(report.starts_with("3 + 1 in total (QC-passed reads + QC-failed reads)\n"));
+        assert!(report.contains("\n2 + 0 mapped (66.67% : 0.00%)\n"));
+
+        Ok(())
+    }
+}
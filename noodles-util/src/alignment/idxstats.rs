@@ -0,0 +1,336 @@
+//! Alignment index statistics.
+//!
+//! This calculates the same statistics as `samtools idxstats`: the number of mapped and unmapped
+//! records per reference sequence and the number of unplaced, unmapped records.
+
+use std::{fmt, io};
+
+use bstr::{BStr, BString};
+use noodles_csi::BinningIndex;
+use noodles_sam::{self as sam, alignment::Record};
+
+use super::io::IndexedReader;
+
+/// Index statistics for a reference sequence.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferenceSequence {
+    name: BString,
+    length: usize,
+    mapped_record_count: u64,
+    unmapped_record_count: u64,
+}
+
+impl ReferenceSequence {
+    /// Returns the reference sequence name.
+    pub fn name(&self) -> &BStr {
+        self.name.as_ref()
+    }
+
+    /// Returns the reference sequence length.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns the number of mapped records.
+    pub fn mapped_record_count(&self) -> u64 {
+        self.mapped_record_count
+    }
+
+    /// Returns the number of placed, unmapped records.
+    pub fn unmapped_record_count(&self) -> u64 {
+        self.unmapped_record_count
+    }
+}
+
+/// Index statistics.
+///
+/// The [`fmt::Display`] implementation writes the same tab-delimited report as
+/// `samtools idxstats`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IdxStats {
+    reference_sequences: Vec<ReferenceSequence>,
+    unplaced_unmapped_record_count: u64,
+}
+
+impl IdxStats {
+    /// Returns the statistics for each reference sequence, in header order.
+    pub fn reference_sequences(&self) -> &[ReferenceSequence] {
+        &self.reference_sequences
+    }
+
+    /// Returns the number of unplaced, unmapped records.
+    pub fn unplaced_unmapped_record_count(&self) -> u64 {
+        self.unplaced_unmapped_record_count
+    }
+
+    fn new(header: &sam::Header) -> Self {
+        let reference_sequences = header
+            .reference_sequences()
+            .iter()
+            .map(|(name, reference_sequence)| ReferenceSequence {
+                name: name.clone(),
+                length: reference_sequence.length().get(),
+                mapped_record_count: 0,
+                unmapped_record_count: 0,
+            })
+            .collect();
+
+        Self {
+            reference_sequences,
+            unplaced_unmapped_record_count: 0,
+        }
+    }
+}
+
+impl fmt::Display for IdxStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for reference_sequence in &self.reference_sequences {
+            writeln!(
+                f,
+                "{}\t{}\t{}\t{}",
+                reference_sequence.name,
+                reference_sequence.length,
+                reference_sequence.mapped_record_count,
+                reference_sequence.unmapped_record_count
+            )?;
+        }
+
+        writeln!(f, "*\t0\t0\t{}", self.unplaced_unmapped_record_count)
+    }
+}
+
+/// Builds index statistics from the metadata pseudo-bins of a binning index.
+///
+/// Reference sequences without metadata, i.e., without any records, are given counts of 0. This
+/// returns `None` if the index has no metadata at all, e.g., it was built by a tool that does not
+/// write metadata pseudo-bins.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_csi as csi;
+/// use noodles_sam as sam;
+/// use noodles_util::alignment::idxstats;
+///
+/// let header = sam::Header::default();
+/// let index = csi::Index::default();
+///
+/// assert!(idxstats::from_index(&header, &index).is_none());
+/// ```
+pub fn from_index<I>(header: &sam::Header, index: &I) -> Option<IdxStats>
+where
+    I: BinningIndex + ?Sized,
+{
+    let mut stats = IdxStats::new(header);
+    let mut has_metadata = false;
+
+    for (reference_sequence, index_reference_sequence) in stats
+        .reference_sequences
+        .iter_mut()
+        .zip(index.reference_sequences())
+    {
+        if let Some(metadata) = index_reference_sequence.metadata() {
+            reference_sequence.mapped_record_count = metadata.mapped_record_count();
+            reference_sequence.unmapped_record_count = metadata.unmapped_record_count();
+            has_metadata = true;
+        }
+    }
+
+    match index.unplaced_unmapped_record_count() {
+        Some(n) => stats.unplaced_unmapped_record_count = n,
+        None if !has_metadata => return None,
+        None => {}
+    }
+
+    Some(stats)
+}
+
+/// Calculates index statistics by scanning records.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+/// use noodles_sam::{self as sam, alignment::RecordBuf};
+/// use noodles_util::alignment::idxstats;
+///
+/// let header = sam::Header::default();
+/// let records = [RecordBuf::default()].into_iter().map(Ok::<_, io::Error>);
+///
+/// let stats = idxstats::from_records(&header, records)?;
+/// assert_eq!(stats.unplaced_unmapped_record_count(), 1);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn from_records<I, R>(header: &sam::Header, records: I) -> io::Result<IdxStats>
+where
+    I: IntoIterator<Item = io::Result<R>>,
+    R: Record,
+{
+    let mut stats = IdxStats::new(header);
+
+    for result in records {
+        let record = result?;
+
+        let Some(id) = record.reference_sequence_id(header).transpose()? else {
+            stats.unplaced_unmapped_record_count += 1;
+            continue;
+        };
+
+        let reference_sequence = stats.reference_sequences.get_mut(id).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid reference sequence ID: {id}"),
+            )
+        })?;
+
+        if record.flags()?.is_unmapped() {
+            reference_sequence.unmapped_record_count += 1;
+        } else {
+            reference_sequence.mapped_record_count += 1;
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Reads index statistics from an indexed alignment reader.
+///
+/// For SAM and BAM, this uses the metadata pseudo-bins of the associated index, when present.
+/// Otherwise, e.g., for CRAM, this falls back to scanning all records from the current stream
+/// position.
+///
+/// # Examples
+///
+/// ```no_run
+/// use noodles_util::alignment::{self, idxstats};
+///
+/// let mut reader = alignment::io::indexed_reader::Builder::default()
+///     .build_from_path("sample.bam")?;
+///
+/// let header = reader.read_header()?;
+/// let stats = idxstats::read(&mut reader, &header)?;
+/// print!("{stats}");
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn read<R>(reader: &mut IndexedReader<R>, header: &sam::Header) -> io::Result<IdxStats>
+where
+    R: io::Read,
+{
+    let stats = match reader {
+        IndexedReader::Sam(r) => from_index(header, r.index()),
+        IndexedReader::Bam(r) => from_index(header, r.index()),
+        IndexedReader::Cram(_) => None,
+    };
+
+    match stats {
+        Some(stats) => Ok(stats),
+        None => from_records(header, reader.records(header)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use noodles_bgzf as bgzf;
+    use noodles_core::Position;
+    use noodles_csi::binning_index::{
+        Indexer,
+        index::reference_sequence::{bin::Chunk, index::LinearIndex},
+    };
+    use sam::{
+        alignment::{RecordBuf, record::Flags},
+        header::record::value::{Map, map::ReferenceSequence as ReferenceSequenceMap},
+    };
+
+    use super::*;
+
+    fn build_header() -> sam::Header {
+        sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequenceMap>::new(NonZero::<usize>::MIN),
+            )
+            .add_reference_sequence(
+                "sq1",
+                Map::<ReferenceSequenceMap>::new(NonZero::<usize>::MAX),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_from_index() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+
+        let mut indexer = Indexer::<LinearIndex>::default();
+        let start = Position::try_from(8)?;
+        let end = Position::try_from(13)?;
+
+        for (i, is_mapped) in [true, true, false].into_iter().enumerate() {
+            let chunk = Chunk::new(
+                bgzf::VirtualPosition::from(i as u64),
+                bgzf::VirtualPosition::from(i as u64 + 1),
+            );
+            indexer.add_record(Some((1, start, end, is_mapped)), chunk)?;
+        }
+
+        indexer.add_record(
+            None,
+            Chunk::new(
+                bgzf::VirtualPosition::from(3),
+                bgzf::VirtualPosition::from(4),
+            ),
+        )?;
+
+        let index = indexer.build(header.reference_sequences().len());
+
+        let stats = from_index(&header, &index).expect("missing metadata");
+
+        assert_eq!(
+            stats.to_string(),
+            format!("sq0\t1\t0\t0\nsq1\t{}\t2\t1\n*\t0\t0\t1\n", usize::MAX)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_records() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+
+        let records = [
+            (Flags::empty(), Some(0)),
+            (Flags::UNMAPPED, Some(0)),
+            (Flags::empty(), Some(1)),
+            (Flags::UNMAPPED, None),
+        ]
+        .into_iter()
+        .map(|(flags, reference_sequence_id)| {
+            let mut builder = RecordBuf::builder().set_flags(flags);
+
+            if let Some(id) = reference_sequence_id {
+                builder = builder.set_reference_sequence_id(id);
+            }
+
+            Ok(builder.build())
+        });
+
+        let stats = from_records(&header, records)?;
+
+        let counts: Vec<_> = stats
+            .reference_sequences()
+            .iter()
+            .map(|reference_sequence| {
+                (
+                    reference_sequence.mapped_record_count(),
+                    reference_sequence.unmapped_record_count(),
+                )
+            })
+            .collect();
+
+        assert_eq!(counts, [(1, 1), (1, 0)]);
+        assert_eq!(stats.unplaced_unmapped_record_count(), 1);
+
+        Ok(())
+    }
+}
@@ -4,12 +4,16 @@ mod builder;
 
 pub use self::builder::Builder;
 
-use std::io::{self, Read, Seek};
+use std::{
+    collections::BTreeMap,
+    io::{self, Read, Seek, SeekFrom},
+};
 
 use noodles_bam as bam;
 use noodles_bgzf as bgzf;
 use noodles_core::Region;
 use noodles_cram as cram;
+use noodles_csi::{BinningIndex, binning_index::Shard};
 use noodles_sam::{self as sam, alignment::Record};
 
 /// An indexed alignment reader.
@@ -151,4 +155,405 @@ where
 
         Ok(records)
     }
+
+    /// Returns an iterator over records owned by the given shard.
+    ///
+    /// A record is owned by the shard that contains its reference sequence and alignment start.
+    /// When reading every shard of a plan, e.g., from
+    /// [`noodles_csi::binning_index::shard::plan`], each record is returned exactly once.
+    ///
+    /// A record with a reference sequence but no alignment start is owned by
+    /// [`Shard::Unmapped`]. Such records are expected at the start of their reference sequence,
+    /// i.e., in coordinate order as sorted by, e.g., `samtools sort`. [`Shard::Unmapped`] reads
+    /// the records before the first record with an alignment start of each reference sequence
+    /// and the unplaced, unmapped records after the last indexed record.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::num::NonZero;
+    /// use noodles_csi::binning_index;
+    /// use noodles_util::alignment;
+    ///
+    /// let mut reader = alignment::io::indexed_reader::Builder::default()
+    ///     .build_from_path("sample.bam")?;
+    ///
+    /// let header = reader.read_header()?;
+    ///
+    /// let index = noodles_bam::bai::fs::read("sample.bam.bai")?;
+    /// let shards = binning_index::shard::plan(
+    ///     &index,
+    ///     header.reference_sequences().keys(),
+    ///     NonZero::new(8).unwrap(),
+    /// );
+    ///
+    /// for shard in &shards {
+    ///     for result in reader.query_shard(&header, shard)? {
+    ///         let record = result?;
+    ///         // ...
+    ///     }
+    /// }
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn query_shard<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h sam::Header,
+        shard: &Shard,
+    ) -> io::Result<impl Iterator<Item = io::Result<Box<dyn Record>>> + use<'r, 'h, R>> {
+        let records: Box<dyn Iterator<Item = io::Result<Box<dyn Record>>>> = match (self, shard) {
+            (Self::Sam(reader), Shard::Regions(regions)) => Box::new(
+                reader
+                    .query_regions(header, regions)?
+                    .map(|result| result.map(|(record, _)| Box::new(record) as Box<dyn Record>)),
+            ),
+            (Self::Sam(reader), Shard::Unmapped) => {
+                reader.get_mut().seek(bgzf::VirtualPosition::default())?;
+                reader.read_header()?;
+
+                let starts =
+                    placed_record_read_starts(reader.index(), reader.get_ref().virtual_position());
+
+                let records = read_placed_records_without_start(
+                    reader,
+                    header,
+                    &starts,
+                    sam::io::IndexedReader::get_mut,
+                    |reader| {
+                        let mut record = sam::Record::default();
+
+                        reader.read_record(&mut record).map(|n| match n {
+                            0 => None,
+                            _ => Some(Box::new(record) as Box<dyn Record>),
+                        })
+                    },
+                )?;
+
+                let unplaced_records = reader
+                    .query_unmapped()?
+                    .map(|result| result.map(|record| Box::new(record) as Box<dyn Record>))
+                    .filter(move |result| is_unplaced(header, result));
+
+                Box::new(records.into_iter().map(Ok).chain(unplaced_records))
+            }
+            (Self::Bam(reader), Shard::Regions(regions)) => Box::new(
+                reader
+                    .query_regions(header, regions)?
+                    .map(|result| result.map(|(record, _)| Box::new(record) as Box<dyn Record>)),
+            ),
+            (Self::Bam(reader), Shard::Unmapped) => {
+                reader.get_mut().seek(bgzf::VirtualPosition::default())?;
+                reader.read_header()?;
+
+                let starts =
+                    placed_record_read_starts(reader.index(), reader.get_ref().virtual_position());
+
+                let records = read_placed_records_without_start(
+                    reader,
+                    header,
+                    &starts,
+                    bam::io::IndexedReader::get_mut,
+                    |reader| {
+                        let mut record = bam::Record::default();
+
+                        reader.read_record(&mut record).map(|n| match n {
+                            0 => None,
+                            _ => Some(Box::new(record) as Box<dyn Record>),
+                        })
+                    },
+                )?;
+
+                let unplaced_records = reader
+                    .query_unmapped()?
+                    .map(|result| result.map(|record| Box::new(record) as Box<dyn Record>))
+                    .filter(move |result| is_unplaced(header, result));
+
+                Box::new(records.into_iter().map(Ok).chain(unplaced_records))
+            }
+            (Self::Cram(reader), Shard::Regions(regions)) => Box::new(
+                reader
+                    .query_regions(header, regions)?
+                    .map(|result| result.map(|(record, _)| Box::new(record) as Box<dyn Record>)),
+            ),
+            (Self::Cram(reader), Shard::Unmapped) => {
+                let records = read_placed_cram_records_without_start(reader, header)?;
+
+                let unplaced_records = reader
+                    .query_unmapped(header)
+                    .map(|result| result.map(|record| Box::new(record) as Box<dyn Record>));
+
+                Box::new(records.into_iter().map(Ok).chain(unplaced_records))
+            }
+        };
+
+        let shard = shard.clone();
+
+        Ok(records.filter_map(move |result| {
+            result
+                .and_then(|record| {
+                    is_owned_by(header, &shard, record.as_ref())
+                        .map(|is_owned| if is_owned { Some(record) } else { None })
+                })
+                .transpose()
+        }))
+    }
+}
+
+// Returns the positions to start reading records with a reference sequence but no alignment
+// start.
+//
+// In coordinate order, these are the first records of their reference sequence. They are read
+// from the first record and from the end of the indexed data of each reference sequence.
+fn placed_record_read_starts(
+    index: &dyn BinningIndex,
+    first_record_position: bgzf::VirtualPosition,
+) -> Vec<bgzf::VirtualPosition> {
+    let mut starts = vec![first_record_position];
+
+    for reference_sequence in index.reference_sequences() {
+        let end = reference_sequence
+            .bins()
+            .flat_map(|(_, bin)| bin.chunks())
+            .map(|chunk| chunk.end())
+            .chain(
+                reference_sequence
+                    .metadata()
+                    .map(|metadata| metadata.end_position()),
+            )
+            .max();
+
+        if let Some(end) = end
+            && starts.last().is_none_or(|&start| end > start)
+        {
+            starts.push(end);
+        }
+    }
+
+    starts
+}
+
+// Reads the records with a reference sequence but no alignment start from a BGZF-compressed
+// SAM or BAM file.
+//
+// Each read stops at the first record with an alignment start or without a reference sequence
+// and is bounded by the start of the next read, so no record is read twice.
+fn read_placed_records_without_start<T, R, G, F>(
+    reader: &mut T,
+    header: &sam::Header,
+    starts: &[bgzf::VirtualPosition],
+    get_mut: G,
+    mut read_record: F,
+) -> io::Result<Vec<Box<dyn Record>>>
+where
+    R: Read + Seek,
+    G: Fn(&mut T) -> &mut bgzf::io::Reader<R>,
+    F: FnMut(&mut T) -> io::Result<Option<Box<dyn Record>>>,
+{
+    let mut records = Vec::new();
+
+    for (i, &start) in starts.iter().enumerate() {
+        let end = starts.get(i + 1).copied();
+
+        get_mut(reader).seek(start)?;
+
+        while end.is_none_or(|end| get_mut(reader).virtual_position() < end) {
+            let Some(record) = read_record(reader)? else {
+                break;
+            };
+
+            if !is_placed_without_start(header, record.as_ref())? {
+                break;
+            }
+
+            records.push(record);
+        }
+    }
+
+    Ok(records)
+}
+
+// Reads the records with a reference sequence but no alignment start from a CRAM file.
+//
+// In coordinate order, these are the first records of their reference sequence, so only the
+// first container with a slice of each reference sequence is read.
+fn read_placed_cram_records_without_start<R>(
+    reader: &mut cram::io::IndexedReader<R>,
+    header: &sam::Header,
+) -> io::Result<Vec<Box<dyn Record>>>
+where
+    R: Read + Seek,
+{
+    let mut offsets = BTreeMap::new();
+
+    for index_record in reader.index() {
+        if let Some(id) = index_record.reference_sequence_id() {
+            offsets
+                .entry(id)
+                .and_modify(|offset: &mut u64| *offset = (*offset).min(index_record.offset()))
+                .or_insert(index_record.offset());
+        }
+    }
+
+    let mut records = Vec::new();
+
+    for (id, offset) in offsets {
+        reader.get_mut().seek(SeekFrom::Start(offset))?;
+
+        for result in reader.records(header) {
+            let record = result?;
+
+            match record.reference_sequence_id() {
+                Some(i) if i == id => {}
+                Some(i) if i < id => continue,
+                _ => break,
+            }
+
+            if record.alignment_start().is_some() {
+                break;
+            }
+
+            records.push(Box::new(record) as Box<dyn Record>);
+        }
+    }
+
+    Ok(records)
+}
+
+fn is_placed_without_start(header: &sam::Header, record: &dyn Record) -> io::Result<bool> {
+    let reference_sequence_id = record.reference_sequence_id(header).transpose()?;
+    let alignment_start = record.alignment_start().transpose()?;
+    Ok(reference_sequence_id.is_some() && alignment_start.is_none())
+}
+
+fn is_unplaced(header: &sam::Header, result: &io::Result<Box<dyn Record>>) -> bool {
+    match result {
+        Ok(record) => record.reference_sequence_id(header).is_none(),
+        Err(_) => true,
+    }
+}
+
+fn is_owned_by(header: &sam::Header, shard: &Shard, record: &dyn Record) -> io::Result<bool> {
+    let reference_sequence_name = record
+        .reference_sequence(header)
+        .transpose()?
+        .map(|(name, _)| name.as_ref());
+
+    let alignment_start = record.alignment_start().transpose()?;
+
+    Ok(shard.contains(reference_sequence_name, alignment_start))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZero;
+
+    use noodles_core::Position;
+    use noodles_csi::binning_index;
+    use noodles_sam::{
+        alignment::{
+            RecordBuf,
+            io::Write as _,
+            record::{
+                Flags,
+                cigar::{Op, op::Kind},
+            },
+        },
+        header::record::value::{Map, map::ReferenceSequence},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_query_shard() -> Result<(), Box<dyn std::error::Error>> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(const { NonZero::new(34).unwrap() }),
+            )
+            .add_reference_sequence(
+                "sq1",
+                Map::<ReferenceSequence>::new(const { NonZero::new(13).unwrap() }),
+            )
+            .build();
+
+        let mut writer = bam::io::IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        // Placed records without an alignment start are first in coordinate order.
+        for (reference_sequence_id, position) in [
+            (0, None),
+            (0, Some(2)),
+            (0, Some(6)),
+            (0, Some(21)),
+            (1, None),
+            (1, Some(5)),
+        ] {
+            let mut builder = RecordBuf::builder().set_reference_sequence_id(reference_sequence_id);
+
+            if let Some(position) = position {
+                builder = builder
+                    .set_flags(Flags::empty())
+                    .set_alignment_start(Position::try_from(position)?)
+                    .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect());
+            }
+
+            writer.write_alignment_record(&header, &builder.build())?;
+        }
+
+        writer.write_alignment_record(&header, &RecordBuf::default())?;
+
+        let index = writer.try_finish()?;
+        let data = writer.into_inner().into_inner();
+
+        let mut reader = IndexedReader::Bam(bam::io::IndexedReader::new(
+            io::Cursor::new(data),
+            index.clone(),
+        ));
+
+        let header = reader.read_header()?;
+
+        let mut read_shard = |shard: &Shard| -> io::Result<Vec<_>> {
+            reader
+                .query_shard(&header, shard)?
+                .map(|result| result.and_then(|record| record.alignment_start().transpose()))
+                .collect()
+        };
+
+        let shards = [
+            Shard::Regions(vec![Region::new(
+                "sq0",
+                Position::MIN..=Position::try_from(8)?,
+            )]),
+            Shard::Regions(vec![
+                Region::new("sq0", Position::try_from(9)?..),
+                Region::new("sq1", Position::MIN..),
+            ]),
+            Shard::Unmapped,
+        ];
+
+        assert_eq!(
+            read_shard(&shards[0])?,
+            [Position::new(2), Position::new(6)]
+        );
+        assert_eq!(
+            read_shard(&shards[1])?,
+            [Position::new(21), Position::new(5)]
+        );
+        assert_eq!(read_shard(&shards[2])?, [None, None, None]);
+
+        let shards = binning_index::shard::plan(
+            &index,
+            header.reference_sequences().keys(),
+            const { NonZero::new(2).unwrap() },
+        );
+
+        let mut record_count = 0;
+
+        for shard in &shards {
+            record_count += read_shard(shard)?.len();
+        }
+
+        assert_eq!(record_count, 7);
+
+        Ok(())
+    }
 }
@@ -1,5 +1,6 @@
 //! Composable iterators for alignment records.
 
 mod pileup;
+mod templates;
 
-pub use self::pileup::Pileup as Depth;
+pub use self::{pileup::Pileup as Depth, templates::Templates};
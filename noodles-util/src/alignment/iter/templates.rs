@@ -0,0 +1,162 @@
+use std::{io, iter::Peekable};
+
+use noodles_sam::alignment::Record;
+
+use crate::alignment::Template;
+
+/// An iterator over templates.
+///
+/// This takes an iterator of records grouped by read name, e.g., queryname-sorted or -grouped
+/// records, and emits a [`Template`] for each group of consecutive records that share a name.
+/// Records without a name are each emitted as their own template.
+pub struct Templates<I>
+where
+    I: Iterator,
+{
+    records: Peekable<I>,
+}
+
+impl<I, R> Templates<I>
+where
+    I: Iterator<Item = io::Result<R>>,
+    R: Record,
+{
+    /// Creates a templates iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use noodles_sam::alignment::RecordBuf;
+    /// use noodles_util::alignment::iter::Templates;
+    ///
+    /// let records = [RecordBuf::default()].into_iter().map(Ok::<_, io::Error>);
+    /// let mut templates = Templates::new(records);
+    ///
+    /// assert!(templates.next().transpose()?.is_some());
+    /// assert!(templates.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn new(records: I) -> Self {
+        Self {
+            records: records.peekable(),
+        }
+    }
+
+    fn next_records(&mut self) -> io::Result<Option<Vec<R>>> {
+        let Some(record) = self.records.next().transpose()? else {
+            return Ok(None);
+        };
+
+        let mut records = vec![record];
+
+        if records[0].name().is_none() {
+            return Ok(Some(records));
+        }
+
+        while let Some(result) = self
+            .records
+            .next_if(|result| is_same_template(&records[0], result))
+        {
+            records.push(result?);
+        }
+
+        Ok(Some(records))
+    }
+}
+
+impl<I, R> Iterator for Templates<I>
+where
+    I: Iterator<Item = io::Result<R>>,
+    R: Record,
+{
+    type Item = io::Result<Template<R>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_records() {
+            Ok(Some(records)) => Some(Template::try_from_records(records)),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+fn is_same_template<R>(a: &R, result: &io::Result<R>) -> bool
+where
+    R: Record,
+{
+    match result {
+        Ok(b) => b.name().is_some() && a.name() == b.name(),
+        // Errors are surfaced by the next call.
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::alignment::{RecordBuf, record::Flags};
+
+    use super::*;
+
+    #[test]
+    fn test_next() -> io::Result<()> {
+        fn build_record(name: Option<&str>, flags: Flags) -> RecordBuf {
+            let mut builder = RecordBuf::builder().set_flags(flags);
+
+            if let Some(name) = name {
+                builder = builder.set_name(name);
+            }
+
+            builder.build()
+        }
+
+        let records = [
+            build_record(Some("r0"), Flags::SEGMENTED | Flags::FIRST_SEGMENT),
+            build_record(Some("r0"), Flags::SEGMENTED | Flags::LAST_SEGMENT),
+            build_record(
+                Some("r0"),
+                Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::SECONDARY,
+            ),
+            build_record(None, Flags::empty()),
+            build_record(None, Flags::empty()),
+            build_record(Some("r1"), Flags::empty()),
+        ];
+
+        let templates: Vec<_> =
+            Templates::new(records.into_iter().map(Ok)).collect::<io::Result<_>>()?;
+
+        assert_eq!(templates.len(), 4);
+
+        let template = &templates[0];
+        assert_eq!(template.name(), Some("r0".into()));
+        assert!(template.read_1().is_some());
+        assert!(template.read_2().is_some());
+        assert_eq!(template.secondary_records().count(), 1);
+        assert_eq!(template.supplementary_records().count(), 0);
+
+        assert!(templates[1].name().is_none());
+        assert!(templates[2].name().is_none());
+
+        let template = &templates[3];
+        assert_eq!(template.name(), Some("r1".into()));
+        assert!(template.read_1().is_some());
+        assert!(template.read_2().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_duplicate_primary_records() {
+        let records = [
+            RecordBuf::builder().set_name("r0").build(),
+            RecordBuf::builder().set_name("r0").build(),
+        ];
+
+        let mut templates = Templates::new(records.into_iter().map(Ok));
+
+        assert!(matches!(
+            templates.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
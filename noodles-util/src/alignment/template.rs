@@ -0,0 +1,128 @@
+//! Alignment template.
+
+mod fix_mates;
+
+use std::io;
+
+use bstr::BStr;
+use noodles_sam::alignment::Record;
+
+/// An alignment template.
+///
+/// A template is the set of records that share a read name, i.e., the primary alignments of each
+/// segment and any secondary and supplementary alignments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template<R> {
+    records: Vec<R>,
+    read_1: Option<usize>,
+    read_2: Option<usize>,
+}
+
+impl<R> Template<R>
+where
+    R: Record,
+{
+    /// Creates a template from records that share a read name.
+    ///
+    /// Primary records that are not segmented or are the first segment are assigned to read 1,
+    /// and primary records that are the last segment are assigned to read 2.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::RecordBuf;
+    /// use noodles_util::alignment::Template;
+    ///
+    /// let template = Template::try_from_records(vec![RecordBuf::default()])?;
+    /// assert!(template.read_1().is_some());
+    /// assert!(template.read_2().is_none());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn try_from_records(records: Vec<R>) -> io::Result<Self> {
+        let mut read_1 = None;
+        let mut read_2 = None;
+
+        for (i, record) in records.iter().enumerate() {
+            let flags = record.flags()?;
+
+            if flags.is_secondary() || flags.is_supplementary() {
+                continue;
+            }
+
+            let slot = match (
+                flags.is_segmented(),
+                flags.is_first_segment(),
+                flags.is_last_segment(),
+            ) {
+                (true, true, true) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "unsupported template segment: neither first nor last segment",
+                    ));
+                }
+                (true, false, true) => &mut read_2,
+                _ => &mut read_1,
+            };
+
+            if slot.replace(i).is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "duplicate primary record in template",
+                ));
+            }
+        }
+
+        Ok(Self {
+            records,
+            read_1,
+            read_2,
+        })
+    }
+
+    /// Returns the read name.
+    pub fn name(&self) -> Option<&BStr> {
+        self.records.first().and_then(|record| record.name())
+    }
+
+    /// Returns the primary record of the first segment.
+    ///
+    /// This is also the primary record of an unsegmented template.
+    pub fn read_1(&self) -> Option<&R> {
+        self.read_1.map(|i| &self.records[i])
+    }
+
+    /// Returns the primary record of the last segment.
+    pub fn read_2(&self) -> Option<&R> {
+        self.read_2.map(|i| &self.records[i])
+    }
+
+    /// Returns an iterator over secondary records.
+    pub fn secondary_records(&self) -> impl Iterator<Item = &R> {
+        self.records.iter().filter(|record| {
+            record
+                .flags()
+                .map(|flags| flags.is_secondary())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns an iterator over supplementary records.
+    pub fn supplementary_records(&self) -> impl Iterator<Item = &R> {
+        self.records.iter().filter(|record| {
+            record
+                .flags()
+                .map(|flags| flags.is_supplementary())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns all records in the template, in input order.
+    pub fn records(&self) -> &[R] {
+        &self.records
+    }
+
+    /// Returns the records in the template, in input order.
+    pub fn into_records(self) -> Vec<R> {
+        self.records
+    }
+}
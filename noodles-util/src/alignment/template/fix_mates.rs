@@ -0,0 +1,302 @@
+use std::io;
+
+use bstr::BString;
+use noodles_core::Position;
+use noodles_sam::{
+    self as sam,
+    alignment::{
+        RecordBuf,
+        record::{Flags, MappingQuality, data::field::Tag},
+        record_buf::data::field::Value,
+    },
+};
+
+use super::Template;
+
+struct Mate {
+    reference_sequence_id: Option<usize>,
+    alignment_start: Option<Position>,
+    alignment_end: Option<Position>,
+    flags: Flags,
+    mapping_quality: Option<MappingQuality>,
+    cigar: BString,
+}
+
+impl Mate {
+    fn try_from_record(record: &RecordBuf) -> io::Result<Self> {
+        let mut cigar = Vec::new();
+        sam::io::writer::record::write_cigar(&mut cigar, record.cigar())?;
+
+        Ok(Self {
+            reference_sequence_id: record.reference_sequence_id(),
+            alignment_start: record.alignment_start(),
+            alignment_end: record.alignment_end(),
+            flags: record.flags(),
+            mapping_quality: record.mapping_quality(),
+            cigar: cigar.into(),
+        })
+    }
+
+    fn is_unmapped(&self) -> bool {
+        self.flags.is_unmapped()
+    }
+}
+
+impl Template<RecordBuf> {
+    /// Fixes mate information between the primary records of a paired template.
+    ///
+    /// This is similar to `samtools fixmate`. For the primary records of the first and last
+    /// segments, this
+    ///
+    ///   * places an unmapped read at the position of its mapped mate,
+    ///   * sets the mate reference sequence ID, mate alignment start, and mate flags
+    ///     (`MATE_UNMAPPED` and `MATE_REVERSE_COMPLEMENTED`) from the mate,
+    ///   * clears the `PROPERLY_SEGMENTED` flag if either read is unmapped,
+    ///   * calculates the template length when both reads are mapped to the same reference
+    ///     sequence, and
+    ///   * sets the mate CIGAR (`MC`) and mate mapping quality (`MQ`) data fields when the mate
+    ///     is mapped, or removes them otherwise.
+    ///
+    /// Secondary and supplementary records receive the mate information, `MC`, and `MQ` of the
+    /// primary record of the opposite segment.
+    ///
+    /// Templates without both a first and last segment primary record are left unchanged.
+    pub fn fix_mates(&mut self) -> io::Result<()> {
+        let (Some(i), Some(j)) = (self.read_1, self.read_2) else {
+            return Ok(());
+        };
+
+        place_unmapped(&mut self.records, i, j);
+        place_unmapped(&mut self.records, j, i);
+
+        let read_1 = Mate::try_from_record(&self.records[i])?;
+        let read_2 = Mate::try_from_record(&self.records[j])?;
+
+        for (k, record) in self.records.iter_mut().enumerate() {
+            let flags = record.flags();
+
+            let (this, mate) = if k == i || (k != j && !flags.is_last_segment()) {
+                (&read_1, &read_2)
+            } else {
+                (&read_2, &read_1)
+            };
+
+            set_mate(record, mate);
+
+            if k == i || k == j {
+                let is_either_unmapped = this.is_unmapped() || mate.is_unmapped();
+
+                if is_either_unmapped {
+                    record.flags_mut().remove(Flags::PROPERLY_SEGMENTED);
+                }
+
+                *record.template_length_mut() = calculate_template_length(this, mate, k == i)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn place_unmapped(records: &mut [RecordBuf], i: usize, j: usize) {
+    let mate_is_unmapped = records[j].flags().is_unmapped();
+
+    if !records[i].flags().is_unmapped() {
+        return;
+    }
+
+    let (reference_sequence_id, alignment_start) = if mate_is_unmapped {
+        (None, None)
+    } else {
+        (
+            records[j].reference_sequence_id(),
+            records[j].alignment_start(),
+        )
+    };
+
+    let record = &mut records[i];
+    *record.reference_sequence_id_mut() = reference_sequence_id;
+    *record.alignment_start_mut() = alignment_start;
+}
+
+fn set_mate(record: &mut RecordBuf, mate: &Mate) {
+    *record.mate_reference_sequence_id_mut() = mate.reference_sequence_id;
+    *record.mate_alignment_start_mut() = mate.alignment_start;
+
+    let flags = record.flags_mut();
+    flags.insert(Flags::SEGMENTED);
+    flags.set(Flags::MATE_UNMAPPED, mate.is_unmapped());
+    flags.set(
+        Flags::MATE_REVERSE_COMPLEMENTED,
+        mate.flags.is_reverse_complemented(),
+    );
+
+    let data = record.data_mut();
+
+    if mate.is_unmapped() {
+        data.remove(&Tag::MATE_CIGAR);
+        data.remove(&Tag::MATE_MAPPING_QUALITY);
+    } else {
+        data.insert(Tag::MATE_CIGAR, Value::String(mate.cigar.clone()));
+
+        match mate.mapping_quality {
+            Some(mapping_quality) => {
+                let n = i32::from(u8::from(mapping_quality));
+                data.insert(Tag::MATE_MAPPING_QUALITY, Value::Int32(n));
+            }
+            None => {
+                data.remove(&Tag::MATE_MAPPING_QUALITY);
+            }
+        }
+    }
+}
+
+fn calculate_template_length(this: &Mate, mate: &Mate, is_read_1: bool) -> io::Result<i32> {
+    if this.is_unmapped()
+        || mate.is_unmapped()
+        || this.reference_sequence_id != mate.reference_sequence_id
+    {
+        return Ok(0);
+    }
+
+    let (Some(start), Some(end), Some(mate_start), Some(mate_end)) = (
+        this.alignment_start,
+        this.alignment_end,
+        mate.alignment_start,
+        mate.alignment_end,
+    ) else {
+        return Ok(0);
+    };
+
+    let leftmost = start.min(mate_start);
+    let rightmost = end.max(mate_end);
+
+    let len = usize::from(rightmost) - usize::from(leftmost) + 1;
+    let len = i32::try_from(len).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    let is_leftmost = start < mate_start || (start == mate_start && is_read_1);
+
+    Ok(if is_leftmost { len } else { -len })
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::alignment::record::cigar::{Op, op::Kind};
+
+    use super::*;
+
+    #[test]
+    fn test_fix_mates() -> Result<(), Box<dyn std::error::Error>> {
+        let read_1 = RecordBuf::builder()
+            .set_name("r0")
+            .set_flags(Flags::SEGMENTED | Flags::PROPERLY_SEGMENTED | Flags::FIRST_SEGMENT)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(8)?)
+            .set_mapping_quality(MappingQuality::try_from(13)?)
+            .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+            .build();
+
+        let read_2 = RecordBuf::builder()
+            .set_name("r0")
+            .set_flags(
+                Flags::SEGMENTED
+                    | Flags::PROPERLY_SEGMENTED
+                    | Flags::LAST_SEGMENT
+                    | Flags::REVERSE_COMPLEMENTED,
+            )
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(13)?)
+            .set_mapping_quality(MappingQuality::try_from(21)?)
+            .set_cigar(
+                [Op::new(Kind::Match, 3), Op::new(Kind::SoftClip, 2)]
+                    .into_iter()
+                    .collect(),
+            )
+            .build();
+
+        let supplementary = RecordBuf::builder()
+            .set_name("r0")
+            .set_flags(Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::SUPPLEMENTARY)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(34)?)
+            .set_cigar([Op::new(Kind::Match, 2)].into_iter().collect())
+            .build();
+
+        let mut template = Template::try_from_records(vec![read_1, read_2, supplementary])?;
+        template.fix_mates()?;
+
+        let [read_1, read_2, supplementary] = template.records() else {
+            panic!("unexpected records");
+        };
+
+        assert_eq!(read_1.mate_reference_sequence_id(), Some(0));
+        assert_eq!(read_1.mate_alignment_start(), Position::new(13));
+        assert!(read_1.flags().is_mate_reverse_complemented());
+        assert_eq!(read_1.template_length(), 8);
+        assert_eq!(
+            read_1.data().get(&Tag::MATE_CIGAR),
+            Some(&Value::from("3M2S"))
+        );
+        assert_eq!(
+            read_1.data().get(&Tag::MATE_MAPPING_QUALITY),
+            Some(&Value::Int32(21))
+        );
+
+        assert_eq!(read_2.mate_alignment_start(), Position::new(8));
+        assert!(!read_2.flags().is_mate_reverse_complemented());
+        assert_eq!(read_2.template_length(), -8);
+        assert_eq!(
+            read_2.data().get(&Tag::MATE_CIGAR),
+            Some(&Value::from("4M"))
+        );
+
+        assert_eq!(supplementary.mate_alignment_start(), Position::new(13));
+        assert_eq!(supplementary.template_length(), 0);
+        assert_eq!(
+            supplementary.data().get(&Tag::MATE_CIGAR),
+            Some(&Value::from("3M2S"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fix_mates_with_unmapped_mate() -> Result<(), Box<dyn std::error::Error>> {
+        let read_1 = RecordBuf::builder()
+            .set_name("r0")
+            .set_flags(Flags::SEGMENTED | Flags::PROPERLY_SEGMENTED | Flags::FIRST_SEGMENT)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(8)?)
+            .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+            .build();
+
+        let mut read_2 = RecordBuf::builder()
+            .set_name("r0")
+            .set_flags(Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::UNMAPPED)
+            .build();
+
+        read_2.data_mut().insert(Tag::MATE_CIGAR, Value::from("1M"));
+
+        let mut template = Template::try_from_records(vec![read_1, read_2])?;
+        template.fix_mates()?;
+
+        let [read_1, read_2] = template.records() else {
+            panic!("unexpected records");
+        };
+
+        assert_eq!(read_2.reference_sequence_id(), Some(0));
+        assert_eq!(read_2.alignment_start(), Position::new(8));
+        assert_eq!(
+            read_2.data().get(&Tag::MATE_CIGAR),
+            Some(&Value::from("4M"))
+        );
+
+        assert!(read_1.flags().is_mate_unmapped());
+        assert!(!read_1.flags().is_properly_segmented());
+        assert_eq!(read_1.mate_alignment_start(), Position::new(8));
+        assert_eq!(read_1.template_length(), 0);
+        assert!(read_1.data().get(&Tag::MATE_CIGAR).is_none());
+
+        Ok(())
+    }
+}
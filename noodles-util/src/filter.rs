@@ -0,0 +1,79 @@
+//! Record filter expressions.
+//!
+//! A filter expression is a small language to select records, e.g., `QUAL > 30 && INFO/DP > 10`
+//! for variant records or `flag & 0x4 == 0 && [NM] < 5` for alignment records. An expression is
+//! compiled once against a header, and the fields it references are read from each record as it
+//! is evaluated.
+//!
+//! Expressions support
+//!
+//!   * literals: integers (e.g., `8`, `0x4`), floats (e.g., `0.5`, `1e-3`), strings (e.g.,
+//!     `"PASS"`), and missing (`.`);
+//!   * fields, which are defined by the record format;
+//!   * logical operators (`||`, `&&`, `!`);
+//!   * comparison operators (`==`, `!=`, `<`, `<=`, `>`, `>=`);
+//!   * bitwise operators (`|`, `&`); and
+//!   * arithmetic operators (`+`, `-`, `*`, `/`).
+//!
+//! Bitwise operators have a higher precedence than comparison operators, i.e., `flag & 0x4 == 0`
+//! is `(flag & 0x4) == 0`. A comparison with a field with multiple values is true if any value
+//! satisfies it.
+//!
+//! Two missing values are equal, i.e., `. == .` is true and `. != .` is false. Otherwise, a
+//! comparison involving a missing value is false, except for `!=`, which is true.
+
+mod expression;
+mod lexer;
+mod parser;
+mod value;
+
+use std::{error, fmt};
+
+pub(crate) use self::{expression::Expression, value::Value};
+
+/// A field subscript.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Subscript {
+    /// All values (`[*]`).
+    All,
+    /// The value at the given index (`[n]`).
+    Index(usize),
+}
+
+/// An error returned when a filter expression fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input ended unexpectedly.
+    UnexpectedEof,
+    /// A character is unexpected.
+    UnexpectedCharacter(char),
+    /// A token is unexpected.
+    UnexpectedToken(String),
+    /// A number is invalid.
+    InvalidNumber(String),
+    /// A string is not terminated.
+    UnterminatedString,
+    /// A field subscript is invalid.
+    InvalidSubscript(String),
+    /// A field is invalid.
+    InvalidField(String),
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty input"),
+            Self::UnexpectedEof => write!(f, "unexpected end of input"),
+            Self::UnexpectedCharacter(c) => write!(f, "unexpected character: {c:?}"),
+            Self::UnexpectedToken(s) => write!(f, "unexpected token: {s}"),
+            Self::InvalidNumber(s) => write!(f, "invalid number: {s}"),
+            Self::UnterminatedString => write!(f, "unterminated string"),
+            Self::InvalidSubscript(name) => write!(f, "invalid subscript for field: {name}"),
+            Self::InvalidField(name) => write!(f, "invalid field: {name}"),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::io;
+
+use super::{
+    ParseError, Subscript, Value,
+    parser::parse,
+    value::{ArithmeticOperator, ComparisonOperator},
+};
+
+/// A compiled filter expression.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Expression<F> {
+    Literal(Value),
+    Field(F),
+    Not(Box<Self>),
+    Negate(Box<Self>),
+    Or(Box<Self>, Box<Self>),
+    And(Box<Self>, Box<Self>),
+    Comparison(ComparisonOperator, Box<Self>, Box<Self>),
+    Arithmetic(ArithmeticOperator, Box<Self>, Box<Self>),
+}
+
+impl<F> Expression<F> {
+    /// Parses and compiles a filter expression.
+    ///
+    /// `resolve` compiles a field, given its name and optional subscript.
+    pub(crate) fn parse<G>(s: &str, resolve: G) -> Result<Self, ParseError>
+    where
+        G: FnMut(&str, Option<Subscript>) -> Result<F, ParseError>,
+    {
+        parse(s, resolve)
+    }
+
+    /// Returns whether the expression is true.
+    ///
+    /// `get` reads the value of a field.
+    pub(crate) fn matches<G>(&self, get: &mut G) -> io::Result<bool>
+    where
+        G: FnMut(&F) -> io::Result<Value>,
+    {
+        self.evaluate(get).map(|value| value.is_truthy())
+    }
+
+    fn evaluate<G>(&self, get: &mut G) -> io::Result<Value>
+    where
+        G: FnMut(&F) -> io::Result<Value>,
+    {
+        match self {
+            Self::Literal(value) => Ok(value.clone()),
+            Self::Field(field) => get(field),
+            Self::Not(expr) => expr.matches(get).map(|b| Value::Boolean(!b)),
+            Self::Negate(expr) => expr.evaluate(get).map(|value| value.negate()),
+            Self::Or(lhs, rhs) => {
+                let b = lhs.matches(get)? || rhs.matches(get)?;
+                Ok(Value::Boolean(b))
+            }
+            Self::And(lhs, rhs) => {
+                let b = lhs.matches(get)? && rhs.matches(get)?;
+                Ok(Value::Boolean(b))
+            }
+            Self::Comparison(operator, lhs, rhs) => {
+                let a = lhs.evaluate(get)?;
+                let b = rhs.evaluate(get)?;
+                Ok(Value::Boolean(a.compare(*operator, &b)))
+            }
+            Self::Arithmetic(operator, lhs, rhs) => {
+                let a = lhs.evaluate(get)?;
+                let b = rhs.evaluate(get)?;
+                Ok(a.apply(*operator, &b))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(name: &str, _: Option<Subscript>) -> Result<String, ParseError> {
+        Ok(name.into())
+    }
+
+    #[test]
+    fn test_matches() -> Result<(), Box<dyn std::error::Error>> {
+        let mut get = |name: &String| -> io::Result<Value> {
+            match name.as_str() {
+                "flag" => Ok(Value::Integer(0x14)),
+                "dp" => Ok(Value::Integer(8)),
+                "af" => Ok(Value::Array(vec![Value::Float(0.25), Value::Float(0.5)])),
+                _ => Ok(Value::Missing),
+            }
+        };
+
+        let cases = [
+            ("flag & 0x4 == 0x4", true),
+            ("flag & 0x4 == 0", false),
+            ("dp > 5 && af > 0.4", true),
+            ("dp > 5 && af > 0.5", false),
+            ("dp < 5 || af >= 0.5", true),
+            ("!(dp == 8)", false),
+            ("dp * 2 - 1 == 15", true),
+            ("-dp < 0", true),
+            ("qual > 30", false),
+            ("qual == .", true),
+            ("qual", false),
+            ("dp", true),
+        ];
+
+        for (s, expected) in cases {
+            let expression = Expression::parse(s, resolve)?;
+            assert_eq!(expression.matches(&mut get)?, expected, "{s}");
+        }
+
+        Ok(())
+    }
+}
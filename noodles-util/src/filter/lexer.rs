@@ -0,0 +1,313 @@
+use std::{fmt, iter::Peekable, str::CharIndices};
+
+use super::{ParseError, Subscript};
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Token {
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Missing,
+    Field(String, Option<Subscript>),
+    LeftParenthesis,
+    RightParenthesis,
+    Or,
+    And,
+    Not,
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    BitOr,
+    BitAnd,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(n) => write!(f, "{n}"),
+            Self::Float(n) => write!(f, "{n}"),
+            Self::String(s) => write!(f, "{s:?}"),
+            Self::Missing => f.write_str("."),
+            Self::Field(name, None) => f.write_str(name),
+            Self::Field(name, Some(Subscript::All)) => write!(f, "{name}[*]"),
+            Self::Field(name, Some(Subscript::Index(i))) => write!(f, "{name}[{i}]"),
+            Self::LeftParenthesis => f.write_str("("),
+            Self::RightParenthesis => f.write_str(")"),
+            Self::Or => f.write_str("||"),
+            Self::And => f.write_str("&&"),
+            Self::Not => f.write_str("!"),
+            Self::Equal => f.write_str("=="),
+            Self::NotEqual => f.write_str("!="),
+            Self::LessThan => f.write_str("<"),
+            Self::LessThanOrEqual => f.write_str("<="),
+            Self::GreaterThan => f.write_str(">"),
+            Self::GreaterThanOrEqual => f.write_str(">="),
+            Self::BitOr => f.write_str("|"),
+            Self::BitAnd => f.write_str("&"),
+            Self::Add => f.write_str("+"),
+            Self::Subtract => f.write_str("-"),
+            Self::Multiply => f.write_str("*"),
+            Self::Divide => f.write_str("/"),
+        }
+    }
+}
+
+pub(super) fn tokenize(s: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+
+    while let Some(&(i, c)) = chars.peek() {
+        let token = match c {
+            _ if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '(' => single(&mut chars, Token::LeftParenthesis),
+            ')' => single(&mut chars, Token::RightParenthesis),
+            '+' => single(&mut chars, Token::Add),
+            '-' => single(&mut chars, Token::Subtract),
+            '*' => single(&mut chars, Token::Multiply),
+            '/' => single(&mut chars, Token::Divide),
+            '|' => double(&mut chars, '|', Token::Or, Token::BitOr),
+            '&' => double(&mut chars, '&', Token::And, Token::BitAnd),
+            '!' => double(&mut chars, '=', Token::NotEqual, Token::Not),
+            '=' => double(&mut chars, '=', Token::Equal, Token::Equal),
+            '<' => double(&mut chars, '=', Token::LessThanOrEqual, Token::LessThan),
+            '>' => double(
+                &mut chars,
+                '=',
+                Token::GreaterThanOrEqual,
+                Token::GreaterThan,
+            ),
+            '"' | '\'' => read_string(&mut chars)?,
+            '[' => read_tag(s, &mut chars)?,
+            '.' if !next_is_digit(s, i + 1) => single(&mut chars, Token::Missing),
+            _ if c.is_ascii_digit() || c == '.' => read_number(s, &mut chars)?,
+            _ if c.is_ascii_alphabetic() || c == '_' => read_field(s, &mut chars)?,
+            _ => return Err(ParseError::UnexpectedCharacter(c)),
+        };
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+fn single(chars: &mut Peekable<CharIndices<'_>>, token: Token) -> Token {
+    chars.next();
+    token
+}
+
+fn double(
+    chars: &mut Peekable<CharIndices<'_>>,
+    next: char,
+    double_token: Token,
+    single_token: Token,
+) -> Token {
+    chars.next();
+
+    if chars.next_if(|&(_, c)| c == next).is_some() {
+        double_token
+    } else {
+        single_token
+    }
+}
+
+fn next_is_digit(s: &str, i: usize) -> bool {
+    s[i..].starts_with(|c: char| c.is_ascii_digit())
+}
+
+fn read_while<F>(s: &str, chars: &mut Peekable<CharIndices<'_>>, mut f: F) -> String
+where
+    F: FnMut(char) -> bool,
+{
+    let start = chars.peek().map(|&(i, _)| i).unwrap_or(s.len());
+    let mut end = start;
+
+    while let Some((i, c)) = chars.next_if(|&(_, c)| f(c)) {
+        end = i + c.len_utf8();
+    }
+
+    s[start..end].into()
+}
+
+fn read_string(chars: &mut Peekable<CharIndices<'_>>) -> Result<Token, ParseError> {
+    let Some((_, quote)) = chars.next() else {
+        return Err(ParseError::UnexpectedEof);
+    };
+
+    let mut buf = String::new();
+
+    for (_, c) in chars.by_ref() {
+        if c == quote {
+            return Ok(Token::String(buf));
+        }
+
+        buf.push(c);
+    }
+
+    Err(ParseError::UnterminatedString)
+}
+
+fn read_number(s: &str, chars: &mut Peekable<CharIndices<'_>>) -> Result<Token, ParseError> {
+    let mut prev = '\0';
+
+    let raw_value = read_while(s, chars, |c| {
+        let is_number_char = c.is_ascii_alphanumeric()
+            || c == '.'
+            || (matches!(c, '+' | '-') && matches!(prev, 'e' | 'E'));
+
+        prev = c;
+        is_number_char
+    });
+
+    if let Some(hex) = raw_value
+        .strip_prefix("0x")
+        .or_else(|| raw_value.strip_prefix("0X"))
+    {
+        return i64::from_str_radix(hex, 16)
+            .map(Token::Integer)
+            .map_err(|_| ParseError::InvalidNumber(raw_value.clone()));
+    }
+
+    if let Ok(n) = raw_value.parse() {
+        Ok(Token::Integer(n))
+    } else {
+        raw_value
+            .parse()
+            .map(Token::Float)
+            .map_err(|_| ParseError::InvalidNumber(raw_value))
+    }
+}
+
+fn read_field(s: &str, chars: &mut Peekable<CharIndices<'_>>) -> Result<Token, ParseError> {
+    let name = read_while(s, chars, |c| {
+        c.is_ascii_alphanumeric() || matches!(c, '_' | '/')
+    });
+    let subscript = read_subscript(s, chars, &name)?;
+    Ok(Token::Field(name, subscript))
+}
+
+fn read_tag(s: &str, chars: &mut Peekable<CharIndices<'_>>) -> Result<Token, ParseError> {
+    chars.next();
+
+    let tag = read_while(s, chars, |c| c != ']');
+
+    if chars.next().is_none() {
+        return Err(ParseError::UnexpectedEof);
+    }
+
+    let name = format!("[{tag}]");
+    let subscript = read_subscript(s, chars, &name)?;
+
+    Ok(Token::Field(name, subscript))
+}
+
+fn read_subscript(
+    s: &str,
+    chars: &mut Peekable<CharIndices<'_>>,
+    name: &str,
+) -> Result<Option<Subscript>, ParseError> {
+    if chars.next_if(|&(_, c)| c == '[').is_none() {
+        return Ok(None);
+    }
+
+    let raw_subscript = read_while(s, chars, |c| c != ']');
+
+    if chars.next().is_none() {
+        return Err(ParseError::UnexpectedEof);
+    }
+
+    match raw_subscript.trim() {
+        "*" => Ok(Some(Subscript::All)),
+        t => t
+            .parse()
+            .map(|i| Some(Subscript::Index(i)))
+            .map_err(|_| ParseError::InvalidSubscript(name.into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() -> Result<(), ParseError> {
+        assert_eq!(
+            tokenize("QUAL>=30.5 && FMT/GQ[*] != . || !INFO/DB")?,
+            [
+                Token::Field(String::from("QUAL"), None),
+                Token::GreaterThanOrEqual,
+                Token::Float(30.5),
+                Token::And,
+                Token::Field(String::from("FMT/GQ"), Some(Subscript::All)),
+                Token::NotEqual,
+                Token::Missing,
+                Token::Or,
+                Token::Not,
+                Token::Field(String::from("INFO/DB"), None),
+            ]
+        );
+
+        assert_eq!(
+            tokenize("flag & 0x4 == 0 && [NM] < 5 && qname = 'r0'")?,
+            [
+                Token::Field(String::from("flag"), None),
+                Token::BitAnd,
+                Token::Integer(4),
+                Token::Equal,
+                Token::Integer(0),
+                Token::And,
+                Token::Field(String::from("[NM]"), None),
+                Token::LessThan,
+                Token::Integer(5),
+                Token::And,
+                Token::Field(String::from("qname"), None),
+                Token::Equal,
+                Token::String(String::from("r0")),
+            ]
+        );
+
+        assert_eq!(tokenize("1e-3")?, [Token::Float(1e-3)]);
+        assert_eq!(tokenize(".5")?, [Token::Float(0.5)]);
+
+        assert_eq!(
+            tokenize("[ZC][0]")?,
+            [Token::Field(
+                String::from("[ZC]"),
+                Some(Subscript::Index(0))
+            )]
+        );
+
+        assert_eq!(
+            tokenize("INFO/AF[1]")?,
+            [Token::Field(
+                String::from("INFO/AF"),
+                Some(Subscript::Index(1))
+            )]
+        );
+
+        assert_eq!(tokenize("\"PASS"), Err(ParseError::UnterminatedString));
+        assert_eq!(
+            tokenize("8x"),
+            Err(ParseError::InvalidNumber(String::from("8x")))
+        );
+        assert_eq!(
+            tokenize("FMT/GQ[a]"),
+            Err(ParseError::InvalidSubscript(String::from("FMT/GQ")))
+        );
+        assert_eq!(
+            tokenize("QUAL ~ 8"),
+            Err(ParseError::UnexpectedCharacter('~'))
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,255 @@
+use std::{iter::Peekable, vec};
+
+use super::{
+    Expression, ParseError, Subscript, Value,
+    lexer::{Token, tokenize},
+    value::{ArithmeticOperator, ComparisonOperator},
+};
+
+struct Parser<G> {
+    tokens: Peekable<vec::IntoIter<Token>>,
+    resolve: G,
+}
+
+pub(super) fn parse<F, G>(s: &str, resolve: G) -> Result<Expression<F>, ParseError>
+where
+    G: FnMut(&str, Option<Subscript>) -> Result<F, ParseError>,
+{
+    let tokens = tokenize(s)?;
+
+    if tokens.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let mut parser = Parser {
+        tokens: tokens.into_iter().peekable(),
+        resolve,
+    };
+
+    let expression = parser.parse_or()?;
+
+    match parser.tokens.next() {
+        Some(token) => Err(ParseError::UnexpectedToken(token.to_string())),
+        None => Ok(expression),
+    }
+}
+
+impl<F, G> Parser<G>
+where
+    G: FnMut(&str, Option<Subscript>) -> Result<F, ParseError>,
+{
+    fn parse_or(&mut self) -> Result<Expression<F>, ParseError> {
+        let mut lhs = self.parse_and()?;
+
+        while self.tokens.next_if_eq(&Token::Or).is_some() {
+            let rhs = self.parse_and()?;
+            lhs = Expression::Or(Box::new(lhs), Box::new(rhs));
+        }
+
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expression<F>, ParseError> {
+        let mut lhs = self.parse_comparison()?;
+
+        while self.tokens.next_if_eq(&Token::And).is_some() {
+            let rhs = self.parse_comparison()?;
+            lhs = Expression::And(Box::new(lhs), Box::new(rhs));
+        }
+
+        Ok(lhs)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expression<F>, ParseError> {
+        let lhs = self.parse_bit_or()?;
+
+        let operator = match self.tokens.peek() {
+            Some(Token::Equal) => ComparisonOperator::Equal,
+            Some(Token::NotEqual) => ComparisonOperator::NotEqual,
+            Some(Token::LessThan) => ComparisonOperator::LessThan,
+            Some(Token::LessThanOrEqual) => ComparisonOperator::LessThanOrEqual,
+            Some(Token::GreaterThan) => ComparisonOperator::GreaterThan,
+            Some(Token::GreaterThanOrEqual) => ComparisonOperator::GreaterThanOrEqual,
+            _ => return Ok(lhs),
+        };
+
+        self.tokens.next();
+
+        let rhs = self.parse_bit_or()?;
+
+        Ok(Expression::Comparison(
+            operator,
+            Box::new(lhs),
+            Box::new(rhs),
+        ))
+    }
+
+    fn parse_bit_or(&mut self) -> Result<Expression<F>, ParseError> {
+        let mut lhs = self.parse_bit_and()?;
+
+        while self.tokens.next_if_eq(&Token::BitOr).is_some() {
+            let rhs = self.parse_bit_and()?;
+            lhs = arithmetic(ArithmeticOperator::BitOr, lhs, rhs);
+        }
+
+        Ok(lhs)
+    }
+
+    fn parse_bit_and(&mut self) -> Result<Expression<F>, ParseError> {
+        let mut lhs = self.parse_sum()?;
+
+        while self.tokens.next_if_eq(&Token::BitAnd).is_some() {
+            let rhs = self.parse_sum()?;
+            lhs = arithmetic(ArithmeticOperator::BitAnd, lhs, rhs);
+        }
+
+        Ok(lhs)
+    }
+
+    fn parse_sum(&mut self) -> Result<Expression<F>, ParseError> {
+        let mut lhs = self.parse_product()?;
+
+        loop {
+            let operator = match self.tokens.peek() {
+                Some(Token::Add) => ArithmeticOperator::Add,
+                Some(Token::Subtract) => ArithmeticOperator::Subtract,
+                _ => return Ok(lhs),
+            };
+
+            self.tokens.next();
+
+            let rhs = self.parse_product()?;
+            lhs = arithmetic(operator, lhs, rhs);
+        }
+    }
+
+    fn parse_product(&mut self) -> Result<Expression<F>, ParseError> {
+        let mut lhs = self.parse_unary()?;
+
+        loop {
+            let operator = match self.tokens.peek() {
+                Some(Token::Multiply) => ArithmeticOperator::Multiply,
+                Some(Token::Divide) => ArithmeticOperator::Divide,
+                _ => return Ok(lhs),
+            };
+
+            self.tokens.next();
+
+            let rhs = self.parse_unary()?;
+            lhs = arithmetic(operator, lhs, rhs);
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression<F>, ParseError> {
+        if self.tokens.next_if_eq(&Token::Not).is_some() {
+            let expression = self.parse_unary()?;
+            Ok(Expression::Not(Box::new(expression)))
+        } else if self.tokens.next_if_eq(&Token::Subtract).is_some() {
+            let expression = self.parse_unary()?;
+            Ok(Expression::Negate(Box::new(expression)))
+        } else {
+            self.parse_primary()
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression<F>, ParseError> {
+        match self.tokens.next() {
+            Some(Token::Integer(n)) => Ok(Expression::Literal(Value::Integer(n))),
+            Some(Token::Float(n)) => Ok(Expression::Literal(Value::Float(n))),
+            Some(Token::String(s)) => Ok(Expression::Literal(Value::String(s))),
+            Some(Token::Missing) => Ok(Expression::Literal(Value::Missing)),
+            Some(Token::Field(name, subscript)) => {
+                (self.resolve)(&name, subscript).map(Expression::Field)
+            }
+            Some(Token::LeftParenthesis) => {
+                let expression = self.parse_or()?;
+
+                match self.tokens.next() {
+                    Some(Token::RightParenthesis) => Ok(expression),
+                    Some(token) => Err(ParseError::UnexpectedToken(token.to_string())),
+                    None => Err(ParseError::UnexpectedEof),
+                }
+            }
+            Some(token) => Err(ParseError::UnexpectedToken(token.to_string())),
+            None => Err(ParseError::UnexpectedEof),
+        }
+    }
+}
+
+fn arithmetic<F>(
+    operator: ArithmeticOperator,
+    lhs: Expression<F>,
+    rhs: Expression<F>,
+) -> Expression<F> {
+    Expression::Arithmetic(operator, Box::new(lhs), Box::new(rhs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(name: &str, _: Option<Subscript>) -> Result<String, ParseError> {
+        match name {
+            "flag" | "qual" => Ok(name.into()),
+            _ => Err(ParseError::InvalidField(name.into())),
+        }
+    }
+
+    fn field(name: &str) -> Box<Expression<String>> {
+        Box::new(Expression::Field(name.into()))
+    }
+
+    fn literal(n: i64) -> Box<Expression<String>> {
+        Box::new(Expression::Literal(Value::Integer(n)))
+    }
+
+    #[test]
+    fn test_parse() -> Result<(), ParseError> {
+        assert_eq!(
+            parse("flag & 0x4 == 0 || qual > 30", resolve)?,
+            Expression::Or(
+                Box::new(Expression::Comparison(
+                    ComparisonOperator::Equal,
+                    Box::new(Expression::Arithmetic(
+                        ArithmeticOperator::BitAnd,
+                        field("flag"),
+                        literal(4),
+                    )),
+                    literal(0),
+                )),
+                Box::new(Expression::Comparison(
+                    ComparisonOperator::GreaterThan,
+                    field("qual"),
+                    literal(30),
+                )),
+            )
+        );
+
+        assert_eq!(
+            parse("1 + 2 * 3", resolve)?,
+            Expression::Arithmetic(
+                ArithmeticOperator::Add,
+                literal(1),
+                Box::new(Expression::Arithmetic(
+                    ArithmeticOperator::Multiply,
+                    literal(2),
+                    literal(3),
+                )),
+            )
+        );
+
+        assert_eq!(parse("", resolve), Err(ParseError::Empty));
+        assert_eq!(parse("(qual > 30", resolve), Err(ParseError::UnexpectedEof));
+        assert_eq!(parse("qual >", resolve), Err(ParseError::UnexpectedEof));
+        assert!(matches!(
+            parse("qual 30", resolve),
+            Err(ParseError::UnexpectedToken(_))
+        ));
+        assert_eq!(
+            parse("mapq > 30", resolve),
+            Err(ParseError::InvalidField(String::from("mapq")))
+        );
+
+        Ok(())
+    }
+}
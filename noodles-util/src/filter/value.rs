@@ -0,0 +1,201 @@
+use std::cmp::Ordering;
+
+/// A filter expression value.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    Missing,
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Value>),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ComparisonOperator {
+    Equal,
+    NotEqual,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+impl ComparisonOperator {
+    fn test(self, ordering: Ordering) -> bool {
+        match self {
+            Self::Equal => ordering.is_eq(),
+            Self::NotEqual => ordering.is_ne(),
+            Self::LessThan => ordering.is_lt(),
+            Self::LessThanOrEqual => ordering.is_le(),
+            Self::GreaterThan => ordering.is_gt(),
+            Self::GreaterThanOrEqual => ordering.is_ge(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ArithmeticOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    BitOr,
+    BitAnd,
+}
+
+impl Value {
+    /// Creates an array value, or a missing value if there are no values.
+    pub(crate) fn from_values(values: Vec<Self>) -> Self {
+        if values.is_empty() {
+            Self::Missing
+        } else {
+            Self::Array(values)
+        }
+    }
+
+    pub(super) fn is_truthy(&self) -> bool {
+        match self {
+            Self::Missing => false,
+            Self::Boolean(b) => *b,
+            Self::Integer(n) => *n != 0,
+            Self::Float(n) => *n != 0.0,
+            Self::String(s) => !s.is_empty(),
+            Self::Array(values) => values.iter().any(Self::is_truthy),
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Boolean(b) => Some(f64::from(u8::from(*b))),
+            Self::Integer(n) => Some(*n as f64),
+            Self::Float(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub(super) fn compare(&self, operator: ComparisonOperator, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Array(values), _) => values.iter().any(|v| v.compare(operator, other)),
+            (_, Self::Array(values)) => values.iter().any(|v| self.compare(operator, v)),
+            (Self::Missing, Self::Missing) => operator == ComparisonOperator::Equal,
+            (Self::String(a), Self::String(b)) => operator.test(a.cmp(b)),
+            (Self::Integer(a), Self::Integer(b)) => operator.test(a.cmp(b)),
+            _ => match (self.as_f64(), other.as_f64()) {
+                (Some(a), Some(b)) => a
+                    .partial_cmp(&b)
+                    .is_some_and(|ordering| operator.test(ordering)),
+                _ => operator == ComparisonOperator::NotEqual,
+            },
+        }
+    }
+
+    pub(super) fn apply(&self, operator: ArithmeticOperator, other: &Self) -> Self {
+        match (self, other) {
+            (Self::Array(values), _) => {
+                Self::Array(values.iter().map(|v| v.apply(operator, other)).collect())
+            }
+            (_, Self::Array(values)) => {
+                Self::Array(values.iter().map(|v| self.apply(operator, v)).collect())
+            }
+            (Self::Integer(a), Self::Integer(b)) => {
+                let value = match operator {
+                    ArithmeticOperator::Add => a.checked_add(*b),
+                    ArithmeticOperator::Subtract => a.checked_sub(*b),
+                    ArithmeticOperator::Multiply => a.checked_mul(*b),
+                    ArithmeticOperator::Divide => return divide(*a as f64, *b as f64),
+                    ArithmeticOperator::BitOr => Some(a | b),
+                    ArithmeticOperator::BitAnd => Some(a & b),
+                };
+
+                value.map(Self::Integer).unwrap_or(Self::Missing)
+            }
+            _ => match (self.as_f64(), other.as_f64()) {
+                (Some(a), Some(b)) => match operator {
+                    ArithmeticOperator::Add => Self::Float(a + b),
+                    ArithmeticOperator::Subtract => Self::Float(a - b),
+                    ArithmeticOperator::Multiply => Self::Float(a * b),
+                    ArithmeticOperator::Divide => divide(a, b),
+                    ArithmeticOperator::BitOr | ArithmeticOperator::BitAnd => Self::Missing,
+                },
+                _ => Self::Missing,
+            },
+        }
+    }
+
+    pub(super) fn negate(&self) -> Self {
+        match self {
+            Self::Integer(n) => n.checked_neg().map(Self::Integer).unwrap_or(Self::Missing),
+            Self::Float(n) => Self::Float(-n),
+            Self::Array(values) => Self::Array(values.iter().map(Self::negate).collect()),
+            _ => Self::Missing,
+        }
+    }
+}
+
+fn divide(a: f64, b: f64) -> Value {
+    if b == 0.0 {
+        Value::Missing
+    } else {
+        Value::Float(a / b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare() {
+        use ComparisonOperator::{Equal, GreaterThan, LessThan, NotEqual};
+
+        assert!(Value::Integer(8).compare(GreaterThan, &Value::Integer(5)));
+        assert!(Value::Integer(8).compare(LessThan, &Value::Float(8.5)));
+        assert!(Value::String(String::from("PASS")).compare(Equal, &Value::String("PASS".into())));
+
+        let values = Value::Array(vec![Value::Integer(5), Value::Missing, Value::Integer(13)]);
+        assert!(values.compare(GreaterThan, &Value::Integer(8)));
+        assert!(!values.compare(GreaterThan, &Value::Integer(13)));
+
+        assert!(!Value::Missing.compare(GreaterThan, &Value::Integer(0)));
+        assert!(!Value::Missing.compare(LessThan, &Value::Integer(0)));
+        assert!(Value::Missing.compare(NotEqual, &Value::Integer(0)));
+        assert!(Value::Missing.compare(Equal, &Value::Missing));
+
+        assert!(!Value::String(String::from("8")).compare(Equal, &Value::Integer(8)));
+    }
+
+    #[test]
+    fn test_apply() {
+        use ArithmeticOperator::{Add, BitAnd, Divide};
+
+        assert_eq!(
+            Value::Integer(8).apply(Add, &Value::Integer(5)),
+            Value::Integer(13)
+        );
+        assert_eq!(
+            Value::Integer(8).apply(Divide, &Value::Integer(5)),
+            Value::Float(1.6)
+        );
+        assert_eq!(
+            Value::Integer(8).apply(Divide, &Value::Integer(0)),
+            Value::Missing
+        );
+        assert_eq!(
+            Value::Integer(0x14).apply(BitAnd, &Value::Integer(0x4)),
+            Value::Integer(0x4)
+        );
+        assert_eq!(
+            Value::Float(0.5).apply(BitAnd, &Value::Integer(1)),
+            Value::Missing
+        );
+        assert_eq!(
+            Value::Missing.apply(Add, &Value::Integer(1)),
+            Value::Missing
+        );
+        assert_eq!(
+            Value::Array(vec![Value::Integer(1), Value::Integer(2)]).apply(Add, &Value::Integer(1)),
+            Value::Array(vec![Value::Integer(2), Value::Integer(3)])
+        );
+    }
+}
@@ -4,5 +4,8 @@
 #[cfg(feature = "alignment")]
 pub mod alignment;
 
+#[cfg(any(feature = "alignment", feature = "variant"))]
+pub mod filter;
+
 #[cfg(feature = "variant")]
 pub mod variant;
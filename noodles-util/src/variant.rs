@@ -3,7 +3,10 @@
 #[cfg(feature = "async")]
 pub mod r#async;
 
+pub mod annotate;
+pub mod filter;
 pub mod io;
+pub mod merge;
 mod record;
 
 pub use self::record::Record;
@@ -0,0 +1,556 @@
+//! Variant record annotation.
+//!
+//! An [`Annotator`] copies annotations from an indexed annotation source into variant records,
+//! like `bcftools annotate --annotations`. The source is either an indexed VCF or BCF, or a
+//! bgzipped BED with a tabix or CSI index.
+//!
+//! The source is read in windows of 16384 positions using index queries, starting at the first
+//! record that is not in the current window. Records sorted by position are therefore annotated
+//! with about one query per window rather than one per record. Unsorted records are annotated
+//! correctly but may need a query each.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::io::{self, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_core::{Position, Region};
+use noodles_csi::{self as csi, BinningIndex, io::IndexedRecord};
+use noodles_vcf::{
+    self as vcf,
+    header::record::value::{
+        Map,
+        map::{Info, info::Number, info::Type},
+    },
+    variant::{Record as _, RecordBuf, record_buf::info::field::Value},
+};
+
+use crate::variant::io::IndexedReader;
+
+const BED_NAME_INDEX: usize = 3;
+
+// The number of positions read from the source by each query.
+const WINDOW_SIZE: usize = 1 << 14;
+
+/// An annotation column.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Column {
+    /// The variant IDs (`ID`).
+    ///
+    /// For BED sources, this is the feature name.
+    Id,
+    /// An INFO field.
+    ///
+    /// For BED sources, this is set to the feature name.
+    Info(String),
+}
+
+/// A strategy to match annotation records to a variant record.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Match {
+    /// The start position, reference bases, and alternate bases are equal.
+    #[default]
+    Alleles,
+    /// The start position is equal.
+    Position,
+    /// The intervals overlap.
+    Overlap,
+}
+
+enum Source<R> {
+    Variant {
+        reader: IndexedReader<bgzf::io::Reader<R>>,
+        header: Box<vcf::Header>,
+    },
+    Bed(csi::io::IndexedReader<bgzf::io::Reader<R>, Box<dyn BinningIndex>>),
+}
+
+enum Annotation {
+    Variant(Box<RecordBuf>),
+    Bed(Option<String>),
+}
+
+// The annotation records that intersect an interval of a reference sequence.
+struct Window {
+    reference_sequence_name: String,
+    start: Position,
+    end: Position,
+    annotations: Vec<(Position, Position, Annotation)>,
+}
+
+impl Window {
+    fn contains(&self, reference_sequence_name: &str, start: Position, end: Position) -> bool {
+        self.reference_sequence_name == reference_sequence_name
+            && self.start <= start
+            && end <= self.end
+    }
+}
+
+/// A variant annotator.
+pub struct Annotator<R> {
+    source: Source<R>,
+    columns: Vec<Column>,
+    r#match: Match,
+    window: Option<Window>,
+}
+
+impl<R> Annotator<R>
+where
+    R: Read + Seek,
+{
+    /// Adds the header records of the annotation columns to a header.
+    ///
+    /// For variant sources, INFO definitions are copied from the source header. For BED sources,
+    /// missing INFO definitions are added as a single string.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if an INFO field is not defined in the header of a variant source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// use noodles_util::variant::annotate::{self, Column};
+    /// use noodles_vcf as vcf;
+    ///
+    /// let annotator = annotate::Builder::default()
+    ///     .add_column(Column::Info(String::from("GENE")))
+    ///     .build_from_bed_reader(std::io::Cursor::new(Vec::new()), csi::Index::default());
+    ///
+    /// let mut header = vcf::Header::default();
+    /// annotator.annotate_header(&mut header)?;
+    ///
+    /// assert!(header.infos().contains_key("GENE"));
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn annotate_header(&self, header: &mut vcf::Header) -> io::Result<()> {
+        for column in &self.columns {
+            let Column::Info(key) = column else {
+                continue;
+            };
+
+            match &self.source {
+                Source::Variant {
+                    header: source_header,
+                    ..
+                } => {
+                    let info = source_header.infos().get(key).ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("missing INFO header definition in source: {key}"),
+                        )
+                    })?;
+
+                    header.infos_mut().insert(key.clone(), info.clone());
+                }
+                Source::Bed(_) => {
+                    if !header.infos().contains_key(key) {
+                        let info = Map::<Info>::new(Number::Count(1), Type::String, "BED name");
+                        header.infos_mut().insert(key.clone(), info);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Annotates a variant record.
+    ///
+    /// The first matching annotation record is used. For variant sources, annotation columns
+    /// missing in the annotation record leave the variant record unchanged. BED sources always
+    /// match by overlap.
+    ///
+    /// This returns whether a matching annotation record was found. Records with a missing start
+    /// position or a reference sequence that is not in the source are not annotated.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the source fails to be queried or an annotation record is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_csi as csi;
+    /// use noodles_util::variant::annotate::{self, Column};
+    /// use noodles_vcf::{self as vcf, variant::RecordBuf};
+    ///
+    /// let mut annotator = annotate::Builder::default()
+    ///     .add_column(Column::Id)
+    ///     .build_from_bed_reader(std::io::Cursor::new(Vec::new()), csi::Index::default());
+    ///
+    /// let header = vcf::Header::default();
+    /// let mut record = RecordBuf::default();
+    /// assert!(!annotator.annotate(&header, &mut record)?);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn annotate(&mut self, header: &vcf::Header, record: &mut RecordBuf) -> io::Result<bool> {
+        let Some(start) = record.variant_start() else {
+            return Ok(false);
+        };
+
+        let r#match = match self.source {
+            Source::Variant { .. } => self.r#match,
+            Source::Bed(_) => Match::Overlap,
+        };
+
+        let end = match r#match {
+            Match::Overlap => record.variant_end(header)?,
+            Match::Alleles | Match::Position => start,
+        };
+
+        let name = record.reference_sequence_name();
+
+        if !self.contains_reference_sequence(name) {
+            return Ok(false);
+        }
+
+        let window = match self.window.take() {
+            Some(window) if window.contains(name, start, end) => window,
+            _ => self.read_window(name, start, end)?,
+        };
+
+        let window = self.window.insert(window);
+
+        let mut annotations = window
+            .annotations
+            .iter()
+            .filter(|(annotation_start, annotation_end, _)| {
+                *annotation_start <= end && start <= *annotation_end
+            })
+            .map(|(_, _, annotation)| annotation);
+
+        let annotation = match r#match {
+            Match::Alleles => annotations.find(|annotation| {
+                matches!(
+                    annotation,
+                    Annotation::Variant(annotation)
+                        if annotation.variant_start() == Some(start)
+                            && annotation.reference_bases() == record.reference_bases()
+                            && annotation.alternate_bases() == record.alternate_bases()
+                )
+            }),
+            Match::Position => annotations.find(|annotation| {
+                matches!(
+                    annotation,
+                    Annotation::Variant(annotation) if annotation.variant_start() == Some(start)
+                )
+            }),
+            Match::Overlap => annotations.next(),
+        };
+
+        match annotation {
+            Some(Annotation::Variant(annotation)) => {
+                for column in &self.columns {
+                    match column {
+                        Column::Id => {
+                            if !annotation.ids().as_ref().is_empty() {
+                                *record.ids_mut() = annotation.ids().clone();
+                            }
+                        }
+                        Column::Info(key) => {
+                            if let Some(value) = annotation.info().get(key) {
+                                record.info_mut().insert(key.clone(), value.cloned());
+                            }
+                        }
+                    }
+                }
+            }
+            Some(Annotation::Bed(Some(name))) => {
+                for column in &self.columns {
+                    match column {
+                        Column::Id => *record.ids_mut() = [name.clone()].into_iter().collect(),
+                        Column::Info(key) => {
+                            record
+                                .info_mut()
+                                .insert(key.clone(), Some(Value::String(name.clone())));
+                        }
+                    }
+                }
+            }
+            Some(Annotation::Bed(None)) | None => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn read_window(&mut self, name: &str, start: Position, end: Position) -> io::Result<Window> {
+        let window_end = start
+            .checked_add(WINDOW_SIZE - 1)
+            .map_or(end, |position| position.max(end));
+
+        let region = Region::new(name, start..=window_end);
+
+        let annotations = match &mut self.source {
+            Source::Variant { reader, header } => {
+                read_variant_annotations(reader, header, &region)?
+            }
+            Source::Bed(reader) => read_bed_annotations(reader, &region)?,
+        };
+
+        Ok(Window {
+            reference_sequence_name: name.into(),
+            start,
+            end: window_end,
+            annotations,
+        })
+    }
+
+    fn contains_reference_sequence(&self, name: &str) -> bool {
+        let (index, reference_sequence_id) = match &self.source {
+            Source::Variant {
+                reader: reader @ IndexedReader::Vcf(_),
+                ..
+            } => {
+                let index = reader.index();
+                let id = index.header().and_then(|header| {
+                    header
+                        .reference_sequence_names()
+                        .get_index_of(name.as_bytes())
+                });
+
+                (index, id)
+            }
+            Source::Variant {
+                reader: reader @ IndexedReader::Bcf(_),
+                header,
+            } => (
+                reader.index(),
+                header.string_maps().contigs().get_index_of(name),
+            ),
+            Source::Bed(reader) => {
+                let index: &dyn BinningIndex = reader.index();
+                let id = index.header().and_then(|header| {
+                    header
+                        .reference_sequence_names()
+                        .get_index_of(name.as_bytes())
+                });
+
+                (index, id)
+            }
+        };
+
+        reference_sequence_id.is_some_and(|i| i < index.reference_sequences().count())
+    }
+}
+
+fn read_variant_annotations<R>(
+    reader: &mut IndexedReader<bgzf::io::Reader<R>>,
+    header: &vcf::Header,
+    region: &Region,
+) -> io::Result<Vec<(Position, Position, Annotation)>>
+where
+    R: Read + Seek,
+{
+    let mut annotations = Vec::new();
+
+    for result in reader.query(header, region)? {
+        let record = result?;
+        let annotation = RecordBuf::try_from_variant_record(header, record.as_ref())?;
+
+        let Some(start) = annotation.variant_start() else {
+            continue;
+        };
+
+        let end = annotation.variant_end(header)?;
+
+        annotations.push((start, end, Annotation::Variant(Box::new(annotation))));
+    }
+
+    Ok(annotations)
+}
+
+fn read_bed_annotations<R>(
+    reader: &mut csi::io::IndexedReader<bgzf::io::Reader<R>, Box<dyn BinningIndex>>,
+    region: &Region,
+) -> io::Result<Vec<(Position, Position, Annotation)>>
+where
+    R: Read + Seek,
+{
+    let mut annotations = Vec::new();
+
+    for result in reader.query(region)? {
+        let record = result?;
+
+        let start = record.indexed_start_position();
+        let end = record.indexed_end_position();
+        let name = record
+            .as_ref()
+            .split('\t')
+            .nth(BED_NAME_INDEX)
+            .map(String::from);
+
+        annotations.push((start, end, Annotation::Bed(name)));
+    }
+
+    Ok(annotations)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use noodles_core::Position;
+    use noodles_csi::binning_index::{
+        Indexer,
+        index::{Header as IndexHeader, reference_sequence::bin::Chunk},
+    };
+    use noodles_vcf::variant::record::info::field::key;
+
+    use super::*;
+
+    fn build_indexed_data(
+        header: &str,
+        records: &[(&str, usize, usize, &str)],
+        index_header: IndexHeader,
+    ) -> io::Result<(Vec<u8>, csi::Index)> {
+        let mut writer = bgzf::io::Writer::new(Vec::new());
+        writer.write_all(header.as_bytes())?;
+        writer.flush()?;
+
+        let mut indexer = Indexer::new(14, 5).set_header(index_header);
+
+        for &(name, start, end, line) in records {
+            let start_position = writer.virtual_position();
+            writer.write_all(line.as_bytes())?;
+            writer.flush()?;
+            let end_position = writer.virtual_position();
+
+            let reference_sequence_id = match name {
+                "sq0" => 0,
+                _ => 1,
+            };
+
+            let start = Position::try_from(start)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            let end = Position::try_from(end)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+            indexer.add_record(
+                Some((reference_sequence_id, start, end, true)),
+                Chunk::new(start_position, end_position),
+            )?;
+        }
+
+        let data = writer.finish()?;
+        let index = indexer.build(2);
+
+        Ok((data, index))
+    }
+
+    fn build_record(
+        name: &str,
+        start: usize,
+        reference_bases: &str,
+        alternate_base: &str,
+    ) -> RecordBuf {
+        RecordBuf::builder()
+            .set_reference_sequence_name(name)
+            .set_variant_start(Position::try_from(start).unwrap())
+            .set_reference_bases(reference_bases)
+            .set_alternate_bases(vec![String::from(alternate_base)].into())
+            .build()
+    }
+
+    #[test]
+    fn test_annotate_with_variant_source() -> Result<(), Box<dyn std::error::Error>> {
+        const HEADER: &str = "##fileformat=VCFv4.5
+##INFO=<ID=AF,Number=A,Type=Float,Description=\"Allele frequency\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+";
+
+        let index_header = csi::binning_index::index::header::Builder::vcf()
+            .set_reference_sequence_names(["sq0", "sq1"].into_iter().map(Into::into).collect())
+            .build();
+
+        let (data, index) = build_indexed_data(
+            HEADER,
+            &[
+                ("sq0", 8, 8, "sq0\t8\trs8\tA\tC\t.\tPASS\tAF=0.25\n"),
+                ("sq0", 13, 13, "sq0\t13\trs13\tA\tG\t.\tPASS\tAF=0.5\n"),
+                ("sq0", 21, 21, "sq0\t21\t.\tA\tC\t.\tPASS\tAF=0.75\n"),
+            ],
+            index_header,
+        )?;
+
+        let reader = crate::variant::io::indexed_reader::Builder::default()
+            .set_index(index)
+            .build_from_reader(Cursor::new(data))?;
+
+        let mut annotator = Builder::default()
+            .add_column(Column::Id)
+            .add_column(Column::Info(String::from(key::ALLELE_FREQUENCIES)))
+            .build_from_variant_reader(reader)?;
+
+        let mut header = vcf::Header::default();
+        annotator.annotate_header(&mut header)?;
+        assert!(header.infos().contains_key(key::ALLELE_FREQUENCIES));
+
+        let mut record = build_record("sq0", 8, "A", "C");
+        assert!(annotator.annotate(&header, &mut record)?);
+        assert_eq!(record.ids().as_ref().iter().collect::<Vec<_>>(), ["rs8"]);
+        assert_eq!(
+            record.info().get(key::ALLELE_FREQUENCIES),
+            Some(Some(&Value::from(vec![Some(0.25)])))
+        );
+
+        let mut record = build_record("sq0", 13, "A", "T");
+        assert!(!annotator.annotate(&header, &mut record)?);
+        assert!(record.ids().as_ref().is_empty());
+
+        // A missing ID in the annotation record keeps the existing IDs.
+        let mut record = build_record("sq0", 21, "A", "C");
+        *record.ids_mut() = [String::from("rs21")].into_iter().collect();
+        assert!(annotator.annotate(&header, &mut record)?);
+        assert_eq!(record.ids().as_ref().iter().collect::<Vec<_>>(), ["rs21"]);
+        assert_eq!(
+            record.info().get(key::ALLELE_FREQUENCIES),
+            Some(Some(&Value::from(vec![Some(0.75)])))
+        );
+
+        // Records can be annotated out of order.
+        let mut record = build_record("sq0", 8, "A", "C");
+        assert!(annotator.annotate(&header, &mut record)?);
+        assert_eq!(record.ids().as_ref().iter().collect::<Vec<_>>(), ["rs8"]);
+
+        let mut record = build_record("sq1", 8, "A", "C");
+        assert!(!annotator.annotate(&header, &mut record)?);
+
+        let mut record = build_record("sq2", 8, "A", "C");
+        assert!(!annotator.annotate(&header, &mut record)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_annotate_with_bed_source() -> Result<(), Box<dyn std::error::Error>> {
+        let index_header = csi::binning_index::index::header::Builder::bed()
+            .set_reference_sequence_names(["sq0", "sq1"].into_iter().map(Into::into).collect())
+            .build();
+
+        let (data, index) =
+            build_indexed_data("", &[("sq0", 6, 10, "sq0\t5\t10\tgene0\n")], index_header)?;
+
+        let mut annotator = Builder::default()
+            .add_column(Column::Info(String::from("GENE")))
+            .build_from_bed_reader(Cursor::new(data), index);
+
+        let mut header = vcf::Header::default();
+        annotator.annotate_header(&mut header)?;
+
+        let mut record = build_record("sq0", 9, "AAA", "A");
+        assert!(annotator.annotate(&header, &mut record)?);
+        assert_eq!(record.info().get("GENE"), Some(Some(&Value::from("gene0"))));
+
+        let mut record = build_record("sq0", 11, "A", "C");
+        assert!(!annotator.annotate(&header, &mut record)?);
+
+        let mut record = build_record("sq0", 4, "AA", "A");
+        assert!(!annotator.annotate(&header, &mut record)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use std::io::{self, Read, Seek};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{self as csi, BinningIndex};
+
+use super::{Annotator, Column, Match, Source};
+use crate::variant::io::IndexedReader;
+
+/// A variant annotator builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    columns: Vec<Column>,
+    r#match: Match,
+}
+
+impl Builder {
+    /// Adds an annotation column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::annotate::{self, Column};
+    /// let builder = annotate::Builder::default().add_column(Column::Id);
+    /// ```
+    pub fn add_column(mut self, column: Column) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    /// Sets the strategy to match annotation records to a variant record.
+    ///
+    /// This only applies to variant sources. By default, records are matched by alleles
+    /// ([`Match::Alleles`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::annotate::{self, Match};
+    /// let builder = annotate::Builder::default().set_match(Match::Overlap);
+    /// ```
+    pub fn set_match(mut self, r#match: Match) -> Self {
+        self.r#match = r#match;
+        self
+    }
+
+    /// Builds a variant annotator from an indexed variant reader.
+    ///
+    /// This reads the header of the source.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_util::variant::{annotate::{self, Column}, io::indexed_reader};
+    ///
+    /// let reader = indexed_reader::Builder::default().build_from_path("annotations.vcf.gz")?;
+    ///
+    /// let annotator = annotate::Builder::default()
+    ///     .add_column(Column::Id)
+    ///     .build_from_variant_reader(reader)?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_variant_reader<R>(
+        self,
+        mut reader: IndexedReader<bgzf::io::Reader<R>>,
+    ) -> io::Result<Annotator<R>>
+    where
+        R: Read + Seek,
+    {
+        let header = reader.read_header().map(Box::new)?;
+
+        Ok(Annotator {
+            source: Source::Variant { reader, header },
+            columns: self.columns,
+            r#match: self.r#match,
+            window: None,
+        })
+    }
+
+    /// Builds a variant annotator from a bgzipped BED reader and its index.
+    ///
+    /// The feature name (the 4th column) is used as the annotation value.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use noodles_csi as csi;
+    /// use noodles_util::variant::annotate::{self, Column};
+    ///
+    /// let index = csi::fs::read("annotations.bed.gz.csi")?;
+    /// let reader = File::open("annotations.bed.gz")?;
+    ///
+    /// let annotator = annotate::Builder::default()
+    ///     .add_column(Column::Info(String::from("GENE")))
+    ///     .build_from_bed_reader(reader, index);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_bed_reader<R, I>(self, reader: R, index: I) -> Annotator<R>
+    where
+        R: Read + Seek,
+        I: BinningIndex + 'static,
+    {
+        let index: Box<dyn BinningIndex> = Box::new(index);
+
+        Annotator {
+            source: Source::Bed(csi::io::IndexedReader::new(reader, index)),
+            columns: self.columns,
+            r#match: self.r#match,
+            window: None,
+        }
+    }
+}
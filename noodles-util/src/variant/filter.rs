@@ -0,0 +1,401 @@
+//! Variant record filtering.
+
+use std::{fmt::Write as _, io};
+
+use noodles_vcf::{
+    self as vcf,
+    variant::{
+        Record,
+        record::{
+            info::field::{Value as InfoValue, value::Array as InfoArray},
+            samples::series::{
+                Value as SampleValue,
+                value::{Array as SampleArray, Genotype, genotype::Phasing},
+            },
+        },
+    },
+};
+
+use crate::filter::{Expression, ParseError, Subscript, Value};
+
+/// A variant record field.
+#[derive(Clone, Debug, PartialEq)]
+enum Field {
+    ReferenceSequenceName,
+    Position,
+    Ids,
+    ReferenceBases,
+    AlternateBases,
+    QualityScore,
+    Filters,
+    Info(String, Option<usize>),
+    Format(String, Option<usize>),
+}
+
+/// A variant record filter.
+///
+/// A filter is compiled from a [filter expression](crate::filter) and a VCF header. The fields
+/// are
+///
+///   * `CHROM`, `POS`, `ID`, `REF`, `ALT`, `QUAL`, and `FILTER`;
+///   * `INFO/<key>`, optionally with a value index, e.g., `INFO/AF[0]`; and
+///   * `FMT/<key>` (or `FORMAT/<key>`), optionally with a sample index, e.g., `FMT/GQ[0]`. With no
+///     subscript or `[*]`, the values of all samples are compared.
+///
+/// Genotypes (`FMT/GT`) are compared as strings, e.g., `FMT/GT == "0/1"`. A flag is 1 if set and
+/// missing otherwise.
+pub struct Filter<'h> {
+    header: &'h vcf::Header,
+    expression: Expression<Field>,
+}
+
+impl<'h> Filter<'h> {
+    /// Compiles a variant record filter.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the expression is invalid, an INFO or FORMAT field is not defined
+    /// in the header, or a sample index is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::filter::Filter;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let header = vcf::Header::default();
+    /// let filter = Filter::new(&header, "QUAL > 30")?;
+    /// # Ok::<_, noodles_util::filter::ParseError>(())
+    /// ```
+    pub fn new(header: &'h vcf::Header, s: &str) -> Result<Self, ParseError> {
+        let expression =
+            Expression::parse(s, |name, subscript| resolve_field(header, name, subscript))?;
+
+        Ok(Self { header, expression })
+    }
+
+    /// Returns whether the given record passes the filter.
+    ///
+    /// Only the fields referenced by the expression are read.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if a referenced field fails to be read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::filter::Filter;
+    /// use noodles_vcf::{self as vcf, variant::RecordBuf};
+    ///
+    /// let header = vcf::Header::default();
+    /// let filter = Filter::new(&header, "QUAL > 30")?;
+    ///
+    /// let record = RecordBuf::builder().set_quality_score(34.0).build();
+    /// assert!(filter.evaluate(&record)?);
+    ///
+    /// let record = RecordBuf::default();
+    /// assert!(!filter.evaluate(&record)?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn evaluate<R>(&self, record: &R) -> io::Result<bool>
+    where
+        R: Record + ?Sized,
+    {
+        self.expression
+            .matches(&mut |field| get_field(self.header, record, field))
+    }
+}
+
+fn resolve_field(
+    header: &vcf::Header,
+    name: &str,
+    subscript: Option<Subscript>,
+) -> Result<Field, ParseError> {
+    let index = match subscript {
+        Some(Subscript::Index(i)) => Some(i),
+        Some(Subscript::All) | None => None,
+    };
+
+    if let Some(key) = name.strip_prefix("INFO/") {
+        return if header.infos().contains_key(key) {
+            Ok(Field::Info(key.into(), index))
+        } else {
+            Err(ParseError::InvalidField(name.into()))
+        };
+    }
+
+    if let Some(key) = name
+        .strip_prefix("FMT/")
+        .or_else(|| name.strip_prefix("FORMAT/"))
+    {
+        if !header.formats().contains_key(key) {
+            return Err(ParseError::InvalidField(name.into()));
+        }
+
+        if index.is_some_and(|i| i >= header.sample_names().len()) {
+            return Err(ParseError::InvalidSubscript(name.into()));
+        }
+
+        return Ok(Field::Format(key.into(), index));
+    }
+
+    if subscript.is_some() {
+        return Err(ParseError::InvalidSubscript(name.into()));
+    }
+
+    match name {
+        "CHROM" => Ok(Field::ReferenceSequenceName),
+        "POS" => Ok(Field::Position),
+        "ID" => Ok(Field::Ids),
+        "REF" => Ok(Field::ReferenceBases),
+        "ALT" => Ok(Field::AlternateBases),
+        "QUAL" => Ok(Field::QualityScore),
+        "FILTER" => Ok(Field::Filters),
+        _ => Err(ParseError::InvalidField(name.into())),
+    }
+}
+
+fn get_field<R>(header: &vcf::Header, record: &R, field: &Field) -> io::Result<Value>
+where
+    R: Record + ?Sized,
+{
+    match field {
+        Field::ReferenceSequenceName => record
+            .reference_sequence_name(header)
+            .map(|name| Value::String(name.into())),
+        Field::Position => record
+            .variant_start()
+            .transpose()
+            .map(|position| position.map_or(Value::Missing, |p| integer(usize::from(p)))),
+        Field::Ids => {
+            let ids = record
+                .ids()
+                .iter()
+                .map(|id| Value::String(id.into()))
+                .collect();
+            Ok(Value::from_values(ids))
+        }
+        Field::ReferenceBases => record
+            .reference_bases()
+            .iter()
+            .map(|result| result.map(char::from))
+            .collect::<io::Result<String>>()
+            .map(Value::String),
+        Field::AlternateBases => record
+            .alternate_bases()
+            .iter()
+            .map(|result| result.map(|allele| Value::String(allele.into())))
+            .collect::<io::Result<_>>()
+            .map(Value::from_values),
+        Field::QualityScore => record
+            .quality_score()
+            .transpose()
+            .map(|n| n.map_or(Value::Missing, |n| Value::Float(f64::from(n)))),
+        Field::Filters => record
+            .filters()
+            .iter(header)
+            .map(|result| result.map(|filter| Value::String(filter.into())))
+            .collect::<io::Result<_>>()
+            .map(Value::from_values),
+        Field::Info(key, index) => {
+            let value = match record.info().get(header, key).transpose()?.flatten() {
+                Some(value) => info_value(value)?,
+                None => Value::Missing,
+            };
+
+            Ok(select(value, *index))
+        }
+        Field::Format(key, index) => {
+            let samples = record.samples()?;
+
+            let Some(series) = samples.select(header, key).transpose()? else {
+                return Ok(Value::Missing);
+            };
+
+            match index {
+                Some(i) => match series.get(header, *i).flatten().transpose()? {
+                    Some(value) => sample_value(value),
+                    None => Ok(Value::Missing),
+                },
+                None => series
+                    .iter(header)
+                    .map(|result| {
+                        result.and_then(|value| value.map_or(Ok(Value::Missing), sample_value))
+                    })
+                    .collect::<io::Result<_>>()
+                    .map(Value::from_values),
+            }
+        }
+    }
+}
+
+fn integer<N>(n: N) -> Value
+where
+    i64: TryFrom<N>,
+{
+    i64::try_from(n).map_or(Value::Missing, Value::Integer)
+}
+
+fn select(value: Value, index: Option<usize>) -> Value {
+    match (value, index) {
+        (Value::Array(mut values), Some(i)) if i < values.len() => values.swap_remove(i),
+        (_, Some(_)) => Value::Missing,
+        (value, None) => value,
+    }
+}
+
+fn collect_values<I, T, F>(values: I, f: F) -> io::Result<Value>
+where
+    I: Iterator<Item = io::Result<Option<T>>>,
+    F: Fn(T) -> Value,
+{
+    values
+        .map(|result| result.map(|value| value.map_or(Value::Missing, &f)))
+        .collect::<io::Result<_>>()
+        .map(Value::from_values)
+}
+
+fn info_value(value: InfoValue<'_>) -> io::Result<Value> {
+    match value {
+        InfoValue::Integer(n) => Ok(Value::Integer(i64::from(n))),
+        InfoValue::Float(n) => Ok(Value::Float(f64::from(n))),
+        InfoValue::Flag => Ok(Value::Integer(1)),
+        InfoValue::Character(c) => Ok(Value::String(c.into())),
+        InfoValue::String(s) => Ok(Value::String(s.into())),
+        InfoValue::Array(InfoArray::Integer(values)) => {
+            collect_values(values.iter(), |n| Value::Integer(i64::from(n)))
+        }
+        InfoValue::Array(InfoArray::Float(values)) => {
+            collect_values(values.iter(), |n| Value::Float(f64::from(n)))
+        }
+        InfoValue::Array(InfoArray::Character(values)) => {
+            collect_values(values.iter(), |c| Value::String(c.into()))
+        }
+        InfoValue::Array(InfoArray::String(values)) => {
+            collect_values(values.iter(), |s| Value::String(s.into()))
+        }
+    }
+}
+
+fn sample_value(value: SampleValue<'_>) -> io::Result<Value> {
+    match value {
+        SampleValue::Integer(n) => Ok(Value::Integer(i64::from(n))),
+        SampleValue::Float(n) => Ok(Value::Float(f64::from(n))),
+        SampleValue::Character(c) => Ok(Value::String(c.into())),
+        SampleValue::String(s) => Ok(Value::String(s.into())),
+        SampleValue::Genotype(genotype) => genotype_to_string(genotype.as_ref()).map(Value::String),
+        SampleValue::Array(SampleArray::Integer(values)) => {
+            collect_values(values.iter(), |n| Value::Integer(i64::from(n)))
+        }
+        SampleValue::Array(SampleArray::Float(values)) => {
+            collect_values(values.iter(), |n| Value::Float(f64::from(n)))
+        }
+        SampleValue::Array(SampleArray::Character(values)) => {
+            collect_values(values.iter(), |c| Value::String(c.into()))
+        }
+        SampleValue::Array(SampleArray::String(values)) => {
+            collect_values(values.iter(), |s| Value::String(s.into()))
+        }
+    }
+}
+
+fn genotype_to_string(genotype: &dyn Genotype) -> io::Result<String> {
+    let mut s = String::new();
+
+    for (i, result) in genotype.iter().enumerate() {
+        let (position, phasing) = result?;
+
+        if i > 0 {
+            s.push(match phasing {
+                Phasing::Phased => '|',
+                Phasing::Unphased => '/',
+            });
+        }
+
+        match position {
+            Some(n) => write!(s, "{n}").map_err(io::Error::other)?,
+            None => s.push('.'),
+        }
+    }
+
+    Ok(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_vcf::variant::io::Read;
+
+    use super::*;
+
+    const SRC: &[u8] = b"##fileformat=VCFv4.5
+##INFO=<ID=DP,Number=1,Type=Integer,Description=\"Combined depth across samples\">
+##INFO=<ID=AF,Number=A,Type=Float,Description=\"Allele frequency\">
+##INFO=<ID=DB,Number=0,Type=Flag,Description=\"dbSNP membership\">
+##FILTER=<ID=q10,Description=\"Quality below 10\">
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=GQ,Number=1,Type=Integer,Description=\"Genotype quality\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0\tsample1
+sq0\t8\trs8\tA\tC,G\t34\tPASS\tDP=13;AF=0.25,0.5;DB\tGT:GQ\t0/1:15\t1|2:34
+sq1\t13\t.\tT\t.\t.\tq10\tDP=5\tGT\t./.\t0/0
+";
+
+    #[test]
+    fn test_evaluate() -> Result<(), Box<dyn std::error::Error>> {
+        let mut reader = vcf::io::Reader::new(SRC);
+        let header = reader.read_variant_header()?;
+        let records: Vec<_> = reader.variant_records(&header).collect::<io::Result<_>>()?;
+
+        let cases = [
+            ("QUAL > 30 && INFO/DP > 10", [true, false]),
+            ("QUAL == .", [false, true]),
+            ("FMT/GQ[*] > 20", [true, false]),
+            ("FMT/GQ[0] > 20", [false, false]),
+            ("FMT/GT == \"1|2\"", [true, false]),
+            ("FMT/GT[0] == \"./.\"", [false, true]),
+            ("INFO/AF > 0.4", [true, false]),
+            ("INFO/AF[0] > 0.4", [false, false]),
+            ("INFO/DB", [true, false]),
+            ("!INFO/DB", [false, true]),
+            ("FILTER == \"PASS\"", [true, false]),
+            ("CHROM == \"sq1\" && POS == 13", [false, true]),
+            ("ID == \"rs8\" || ALT == \"G\"", [true, false]),
+            ("ALT == .", [false, true]),
+            ("REF == \"T\"", [false, true]),
+            ("INFO/DP * 2 >= 26", [true, false]),
+        ];
+
+        for (s, expected) in cases {
+            let filter = Filter::new(&header, s)?;
+
+            for (record, expected) in records.iter().zip(expected) {
+                assert_eq!(filter.evaluate(record.as_ref())?, expected, "{s}");
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_new_with_invalid_fields() -> io::Result<()> {
+        let mut reader = vcf::io::Reader::new(SRC);
+        let header = reader.read_variant_header()?;
+
+        assert!(matches!(
+            Filter::new(&header, "INFO/NS > 1"),
+            Err(ParseError::InvalidField(name)) if name == "INFO/NS"
+        ));
+
+        assert!(matches!(
+            Filter::new(&header, "FMT/GQ[2] > 1"),
+            Err(ParseError::InvalidSubscript(name)) if name == "FMT/GQ"
+        ));
+
+        assert!(matches!(
+            Filter::new(&header, "DEPTH > 1"),
+            Err(ParseError::InvalidField(name)) if name == "DEPTH"
+        ));
+
+        Ok(())
+    }
+}
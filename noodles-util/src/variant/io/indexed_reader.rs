@@ -4,12 +4,15 @@ mod builder;
 
 pub use self::builder::Builder;
 
-use std::io::{self, BufRead};
+use std::{
+    io::{self, BufRead},
+    iter,
+};
 
 use noodles_bcf as bcf;
 use noodles_bgzf as bgzf;
-use noodles_core::Region;
-use noodles_csi::BinningIndex;
+use noodles_core::{Position, Region};
+use noodles_csi::{BinningIndex, binning_index::Shard};
 use noodles_vcf::{self as vcf, variant::Record};
 
 /// An indexed variant reader.
@@ -86,4 +89,58 @@ where
 
         Ok(records)
     }
+
+    /// Returns an iterator over records owned by the given shard.
+    ///
+    /// A record is owned by the shard that contains its reference sequence name and variant
+    /// start. Records at a telomere, i.e., with a variant start of 0, are owned by the shard that
+    /// contains the first position of the reference sequence. When reading every shard of a plan,
+    /// e.g., from [`noodles_csi::binning_index::shard::plan`], each record is returned exactly
+    /// once.
+    ///
+    /// Variant formats have no unplaced records, so [`Shard::Unmapped`] is always empty.
+    pub fn query_shard<'r, 'h: 'r>(
+        &'r mut self,
+        header: &'h vcf::Header,
+        shard: &Shard,
+    ) -> io::Result<impl Iterator<Item = io::Result<Box<dyn Record>>> + use<'r, 'h, R>> {
+        let Shard::Regions(regions) = shard else {
+            return Ok(Box::new(iter::empty()) as Box<dyn Iterator<Item = _>>);
+        };
+
+        let records: Box<dyn Iterator<Item = io::Result<Box<dyn Record>>>> = match self {
+            Self::Vcf(reader) => Box::new(
+                reader
+                    .query_regions(header, regions)?
+                    .map(|result| result.map(|(record, _)| Box::new(record) as Box<dyn Record>)),
+            ),
+            Self::Bcf(reader) => Box::new(
+                reader
+                    .query_regions(header, regions)?
+                    .map(|result| result.map(|(record, _)| Box::new(record) as Box<dyn Record>)),
+            ),
+        };
+
+        let shard = shard.clone();
+
+        Ok(Box::new(records.filter_map(move |result| {
+            result
+                .and_then(|record| {
+                    is_owned_by(header, &shard, record.as_ref())
+                        .map(|is_owned| if is_owned { Some(record) } else { None })
+                })
+                .transpose()
+        })))
+    }
+}
+
+fn is_owned_by(header: &vcf::Header, shard: &Shard, record: &dyn Record) -> io::Result<bool> {
+    let reference_sequence_name = record.reference_sequence_name(header)?;
+
+    let variant_start = record.variant_start().transpose()?.unwrap_or(Position::MIN);
+
+    Ok(shard.contains(
+        Some(reference_sequence_name.as_bytes()),
+        Some(variant_start),
+    ))
 }
@@ -0,0 +1,600 @@
+//! Variant multi-input merging.
+//!
+//! A [`Merger`] reads several coordinate-sorted variant inputs (VCF or BCF) in sync by position.
+//! It either concatenates inputs with the same samples, like `bcftools concat --allow-overlaps`,
+//! or merges inputs with different samples into one record per site, like `bcftools merge
+//! --merge none`.
+//!
+//! The merged records can be written using any variant writer, e.g., a VCF or BCF writer.
+
+mod builder;
+mod input;
+
+pub use self::builder::Builder;
+
+use std::{
+    collections::HashMap,
+    io::{self, Read},
+    iter, mem,
+};
+
+use indexmap::IndexSet;
+use noodles_core::Position;
+use noodles_vcf::{
+    self as vcf,
+    header::merge::Translation,
+    variant::{
+        RecordBuf,
+        record::samples::{keys::key, series::value::genotype::Phasing},
+        record_buf::{
+            Samples,
+            samples::sample::{
+                Value,
+                value::{Genotype, genotype::Allele},
+            },
+        },
+    },
+};
+
+use self::input::Input;
+
+const PASS: &str = "PASS";
+const DEFAULT_PLOIDY: usize = 2;
+
+/// A merge mode.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+    /// Concatenates inputs with the same samples.
+    ///
+    /// Records from all inputs are read in position order, so inputs may overlap.
+    #[default]
+    Concat,
+    /// Merges inputs with different samples.
+    ///
+    /// Records with the same position, reference bases, and alternate bases are merged into a
+    /// single record. Samples without a record at the site have missing values, and their
+    /// genotypes are set to missing. Records at the same position with different alleles are not
+    /// merged.
+    Merge,
+}
+
+// The reference sequence index and variant start.
+type Key = (usize, Option<Position>);
+
+type Alleles = (String, Vec<String>);
+
+/// A variant merger.
+pub struct Merger<R> {
+    inputs: Vec<Input<R>>,
+    header: vcf::Header,
+    translations: Vec<Translation>,
+    mode: Mode,
+    remove_duplicates: bool,
+    reference_sequence_indices: HashMap<String, usize>,
+    previous: Option<(Key, Vec<Alleles>)>,
+}
+
+impl<R> Merger<R>
+where
+    R: Read,
+{
+    fn new(
+        inputs: Vec<Input<R>>,
+        header: vcf::Header,
+        translations: Vec<Translation>,
+        mode: Mode,
+        remove_duplicates: bool,
+    ) -> Self {
+        let reference_sequence_indices = header
+            .contigs()
+            .keys()
+            .enumerate()
+            .map(|(i, name)| (name.clone(), i))
+            .collect();
+
+        Self {
+            inputs,
+            header,
+            translations,
+            mode,
+            remove_duplicates,
+            reference_sequence_indices,
+            previous: None,
+        }
+    }
+
+    /// Returns the merged header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::{self, merge};
+    ///
+    /// let src = b"##fileformat=VCFv4.5
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// ";
+    ///
+    /// let readers = vec![variant::io::Reader::new(&src[..])?];
+    /// let merger = merge::Builder::default().build_from_readers(readers)?;
+    ///
+    /// assert!(merger.header().sample_names().is_empty());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn header(&self) -> &vcf::Header {
+        &self.header
+    }
+
+    /// Reads the next merged record.
+    ///
+    /// This returns the number of input records read, or 0 if all inputs are at EOF.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if an input record fails to read or if an input is not sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::{self, merge};
+    /// use noodles_vcf::variant::RecordBuf;
+    ///
+    /// let src0 = b"##fileformat=VCFv4.5
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// sq0\t1\t.\tA\t.\t.\tPASS\t.
+    /// sq0\t3\t.\tA\t.\t.\tPASS\t.
+    /// ";
+    ///
+    /// let src1 = b"##fileformat=VCFv4.5
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// sq0\t2\t.\tA\t.\t.\tPASS\t.
+    /// ";
+    ///
+    /// let readers = vec![
+    ///     variant::io::Reader::new(&src0[..])?,
+    ///     variant::io::Reader::new(&src1[..])?,
+    /// ];
+    ///
+    /// let mut merger = merge::Builder::default().build_from_readers(readers)?;
+    ///
+    /// let mut record = RecordBuf::default();
+    /// let mut starts = Vec::new();
+    ///
+    /// while merger.read_record(&mut record)? != 0 {
+    ///     starts.push(record.variant_start().map(usize::from));
+    /// }
+    ///
+    /// assert_eq!(starts, [Some(1), Some(2), Some(3)]);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn read_record(&mut self, record: &mut RecordBuf) -> io::Result<usize> {
+        loop {
+            let Some((i, key)) = self.next_input() else {
+                return Ok(0);
+            };
+
+            match self.mode {
+                Mode::Concat => {
+                    let mut next_record = self.pop(i, key)?;
+
+                    if self.remove_duplicates && self.is_duplicate(key, &next_record) {
+                        continue;
+                    }
+
+                    self.translations[i].apply(&mut next_record);
+                    *record = next_record;
+
+                    return Ok(1);
+                }
+                Mode::Merge => {
+                    let alleles = self.inputs[i].peek().map(alleles);
+
+                    let mut records = Vec::new();
+
+                    for j in i..self.inputs.len() {
+                        let is_match = match self.inputs[j].peek() {
+                            Some(r) => self.key(r) == key && Some(self::alleles(r)) == alleles,
+                            None => false,
+                        };
+
+                        if is_match {
+                            records.push((j, self.pop(j, key)?));
+                        }
+                    }
+
+                    let n = records.len();
+                    *record = merge_records(&self.header, &self.translations, records);
+
+                    return Ok(n);
+                }
+            }
+        }
+    }
+
+    /// Returns an iterator over merged records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::{self, merge::{self, Mode}};
+    ///
+    /// let src0 = b"##fileformat=VCFv4.5
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+    /// sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT\t0/1
+    /// ";
+    ///
+    /// let src1 = b"##fileformat=VCFv4.5
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample1
+    /// sq0\t1\t.\tA\tC\t.\tPASS\t.\tGT\t1/1
+    /// ";
+    ///
+    /// let readers = vec![
+    ///     variant::io::Reader::new(&src0[..])?,
+    ///     variant::io::Reader::new(&src1[..])?,
+    /// ];
+    ///
+    /// let mut merger = merge::Builder::default()
+    ///     .set_mode(Mode::Merge)
+    ///     .build_from_readers(readers)?;
+    ///
+    /// let records: Vec<_> = merger.records().collect::<std::io::Result<_>>()?;
+    ///
+    /// assert_eq!(records.len(), 1);
+    /// assert_eq!(records[0].samples().values().count(), 2);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn records(&mut self) -> impl Iterator<Item = io::Result<RecordBuf>> + '_ {
+        let mut record = RecordBuf::default();
+
+        iter::from_fn(move || match self.read_record(&mut record) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(record.clone())),
+            Err(e) => Some(Err(e)),
+        })
+    }
+
+    fn key(&self, record: &RecordBuf) -> Key {
+        let name = record.reference_sequence_name();
+
+        let i = self
+            .reference_sequence_indices
+            .get(name)
+            .copied()
+            .unwrap_or(usize::MAX);
+
+        (i, record.variant_start())
+    }
+
+    // Returns the index of the first input with the least key.
+    fn next_input(&mut self) -> Option<(usize, Key)> {
+        // Reference sequences that are not in the header are ordered by first appearance.
+        for input in &self.inputs {
+            if let Some(record) = input.peek() {
+                let name = record.reference_sequence_name();
+
+                if !self.reference_sequence_indices.contains_key(name) {
+                    let i = self.reference_sequence_indices.len();
+                    self.reference_sequence_indices.insert(name.into(), i);
+                }
+            }
+        }
+
+        self.inputs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, input)| input.peek().map(|record| (i, self.key(record))))
+            .min_by_key(|(_, key)| *key)
+    }
+
+    fn pop(&mut self, i: usize, key: Key) -> io::Result<RecordBuf> {
+        let input = &mut self.inputs[i];
+
+        let record = input
+            .pop()
+            .ok_or_else(|| io::Error::other("missing input record"))?;
+
+        input.fill()?;
+
+        if let Some(next_record) = self.inputs[i].peek() {
+            let name = next_record.reference_sequence_name();
+
+            if !self.reference_sequence_indices.contains_key(name) {
+                let j = self.reference_sequence_indices.len();
+                self.reference_sequence_indices.insert(name.into(), j);
+            }
+
+            if self.key(next_record) < key {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("input {i} is not sorted"),
+                ));
+            }
+        }
+
+        Ok(record)
+    }
+
+    fn is_duplicate(&mut self, key: Key, record: &RecordBuf) -> bool {
+        let record_alleles = alleles(record);
+
+        match &mut self.previous {
+            Some((previous_key, previous_alleles)) if *previous_key == key => {
+                if previous_alleles.contains(&record_alleles) {
+                    true
+                } else {
+                    previous_alleles.push(record_alleles);
+                    false
+                }
+            }
+            _ => {
+                self.previous = Some((key, vec![record_alleles]));
+                false
+            }
+        }
+    }
+}
+
+fn alleles(record: &RecordBuf) -> Alleles {
+    (
+        record.reference_bases().into(),
+        record.alternate_bases().as_ref().to_vec(),
+    )
+}
+
+fn merge_records(
+    header: &vcf::Header,
+    translations: &[Translation],
+    records: Vec<(usize, RecordBuf)>,
+) -> RecordBuf {
+    let sample_count = header.sample_names().len();
+
+    let keys: IndexSet<String> = records
+        .iter()
+        .flat_map(|(_, record)| record.samples().keys().as_ref().iter().cloned())
+        .collect();
+
+    let mut values = vec![vec![None; keys.len()]; sample_count];
+    let mut is_present = vec![false; sample_count];
+
+    let mut records = records.into_iter();
+
+    let Some((i, mut merged_record)) = records.next() else {
+        return RecordBuf::default();
+    };
+
+    let first_samples = mem::take(merged_record.samples_mut());
+    let mut is_pass = merged_record.filters().is_pass();
+    merged_record.filters_mut().as_mut().shift_remove(PASS);
+
+    let mut merge_samples = |i: usize, samples: &Samples| {
+        let sample_indices = translations[i].sample_indices();
+
+        for (sample, &j) in samples.values().zip(sample_indices) {
+            is_present[j] = true;
+
+            for (key, value) in samples.keys().as_ref().iter().zip(sample.values()) {
+                if let Some(k) = keys.get_index_of(key) {
+                    values[j][k].clone_from(value);
+                }
+            }
+        }
+    };
+
+    merge_samples(i, &first_samples);
+
+    for (i, record) in records {
+        merge_samples(i, record.samples());
+
+        merged_record
+            .ids_mut()
+            .extend(record.ids().as_ref().iter().cloned());
+
+        if let Some(quality_score) = record.quality_score()
+            && merged_record
+                .quality_score()
+                .is_none_or(|merged_quality_score| quality_score > merged_quality_score)
+        {
+            *merged_record.quality_score_mut() = Some(quality_score);
+        }
+
+        is_pass |= record.filters().is_pass();
+
+        for filter in record.filters().as_ref() {
+            if filter != PASS {
+                merged_record.filters_mut().as_mut().insert(filter.clone());
+            }
+        }
+
+        for (key, value) in record.info().as_ref() {
+            if merged_record.info().get(key).is_none() {
+                merged_record.info_mut().insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    if is_pass && merged_record.filters().as_ref().is_empty() {
+        merged_record.filters_mut().as_mut().insert(PASS.into());
+    }
+
+    if let Some(k) = keys.get_index_of(key::GENOTYPE) {
+        fill_missing_genotypes(&mut values, &is_present, k);
+    }
+
+    *merged_record.samples_mut() = Samples::new(keys.into_iter().collect(), values);
+
+    merged_record
+}
+
+fn fill_missing_genotypes(values: &mut [Vec<Option<Value>>], is_present: &[bool], k: usize) {
+    let ploidy = values
+        .iter()
+        .find_map(|sample| match &sample[k] {
+            Some(Value::Genotype(genotype)) => Some(genotype.as_ref().len()),
+            _ => None,
+        })
+        .unwrap_or(DEFAULT_PLOIDY);
+
+    let genotype: Genotype = iter::repeat_n(Allele::new(None, Phasing::Unphased), ploidy).collect();
+
+    for (sample, _) in values
+        .iter_mut()
+        .zip(is_present)
+        .filter(|(_, is_present)| !**is_present)
+    {
+        sample[k] = Some(Value::Genotype(genotype.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_reader(src: &'static [u8]) -> io::Result<crate::variant::io::Reader<&'static [u8]>> {
+        crate::variant::io::Reader::new(src)
+    }
+
+    #[test]
+    fn test_read_record_with_concat() -> io::Result<()> {
+        const SRC0: &[u8] = b"##fileformat=VCFv4.5
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t1\t.\tA\tC\t.\tPASS\t.
+sq1\t5\t.\tA\tC\t.\tPASS\t.
+";
+
+        const SRC1: &[u8] = b"##fileformat=VCFv4.5
+##contig=<ID=sq0>
+##contig=<ID=sq1>
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t1\t.\tA\tC\t.\tPASS\t.
+sq0\t8\t.\tA\tC\t.\tPASS\t.
+";
+
+        let readers = vec![build_reader(SRC0)?, build_reader(SRC1)?];
+        let mut merger = Builder::default().build_from_readers(readers)?;
+
+        let positions: Vec<_> = merger
+            .records()
+            .map(|result| {
+                result.map(|record| {
+                    (
+                        record.reference_sequence_name().to_string(),
+                        record.variant_start().map(usize::from),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(
+            positions,
+            [
+                (String::from("sq0"), Some(1)),
+                (String::from("sq0"), Some(1)),
+                (String::from("sq0"), Some(8)),
+                (String::from("sq1"), Some(5)),
+            ]
+        );
+
+        let readers = vec![build_reader(SRC0)?, build_reader(SRC1)?];
+        let mut merger = Builder::default()
+            .set_remove_duplicates(true)
+            .build_from_readers(readers)?;
+
+        assert_eq!(merger.records().count(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_record_with_merge() -> Result<(), Box<dyn std::error::Error>> {
+        const SRC0: &[u8] = b"##fileformat=VCFv4.5
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+##FORMAT=<ID=DP,Number=1,Type=Integer,Description=\"Read depth\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample0
+sq0\t1\tid0\tA\tC\t5\tPASS\t.\tGT:DP\t0/1:8
+sq0\t2\t.\tA\tC\t.\tPASS\t.\tGT:DP\t1/1:13
+";
+
+        const SRC1: &[u8] = b"##fileformat=VCFv4.5
+##FORMAT=<ID=GT,Number=1,Type=String,Description=\"Genotype\">
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\tsample1
+sq0\t1\tid1\tA\tC\t8\tq10\t.\tGT\t1|1
+sq0\t2\t.\tA\tG\t.\tPASS\t.\tGT\t0|1
+";
+
+        let readers = vec![build_reader(SRC0)?, build_reader(SRC1)?];
+        let mut merger = Builder::default()
+            .set_mode(Mode::Merge)
+            .build_from_readers(readers)?;
+
+        let records: Vec<_> = merger.records().collect::<io::Result<_>>()?;
+        assert_eq!(records.len(), 3);
+
+        let record = &records[0];
+        assert_eq!(
+            record.ids().as_ref().iter().collect::<Vec<_>>(),
+            ["id0", "id1"]
+        );
+        assert_eq!(record.quality_score(), Some(8.0));
+        assert_eq!(
+            record.filters().as_ref().iter().collect::<Vec<_>>(),
+            ["q10"]
+        );
+
+        let expected = Samples::new(
+            [String::from(key::GENOTYPE), String::from(key::READ_DEPTH)]
+                .into_iter()
+                .collect(),
+            vec![
+                vec![Some(Value::Genotype("0/1".parse()?)), Some(Value::from(8))],
+                vec![Some(Value::Genotype("1|1".parse()?)), None],
+            ],
+        );
+        assert_eq!(record.samples(), &expected);
+
+        let record = &records[1];
+        assert_eq!(record.alternate_bases().as_ref(), [String::from("C")]);
+        assert_eq!(
+            record
+                .samples()
+                .values()
+                .nth(1)
+                .map(|sample| sample.values().to_vec()),
+            Some(vec![Some(Value::Genotype("./.".parse()?)), None])
+        );
+
+        let record = &records[2];
+        assert_eq!(record.alternate_bases().as_ref(), [String::from("G")]);
+        assert_eq!(
+            record
+                .samples()
+                .values()
+                .next()
+                .map(|sample| sample.values()[0].clone()),
+            Some(Some(Value::Genotype("./.".parse()?)))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_record_with_unsorted_input() -> io::Result<()> {
+        const SRC: &[u8] = b"##fileformat=VCFv4.5
+#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+sq0\t8\t.\tA\tC\t.\tPASS\t.
+sq0\t1\t.\tA\tC\t.\tPASS\t.
+";
+
+        let readers = vec![build_reader(SRC)?];
+        let mut merger = Builder::default().build_from_readers(readers)?;
+
+        let mut record = RecordBuf::default();
+
+        assert!(matches!(
+            merger.read_record(&mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use std::io::{self, Read};
+
+use noodles_vcf::header::merge::{self as header_merge, SampleNames};
+
+use super::{Input, Merger, Mode};
+use crate::variant;
+
+/// A variant merger builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    mode: Mode,
+    remove_duplicates: bool,
+}
+
+impl Builder {
+    /// Sets the merge mode.
+    ///
+    /// By default, the mode is [`Mode::Concat`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::merge::{self, Mode};
+    /// let builder = merge::Builder::default().set_mode(Mode::Merge);
+    /// ```
+    pub fn set_mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets whether duplicate records are removed when concatenating.
+    ///
+    /// A duplicate record has the same position and alleles as a previously read record. By
+    /// default, duplicates are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::merge;
+    /// let builder = merge::Builder::default().set_remove_duplicates(true);
+    /// ```
+    pub fn set_remove_duplicates(mut self, remove_duplicates: bool) -> Self {
+        self.remove_duplicates = remove_duplicates;
+        self
+    }
+
+    /// Builds a variant merger from readers.
+    ///
+    /// This reads the header of each reader and merges them. When concatenating, samples with the
+    /// same name are the same sample. When merging, duplicate sample names are prefixed with the
+    /// 1-based input number, e.g., `2:sample0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_util::variant::{self, merge};
+    ///
+    /// let src0 = b"##fileformat=VCFv4.5
+    /// #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO
+    /// ";
+    /// let src1 = src0;
+    ///
+    /// let readers = vec![
+    ///     variant::io::Reader::new(&src0[..])?,
+    ///     variant::io::Reader::new(&src1[..])?,
+    /// ];
+    ///
+    /// let merger = merge::Builder::default().build_from_readers(readers)?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_readers<R>(
+        self,
+        readers: Vec<variant::io::Reader<R>>,
+    ) -> io::Result<Merger<R>>
+    where
+        R: Read,
+    {
+        let mut inputs = readers
+            .into_iter()
+            .map(Input::new)
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let headers: Vec<_> = inputs.iter().map(|input| input.header().clone()).collect();
+
+        let sample_names = match self.mode {
+            Mode::Concat => SampleNames::Merge,
+            Mode::Merge => SampleNames::Rename,
+        };
+
+        let (header, translations) = header_merge::merge(&headers, sample_names)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        for input in &mut inputs {
+            input.fill()?;
+        }
+
+        Ok(Merger::new(
+            inputs,
+            header,
+            translations,
+            self.mode,
+            self.remove_duplicates,
+        ))
+    }
+}
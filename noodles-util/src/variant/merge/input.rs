@@ -0,0 +1,49 @@
+use std::io::{self, Read};
+
+use noodles_vcf::{self as vcf, variant::RecordBuf};
+
+use crate::variant::{self, Record};
+
+pub(super) struct Input<R> {
+    reader: variant::io::Reader<R>,
+    header: vcf::Header,
+    record: Record,
+    next: Option<RecordBuf>,
+}
+
+impl<R> Input<R>
+where
+    R: Read,
+{
+    pub(super) fn new(mut reader: variant::io::Reader<R>) -> io::Result<Self> {
+        let header = reader.read_header()?;
+
+        Ok(Self {
+            reader,
+            header,
+            record: Record::default(),
+            next: None,
+        })
+    }
+
+    pub(super) fn header(&self) -> &vcf::Header {
+        &self.header
+    }
+
+    pub(super) fn peek(&self) -> Option<&RecordBuf> {
+        self.next.as_ref()
+    }
+
+    pub(super) fn pop(&mut self) -> Option<RecordBuf> {
+        self.next.take()
+    }
+
+    pub(super) fn fill(&mut self) -> io::Result<()> {
+        if self.next.is_none() && self.reader.read_record(&mut self.record)? != 0 {
+            let record = RecordBuf::try_from_variant_record(&self.header, &self.record)?;
+            self.next = Some(record);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use std::{
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+};
+
+use noodles_bgzf as bgzf;
+use noodles_csi::{self as csi, BinningIndex};
+use noodles_tabix as tabix;
+use tokio::{
+    fs::File,
+    io::{self, AsyncRead},
+};
+
+use super::IndexedReader;
+
+type Index = Box<dyn BinningIndex + Send + Sync>;
+
+/// An async indexed VCF reader builder.
+#[derive(Default)]
+pub struct Builder {
+    index: Option<Index>,
+}
+
+impl Builder {
+    /// Sets an index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix as tabix;
+    /// use noodles_vcf::r#async::io::indexed_reader::Builder;
+    ///
+    /// let index = tabix::Index::default();
+    /// let builder = Builder::default().set_index(index);
+    /// ```
+    pub fn set_index<I>(mut self, index: I) -> Self
+    where
+        I: BinningIndex + Send + Sync + 'static,
+    {
+        self.index = Some(Box::new(index));
+        self
+    }
+
+    /// Builds an async indexed VCF reader from a path.
+    ///
+    /// If no index is set, this will attempt to read an associated index at `<src>.tbi` or
+    /// `<src>.csi`, in that order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> tokio::io::Result<()> {
+    /// use noodles_vcf::r#async::io::indexed_reader::Builder;
+    /// let reader = Builder::default().build_from_path("sample.vcf.gz").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn build_from_path<P>(
+        self,
+        src: P,
+    ) -> io::Result<IndexedReader<bgzf::r#async::io::Reader<File>>>
+    where
+        P: AsRef<Path>,
+    {
+        let src = src.as_ref();
+
+        let index = match self.index {
+            Some(index) => index,
+            None => read_associated_index(src).await?,
+        };
+
+        let file = File::open(src).await?;
+
+        Ok(IndexedReader::new(file, index))
+    }
+
+    /// Builds an async indexed VCF reader from a reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_tabix as tabix;
+    /// use noodles_vcf::r#async::io::indexed_reader::Builder;
+    ///
+    /// let index = tabix::Index::default();
+    /// let reader = Builder::default().set_index(index).build_from_reader(tokio::io::empty())?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_reader<R>(
+        self,
+        reader: R,
+    ) -> io::Result<IndexedReader<bgzf::r#async::io::Reader<R>>>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let index = self
+            .index
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing index"))?;
+
+        Ok(IndexedReader::new(reader, index))
+    }
+}
+
+async fn read_associated_index<P>(src: P) -> io::Result<Index>
+where
+    P: AsRef<Path>,
+{
+    let src = src.as_ref();
+
+    match tabix::r#async::fs::read(build_index_src(src, "tbi")).await {
+        Ok(index) => Ok(Box::new(index)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let index = csi::r#async::fs::read(build_index_src(src, "csi")).await?;
+            Ok(Box::new(index))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn build_index_src<P, S>(src: P, ext: S) -> PathBuf
+where
+    P: AsRef<Path>,
+    S: AsRef<OsStr>,
+{
+    push_ext(src.as_ref().into(), ext)
+}
+
+fn push_ext<S>(path: PathBuf, ext: S) -> PathBuf
+where
+    S: AsRef<OsStr>,
+{
+    let mut s = OsString::from(path);
+    s.push(".");
+    s.push(ext);
+    PathBuf::from(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_ext() {
+        assert_eq!(
+            push_ext(PathBuf::from("sample.vcf.gz"), "tbi"),
+            PathBuf::from("sample.vcf.gz.tbi")
+        );
+    }
+}
@@ -2,6 +2,8 @@
 
 mod builder;
 pub mod file_format;
+pub mod merge;
+pub mod metadata;
 pub mod parser;
 pub mod record;
 pub mod string_maps;
@@ -0,0 +1,501 @@
+//! VCF header merging.
+
+mod translation;
+
+pub use self::translation::Translation;
+
+use std::{error, fmt};
+
+use indexmap::IndexMap;
+
+use super::{Header, ParseError, StringMaps, record::value::Collection};
+use crate::header::record::value::{Map, map::Contig};
+
+/// How sample names that appear in more than one input are handled.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SampleNames {
+    /// Samples with the same name are merged into a single sample.
+    ///
+    /// This is used when the inputs are different sets of variants for the same samples.
+    #[default]
+    Merge,
+    /// Duplicate sample names are prefixed with the 1-based input number, e.g., `2:sample0`.
+    ///
+    /// This keeps each input sample as a separate sample.
+    Rename,
+}
+
+/// An error returned when VCF headers fail to merge.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MergeError {
+    /// An INFO field is defined with a different number or type.
+    InfoConflict(String),
+    /// A FORMAT field is defined with a different number or type.
+    FormatConflict(String),
+    /// A contig is defined with a different length.
+    ContigConflict(String),
+    /// The contigs of an input are not in the same order as the merged contigs.
+    InconsistentContigOrder(String),
+    /// The string maps of an input are invalid.
+    InvalidStringMaps(ParseError),
+}
+
+impl error::Error for MergeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidStringMaps(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InfoConflict(id) => write!(f, "conflicting INFO definitions: {id}"),
+            Self::FormatConflict(id) => write!(f, "conflicting FORMAT definitions: {id}"),
+            Self::ContigConflict(id) => write!(f, "conflicting contig lengths: {id}"),
+            Self::InconsistentContigOrder(id) => write!(f, "inconsistent contig order: {id}"),
+            Self::InvalidStringMaps(_) => write!(f, "invalid string maps"),
+        }
+    }
+}
+
+/// Merges VCF headers.
+///
+/// The merged header has the union of the INFO, FILTER, FORMAT, ALT, contig, and other records of
+/// the inputs. The first definition of a record is kept, but INFO and FORMAT definitions with the
+/// same ID must have the same number and type, and contigs with the same name must not have
+/// different lengths. Contigs are ordered consistently with the contig order of every input.
+///
+/// The file format is the latest of the inputs. `IDX` fields are removed, and the string maps of
+/// the merged header are rebuilt.
+///
+/// This also returns a [`Translation`] for each input that maps its samples, strings, and contigs
+/// to those of the merged header.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{
+///     self as vcf,
+///     header::{merge::{self, SampleNames}, record::value::{map::Contig, Map}},
+/// };
+///
+/// let header0 = vcf::Header::builder()
+///     .add_contig("sq0", Map::<Contig>::new())
+///     .add_sample_name("sample0")
+///     .build();
+///
+/// let header1 = vcf::Header::builder()
+///     .add_contig("sq1", Map::<Contig>::new())
+///     .add_sample_name("sample0")
+///     .add_sample_name("sample1")
+///     .build();
+///
+/// let (header, translations) = merge::merge(&[header0, header1], SampleNames::Merge)?;
+///
+/// assert_eq!(header.contigs().len(), 2);
+/// assert_eq!(header.sample_names().len(), 2);
+/// assert_eq!(translations[1].sample_indices(), [0, 1]);
+/// # Ok::<_, merge::MergeError>(())
+/// ```
+pub fn merge(
+    headers: &[Header],
+    sample_names: SampleNames,
+) -> Result<(Header, Vec<Translation>), MergeError> {
+    let mut header = Header::default();
+
+    if let Some(file_format) = headers.iter().map(|h| h.file_format()).max() {
+        *header.file_format_mut() = file_format;
+    }
+
+    for input in headers {
+        merge_infos(&mut header, input)?;
+        merge_filters(&mut header, input);
+        merge_formats(&mut header, input)?;
+        merge_alternative_alleles(&mut header, input);
+        merge_other_records(&mut header, input);
+    }
+
+    *header.contigs_mut() = merge_contigs(headers)?;
+
+    let sample_indices = merge_sample_names(&mut header, headers, sample_names);
+
+    clear_indices(&mut header);
+    *header.string_maps_mut() =
+        StringMaps::try_from(&header).map_err(MergeError::InvalidStringMaps)?;
+
+    let translations = headers
+        .iter()
+        .zip(sample_indices)
+        .map(|(input, sample_indices)| Translation::new(&header, input, sample_indices))
+        .collect::<Result<_, _>>()?;
+
+    Ok((header, translations))
+}
+
+fn merge_infos(header: &mut Header, input: &Header) -> Result<(), MergeError> {
+    for (id, info) in input.infos() {
+        match header.infos().get(id) {
+            Some(merged) => {
+                if merged.number() != info.number() || merged.ty() != info.ty() {
+                    return Err(MergeError::InfoConflict(id.into()));
+                }
+            }
+            None => {
+                header.infos_mut().insert(id.into(), info.clone());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn merge_filters(header: &mut Header, input: &Header) {
+    for (id, filter) in input.filters() {
+        if !header.filters().contains_key(id) {
+            header.filters_mut().insert(id.into(), filter.clone());
+        }
+    }
+}
+
+fn merge_formats(header: &mut Header, input: &Header) -> Result<(), MergeError> {
+    for (id, format) in input.formats() {
+        match header.formats().get(id) {
+            Some(merged) => {
+                if merged.number() != format.number() || merged.ty() != format.ty() {
+                    return Err(MergeError::FormatConflict(id.into()));
+                }
+            }
+            None => {
+                header.formats_mut().insert(id.into(), format.clone());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn merge_alternative_alleles(header: &mut Header, input: &Header) {
+    for (id, alternative_allele) in input.alternative_alleles() {
+        if !header.alternative_alleles().contains_key(id) {
+            header
+                .alternative_alleles_mut()
+                .insert(id.into(), alternative_allele.clone());
+        }
+    }
+}
+
+fn merge_other_records(header: &mut Header, input: &Header) {
+    for (key, collection) in input.other_records() {
+        let Some(merged) = header.other_records_mut().get_mut(key) else {
+            header
+                .other_records_mut()
+                .insert(key.clone(), collection.clone());
+
+            continue;
+        };
+
+        match (merged, collection) {
+            (Collection::Unstructured(merged), Collection::Unstructured(values)) => {
+                for value in values {
+                    if !merged.contains(value) {
+                        merged.push(value.clone());
+                    }
+                }
+            }
+            (Collection::Structured(merged), Collection::Structured(maps)) => {
+                for (id, map) in maps {
+                    if !merged.contains_key(id) {
+                        merged.insert(id.clone(), map.clone());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// Each new contig is inserted before the next contig of its input that is already merged. This
+// keeps the relative order of the contigs of every input, if one exists.
+fn merge_contigs(headers: &[Header]) -> Result<IndexMap<String, Map<Contig>>, MergeError> {
+    let mut contigs: IndexMap<String, Map<Contig>> = IndexMap::new();
+
+    for input in headers {
+        let mut indices = Vec::with_capacity(input.contigs().len());
+
+        for (name, contig) in input.contigs() {
+            let i = contigs.get_full_mut(name.as_str()).map(|(i, _, merged)| {
+                match (merged.length(), contig.length()) {
+                    (Some(a), Some(b)) if a != b => {
+                        return Err(MergeError::ContigConflict(name.into()));
+                    }
+                    (None, Some(length)) => *merged.length_mut() = Some(length),
+                    _ => {}
+                }
+
+                Ok(i)
+            });
+
+            indices.push(i.transpose()?);
+        }
+
+        let mut insertions = vec![Vec::new(); contigs.len() + 1];
+        let mut next = contigs.len();
+
+        for ((name, contig), i) in input.contigs().iter().zip(&indices).rev() {
+            match i {
+                Some(i) => next = *i,
+                None => insertions[next].push((name.clone(), contig.clone())),
+            }
+        }
+
+        if insertions.iter().all(|contigs| contigs.is_empty()) {
+            continue;
+        }
+
+        let mut merged_contigs = IndexMap::with_capacity(contigs.len() + input.contigs().len());
+        let mut insertions = insertions.into_iter();
+
+        for (name, contig) in contigs {
+            // SAFETY: `insertions` has one more element than `contigs`.
+            merged_contigs.extend(insertions.next().unwrap().into_iter().rev());
+            merged_contigs.insert(name, contig);
+        }
+
+        if let Some(new_contigs) = insertions.next() {
+            merged_contigs.extend(new_contigs.into_iter().rev());
+        }
+
+        contigs = merged_contigs;
+    }
+
+    for input in headers {
+        let mut previous = None;
+
+        for name in input.contigs().keys() {
+            let i = contigs.get_index_of(name.as_str());
+
+            if i < previous {
+                return Err(MergeError::InconsistentContigOrder(name.into()));
+            }
+
+            previous = i;
+        }
+    }
+
+    Ok(contigs)
+}
+
+fn merge_sample_names(
+    header: &mut Header,
+    headers: &[Header],
+    mode: SampleNames,
+) -> Vec<Vec<usize>> {
+    let mut sample_indices = Vec::with_capacity(headers.len());
+
+    for (i, input) in headers.iter().enumerate() {
+        let indices = input
+            .sample_names()
+            .iter()
+            .map(|name| match mode {
+                SampleNames::Merge => header.sample_names_mut().insert_full(name.clone()).0,
+                SampleNames::Rename => {
+                    let mut name = name.clone();
+
+                    while header.sample_names().contains(&name) {
+                        name = format!("{}:{name}", i + 1);
+                    }
+
+                    header.sample_names_mut().insert_full(name).0
+                }
+            })
+            .collect();
+
+        sample_indices.push(indices);
+    }
+
+    sample_indices
+}
+
+fn clear_indices(header: &mut Header) {
+    for info in header.infos_mut().values_mut() {
+        *info.idx_mut() = None;
+    }
+
+    for filter in header.filters_mut().values_mut() {
+        *filter.idx_mut() = None;
+    }
+
+    for format in header.formats_mut().values_mut() {
+        *format.idx_mut() = None;
+    }
+
+    for contig in header.contigs_mut().values_mut() {
+        *contig.idx_mut() = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        header::record::value::map::{Filter, Format, Info, info},
+        variant::record::{info::field::key as info_key, samples::keys::key as format_key},
+    };
+
+    fn contig(length: usize) -> Map<Contig> {
+        let mut contig = Map::<Contig>::new();
+        *contig.length_mut() = Some(length);
+        contig
+    }
+
+    #[test]
+    fn test_merge() -> Result<(), MergeError> {
+        let header0 = Header::builder()
+            .add_info(
+                info_key::TOTAL_DEPTH,
+                Map::<Info>::from(info_key::TOTAL_DEPTH),
+            )
+            .add_filter("q10", Map::<Filter>::new("Quality below 10"))
+            .add_format(
+                format_key::GENOTYPE,
+                Map::<Format>::from(format_key::GENOTYPE),
+            )
+            .add_contig("sq0", contig(8))
+            .add_contig("sq2", contig(21))
+            .add_sample_name("sample0")
+            .build();
+
+        let header1 = Header::builder()
+            .add_info(
+                info_key::TOTAL_DEPTH,
+                Map::<Info>::from(info_key::TOTAL_DEPTH),
+            )
+            .add_info(
+                info_key::ALLELE_COUNT,
+                Map::<Info>::from(info_key::ALLELE_COUNT),
+            )
+            .add_filter("q20", Map::<Filter>::new("Quality below 20"))
+            .add_contig("sq1", Map::<Contig>::new())
+            .add_contig("sq2", contig(21))
+            .add_sample_name("sample1")
+            .add_sample_name("sample0")
+            .build();
+
+        let (header, translations) = merge(&[header0, header1], SampleNames::Merge)?;
+
+        assert_eq!(
+            header.infos().keys().collect::<Vec<_>>(),
+            [info_key::TOTAL_DEPTH, info_key::ALLELE_COUNT]
+        );
+        assert_eq!(header.filters().keys().collect::<Vec<_>>(), ["q10", "q20"]);
+        assert_eq!(
+            header.contigs().keys().collect::<Vec<_>>(),
+            ["sq0", "sq1", "sq2"]
+        );
+        assert_eq!(
+            header.sample_names().iter().collect::<Vec<_>>(),
+            ["sample0", "sample1"]
+        );
+
+        assert_eq!(translations[0].sample_indices(), [0]);
+        assert_eq!(translations[1].sample_indices(), [1, 0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_contigs() -> Result<(), MergeError> {
+        fn build_header(names: &[&str]) -> Header {
+            names
+                .iter()
+                .fold(Header::builder(), |builder, name| {
+                    builder.add_contig(*name, Map::<Contig>::new())
+                })
+                .build()
+        }
+
+        let headers = [
+            build_header(&["sq1", "sq3"]),
+            build_header(&["sq0", "sq1", "sq2", "sq3", "sq4"]),
+            build_header(&["sq5", "sq2"]),
+        ];
+
+        let contigs = merge_contigs(&headers)?;
+        let names: Vec<_> = contigs.keys().map(|name| name.as_str()).collect();
+        assert_eq!(names, ["sq0", "sq1", "sq5", "sq2", "sq3", "sq4"]);
+
+        let headers = [build_header(&["sq0", "sq1"]), build_header(&["sq1", "sq0"])];
+
+        assert_eq!(
+            merge_contigs(&headers),
+            Err(MergeError::InconsistentContigOrder(String::from("sq0")))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_with_rename() -> Result<(), MergeError> {
+        let header0 = Header::builder().add_sample_name("sample0").build();
+        let header1 = Header::builder().add_sample_name("sample0").build();
+
+        let (header, translations) = merge(&[header0, header1], SampleNames::Rename)?;
+
+        assert_eq!(
+            header.sample_names().iter().collect::<Vec<_>>(),
+            ["sample0", "2:sample0"]
+        );
+        assert_eq!(translations[1].sample_indices(), [1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_with_conflicts() {
+        let header0 = Header::builder()
+            .add_info(
+                info_key::TOTAL_DEPTH,
+                Map::<Info>::from(info_key::TOTAL_DEPTH),
+            )
+            .add_contig("sq0", contig(8))
+            .build();
+
+        let mut info = Map::<Info>::from(info_key::TOTAL_DEPTH);
+        *info.type_mut() = info::Type::Float;
+        let header1 = Header::builder()
+            .add_info(info_key::TOTAL_DEPTH, info)
+            .build();
+
+        assert_eq!(
+            merge(&[header0.clone(), header1], SampleNames::Merge),
+            Err(MergeError::InfoConflict(String::from(
+                info_key::TOTAL_DEPTH
+            )))
+        );
+
+        let header1 = Header::builder().add_contig("sq0", contig(13)).build();
+
+        assert_eq!(
+            merge(&[header0, header1], SampleNames::Merge),
+            Err(MergeError::ContigConflict(String::from("sq0")))
+        );
+
+        let header0 = Header::builder()
+            .add_contig("sq0", Map::<Contig>::new())
+            .add_contig("sq1", Map::<Contig>::new())
+            .build();
+
+        let header1 = Header::builder()
+            .add_contig("sq1", Map::<Contig>::new())
+            .add_contig("sq0", Map::<Contig>::new())
+            .build();
+
+        assert_eq!(
+            merge(&[header0, header1], SampleNames::Merge),
+            Err(MergeError::InconsistentContigOrder(String::from("sq0")))
+        );
+    }
+}
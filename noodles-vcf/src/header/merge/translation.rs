@@ -0,0 +1,162 @@
+use std::mem;
+
+use super::MergeError;
+use crate::{
+    Header,
+    header::{StringMaps, string_maps::StringMap},
+    variant::{RecordBuf, record_buf::Samples},
+};
+
+/// A map from an input header to a merged header.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Translation {
+    sample_count: usize,
+    sample_indices: Vec<usize>,
+    string_indices: Vec<Option<usize>>,
+    contig_indices: Vec<Option<usize>>,
+}
+
+impl Translation {
+    pub(super) fn new(
+        header: &Header,
+        input: &Header,
+        sample_indices: Vec<usize>,
+    ) -> Result<Self, MergeError> {
+        let string_maps = StringMaps::try_from(input).map_err(MergeError::InvalidStringMaps)?;
+
+        Ok(Self {
+            sample_count: header.sample_names().len(),
+            sample_indices,
+            string_indices: translate(string_maps.strings(), header.string_maps().strings()),
+            contig_indices: translate(string_maps.contigs(), header.string_maps().contigs()),
+        })
+    }
+
+    /// Returns the merged sample index of each input sample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, header::merge::{self, SampleNames}};
+    ///
+    /// let header = vcf::Header::builder().add_sample_name("sample0").build();
+    /// let (_, translations) = merge::merge(&[header.clone(), header], SampleNames::Rename)?;
+    ///
+    /// assert_eq!(translations[1].sample_indices(), [1]);
+    /// # Ok::<_, merge::MergeError>(())
+    /// ```
+    pub fn sample_indices(&self) -> &[usize] {
+        &self.sample_indices
+    }
+
+    /// Returns the merged index of an input dictionary of strings index.
+    ///
+    /// This is the index of a FILTER, INFO, or FORMAT ID in the merged header string map, e.g.,
+    /// when remapping BCF records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::{merge::{self, SampleNames}, record::value::{map::Filter, Map}},
+    /// };
+    ///
+    /// let header0 = vcf::Header::builder()
+    ///     .add_filter("q10", Map::<Filter>::new("Quality below 10"))
+    ///     .build();
+    ///
+    /// let header1 = vcf::Header::builder()
+    ///     .add_filter("q20", Map::<Filter>::new("Quality below 20"))
+    ///     .build();
+    ///
+    /// let (_, translations) = merge::merge(&[header0, header1], SampleNames::Merge)?;
+    ///
+    /// assert_eq!(translations[1].string_index(0), Some(0)); // PASS
+    /// assert_eq!(translations[1].string_index(1), Some(2)); // q20
+    /// # Ok::<_, merge::MergeError>(())
+    /// ```
+    pub fn string_index(&self, i: usize) -> Option<usize> {
+        self.string_indices.get(i).copied().flatten()
+    }
+
+    /// Returns the merged index of an input dictionary of contigs index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::{merge::{self, SampleNames}, record::value::{map::Contig, Map}},
+    /// };
+    ///
+    /// let header0 = vcf::Header::builder().add_contig("sq0", Map::<Contig>::new()).build();
+    /// let header1 = vcf::Header::builder().add_contig("sq1", Map::<Contig>::new()).build();
+    ///
+    /// let (_, translations) = merge::merge(&[header0, header1], SampleNames::Merge)?;
+    ///
+    /// assert_eq!(translations[1].contig_index(0), Some(1));
+    /// # Ok::<_, merge::MergeError>(())
+    /// ```
+    pub fn contig_index(&self, i: usize) -> Option<usize> {
+        self.contig_indices.get(i).copied().flatten()
+    }
+
+    /// Applies the translation to a record.
+    ///
+    /// This moves the sample values of the record to their merged sample columns. Samples that
+    /// are not in the input are set to missing values. Records without sample fields are
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{
+    ///     self as vcf,
+    ///     header::merge::{self, SampleNames},
+    ///     variant::{
+    ///         record::samples::keys::key,
+    ///         record_buf::{RecordBuf, Samples, samples::sample::Value},
+    ///     },
+    /// };
+    ///
+    /// let header0 = vcf::Header::builder().add_sample_name("sample0").build();
+    /// let header1 = vcf::Header::builder().add_sample_name("sample1").build();
+    ///
+    /// let (_, translations) = merge::merge(&[header0, header1], SampleNames::Merge)?;
+    ///
+    /// let keys = [String::from(key::GENOTYPE)].into_iter().collect();
+    /// let genotype = Value::Genotype("0/1".parse()?);
+    ///
+    /// let mut record = RecordBuf::builder()
+    ///     .set_samples(Samples::new(keys, vec![vec![Some(genotype.clone())]]))
+    ///     .build();
+    ///
+    /// translations[1].apply(&mut record);
+    ///
+    /// let samples = record.samples();
+    /// let values: Vec<_> = samples.values().map(|sample| sample.values().to_vec()).collect();
+    /// assert_eq!(values, [vec![None], vec![Some(genotype)]]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn apply(&self, record: &mut RecordBuf) {
+        if record.samples().keys().as_ref().is_empty() {
+            return;
+        }
+
+        let (keys, samples) = mem::take(record.samples_mut()).into();
+        let mut merged_samples = vec![vec![None; keys.as_ref().len()]; self.sample_count];
+
+        for (sample, &j) in samples.into_iter().zip(&self.sample_indices) {
+            merged_samples[j] = sample;
+        }
+
+        *record.samples_mut() = Samples::new(keys, merged_samples);
+    }
+}
+
+fn translate(src: &StringMap, dst: &StringMap) -> Vec<Option<usize>> {
+    (0..src.len())
+        .map(|i| src.get_index(i).and_then(|s| dst.get_index_of(s)))
+        .collect()
+}
@@ -0,0 +1,395 @@
+//! VCF header sample metadata.
+//!
+//! VCF 4.3+ describes samples using META, SAMPLE, and PEDIGREE records. These are stored as
+//! nonstandard records in the header; the functions here read them as typed maps.
+
+use std::{error, fmt};
+
+use indexmap::IndexMap;
+
+use super::{
+    Header,
+    record::value::{
+        Collection, Map,
+        map::{
+            Meta, Other, Pedigree, Sample,
+            meta::{self, Number, TryFromOtherError, Type},
+        },
+    },
+};
+
+const META: &str = "META";
+const SAMPLE: &str = "SAMPLE";
+const PEDIGREE: &str = "PEDIGREE";
+
+/// An error returned when VCF header sample metadata is invalid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationError {
+    /// A META record is invalid.
+    InvalidMeta {
+        /// The META record ID.
+        id: String,
+        /// The conversion error.
+        source: TryFromOtherError,
+    },
+    /// A SAMPLE field has an unexpected number of values.
+    InvalidNumber {
+        /// The SAMPLE record ID.
+        id: String,
+        /// The field key.
+        key: String,
+        /// The expected number of values.
+        expected: usize,
+        /// The actual number of values.
+        actual: usize,
+    },
+    /// A SAMPLE field value does not match the META type.
+    InvalidType {
+        /// The SAMPLE record ID.
+        id: String,
+        /// The field key.
+        key: String,
+        /// The expected type.
+        ty: Type,
+        /// The invalid value.
+        value: String,
+    },
+    /// A SAMPLE field value is not one of the META values.
+    InvalidValue {
+        /// The SAMPLE record ID.
+        id: String,
+        /// The field key.
+        key: String,
+        /// The invalid value.
+        value: String,
+    },
+}
+
+impl error::Error for ValidationError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidMeta { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMeta { id, .. } => write!(f, "invalid META record: {id}"),
+            Self::InvalidNumber {
+                id,
+                key,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "invalid SAMPLE record {id} field {key}: expected {expected} values, got {actual}"
+            ),
+            Self::InvalidType { id, key, ty, value } => write!(
+                f,
+                "invalid SAMPLE record {id} field {key}: expected {ty}, got {value}"
+            ),
+            Self::InvalidValue { id, key, value } => {
+                write!(
+                    f,
+                    "invalid SAMPLE record {id} field {key}: invalid value {value}"
+                )
+            }
+        }
+    }
+}
+
+/// Returns the META records in the header.
+///
+/// # Errors
+///
+/// An error is returned if a META record is missing or has an invalid `Number` or `Type`.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{
+///     self as vcf,
+///     header::{
+///         metadata,
+///         record::{value::{map::{meta::{Number, Type}, Meta, Other}, Map}, Value},
+///     },
+/// };
+///
+/// let values = vec![String::from("WholeGenome"), String::from("Exome")];
+/// let meta = Map::<Meta>::new(Number::Unknown, Type::String, values);
+///
+/// let header = vcf::Header::builder()
+///     .insert("META".parse()?, Value::from((String::from("Assay"), Map::<Other>::from(meta))))?
+///     .build();
+///
+/// let metas = metadata::metas(&header)?;
+/// assert_eq!(metas["Assay"].values(), ["WholeGenome", "Exome"]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn metas(header: &Header) -> Result<IndexMap<String, Map<Meta>>, ValidationError> {
+    structured_records(header, META)
+        .map(|(id, map)| {
+            Map::<Meta>::try_from(map)
+                .map(|meta| (id.into(), meta))
+                .map_err(|source| ValidationError::InvalidMeta {
+                    id: id.into(),
+                    source,
+                })
+        })
+        .collect()
+}
+
+/// Returns the SAMPLE records in the header.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{
+///     self as vcf,
+///     header::{
+///         metadata,
+///         record::{value::{map::{Other, Sample}, Map}, Value},
+///     },
+/// };
+///
+/// let mut sample = Map::<Sample>::builder()
+///     .insert("Assay".parse()?, "WholeGenome")
+///     .build()?;
+/// *sample.description_mut() = Some(String::from("Patient 1"));
+///
+/// let header = vcf::Header::builder()
+///     .insert("SAMPLE".parse()?, Value::from((String::from("sample0"), Map::<Other>::from(sample))))?
+///     .build();
+///
+/// let samples = metadata::samples(&header);
+/// assert_eq!(samples["sample0"].description(), Some("Patient 1"));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn samples(header: &Header) -> IndexMap<String, Map<Sample>> {
+    structured_records(header, SAMPLE)
+        .map(|(id, map)| (id.into(), Map::<Sample>::from(map)))
+        .collect()
+}
+
+/// Returns the PEDIGREE records in the header.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{
+///     self as vcf,
+///     header::{
+///         metadata,
+///         record::{value::{map::{Other, Pedigree}, Map}, Value},
+///     },
+/// };
+///
+/// let pedigree = Map::<Pedigree>::builder()
+///     .set_father("sample0")
+///     .set_mother("sample1")
+///     .build()?;
+///
+/// let header = vcf::Header::builder()
+///     .insert("PEDIGREE".parse()?, Value::from((String::from("sample2"), Map::<Other>::from(pedigree))))?
+///     .build();
+///
+/// let pedigrees = metadata::pedigrees(&header);
+/// assert_eq!(pedigrees["sample2"].father(), Some("sample0"));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn pedigrees(header: &Header) -> IndexMap<String, Map<Pedigree>> {
+    structured_records(header, PEDIGREE)
+        .map(|(id, map)| (id.into(), Map::<Pedigree>::from(map)))
+        .collect()
+}
+
+/// Validates SAMPLE records against the META records in the header.
+///
+/// Each SAMPLE field with a key defined by a META record must have the number of values given by
+/// `Number`, values that parse as `Type`, and, if the META record lists `Values`, only values from
+/// that list. A list of values is written as `[a, b]`. Fields without a META record are not
+/// checked.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{
+///     self as vcf,
+///     header::{
+///         metadata,
+///         record::{value::{map::{meta::{Number, Type}, Meta, Other, Sample}, Map}, Value},
+///     },
+/// };
+///
+/// let values = vec![String::from("WholeGenome"), String::from("Exome")];
+/// let meta = Map::<Meta>::new(Number::Count(1), Type::String, values);
+///
+/// let sample = Map::<Sample>::builder()
+///     .insert("Assay".parse()?, "Panel")
+///     .build()?;
+///
+/// let header = vcf::Header::builder()
+///     .insert("META".parse()?, Value::from((String::from("Assay"), Map::<Other>::from(meta))))?
+///     .insert("SAMPLE".parse()?, Value::from((String::from("sample0"), Map::<Other>::from(sample))))?
+///     .build();
+///
+/// assert!(matches!(
+///     metadata::validate(&header),
+///     Err(metadata::ValidationError::InvalidValue { .. })
+/// ));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn validate(header: &Header) -> Result<(), ValidationError> {
+    let metas = metas(header)?;
+
+    for (id, sample) in samples(header) {
+        for (key, value) in sample.other_fields() {
+            let Some(meta) = metas.get(key.as_ref()) else {
+                continue;
+            };
+
+            validate_field(&id, key.as_ref(), value, meta)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn structured_records<'h>(
+    header: &'h Header,
+    key: &str,
+) -> impl Iterator<Item = (&'h str, &'h Map<Other>)> {
+    header
+        .get(key)
+        .and_then(|collection| match collection {
+            Collection::Structured(maps) => Some(maps),
+            Collection::Unstructured(_) => None,
+        })
+        .into_iter()
+        .flatten()
+        .map(|(id, map)| (id.as_str(), map))
+}
+
+fn validate_field(
+    id: &str,
+    key: &str,
+    value: &str,
+    meta: &Map<Meta>,
+) -> Result<(), ValidationError> {
+    let items = meta::parse_values(value);
+
+    if let Number::Count(n) = meta.number()
+        && items.len() != n
+    {
+        return Err(ValidationError::InvalidNumber {
+            id: id.into(),
+            key: key.into(),
+            expected: n,
+            actual: items.len(),
+        });
+    }
+
+    for item in &items {
+        if !is_valid_type(meta.ty(), item) {
+            return Err(ValidationError::InvalidType {
+                id: id.into(),
+                key: key.into(),
+                ty: meta.ty(),
+                value: item.clone(),
+            });
+        }
+
+        if !meta.values().is_empty() && !meta.values().contains(item) {
+            return Err(ValidationError::InvalidValue {
+                id: id.into(),
+                key: key.into(),
+                value: item.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn is_valid_type(ty: Type, s: &str) -> bool {
+    match ty {
+        Type::Integer => s.parse::<i32>().is_ok(),
+        Type::Float => s.parse::<f32>().is_ok(),
+        Type::Character => s.chars().count() == 1,
+        Type::String => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_header(meta: &str, sample: &str) -> Result<Header, Box<dyn std::error::Error>> {
+        let s = format!(
+            "##fileformat=VCFv4.3\n##META={meta}\n##SAMPLE={sample}\n#CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\n"
+        );
+
+        Ok(s.parse()?)
+    }
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header(
+            "<ID=Assay,Type=String,Number=.,Values=[WholeGenome, Exome]>",
+            "<ID=sample0,Assay=WholeGenome,Tissue=Blood>",
+        )?;
+        assert!(validate(&header).is_ok());
+
+        let header = build_header(
+            "<ID=Age,Type=Integer,Number=1>",
+            "<ID=sample0,Age=[30, 31]>",
+        )?;
+        assert_eq!(
+            validate(&header),
+            Err(ValidationError::InvalidNumber {
+                id: String::from("sample0"),
+                key: String::from("Age"),
+                expected: 1,
+                actual: 2,
+            })
+        );
+
+        let header = build_header("<ID=Age,Type=Integer,Number=1>", "<ID=sample0,Age=adult>")?;
+        assert_eq!(
+            validate(&header),
+            Err(ValidationError::InvalidType {
+                id: String::from("sample0"),
+                key: String::from("Age"),
+                ty: Type::Integer,
+                value: String::from("adult"),
+            })
+        );
+
+        let header = build_header(
+            "<ID=Assay,Type=String,Number=1,Values=[WholeGenome, Exome]>",
+            "<ID=sample0,Assay=Panel>",
+        )?;
+        assert_eq!(
+            validate(&header),
+            Err(ValidationError::InvalidValue {
+                id: String::from("sample0"),
+                key: String::from("Assay"),
+                value: String::from("Panel"),
+            })
+        );
+
+        let header = build_header("<ID=Age,Type=Integer>", "<ID=sample0,Age=30>")?;
+        assert_eq!(
+            validate(&header),
+            Err(ValidationError::InvalidMeta {
+                id: String::from("Age"),
+                source: TryFromOtherError::MissingNumber,
+            })
+        );
+
+        Ok(())
+    }
+}
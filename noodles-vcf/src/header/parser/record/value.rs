@@ -170,6 +170,7 @@ pub(super) fn parse_value(
 ) -> Result<Record, ParseError> {
     const META: &str = "META";
     const PEDIGREE: &str = "PEDIGREE";
+    const SAMPLE: &str = "SAMPLE";
 
     match key {
         key::FILE_FORMAT => string::parse_file_format(src)
@@ -204,6 +205,10 @@ pub(super) fn parse_value(
                 map::other::parse_pedigree(src, file_format)
                     .map(Value::from)
                     .map_err(|e| ParseError::InvalidOtherMap(k.clone(), e))?
+            } else if k.as_ref() == SAMPLE && map::is_map(src, file_format) {
+                map::other::parse_sample(src, file_format)
+                    .map(Value::from)
+                    .map_err(|e| ParseError::InvalidOtherMap(k.clone(), e))?
             } else if map::is_map(src, file_format) {
                 map::parse_other(src)
                     .map(Value::from)
@@ -154,6 +154,58 @@ pub fn parse_meta(
     ))
 }
 
+pub fn parse_sample(
+    src: &mut &[u8],
+    file_format: FileFormat,
+) -> Result<(String, Map<Other>), ParseError> {
+    const VCF_4_3: FileFormat = FileFormat::new(4, 3);
+
+    super::consume_prefix(src).map_err(|e| ParseError::new(None, ParseErrorKind::InvalidMap(e)))?;
+
+    let mut id = None;
+    let mut other_fields = OtherFields::new();
+
+    loop {
+        let tag = parse_key(src)
+            .map(Tag::from)
+            .map_err(|e| ParseError::new(id.clone(), ParseErrorKind::InvalidKey(e)))?;
+
+        match tag {
+            tag::ID => parse_id(src, &id).and_then(|v| try_replace(&mut id, &None, tag::ID, v))?,
+            Tag::Other(t) => {
+                // In VCF 4.3+, SAMPLE field values may be lists, e.g., `Tissue=[Blood, Saliva]`.
+                if file_format >= VCF_4_3 {
+                    parse_values(src, &id, &t)
+                        .and_then(|value| try_insert(&mut other_fields, &id, t, value))?;
+                } else {
+                    parse_other_value(src, &id, &t)
+                        .and_then(|value| try_insert(&mut other_fields, &id, t, value))?;
+                }
+            }
+        }
+
+        let has_separator = super::field::consume_separator(src)
+            .map_err(|e| ParseError::new(id.clone(), ParseErrorKind::InvalidField(e)))?;
+
+        if !has_separator {
+            break;
+        }
+    }
+
+    super::consume_suffix(src)
+        .map_err(|e| ParseError::new(id.clone(), ParseErrorKind::InvalidMap(e)))?;
+
+    let id = id.ok_or_else(|| ParseError::new(None, ParseErrorKind::MissingId))?;
+
+    Ok((
+        id,
+        Map {
+            inner: Other::default(),
+            other_fields,
+        },
+    ))
+}
+
 pub fn parse_pedigree(
     src: &mut &[u8],
     file_format: FileFormat,
@@ -330,6 +382,35 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_sample() -> Result<(), Box<dyn std::error::Error>> {
+        const VCF_4_2: FileFormat = FileFormat::new(4, 2);
+        const VCF_4_3: FileFormat = FileFormat::new(4, 3);
+
+        let mut src = &br#"<ID=sample0,Tissue=[Blood, Saliva],Description="Patient 1">"#[..];
+        assert_eq!(
+            parse_sample(&mut src, VCF_4_3),
+            Ok((
+                String::from("sample0"),
+                Map::<Other>::builder()
+                    .insert("Tissue".parse()?, "[Blood, Saliva]")
+                    .insert("Description".parse()?, "Patient 1")
+                    .build()?
+            ))
+        );
+
+        let mut src = &b"<ID=sample0,Tissue=[Blood, Saliva]>"[..];
+        assert!(matches!(
+            parse_sample(&mut src, VCF_4_2),
+            Err(ParseError {
+                id,
+                kind: ParseErrorKind::InvalidKey(_)
+            }) if id == Some(String::from("sample0"))
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_parse_pedigree() -> Result<(), Box<dyn std::error::Error>> {
         const VCF_4_2: FileFormat = FileFormat::new(4, 2);
@@ -0,0 +1,299 @@
+//! Inner VCF header META map value.
+
+mod builder;
+mod number;
+pub(crate) mod tag;
+mod ty;
+
+pub use self::{number::Number, tag::Tag, ty::Type};
+
+use std::{error, fmt, marker::PhantomData};
+
+use super::{Inner, Map, Other, OtherFields, Typed};
+
+/// An inner VCF header META map value.
+///
+/// A META record (VCF 4.3+) defines the type, cardinality, and, optionally, the allowed values of a
+/// SAMPLE record field with the same key as the META record ID.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Meta {
+    pub(crate) number: Number,
+    pub(crate) ty: Type,
+    pub(crate) values: Vec<String>,
+}
+
+impl Inner for Meta {
+    type StandardTag = tag::Standard;
+    type Builder = builder::Builder;
+}
+
+impl Typed for Meta {
+    type Number = Number;
+    type Type = Type;
+
+    fn number(&self) -> Number {
+        self.number
+    }
+
+    fn number_mut(&mut self) -> &mut Number {
+        &mut self.number
+    }
+
+    fn ty(&self) -> Self::Type {
+        self.ty
+    }
+
+    fn type_mut(&mut self) -> &mut Self::Type {
+        &mut self.ty
+    }
+}
+
+impl Map<Meta> {
+    /// Creates a VCF header META map value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::record::value::{map::{meta::{Number, Type}, Meta}, Map};
+    /// let map = Map::<Meta>::new(Number::Unknown, Type::String, Vec::new());
+    /// ```
+    pub fn new(number: Number, ty: Type, values: Vec<String>) -> Self {
+        Self {
+            inner: Meta { number, ty, values },
+            other_fields: OtherFields::new(),
+        }
+    }
+
+    /// Returns the allowed values.
+    ///
+    /// An empty list means any value of the field type is allowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::record::value::{map::{meta::{Number, Type}, Meta}, Map};
+    ///
+    /// let values = vec![String::from("WholeGenome"), String::from("Exome")];
+    /// let map = Map::<Meta>::new(Number::Unknown, Type::String, values.clone());
+    ///
+    /// assert_eq!(map.values(), values);
+    /// ```
+    pub fn values(&self) -> &[String] {
+        &self.inner.values
+    }
+
+    /// Returns a mutable reference to the allowed values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::record::value::{map::{meta::{Number, Type}, Meta}, Map};
+    ///
+    /// let mut map = Map::<Meta>::new(Number::Unknown, Type::String, Vec::new());
+    /// map.values_mut().push(String::from("WholeGenome"));
+    ///
+    /// assert_eq!(map.values(), [String::from("WholeGenome")]);
+    /// ```
+    pub fn values_mut(&mut self) -> &mut Vec<String> {
+        &mut self.inner.values
+    }
+}
+
+/// An error returned when a VCF header other map value fails to convert to a META map value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TryFromOtherError {
+    /// The number is missing.
+    MissingNumber,
+    /// The number is invalid.
+    InvalidNumber(number::ParseError),
+    /// The type is missing.
+    MissingType,
+    /// The type is invalid.
+    InvalidType(ty::ParseError),
+}
+
+impl error::Error for TryFromOtherError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidNumber(e) => Some(e),
+            Self::InvalidType(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TryFromOtherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingNumber => write!(f, "missing number"),
+            Self::InvalidNumber(_) => write!(f, "invalid number"),
+            Self::MissingType => write!(f, "missing type"),
+            Self::InvalidType(_) => write!(f, "invalid type"),
+        }
+    }
+}
+
+impl TryFrom<&Map<Other>> for Map<Meta> {
+    type Error = TryFromOtherError;
+
+    fn try_from(map: &Map<Other>) -> Result<Self, Self::Error> {
+        let mut number = None;
+        let mut ty = None;
+        let mut values = Vec::new();
+        let mut other_fields = OtherFields::new();
+
+        for (key, value) in map.other_fields() {
+            match Tag::from(key.as_ref()) {
+                tag::ID => {}
+                tag::NUMBER => {
+                    number = value
+                        .parse()
+                        .map(Some)
+                        .map_err(TryFromOtherError::InvalidNumber)?;
+                }
+                tag::TYPE => {
+                    ty = value
+                        .parse()
+                        .map(Some)
+                        .map_err(TryFromOtherError::InvalidType)?;
+                }
+                tag::VALUES => values = parse_values(value),
+                Tag::Other(t) => {
+                    other_fields.insert(t, value.clone());
+                }
+            }
+        }
+
+        Ok(Self {
+            inner: Meta {
+                number: number.ok_or(TryFromOtherError::MissingNumber)?,
+                ty: ty.ok_or(TryFromOtherError::MissingType)?,
+                values,
+            },
+            other_fields,
+        })
+    }
+}
+
+impl From<Map<Meta>> for Map<Other> {
+    fn from(map: Map<Meta>) -> Self {
+        let mut other_fields = OtherFields::new();
+
+        other_fields.insert(other_tag(tag::Standard::Type), map.inner.ty.to_string());
+        other_fields.insert(
+            other_tag(tag::Standard::Number),
+            map.inner.number.to_string(),
+        );
+
+        if !map.inner.values.is_empty() {
+            let values = format!("[{}]", map.inner.values.join(", "));
+            other_fields.insert(other_tag(tag::Standard::Values), values);
+        }
+
+        for (key, value) in map.other_fields {
+            other_fields.insert(super::tag::Other(key.0, PhantomData), value);
+        }
+
+        Self {
+            inner: Other::default(),
+            other_fields,
+        }
+    }
+}
+
+fn other_tag(tag: tag::Standard) -> super::tag::Other<super::other::tag::Standard> {
+    super::tag::Other(tag.as_ref().into(), PhantomData)
+}
+
+// Parses a list of values, e.g., `[WholeGenome, Exome]`. An undelimited value is a list with a
+// single value.
+pub(crate) fn parse_values(s: &str) -> Vec<String> {
+    let s = s
+        .strip_prefix('[')
+        .and_then(|t| t.strip_suffix(']'))
+        .unwrap_or(s);
+
+    s.split(',')
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_map_other_for_map_meta() -> Result<(), Box<dyn std::error::Error>> {
+        let map = Map::<Other>::builder()
+            .insert("Type".parse()?, "String")
+            .insert("Number".parse()?, ".")
+            .insert("Values".parse()?, "[WholeGenome, Exome]")
+            .insert("noodles".parse()?, "vcf")
+            .build()?;
+
+        let actual = Map::<Meta>::try_from(&map)?;
+
+        let mut expected = Map::<Meta>::new(
+            Number::Unknown,
+            Type::String,
+            vec![String::from("WholeGenome"), String::from("Exome")],
+        );
+        expected
+            .other_fields_mut()
+            .insert("noodles".parse()?, String::from("vcf"));
+
+        assert_eq!(actual, expected);
+
+        let map = Map::<Other>::builder()
+            .insert("Type".parse()?, "Integer")
+            .build()?;
+        assert_eq!(
+            Map::<Meta>::try_from(&map),
+            Err(TryFromOtherError::MissingNumber)
+        );
+
+        let map = Map::<Other>::builder()
+            .insert("Type".parse()?, "Flag")
+            .insert("Number".parse()?, "1")
+            .build()?;
+        assert!(matches!(
+            Map::<Meta>::try_from(&map),
+            Err(TryFromOtherError::InvalidType(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_map_meta_for_map_other() -> Result<(), Box<dyn std::error::Error>> {
+        let map = Map::<Meta>::new(
+            Number::Count(1),
+            Type::String,
+            vec![String::from("WholeGenome"), String::from("Exome")],
+        );
+
+        let actual = Map::<Other>::from(map);
+
+        let expected = Map::<Other>::builder()
+            .insert("Type".parse()?, "String")
+            .insert("Number".parse()?, "1")
+            .insert("Values".parse()?, "[WholeGenome, Exome]")
+            .build()?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_values() {
+        assert_eq!(
+            parse_values("[WholeGenome, Exome]"),
+            [String::from("WholeGenome"), String::from("Exome")]
+        );
+        assert_eq!(parse_values("Exome"), [String::from("Exome")]);
+        assert!(parse_values("[]").is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+use super::{Meta, Number, Type};
+use crate::header::record::value::map::{self, builder::BuildError};
+
+#[derive(Default)]
+pub struct Builder {
+    number: Option<Number>,
+    ty: Option<Type>,
+    values: Vec<String>,
+}
+
+impl map::builder::Inner<Meta> for Builder {
+    fn build(self) -> Result<Meta, BuildError> {
+        let number = self.number.ok_or(BuildError::MissingField("Number"))?;
+
+        let ty = self.ty.ok_or(BuildError::MissingField("Type"))?;
+
+        Ok(Meta {
+            number,
+            ty,
+            values: self.values,
+        })
+    }
+}
+
+impl map::builder::Typed<Meta> for Builder {
+    fn set_number(mut self, number: Number) -> Self {
+        self.number = Some(number);
+        self
+    }
+
+    fn set_type(mut self, ty: Type) -> Self {
+        self.ty = Some(ty);
+        self
+    }
+}
+
+impl map::Builder<Meta> {
+    /// Sets the allowed values.
+    pub fn set_values(mut self, values: Vec<String>) -> Self {
+        self.inner.values = values;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::record::value::Map;
+
+    #[test]
+    fn test_build() {
+        assert_eq!(
+            Map::<Meta>::builder().set_type(Type::String).build(),
+            Err(BuildError::MissingField("Number"))
+        );
+
+        assert_eq!(
+            Map::<Meta>::builder().set_number(Number::Unknown).build(),
+            Err(BuildError::MissingField("Type"))
+        );
+    }
+}
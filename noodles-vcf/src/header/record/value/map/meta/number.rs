@@ -0,0 +1,83 @@
+//! VCF header META map value number.
+
+use std::{error, fmt, num, str::FromStr};
+
+/// A VCF header META map value number.
+///
+/// This describes the cardinality of a SAMPLE field value.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Number {
+    /// An explicit size.
+    Count(usize),
+    /// The size is unknown (`.`).
+    #[default]
+    Unknown,
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Count(n) => write!(f, "{n}"),
+            Self::Unknown => f.write_str("."),
+        }
+    }
+}
+
+/// An error returned when a raw VCF header META map value number fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input is empty.
+    Empty,
+    /// The input is invalid.
+    Invalid(num::ParseIntError),
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Empty => None,
+            Self::Invalid(e) => Some(e),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty input"),
+            Self::Invalid(_) => write!(f, "invalid input"),
+        }
+    }
+}
+
+impl FromStr for Number {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Err(ParseError::Empty),
+            "." => Ok(Self::Unknown),
+            _ => s.parse().map(Self::Count).map_err(ParseError::Invalid),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fmt() {
+        assert_eq!(Number::Count(2).to_string(), "2");
+        assert_eq!(Number::Unknown.to_string(), ".");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("2".parse(), Ok(Number::Count(2)));
+        assert_eq!(".".parse(), Ok(Number::Unknown));
+
+        assert_eq!("".parse::<Number>(), Err(ParseError::Empty));
+        assert!(matches!("A".parse::<Number>(), Err(ParseError::Invalid(_))));
+    }
+}
@@ -0,0 +1,243 @@
+//! Inner VCF header PEDIGREE map value.
+
+mod builder;
+pub(crate) mod tag;
+
+pub use self::tag::Tag;
+
+use std::marker::PhantomData;
+
+use super::{Inner, Map, Other, OtherFields};
+
+/// An inner VCF header PEDIGREE map value.
+///
+/// A PEDIGREE record (VCF 4.3+) relates a sample to its parents (`Father` and `Mother`) or to the
+/// sample it is derived from (`Original`). Generic relationships (`Name_0`, `Name_1`, etc.) are
+/// stored in the other fields.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Pedigree {
+    pub(crate) father: Option<String>,
+    pub(crate) mother: Option<String>,
+    pub(crate) original: Option<String>,
+}
+
+impl Inner for Pedigree {
+    type StandardTag = tag::Standard;
+    type Builder = builder::Builder;
+}
+
+impl Map<Pedigree> {
+    /// Creates a VCF header PEDIGREE map value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::record::value::{map::Pedigree, Map};
+    /// let map = Map::<Pedigree>::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the father sample ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::record::value::{map::Pedigree, Map};
+    /// let map = Map::<Pedigree>::builder().set_father("sample0").build()?;
+    /// assert_eq!(map.father(), Some("sample0"));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn father(&self) -> Option<&str> {
+        self.inner.father.as_deref()
+    }
+
+    /// Returns a mutable reference to the father sample ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::record::value::{map::Pedigree, Map};
+    ///
+    /// let mut map = Map::<Pedigree>::new();
+    /// *map.father_mut() = Some(String::from("sample0"));
+    ///
+    /// assert_eq!(map.father(), Some("sample0"));
+    /// ```
+    pub fn father_mut(&mut self) -> &mut Option<String> {
+        &mut self.inner.father
+    }
+
+    /// Returns the mother sample ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::record::value::{map::Pedigree, Map};
+    /// let map = Map::<Pedigree>::new();
+    /// assert!(map.mother().is_none());
+    /// ```
+    pub fn mother(&self) -> Option<&str> {
+        self.inner.mother.as_deref()
+    }
+
+    /// Returns a mutable reference to the mother sample ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::record::value::{map::Pedigree, Map};
+    ///
+    /// let mut map = Map::<Pedigree>::new();
+    /// *map.mother_mut() = Some(String::from("sample1"));
+    ///
+    /// assert_eq!(map.mother(), Some("sample1"));
+    /// ```
+    pub fn mother_mut(&mut self) -> &mut Option<String> {
+        &mut self.inner.mother
+    }
+
+    /// Returns the original sample ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::record::value::{map::Pedigree, Map};
+    /// let map = Map::<Pedigree>::new();
+    /// assert!(map.original().is_none());
+    /// ```
+    pub fn original(&self) -> Option<&str> {
+        self.inner.original.as_deref()
+    }
+
+    /// Returns a mutable reference to the original sample ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::record::value::{map::Pedigree, Map};
+    ///
+    /// let mut map = Map::<Pedigree>::new();
+    /// *map.original_mut() = Some(String::from("germline"));
+    ///
+    /// assert_eq!(map.original(), Some("germline"));
+    /// ```
+    pub fn original_mut(&mut self) -> &mut Option<String> {
+        &mut self.inner.original
+    }
+
+    /// Returns all related sample IDs with their relationship names.
+    ///
+    /// This includes the standard relationships (`Father`, `Mother`, and `Original`) and generic
+    /// ones in the other fields (e.g., `Name_0`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::record::value::{map::Pedigree, Map};
+    ///
+    /// let map = Map::<Pedigree>::builder()
+    ///     .set_father("sample0")
+    ///     .insert("Name_0".parse()?, "sample2")
+    ///     .build()?;
+    ///
+    /// let relationships: Vec<_> = map.relationships().collect();
+    /// assert_eq!(relationships, [("Father", "sample0"), ("Name_0", "sample2")]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn relationships(&self) -> impl Iterator<Item = (&str, &str)> {
+        let standard = [
+            ("Father", &self.inner.father),
+            ("Mother", &self.inner.mother),
+            ("Original", &self.inner.original),
+        ]
+        .into_iter()
+        .filter_map(|(name, id)| id.as_deref().map(|id| (name, id)));
+
+        let other = self
+            .other_fields
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value.as_str()));
+
+        standard.chain(other)
+    }
+}
+
+impl From<&Map<Other>> for Map<Pedigree> {
+    fn from(map: &Map<Other>) -> Self {
+        let mut pedigree = Pedigree::default();
+        let mut other_fields = OtherFields::new();
+
+        for (key, value) in map.other_fields() {
+            match Tag::from(key.as_ref()) {
+                tag::ID => {}
+                tag::FATHER => pedigree.father = Some(value.clone()),
+                tag::MOTHER => pedigree.mother = Some(value.clone()),
+                tag::ORIGINAL => pedigree.original = Some(value.clone()),
+                Tag::Other(t) => {
+                    other_fields.insert(t, value.clone());
+                }
+            }
+        }
+
+        Self {
+            inner: pedigree,
+            other_fields,
+        }
+    }
+}
+
+impl From<Map<Pedigree>> for Map<Other> {
+    fn from(map: Map<Pedigree>) -> Self {
+        let mut other_fields = OtherFields::new();
+
+        let standard = [
+            (tag::Standard::Father, map.inner.father),
+            (tag::Standard::Mother, map.inner.mother),
+            (tag::Standard::Original, map.inner.original),
+        ];
+
+        for (tag, id) in standard {
+            if let Some(id) = id {
+                other_fields.insert(super::tag::Other(tag.as_ref().into(), PhantomData), id);
+            }
+        }
+
+        for (key, value) in map.other_fields {
+            other_fields.insert(super::tag::Other(key.0, PhantomData), value);
+        }
+
+        Self {
+            inner: Other::default(),
+            other_fields,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_map_other_for_map_pedigree() -> Result<(), Box<dyn std::error::Error>> {
+        let map = Map::<Other>::builder()
+            .insert("Father".parse()?, "sample0")
+            .insert("Mother".parse()?, "sample1")
+            .insert("Name_0".parse()?, "sample2")
+            .build()?;
+
+        let actual = Map::<Pedigree>::from(&map);
+
+        let expected = Map::<Pedigree>::builder()
+            .set_father("sample0")
+            .set_mother("sample1")
+            .insert("Name_0".parse()?, "sample2")
+            .build()?;
+
+        assert_eq!(actual, expected);
+        assert_eq!(Map::<Other>::from(actual), map);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use super::Pedigree;
+use crate::header::record::value::map::{self, builder::BuildError};
+
+#[derive(Default)]
+pub struct Builder {
+    father: Option<String>,
+    mother: Option<String>,
+    original: Option<String>,
+}
+
+impl map::builder::Inner<Pedigree> for Builder {
+    fn build(self) -> Result<Pedigree, BuildError> {
+        Ok(Pedigree {
+            father: self.father,
+            mother: self.mother,
+            original: self.original,
+        })
+    }
+}
+
+impl map::Builder<Pedigree> {
+    /// Sets the father sample ID.
+    pub fn set_father<F>(mut self, father: F) -> Self
+    where
+        F: Into<String>,
+    {
+        self.inner.father = Some(father.into());
+        self
+    }
+
+    /// Sets the mother sample ID.
+    pub fn set_mother<M>(mut self, mother: M) -> Self
+    where
+        M: Into<String>,
+    {
+        self.inner.mother = Some(mother.into());
+        self
+    }
+
+    /// Sets the original sample ID.
+    pub fn set_original<O>(mut self, original: O) -> Self
+    where
+        O: Into<String>,
+    {
+        self.inner.original = Some(original.into());
+        self
+    }
+}
@@ -0,0 +1,135 @@
+//! Inner VCF header SAMPLE map value.
+
+pub(crate) mod tag;
+
+pub use self::tag::Tag;
+
+use std::marker::PhantomData;
+
+use super::{Inner, Map, Other, OtherFields, builder};
+
+/// An inner VCF header SAMPLE map value.
+///
+/// Fields other than the description are stored in the other fields. In VCF 4.3+, their keys may
+/// be defined by META records.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Sample {
+    pub(crate) description: Option<String>,
+}
+
+impl Inner for Sample {
+    type StandardTag = tag::Standard;
+    type Builder = builder::Identity;
+}
+
+impl Map<Sample> {
+    /// Creates a VCF header SAMPLE map value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::record::value::{map::Sample, Map};
+    /// let map = Map::<Sample>::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the description.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::record::value::{map::Sample, Map};
+    /// let map = Map::<Sample>::new();
+    /// assert!(map.description().is_none());
+    /// ```
+    pub fn description(&self) -> Option<&str> {
+        self.inner.description.as_deref()
+    }
+
+    /// Returns a mutable reference to the description.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::record::value::{map::Sample, Map};
+    ///
+    /// let mut map = Map::<Sample>::new();
+    /// *map.description_mut() = Some(String::from("Patient 1"));
+    ///
+    /// assert_eq!(map.description(), Some("Patient 1"));
+    /// ```
+    pub fn description_mut(&mut self) -> &mut Option<String> {
+        &mut self.inner.description
+    }
+}
+
+impl From<&Map<Other>> for Map<Sample> {
+    fn from(map: &Map<Other>) -> Self {
+        let mut description = None;
+        let mut other_fields = OtherFields::new();
+
+        for (key, value) in map.other_fields() {
+            match Tag::from(key.as_ref()) {
+                tag::ID => {}
+                tag::DESCRIPTION => description = Some(value.clone()),
+                Tag::Other(t) => {
+                    other_fields.insert(t, value.clone());
+                }
+            }
+        }
+
+        Self {
+            inner: Sample { description },
+            other_fields,
+        }
+    }
+}
+
+impl From<Map<Sample>> for Map<Other> {
+    fn from(map: Map<Sample>) -> Self {
+        let mut other_fields = OtherFields::new();
+
+        if let Some(description) = map.inner.description {
+            let tag = super::tag::Other(tag::DESCRIPTION.to_string(), PhantomData);
+            other_fields.insert(tag, description);
+        }
+
+        for (key, value) in map.other_fields {
+            other_fields.insert(super::tag::Other(key.0, PhantomData), value);
+        }
+
+        Self {
+            inner: Other::default(),
+            other_fields,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_map_other_for_map_sample() -> Result<(), Box<dyn std::error::Error>> {
+        let map = Map::<Other>::builder()
+            .insert("Assay".parse()?, "WholeGenome")
+            .insert("Description".parse()?, "Patient 1")
+            .build()?;
+
+        let actual = Map::<Sample>::from(&map);
+
+        let mut expected = Map::<Sample>::builder()
+            .insert("Assay".parse()?, "WholeGenome")
+            .build()?;
+        *expected.description_mut() = Some(String::from("Patient 1"));
+
+        assert_eq!(actual, expected);
+
+        let map = Map::<Other>::from(actual);
+        assert_eq!(Map::<Sample>::from(&map), expected);
+
+        Ok(())
+    }
+}
@@ -38,6 +38,32 @@ impl StringMap {
         self.indices.get(value).copied()
     }
 
+    /// Returns the number of entries, including unset indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::string_maps::StringMap;
+    /// let string_map = StringMap::default();
+    /// assert_eq!(string_map.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the map has any entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::header::string_maps::StringMap;
+    /// let string_map = StringMap::default();
+    /// assert!(string_map.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
     pub(super) fn get_full(&self, value: &str) -> Option<(usize, &str)> {
         self.get_index_of(value)
             .and_then(|i| self.get_index(i).map(|entry| (i, entry)))
@@ -2,10 +2,11 @@
 
 mod compression_method;
 pub mod indexed_reader;
+mod indexed_writer;
 pub mod reader;
 pub mod writer;
 
 pub use self::{
-    compression_method::CompressionMethod, indexed_reader::IndexedReader, reader::Reader,
-    writer::Writer,
+    compression_method::CompressionMethod, indexed_reader::IndexedReader,
+    indexed_writer::IndexedWriter, reader::Reader, writer::Writer,
 };
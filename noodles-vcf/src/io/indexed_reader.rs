@@ -90,6 +90,17 @@ where
     ) -> io::Result<Query<'r, 'h, R>> {
         self.inner.query(header, &self.index, region)
     }
+
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// Each record is returned along with the indices of the regions it intersects.
+    pub fn query_regions<'r, 'h>(
+        &'r mut self,
+        header: &'h Header,
+        regions: &[Region],
+    ) -> io::Result<impl Iterator<Item = io::Result<(Record, Vec<usize>)>> + use<'r, 'h, R>> {
+        self.inner.query_regions(header, &self.index, regions)
+    }
 }
 
 impl<R> IndexedReader<bgzf::io::Reader<R>>
@@ -107,3 +118,66 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+
+    use super::*;
+    use crate::{header::record::value::Map, variant::io::Write as _};
+
+    #[test]
+    fn test_query_regions() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_contig("sq0", Map::default())
+            .add_contig("sq1", Map::default())
+            .build();
+
+        let mut writer = crate::io::IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for (name, position) in [("sq0", 8), ("sq0", 13), ("sq1", 5)] {
+            let record = RecordBuf::builder()
+                .set_reference_sequence_name(name)
+                .set_variant_start(Position::try_from(position)?)
+                .set_reference_bases("A")
+                .build();
+
+            writer.write_variant_record(&header, &record)?;
+        }
+
+        let index = writer.try_finish()?;
+        let data = writer.into_inner().into_inner();
+
+        let mut reader = IndexedReader::new(io::Cursor::new(data), index);
+        let header = reader.read_header()?;
+
+        let regions = [
+            "sq0:1-10".parse()?,
+            "sq0:8-13".parse()?,
+            "sq1:5-5".parse()?,
+            "sq1:6-10".parse()?,
+        ];
+
+        let actual: Vec<_> = reader
+            .query_regions(&header, &regions)?
+            .map(|result| {
+                result.and_then(|(record, region_indices)| {
+                    let name = record.reference_sequence_name().to_string();
+                    let start = record.variant_start().transpose()?;
+                    Ok((name, start, region_indices))
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (String::from("sq0"), Position::new(8), vec![0, 1]),
+            (String::from("sq0"), Position::new(13), vec![1]),
+            (String::from("sq1"), Position::new(5), vec![2]),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,302 @@
+//! Indexed VCF writer.
+
+use std::{
+    collections::HashSet,
+    io::{self, Write},
+    mem,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+use noodles_csi::{self as csi, binning_index::index::reference_sequence::bin::Chunk};
+use noodles_tabix as tabix;
+
+use super::Writer;
+use crate::{Header, Record, variant::io::Write as _};
+
+/// An indexed VCF writer.
+///
+/// This writes a bgzipped VCF and builds a tabix index (TBI) from the records as they are
+/// written, i.e., without a second pass over the output. Records must be coordinate-sorted.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_vcf::{self as vcf, variant::{io::Write, RecordBuf}};
+///
+/// let mut writer = vcf::io::IndexedWriter::new(Vec::new());
+///
+/// let header = vcf::Header::default();
+/// writer.write_header(&header)?;
+///
+/// let record = RecordBuf::builder()
+///     .set_reference_sequence_name("sq0")
+///     .set_variant_start(Position::MIN)
+///     .set_reference_bases("A")
+///     .build();
+///
+/// writer.write_variant_record(&header, &record)?;
+///
+/// let index = writer.try_finish()?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub struct IndexedWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<bgzf::io::Writer<W>>,
+    indexer: tabix::index::Indexer,
+    previous: Option<(String, Position)>,
+    finished_reference_sequence_names: HashSet<String>,
+}
+
+impl<W> IndexedWriter<W>
+where
+    W: Write,
+{
+    /// Creates an indexed VCF writer.
+    ///
+    /// The given stream is wrapped in a BGZF encoder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let writer = vcf::io::IndexedWriter::new(Vec::new());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self::from(Writer::new(bgzf::io::Writer::new(inner)))
+    }
+
+    /// Returns a reference to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let writer = vcf::io::IndexedWriter::new(Vec::new());
+    /// assert!(writer.get_ref().get_ref().is_empty());
+    /// ```
+    pub fn get_ref(&self) -> &bgzf::io::Writer<W> {
+        self.inner.get_ref()
+    }
+
+    /// Returns the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    /// let writer = vcf::io::IndexedWriter::new(Vec::new());
+    /// assert!(writer.into_inner().get_ref().is_empty());
+    /// ```
+    pub fn into_inner(self) -> bgzf::io::Writer<W> {
+        self.inner.into_inner()
+    }
+
+    /// Writes a VCF header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut writer = vcf::io::IndexedWriter::new(Vec::new());
+    ///
+    /// let header = vcf::Header::default();
+    /// writer.write_header(&header)?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn write_header(&mut self, header: &Header) -> io::Result<()> {
+        self.inner.write_header(header)
+    }
+
+    /// Writes a VCF record and adds it to the index.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the record has no position or is not coordinate-sorted relative to
+    /// the previously written records.
+    pub fn write_record(&mut self, header: &Header, record: &Record) -> io::Result<()> {
+        self.write_variant_record(header, record)
+    }
+
+    /// Attempts to finish the output stream and returns the built index.
+    ///
+    /// The index can be written using [`tabix::fs::write`] or a [`tabix::io::Writer`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut writer = vcf::io::IndexedWriter::new(Vec::new());
+    ///
+    /// let header = vcf::Header::default();
+    /// writer.write_header(&header)?;
+    ///
+    /// let index = writer.try_finish()?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn try_finish(&mut self) -> io::Result<tabix::Index> {
+        self.inner.get_mut().try_finish()?;
+        let indexer = mem::take(&mut self.indexer);
+        Ok(indexer.build())
+    }
+}
+
+impl<W> From<Writer<bgzf::io::Writer<W>>> for IndexedWriter<W>
+where
+    W: Write,
+{
+    fn from(inner: Writer<bgzf::io::Writer<W>>) -> Self {
+        let mut indexer = tabix::index::Indexer::default();
+        indexer.set_header(csi::binning_index::index::header::Builder::vcf().build());
+
+        Self {
+            inner,
+            indexer,
+            previous: None,
+            finished_reference_sequence_names: HashSet::new(),
+        }
+    }
+}
+
+impl<W> crate::variant::io::Write for IndexedWriter<W>
+where
+    W: Write,
+{
+    fn write_variant_header(&mut self, header: &Header) -> io::Result<()> {
+        self.write_header(header)
+    }
+
+    fn write_variant_record(
+        &mut self,
+        header: &Header,
+        record: &dyn crate::variant::Record,
+    ) -> io::Result<()> {
+        let reference_sequence_name = record.reference_sequence_name(header)?;
+
+        let start = record
+            .variant_start()
+            .transpose()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing position"))?;
+
+        let end = record.variant_end(header)?;
+
+        // Records are checked before they are written so that the output stays sorted.
+        let is_sorted = match &self.previous {
+            Some((name, position)) if name == reference_sequence_name => start >= *position,
+            _ => !self
+                .finished_reference_sequence_names
+                .contains(reference_sequence_name),
+        };
+
+        if !is_sorted {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "record is not coordinate-sorted",
+            ));
+        }
+
+        match &mut self.previous {
+            Some((name, position)) if name == reference_sequence_name => *position = start,
+            previous => {
+                if let Some((name, _)) = previous.replace((reference_sequence_name.into(), start)) {
+                    self.finished_reference_sequence_names.insert(name);
+                }
+            }
+        }
+
+        let start_position = self.inner.get_ref().virtual_position();
+        self.inner.write_variant_record(header, record)?;
+        let end_position = self.inner.get_ref().virtual_position();
+
+        let chunk = Chunk::new(start_position, end_position);
+
+        self.indexer
+            .add_record(reference_sequence_name, start, end, chunk)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Region;
+
+    use super::*;
+    use crate::{header::record::value::Map, variant::RecordBuf};
+
+    fn build_record(reference_sequence_name: &str, position: usize) -> io::Result<RecordBuf> {
+        let variant_start = Position::try_from(position)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        Ok(RecordBuf::builder()
+            .set_reference_sequence_name(reference_sequence_name)
+            .set_variant_start(variant_start)
+            .set_reference_bases("A")
+            .build())
+    }
+
+    #[test]
+    fn test_try_finish() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_contig("sq0", Map::default())
+            .add_contig("sq1", Map::default())
+            .build();
+
+        let mut writer = IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for (name, position) in [("sq0", 8), ("sq0", 13), ("sq1", 5)] {
+            let record = build_record(name, position)?;
+            writer.write_variant_record(&header, &record)?;
+        }
+
+        let index = writer.try_finish()?;
+        let data = writer.into_inner().into_inner();
+
+        let mut reader = crate::io::IndexedReader::new(io::Cursor::new(data), index);
+        let header = reader.read_header()?;
+
+        let region: Region = "sq0:10-20".parse()?;
+        let records: Vec<_> = reader
+            .query(&header, &region)?
+            .records()
+            .collect::<io::Result<_>>()?;
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].variant_start().transpose()?, Position::new(13));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_variant_record_with_unsorted_records() -> io::Result<()> {
+        let header = Header::default();
+
+        let mut writer = IndexedWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        writer.write_variant_record(&header, &build_record("sq0", 13)?)?;
+
+        assert!(matches!(
+            writer.write_variant_record(&header, &build_record("sq0", 8)?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        writer.write_variant_record(&header, &build_record("sq1", 5)?)?;
+        let position = writer.get_ref().virtual_position();
+
+        assert!(matches!(
+            writer.write_variant_record(&header, &build_record("sq0", 21)?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        // The rejected record is not written.
+        assert_eq!(writer.get_ref().virtual_position(), position);
+
+        Ok(())
+    }
+}
@@ -18,7 +18,7 @@ use std::{
 
 use noodles_bgzf as bgzf;
 use noodles_core::Region;
-use noodles_csi::BinningIndex;
+use noodles_csi::{self as csi, BinningIndex, binning_index::RegionSet};
 
 use self::header::read_header;
 use crate::{Header, Record, variant::RecordBuf};
@@ -372,6 +372,69 @@ where
             header,
         ))
     }
+
+    /// Returns an iterator over records that intersect any of the given regions.
+    ///
+    /// The chunks of all regions are merged, so each block is read at most once, and each record
+    /// is returned once, along with the indices of the regions it intersects, in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::fs::File;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_tabix as tabix;
+    /// use noodles_vcf as vcf;
+    ///
+    /// let mut reader = File::open("sample.vcf.gz")
+    ///     .map(bgzf::io::Reader::new)
+    ///     .map(vcf::io::Reader::new)?;
+    ///
+    /// let header = reader.read_header()?;
+    ///
+    /// let index = tabix::fs::read("sample.vcf.gz.tbi")?;
+    /// let regions = ["sq0:8-13".parse()?, "sq1:21-34".parse()?];
+    ///
+    /// for result in reader.query_regions(&header, &index, &regions)? {
+    ///     let (record, region_indices) = result?;
+    ///     // ...
+    /// }
+    /// Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query_regions<'r, 'h, I>(
+        &'r mut self,
+        header: &'h Header,
+        index: &'r I,
+        regions: &[Region],
+    ) -> io::Result<impl Iterator<Item = io::Result<(Record, Vec<usize>)>> + use<'r, 'h, I, R>>
+    where
+        I: BinningIndex,
+    {
+        let region_set = regions
+            .iter()
+            .map(|region| resolve_region(index, region).map(|(id, _)| (id, region.interval())))
+            .collect::<io::Result<Vec<_>>>()
+            .map(RegionSet::new)?;
+
+        let chunks = region_set.query(index)?;
+        let mut reader = Reader::new(csi::io::Query::new(self.get_mut(), chunks));
+
+        Ok(iter::from_fn(move || {
+            loop {
+                let mut record = Record::default();
+
+                match reader.read_record(&mut record) {
+                    Ok(0) => return None,
+                    Ok(_) => match intersecting_regions(header, index, &region_set, &record) {
+                        Ok(region_indices) if region_indices.is_empty() => {}
+                        Ok(region_indices) => return Some(Ok((record, region_indices))),
+                        Err(e) => return Some(Err(e)),
+                    },
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+        }))
+    }
 }
 
 impl<R> crate::variant::io::Read<R> for Reader<R>
@@ -421,6 +484,40 @@ where
     }
 }
 
+fn intersecting_regions<I>(
+    header: &Header,
+    index: &I,
+    region_set: &RegionSet,
+    record: &Record,
+) -> io::Result<Vec<usize>>
+where
+    I: BinningIndex,
+{
+    use crate::variant::Record as _;
+
+    let reference_sequence_id = index
+        .header()
+        .and_then(|index_header| {
+            index_header
+                .reference_sequence_names()
+                .get_index_of(record.reference_sequence_name().as_bytes())
+        })
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "record reference sequence does not exist in reference sequences",
+            )
+        })?;
+
+    let Some(start) = record.variant_start().transpose()? else {
+        return Ok(Vec::new());
+    };
+
+    let end = record.variant_end(header)?;
+
+    Ok(region_set.intersecting(reference_sequence_id, (start..=end).into()))
+}
+
 pub(crate) fn resolve_region<I>(index: &I, region: &Region) -> io::Result<(usize, Vec<u8>)>
 where
     I: BinningIndex,
@@ -109,6 +109,7 @@ where
     W: Write,
 {
     const META: &str = "META";
+    const SAMPLE: &str = "SAMPLE";
 
     match collection {
         Collection::Unstructured(vs) => {
@@ -122,6 +123,8 @@ where
                     value::write_other_map(w, map.id_tag(), id, |x| {
                         if key.as_ref() == META {
                             value::map::write_meta(x, map)
+                        } else if key.as_ref() == SAMPLE {
+                            value::map::write_sample(x, map)
                         } else {
                             value::map::write_other(x, map)
                         }
@@ -5,12 +5,14 @@ mod format;
 mod info;
 mod meta;
 mod other;
+mod sample;
 
 use std::io::{self, Write};
 
 pub(crate) use self::{
     alternative_allele::write_alternative_allele, contig::write_contig, filter::write_filter,
     format::write_format, info::write_info, meta::write_meta, other::write_other,
+    sample::write_sample,
 };
 use crate::{
     header::record::value::map::{self, OtherFields, tag},
@@ -0,0 +1,44 @@
+use std::io::{self, Write};
+
+use super::{write_delimiter, write_string_field, write_value_field};
+use crate::header::record::value::{Map, map::Other};
+
+pub(crate) fn write_sample<W>(writer: &mut W, sample: &Map<Other>) -> io::Result<()>
+where
+    W: Write,
+{
+    for (key, value) in sample.other_fields() {
+        write_delimiter(writer)?;
+
+        if is_list(value) {
+            write_value_field(writer, key, value)?;
+        } else {
+            write_string_field(writer, key, value)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn is_list(s: &str) -> bool {
+    s.starts_with('[') && s.ends_with(']')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_sample() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buf = Vec::new();
+
+        let map = Map::<Other>::builder()
+            .insert("Tissue".parse()?, "[Blood, Saliva]")
+            .insert("Description".parse()?, "Patient 1")
+            .build()?;
+        write_sample(&mut buf, &map)?;
+        assert_eq!(buf, br#",Tissue=[Blood, Saliva],Description="Patient 1""#);
+
+        Ok(())
+    }
+}
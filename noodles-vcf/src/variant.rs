@@ -1,7 +1,13 @@
 //! Variant format.
 
+pub mod genotypes;
 pub mod io;
+pub mod local_alleles;
+pub mod multiallelic;
+#[cfg(feature = "fasta")]
+pub mod normalization;
 pub mod record;
 pub mod record_buf;
+pub mod validation;
 
 pub use self::{record::Record, record_buf::RecordBuf};
@@ -0,0 +1,64 @@
+//! Variant record genotype summaries.
+//!
+//! These work with any [`Record`], e.g., VCF and BCF records.
+
+mod allele_counts;
+mod matrix;
+
+pub use self::{
+    allele_counts::{AlleleCounts, count_alleles, update_allele_count_fields},
+    matrix::{END_OF_VECTOR, MISSING, extend_matrix, read_matrix},
+};
+
+use std::io;
+
+use super::{
+    Record,
+    record::samples::{
+        Samples,
+        keys::key,
+        series::{Value, value::Genotype},
+    },
+};
+use crate::Header;
+
+// Calls `f` with the genotype of each sample, or `None` if the sample has no genotype.
+fn for_each_genotype<R, F>(header: &Header, record: &R, mut f: F) -> io::Result<()>
+where
+    R: Record + ?Sized,
+    F: FnMut(Option<&dyn Genotype>) -> io::Result<()>,
+{
+    let sample_count = header.sample_names().len();
+    let samples = record.samples()?;
+
+    let Some(series) = samples.select(header, key::GENOTYPE).transpose()? else {
+        for _ in 0..sample_count {
+            f(None)?;
+        }
+
+        return Ok(());
+    };
+
+    let mut n = 0;
+
+    for result in series.iter(header) {
+        match result? {
+            Some(Value::Genotype(genotype)) => f(Some(genotype.as_ref()))?,
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "invalid GT value type",
+                ));
+            }
+            None => f(None)?,
+        }
+
+        n += 1;
+    }
+
+    for _ in n..sample_count {
+        f(None)?;
+    }
+
+    Ok(())
+}
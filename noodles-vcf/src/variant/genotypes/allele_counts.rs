@@ -0,0 +1,309 @@
+use std::io;
+
+use super::for_each_genotype;
+use crate::{
+    Header,
+    variant::{
+        Record, RecordBuf,
+        record::{AlternateBases, info::field::key},
+        record_buf::info::field::Value,
+    },
+};
+
+/// Allele counts across all samples.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AlleleCounts(Vec<usize>);
+
+impl AlleleCounts {
+    /// Returns the number of called alleles for each allele, starting with the reference allele.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::variant::genotypes::AlleleCounts;
+    /// let counts = AlleleCounts::default();
+    /// assert!(counts.counts().is_empty());
+    /// ```
+    pub fn counts(&self) -> &[usize] {
+        &self.0
+    }
+
+    /// Returns the number of called alleles for each alternate allele (`AC`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::variant::genotypes::AlleleCounts;
+    /// let counts = AlleleCounts::default();
+    /// assert!(counts.alternate_allele_counts().is_empty());
+    /// ```
+    pub fn alternate_allele_counts(&self) -> &[usize] {
+        self.0.get(1..).unwrap_or_default()
+    }
+
+    /// Returns the total number of called alleles (`AN`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::variant::genotypes::AlleleCounts;
+    /// let counts = AlleleCounts::default();
+    /// assert_eq!(counts.allele_number(), 0);
+    /// ```
+    pub fn allele_number(&self) -> usize {
+        self.0.iter().sum()
+    }
+
+    /// Returns the frequency of each alternate allele (`AF`).
+    ///
+    /// This returns `None` if there are no called alleles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::variant::genotypes::AlleleCounts;
+    /// let counts = AlleleCounts::default();
+    /// assert!(counts.alternate_allele_frequencies().is_none());
+    /// ```
+    pub fn alternate_allele_frequencies(&self) -> Option<Vec<f32>> {
+        let n = self.allele_number();
+
+        if n == 0 {
+            return None;
+        }
+
+        let frequencies = self
+            .alternate_allele_counts()
+            .iter()
+            .map(|&count| (count as f64 / n as f64) as f32)
+            .collect();
+
+        Some(frequencies)
+    }
+}
+
+/// Counts the called alleles in the genotypes (`GT`) of all samples.
+///
+/// # Errors
+///
+/// An error is returned if a genotype cannot be read or if it has an allele position that is not
+/// an allele of the record.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{
+///     self as vcf,
+///     variant::{
+///         genotypes,
+///         record::samples::keys::key,
+///         record_buf::{RecordBuf, Samples, samples::sample::Value},
+///     },
+/// };
+///
+/// let header = vcf::Header::builder()
+///     .add_sample_name("sample0")
+///     .add_sample_name("sample1")
+///     .build();
+///
+/// let keys = [String::from(key::GENOTYPE)].into_iter().collect();
+///
+/// let record = RecordBuf::builder()
+///     .set_reference_bases("A")
+///     .set_alternate_bases(vec![String::from("C")].into())
+///     .set_samples(Samples::new(
+///         keys,
+///         vec![
+///             vec![Some(Value::Genotype("0/1".parse()?))],
+///             vec![Some(Value::Genotype("1/.".parse()?))],
+///         ],
+///     ))
+///     .build();
+///
+/// let counts = genotypes::count_alleles(&header, &record)?;
+/// assert_eq!(counts.counts(), [1, 2]);
+/// assert_eq!(counts.allele_number(), 3);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn count_alleles<R>(header: &Header, record: &R) -> io::Result<AlleleCounts>
+where
+    R: Record + ?Sized,
+{
+    let allele_count = record.alternate_bases().len() + 1;
+    let mut counts = vec![0; allele_count];
+
+    for_each_genotype(header, record, |genotype| {
+        let Some(genotype) = genotype else {
+            return Ok(());
+        };
+
+        for result in genotype.iter() {
+            let (position, _) = result?;
+
+            if let Some(i) = position {
+                let count = counts.get_mut(i).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid genotype allele position: {i}"),
+                    )
+                })?;
+
+                *count += 1;
+            }
+        }
+
+        Ok(())
+    })?;
+
+    Ok(AlleleCounts(counts))
+}
+
+/// Recomputes the allele count fields (`AC`, `AN`, and `AF`) of a record from its genotypes.
+///
+/// `AF` is set to missing if there are no called alleles.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{
+///     self as vcf,
+///     variant::{
+///         genotypes,
+///         record::{info::field::key as info_key, samples::keys::key},
+///         record_buf::{RecordBuf, Samples, info::field::Value as InfoValue, samples::sample::Value},
+///     },
+/// };
+///
+/// let header = vcf::Header::builder().add_sample_name("sample0").build();
+///
+/// let keys = [String::from(key::GENOTYPE)].into_iter().collect();
+///
+/// let mut record = RecordBuf::builder()
+///     .set_reference_bases("A")
+///     .set_alternate_bases(vec![String::from("C")].into())
+///     .set_samples(Samples::new(
+///         keys,
+///         vec![vec![Some(Value::Genotype("0/1".parse()?))]],
+///     ))
+///     .build();
+///
+/// genotypes::update_allele_count_fields(&header, &mut record)?;
+///
+/// assert_eq!(
+///     record.info().get(info_key::TOTAL_ALLELE_COUNT),
+///     Some(Some(&InfoValue::from(2)))
+/// );
+/// assert_eq!(
+///     record.info().get(info_key::ALLELE_FREQUENCIES),
+///     Some(Some(&InfoValue::from(vec![Some(0.5)])))
+/// );
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn update_allele_count_fields(
+    header: &Header,
+    record: &mut RecordBuf,
+) -> io::Result<AlleleCounts> {
+    fn to_i32(n: usize) -> io::Result<i32> {
+        i32::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    let counts = count_alleles(header, record)?;
+
+    let alternate_allele_counts = counts
+        .alternate_allele_counts()
+        .iter()
+        .map(|&n| to_i32(n).map(Some))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let allele_number = to_i32(counts.allele_number())?;
+
+    let alternate_allele_frequencies = counts
+        .alternate_allele_frequencies()
+        .map(|frequencies| Value::from(frequencies.into_iter().map(Some).collect::<Vec<_>>()));
+
+    let info = record.info_mut();
+
+    info.insert(
+        String::from(key::ALLELE_COUNT),
+        Some(Value::from(alternate_allele_counts)),
+    );
+
+    info.insert(
+        String::from(key::TOTAL_ALLELE_COUNT),
+        Some(Value::from(allele_number)),
+    );
+
+    info.insert(
+        String::from(key::ALLELE_FREQUENCIES),
+        alternate_allele_frequencies,
+    );
+
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variant::{
+        record::samples::keys::key as format_key,
+        record_buf::{Samples, samples::sample::Value as SampleValue},
+    };
+
+    #[test]
+    fn test_count_alleles() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .add_sample_name("sample2")
+            .build();
+
+        let keys = [String::from(format_key::GENOTYPE)].into_iter().collect();
+
+        let record = RecordBuf::builder()
+            .set_reference_bases("A")
+            .set_alternate_bases(vec![String::from("C"), String::from("G")].into())
+            .set_samples(Samples::new(
+                keys,
+                vec![
+                    vec![Some(SampleValue::Genotype("0|2".parse()?))],
+                    vec![Some(SampleValue::Genotype("1".parse()?))],
+                    vec![None],
+                ],
+            ))
+            .build();
+
+        let counts = count_alleles(&header, &record)?;
+        assert_eq!(counts.counts(), [1, 1, 1]);
+        assert_eq!(counts.alternate_allele_counts(), [1, 1]);
+        assert_eq!(counts.allele_number(), 3);
+
+        let frequencies = counts.alternate_allele_frequencies().unwrap();
+        assert_eq!(frequencies.len(), 2);
+        assert!((frequencies[0] - 1.0 / 3.0).abs() < f32::EPSILON);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_alleles_with_invalid_allele_position() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder().add_sample_name("sample0").build();
+
+        let keys = [String::from(format_key::GENOTYPE)].into_iter().collect();
+
+        let record = RecordBuf::builder()
+            .set_reference_bases("A")
+            .set_alternate_bases(vec![String::from("C")].into())
+            .set_samples(Samples::new(
+                keys,
+                vec![vec![Some(SampleValue::Genotype("0/2".parse()?))]],
+            ))
+            .build();
+
+        assert!(matches!(
+            count_alleles(&header, &record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
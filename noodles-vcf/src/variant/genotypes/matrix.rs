@@ -0,0 +1,191 @@
+use std::io;
+
+use super::for_each_genotype;
+use crate::{Header, variant::Record};
+
+/// The genotype matrix value for a missing allele.
+pub const MISSING: i8 = -1;
+
+/// The genotype matrix value that pads a genotype with fewer alleles than the matrix ploidy.
+pub const END_OF_VECTOR: i8 = -2;
+
+/// Appends the genotypes of a record to a genotype matrix.
+///
+/// The matrix is row-major with one row per record. Each row has `ploidy` values for each sample,
+/// in header order. A value is an allele position, [`MISSING`] for a missing allele, or
+/// [`END_OF_VECTOR`] when a genotype has fewer alleles than `ploidy`. Samples without a genotype
+/// are filled with [`MISSING`].
+///
+/// # Errors
+///
+/// An error is returned if a genotype has more alleles than `ploidy` or if an allele position does
+/// not fit in an `i8`.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{
+///     self as vcf,
+///     variant::{
+///         genotypes::{self, END_OF_VECTOR, MISSING},
+///         record::samples::keys::key,
+///         record_buf::{RecordBuf, Samples, samples::sample::Value},
+///     },
+/// };
+///
+/// let header = vcf::Header::builder()
+///     .add_sample_name("sample0")
+///     .add_sample_name("sample1")
+///     .build();
+///
+/// let keys = [String::from(key::GENOTYPE)].into_iter().collect();
+///
+/// let record = RecordBuf::builder()
+///     .set_samples(Samples::new(
+///         keys,
+///         vec![
+///             vec![Some(Value::Genotype("0/.".parse()?))],
+///             vec![Some(Value::Genotype("1".parse()?))],
+///         ],
+///     ))
+///     .build();
+///
+/// let mut matrix = Vec::new();
+/// genotypes::extend_matrix(&header, &record, 2, &mut matrix)?;
+/// assert_eq!(matrix, [0, MISSING, 1, END_OF_VECTOR]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn extend_matrix<R>(
+    header: &Header,
+    record: &R,
+    ploidy: usize,
+    dst: &mut Vec<i8>,
+) -> io::Result<()>
+where
+    R: Record + ?Sized,
+{
+    let start = dst.len();
+    dst.reserve(header.sample_names().len() * ploidy);
+
+    let result = for_each_genotype(header, record, |genotype| {
+        let Some(genotype) = genotype else {
+            dst.extend(std::iter::repeat_n(MISSING, ploidy));
+            return Ok(());
+        };
+
+        let mut n = 0;
+
+        for result in genotype.iter() {
+            let (position, _) = result?;
+
+            if n == ploidy {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("genotype ploidy exceeds {ploidy}"),
+                ));
+            }
+
+            let value = match position {
+                Some(i) => {
+                    i8::try_from(i).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+                }
+                None => MISSING,
+            };
+
+            dst.push(value);
+            n += 1;
+        }
+
+        dst.extend(std::iter::repeat_n(END_OF_VECTOR, ploidy - n));
+
+        Ok(())
+    });
+
+    if result.is_err() {
+        dst.truncate(start);
+    }
+
+    result
+}
+
+/// Reads the genotypes of records into a genotype matrix.
+///
+/// This appends a row for each record using [`extend_matrix`] and returns the number of records
+/// read. Pass the records of a region query to build the matrix of a genomic range.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+/// use noodles_vcf::{self as vcf, variant::{genotypes, RecordBuf}};
+///
+/// let header = vcf::Header::builder().add_sample_name("sample0").build();
+/// let records = [RecordBuf::default(), RecordBuf::default()];
+///
+/// let mut matrix = Vec::new();
+/// let n = genotypes::read_matrix(&header, records.into_iter().map(Ok), 2, &mut matrix)?;
+///
+/// assert_eq!(n, 2);
+/// assert_eq!(matrix, [genotypes::MISSING; 4]);
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn read_matrix<I, R>(
+    header: &Header,
+    records: I,
+    ploidy: usize,
+    dst: &mut Vec<i8>,
+) -> io::Result<usize>
+where
+    I: IntoIterator<Item = io::Result<R>>,
+    R: Record,
+{
+    let mut n = 0;
+
+    for result in records {
+        let record = result?;
+        extend_matrix(header, &record, ploidy, dst)?;
+        n += 1;
+    }
+
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variant::{
+        RecordBuf,
+        record::samples::keys::key,
+        record_buf::{Samples, samples::sample::Value},
+    };
+
+    #[test]
+    fn test_extend_matrix() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .build();
+
+        let keys = [String::from(key::GENOTYPE)].into_iter().collect();
+
+        let record = RecordBuf::builder()
+            .set_samples(Samples::new(
+                keys,
+                vec![
+                    vec![Some(Value::Genotype("0|1".parse()?))],
+                    vec![Some(Value::Genotype("1/1/2".parse()?))],
+                ],
+            ))
+            .build();
+
+        let mut matrix = vec![0];
+        extend_matrix(&header, &record, 3, &mut matrix)?;
+        assert_eq!(matrix, [0, 0, 1, END_OF_VECTOR, 1, 1, 2]);
+
+        let mut matrix = vec![0];
+        assert!(extend_matrix(&header, &record, 2, &mut matrix).is_err());
+        assert_eq!(matrix, [0]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,880 @@
+//! Local-allele sample field expansion and compaction.
+//!
+//! VCF 4.5 adds local-allele representations of allele-specific sample fields. Rather than
+//! having values for every allele, a sample lists the alternate alleles relevant to it in `LAA`
+//! (1-based indices into ALT), and local fields, e.g., `LAD` and `LPL`, only have values for the
+//! reference allele and these local alternate alleles. This keeps sample fields small for sites
+//! with many alternate alleles.
+//!
+//! Local fields use the `LA`, `LR`, and `LG` numbers. The reserved local fields and their global
+//! counterparts are `LAD` (`AD`), `LADF` (`ADF`), `LADR` (`ADR`), `LEC` (`EC`), `LGL` (`GL`),
+//! `LGP` (`GP`), `LPL` (`PL`), and `LPP` (`PP`). The local genotype `LGT` has alleles indexed into
+//! the local alleles and corresponds to `GT`.
+
+use std::{collections::BTreeSet, io, mem};
+
+use super::{
+    RecordBuf,
+    multiallelic::{Array, genotype_count, genotype_index, genotypes, ploidy},
+    record::samples::keys::key,
+    record_buf::samples::{
+        Keys, Samples,
+        sample::{
+            Value,
+            value::{Array as SampleArray, Genotype},
+        },
+    },
+};
+use crate::{Header, header::record::value::map::format::Number};
+
+// Local genotype (`LGT`).
+const LOCAL_GENOTYPE: &str = "LGT";
+
+// Local fields with `LA`, `LR`, or `LG` numbers and their global counterparts.
+const LOCAL_KEYS: [(&str, &str); 8] = [
+    (key::LOCAL_READ_DEPTHS, key::READ_DEPTHS),
+    (
+        key::LOCAL_FORWARD_STRAND_READ_DEPTHS,
+        key::FORWARD_STRAND_READ_DEPTHS,
+    ),
+    (
+        key::LOCAL_REVERSE_STRAND_READ_DEPTHS,
+        key::REVERSE_STRAND_READ_DEPTHS,
+    ),
+    (
+        key::LOCAL_EXPECTED_ALTERNATE_ALLELE_COUNTS,
+        key::EXPECTED_ALTERNATE_ALLELE_COUNTS,
+    ),
+    (key::LOCAL_GENOTYPE_LIKELIHOODS, key::GENOTYPE_LIKELIHOODS),
+    (
+        key::LOCAL_GENOTYPE_POSTERIOR_PROBABILITIES,
+        key::GENOTYPE_POSTERIOR_PROBABILITIES,
+    ),
+    (
+        key::LOCAL_ROUNDED_GENOTYPE_LIKELIHOODS,
+        key::ROUNDED_GENOTYPE_LIKELIHOODS,
+    ),
+    (
+        key::LOCAL_ROUNDED_GENOTYPE_POSTERIOR_PROBABILITIES,
+        key::ROUNDED_GENOTYPE_POSTERIOR_PROBABILITIES,
+    ),
+];
+
+/// Expands local-allele sample fields into global fields.
+///
+/// For each sample, values of the reserved local fields with `LA`, `LR`, or `LG` numbers are
+/// reindexed from the local alleles listed in `LAA` to all alleles and moved to their global
+/// counterparts, e.g., `LPL` to `PL`. Values for alleles that are not local to a sample are set to
+/// missing. The local genotype (`LGT`) alleles are mapped through the local alleles and moved to
+/// `GT`. The local fields and `LAA` are then removed.
+///
+/// If a record already has the global counterpart of a local field, the local field is removed
+/// and the global field is kept as is. Other fields, including local fields without a reserved
+/// global counterpart, are kept as is. Records without `LAA` are left unchanged.
+///
+/// # Errors
+///
+/// An error is returned if an `LAA` value is not a valid alternate allele index or if an `LGT`
+/// allele is not a valid local allele index.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{
+///     self as vcf,
+///     header::FileFormat,
+///     variant::{
+///         local_alleles,
+///         record::samples::keys::key,
+///         record_buf::{RecordBuf, Samples, samples::sample::Value},
+///     },
+/// };
+///
+/// let header = vcf::Header::builder()
+///     .set_file_format(FileFormat::new(4, 5))
+///     .add_sample_name("sample0")
+///     .build();
+///
+/// let keys = [
+///     String::from(key::LOCAL_ALTERNATIVE_ALLELE),
+///     String::from(key::LOCAL_READ_DEPTHS),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let mut record = RecordBuf::builder()
+///     .set_reference_bases("A")
+///     .set_alternate_bases(vec![String::from("C"), String::from("G")].into())
+///     .set_samples(Samples::new(
+///         keys,
+///         vec![vec![
+///             Some(Value::from(vec![Some(2)])),
+///             Some(Value::from(vec![Some(3), Some(8)])),
+///         ]],
+///     ))
+///     .build();
+///
+/// local_alleles::expand(&header, &mut record)?;
+///
+/// let sample = record.samples().get_index(0).unwrap();
+/// assert_eq!(
+///     sample.get(key::READ_DEPTHS),
+///     Some(Some(&Value::from(vec![Some(3), None, Some(8)])))
+/// );
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn expand(header: &Header, record: &mut RecordBuf) -> io::Result<()> {
+    let format = record.format().as_ref();
+
+    let Some(laa_index) = format.get_index_of(key::LOCAL_ALTERNATIVE_ALLELE) else {
+        return Ok(());
+    };
+
+    let actions: Vec<_> = format
+        .iter()
+        .enumerate()
+        .map(|(i, k)| {
+            if i == laa_index {
+                return Action::Remove;
+            }
+
+            if k == LOCAL_GENOTYPE {
+                return if format.contains(key::GENOTYPE) {
+                    Action::Remove
+                } else {
+                    Action::ConvertGenotype
+                };
+            }
+
+            let Some(global_key) = global_key(k) else {
+                return Action::Keep;
+            };
+
+            match format_number(header, k) {
+                Some(
+                    number @ (Number::LocalAlternateBases
+                    | Number::LocalReferenceAlternateBases
+                    | Number::LocalSamples),
+                ) => {
+                    if format.contains(global_key) {
+                        Action::Remove
+                    } else {
+                        Action::Convert(global_key.into(), number)
+                    }
+                }
+                _ => Action::Keep,
+            }
+        })
+        .collect();
+
+    let allele_count = record.alternate_bases().as_ref().len() + 1;
+
+    let local_genotype_index = format
+        .get_index_of(LOCAL_GENOTYPE)
+        .filter(|&i| matches!(actions[i], Action::ConvertGenotype));
+
+    let genotype_key_index = format.get_index_of(key::GENOTYPE).or(local_genotype_index);
+
+    let (keys, samples) = mem::take(record.samples_mut()).into();
+    let new_keys = rekey(&keys, &actions);
+
+    let samples = samples
+        .into_iter()
+        .map(|mut values| {
+            let mut local_alleles = vec![0];
+
+            if let Some(value) = values.get(laa_index) {
+                local_alleles.extend(parse_local_alternate_alleles(value, allele_count)?);
+            }
+
+            // The local genotype is expanded first, as its ploidy is used to expand `LG` values.
+            if let Some(value) = local_genotype_index.and_then(|i| values.get_mut(i)) {
+                *value = expand_genotype(value.take(), &local_alleles)?;
+            }
+
+            let sample_ploidy = genotype_ploidy(genotype_key_index.and_then(|i| values.get(i)));
+
+            let values = values
+                .into_iter()
+                .zip(&actions)
+                .filter_map(|(value, action)| match action {
+                    Action::Keep | Action::ConvertGenotype => Some(value),
+                    Action::Convert(_, number) => Some(expand_value(
+                        *number,
+                        value,
+                        &local_alleles,
+                        allele_count,
+                        sample_ploidy,
+                    )),
+                    Action::Remove => None,
+                })
+                .collect();
+
+            Ok(values)
+        })
+        .collect::<io::Result<_>>()?;
+
+    *record.samples_mut() = Samples::new(new_keys, samples);
+
+    Ok(())
+}
+
+/// Compacts global allele-specific sample fields into local-allele fields.
+///
+/// For each sample, the local alternate alleles are those called in the genotype (`GT`) or
+/// with a positive read depth (`AD`). These are written to `LAA`, and values of fields with `A`,
+/// `R`, or `G` numbers that have a local counterpart with an `LA`, `LR`, or `LG` number,
+/// respectively, are reindexed to the local alleles and moved to the local field, e.g., `PL` to
+/// `LPL`. Local field definitions are looked up in the header and then in the reserved
+/// definitions of the header file format.
+///
+/// Records that already have `LAA` or that have no fields with local counterparts are left
+/// unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_vcf::{
+///     self as vcf,
+///     header::{FileFormat, record::value::{Map, map::Format}},
+///     variant::{
+///         local_alleles,
+///         record::samples::keys::key,
+///         record_buf::{RecordBuf, Samples, samples::sample::Value},
+///     },
+/// };
+///
+/// let header = vcf::Header::builder()
+///     .set_file_format(FileFormat::new(4, 5))
+///     .add_format(key::READ_DEPTHS, Map::<Format>::from(key::READ_DEPTHS))
+///     .add_sample_name("sample0")
+///     .build();
+///
+/// let keys = [String::from(key::READ_DEPTHS)].into_iter().collect();
+///
+/// let mut record = RecordBuf::builder()
+///     .set_reference_bases("A")
+///     .set_alternate_bases(vec![String::from("C"), String::from("G")].into())
+///     .set_samples(Samples::new(
+///         keys,
+///         vec![vec![Some(Value::from(vec![Some(3), Some(0), Some(8)]))]],
+///     ))
+///     .build();
+///
+/// local_alleles::compact(&header, &mut record);
+///
+/// let sample = record.samples().get_index(0).unwrap();
+/// assert_eq!(
+///     sample.get(key::LOCAL_ALTERNATIVE_ALLELE),
+///     Some(Some(&Value::from(vec![Some(2)])))
+/// );
+/// assert_eq!(
+///     sample.get(key::LOCAL_READ_DEPTHS),
+///     Some(Some(&Value::from(vec![Some(3), Some(8)])))
+/// );
+/// ```
+pub fn compact(header: &Header, record: &mut RecordBuf) {
+    let format = record.format().as_ref();
+
+    if format.contains(key::LOCAL_ALTERNATIVE_ALLELE) {
+        return;
+    }
+
+    let actions: Vec<_> = format
+        .iter()
+        .map(|k| {
+            let Some(local_key) = local_key(k) else {
+                return Action::Keep;
+            };
+
+            if format.contains(local_key) {
+                return Action::Keep;
+            }
+
+            match (format_number(header, k), format_number(header, local_key)) {
+                (Some(Number::AlternateBases), Some(number @ Number::LocalAlternateBases))
+                | (
+                    Some(Number::ReferenceAlternateBases),
+                    Some(number @ Number::LocalReferenceAlternateBases),
+                )
+                | (Some(Number::Samples), Some(number @ Number::LocalSamples)) => {
+                    Action::Convert(local_key.into(), number)
+                }
+                _ => Action::Keep,
+            }
+        })
+        .collect();
+
+    if !actions
+        .iter()
+        .any(|action| matches!(action, Action::Convert(..)))
+    {
+        return;
+    }
+
+    let genotype_key_index = format.get_index_of(key::GENOTYPE);
+    let read_depths_index = format.get_index_of(key::READ_DEPTHS);
+    let allele_count = record.alternate_bases().as_ref().len() + 1;
+
+    let (keys, samples) = mem::take(record.samples_mut()).into();
+
+    let mut new_keys = rekey(&keys, &actions);
+    new_keys
+        .as_mut()
+        .insert(String::from(key::LOCAL_ALTERNATIVE_ALLELE));
+
+    let samples = samples
+        .into_iter()
+        .map(|values| {
+            let genotype = genotype_key_index.and_then(|i| values.get(i));
+            let sample_ploidy = genotype_ploidy(genotype);
+
+            let local_alleles = select_local_alleles(
+                genotype,
+                read_depths_index.and_then(|i| values.get(i)),
+                allele_count,
+            );
+
+            let mut values: Vec<_> = values
+                .into_iter()
+                .zip(&actions)
+                .map(|(value, action)| match action {
+                    Action::Convert(_, number) => {
+                        compact_value(*number, value, &local_alleles, allele_count, sample_ploidy)
+                    }
+                    _ => value,
+                })
+                .collect();
+
+            let local_alternate_alleles: Vec<_> = local_alleles[1..]
+                .iter()
+                .map(|&i| i32::try_from(i).ok())
+                .collect();
+
+            values.push(if local_alternate_alleles.is_empty() {
+                None
+            } else {
+                Some(Value::from(local_alternate_alleles))
+            });
+
+            values
+        })
+        .collect();
+
+    *record.samples_mut() = Samples::new(new_keys, samples);
+}
+
+enum Action {
+    Keep,
+    Convert(String, Number),
+    ConvertGenotype,
+    Remove,
+}
+
+fn global_key(local_key: &str) -> Option<&'static str> {
+    LOCAL_KEYS
+        .iter()
+        .find(|(k, _)| *k == local_key)
+        .map(|(_, global_key)| *global_key)
+}
+
+fn local_key(global_key: &str) -> Option<&'static str> {
+    LOCAL_KEYS
+        .iter()
+        .find(|(_, k)| *k == global_key)
+        .map(|(local_key, _)| *local_key)
+}
+
+fn format_number(header: &Header, key: &str) -> Option<Number> {
+    use crate::header::record::value::map::format::definition::definition;
+
+    header
+        .formats()
+        .get(key)
+        .map(|format| format.number())
+        .or_else(|| definition(header.file_format(), key).map(|(n, _, _)| n))
+}
+
+fn rekey(keys: &Keys, actions: &[Action]) -> Keys {
+    keys.as_ref()
+        .iter()
+        .zip(actions)
+        .filter_map(|(key, action)| match action {
+            Action::Keep => Some(key.clone()),
+            Action::Convert(new_key, _) => Some(new_key.clone()),
+            Action::ConvertGenotype => Some(String::from(key::GENOTYPE)),
+            Action::Remove => None,
+        })
+        .collect()
+}
+
+fn parse_local_alternate_alleles(
+    value: &Option<Value>,
+    allele_count: usize,
+) -> io::Result<Vec<usize>> {
+    let indices = match value {
+        None => return Ok(Vec::new()),
+        Some(Value::Integer(n)) => vec![Some(*n)],
+        Some(Value::Array(SampleArray::Integer(indices))) => indices.clone(),
+        Some(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid LAA value type",
+            ));
+        }
+    };
+
+    indices
+        .into_iter()
+        .flatten()
+        .map(|n| {
+            usize::try_from(n)
+                .ok()
+                .filter(|i| (1..allele_count).contains(i))
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid LAA value: {n}"),
+                    )
+                })
+        })
+        .collect()
+}
+
+fn expand_genotype(value: Option<Value>, local_alleles: &[usize]) -> io::Result<Option<Value>> {
+    let mut genotype: Genotype = match value {
+        None => return Ok(None),
+        Some(Value::Genotype(genotype)) => genotype,
+        Some(Value::String(s)) => s
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+        Some(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid LGT value type",
+            ));
+        }
+    };
+
+    for allele in genotype.as_mut() {
+        if let Some(i) = allele.position() {
+            let position = local_alleles.get(i).copied().ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid LGT allele: {i}"),
+                )
+            })?;
+
+            *allele.position_mut() = Some(position);
+        }
+    }
+
+    Ok(Some(Value::Genotype(genotype)))
+}
+
+fn select_local_alleles(
+    genotype: Option<&Option<Value>>,
+    read_depths: Option<&Option<Value>>,
+    allele_count: usize,
+) -> Vec<usize> {
+    let mut alleles = BTreeSet::from([0]);
+
+    if let Some(Some(Value::Genotype(genotype))) = genotype {
+        alleles.extend(
+            genotype
+                .as_ref()
+                .iter()
+                .filter_map(|allele| allele.position()),
+        );
+    }
+
+    if let Some(Some(Value::Array(SampleArray::Integer(depths)))) = read_depths {
+        alleles.extend(
+            depths
+                .iter()
+                .enumerate()
+                .filter(|(_, depth)| matches!(depth, Some(n) if *n > 0))
+                .map(|(i, _)| i),
+        );
+    }
+
+    alleles.retain(|&i| i < allele_count);
+    alleles.into_iter().collect()
+}
+
+fn genotype_ploidy(genotype: Option<&Option<Value>>) -> Option<usize> {
+    match genotype {
+        Some(Some(Value::Genotype(genotype))) => Some(genotype.as_ref().len()),
+        _ => None,
+    }
+}
+
+// Infers the ploidy of a `G` value, preferring the ploidy of the sample genotype, if it is
+// consistent with the number of values.
+fn value_ploidy(allele_count: usize, len: usize, sample_ploidy: Option<usize>) -> Option<usize> {
+    sample_ploidy
+        .filter(|&p| p > 0 && genotype_count(allele_count, p) == len)
+        .or_else(|| ploidy(allele_count, len))
+}
+
+fn expand_value(
+    number: Number,
+    value: Option<Value>,
+    local_alleles: &[usize],
+    allele_count: usize,
+    sample_ploidy: Option<usize>,
+) -> Option<Value> {
+    let array = SampleArray::from_value(value.as_ref()?)?;
+    let local_index = |allele| local_alleles.iter().position(|&a| a == allele);
+
+    let sources: Vec<_> = match number {
+        Number::LocalAlternateBases => (1..allele_count)
+            .map(|a| local_index(a).map(|i| (array.as_ref(), i - 1)))
+            .collect(),
+        Number::LocalReferenceAlternateBases => (0..allele_count)
+            .map(|a| local_index(a).map(|i| (array.as_ref(), i)))
+            .collect(),
+        Number::LocalSamples => {
+            let ploidy = value_ploidy(local_alleles.len(), array.len(), sample_ploidy)?;
+
+            genotypes(allele_count, ploidy)
+                .into_iter()
+                .map(|alleles| {
+                    let mut local_alleles: Vec<_> = alleles
+                        .into_iter()
+                        .map(local_index)
+                        .collect::<Option<_>>()?;
+
+                    local_alleles.sort_unstable();
+
+                    Some((array.as_ref(), genotype_index(&local_alleles)))
+                })
+                .collect()
+        }
+        _ => return value,
+    };
+
+    SampleArray::gather(&sources).map(SampleArray::into_value)
+}
+
+fn compact_value(
+    number: Number,
+    value: Option<Value>,
+    local_alleles: &[usize],
+    allele_count: usize,
+    sample_ploidy: Option<usize>,
+) -> Option<Value> {
+    let array = SampleArray::from_value(value.as_ref()?)?;
+
+    let sources: Vec<_> = match number {
+        Number::LocalAlternateBases => local_alleles[1..]
+            .iter()
+            .map(|&a| Some((array.as_ref(), a - 1)))
+            .collect(),
+        Number::LocalReferenceAlternateBases => local_alleles
+            .iter()
+            .map(|&a| Some((array.as_ref(), a)))
+            .collect(),
+        Number::LocalSamples => {
+            let ploidy = value_ploidy(allele_count, array.len(), sample_ploidy)?;
+
+            genotypes(local_alleles.len(), ploidy)
+                .into_iter()
+                .map(|alleles| {
+                    let mut alleles: Vec<_> =
+                        alleles.into_iter().map(|i| local_alleles[i]).collect();
+                    alleles.sort_unstable();
+                    Some((array.as_ref(), genotype_index(&alleles)))
+                })
+                .collect()
+        }
+        _ => return value,
+    };
+
+    SampleArray::gather(&sources).map(SampleArray::into_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::FileFormat;
+
+    fn build_header() -> Header {
+        Header::builder()
+            .set_file_format(FileFormat::new(4, 5))
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .build()
+    }
+
+    #[test]
+    fn test_expand() -> io::Result<()> {
+        let header = build_header();
+
+        let keys: Keys = [
+            String::from(key::GENOTYPE),
+            String::from(key::LOCAL_ALTERNATIVE_ALLELE),
+            String::from(key::LOCAL_READ_DEPTHS),
+            String::from(key::LOCAL_ROUNDED_GENOTYPE_LIKELIHOODS),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut record = RecordBuf::builder()
+            .set_reference_bases("A")
+            .set_alternate_bases(vec![String::from("C"), String::from("G")].into())
+            .set_samples(Samples::new(
+                keys,
+                vec![
+                    vec![
+                        Some(Value::Genotype("0/2".parse().unwrap())),
+                        Some(Value::from(vec![Some(2)])),
+                        Some(Value::from(vec![Some(3), Some(8)])),
+                        Some(Value::from(vec![Some(10), Some(0), Some(20)])),
+                    ],
+                    vec![
+                        Some(Value::Genotype("0/0".parse().unwrap())),
+                        None,
+                        Some(Value::from(vec![Some(5)])),
+                        Some(Value::from(vec![Some(0)])),
+                    ],
+                ],
+            ))
+            .build();
+
+        expand(&header, &mut record)?;
+
+        let expected_keys: Keys = [
+            String::from(key::GENOTYPE),
+            String::from(key::READ_DEPTHS),
+            String::from(key::ROUNDED_GENOTYPE_LIKELIHOODS),
+        ]
+        .into_iter()
+        .collect();
+
+        let expected = Samples::new(
+            expected_keys,
+            vec![
+                vec![
+                    Some(Value::Genotype("0/2".parse().unwrap())),
+                    Some(Value::from(vec![Some(3), None, Some(8)])),
+                    Some(Value::from(vec![
+                        Some(10),
+                        None,
+                        None,
+                        Some(0),
+                        None,
+                        Some(20),
+                    ])),
+                ],
+                vec![
+                    Some(Value::Genotype("0/0".parse().unwrap())),
+                    Some(Value::from(vec![Some(5), None, None])),
+                    Some(Value::from(vec![Some(0), None, None, None, None, None])),
+                ],
+            ],
+        );
+
+        assert_eq!(record.samples(), &expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expand_with_local_genotype() -> io::Result<()> {
+        let header = build_header();
+
+        let keys: Keys = [
+            String::from(LOCAL_GENOTYPE),
+            String::from(key::LOCAL_ALTERNATIVE_ALLELE),
+            String::from(key::LOCAL_ROUNDED_GENOTYPE_LIKELIHOODS),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut record = RecordBuf::builder()
+            .set_reference_bases("A")
+            .set_alternate_bases(vec![String::from("C"), String::from("G")].into())
+            .set_samples(Samples::new(
+                keys,
+                vec![
+                    vec![
+                        Some(Value::from("0|1")),
+                        Some(Value::from(vec![Some(2)])),
+                        Some(Value::from(vec![Some(10), Some(0), Some(20)])),
+                    ],
+                    vec![
+                        Some(Value::Genotype("1/1".parse().unwrap())),
+                        Some(Value::from(vec![Some(1)])),
+                        None,
+                    ],
+                ],
+            ))
+            .build();
+
+        expand(&header, &mut record)?;
+
+        let expected_keys: Keys = [
+            String::from(key::GENOTYPE),
+            String::from(key::ROUNDED_GENOTYPE_LIKELIHOODS),
+        ]
+        .into_iter()
+        .collect();
+
+        let expected = Samples::new(
+            expected_keys,
+            vec![
+                vec![
+                    Some(Value::Genotype("0|2".parse().unwrap())),
+                    Some(Value::from(vec![
+                        Some(10),
+                        None,
+                        None,
+                        Some(0),
+                        None,
+                        Some(20),
+                    ])),
+                ],
+                vec![Some(Value::Genotype("1/1".parse().unwrap())), None],
+            ],
+        );
+
+        assert_eq!(record.samples(), &expected);
+
+        // An `LGT` allele past the local alleles is invalid.
+        let keys: Keys = [
+            String::from(LOCAL_GENOTYPE),
+            String::from(key::LOCAL_ALTERNATIVE_ALLELE),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut record = RecordBuf::builder()
+            .set_reference_bases("A")
+            .set_alternate_bases(vec![String::from("C"), String::from("G")].into())
+            .set_samples(Samples::new(
+                keys,
+                vec![vec![
+                    Some(Value::from("0/2")),
+                    Some(Value::from(vec![Some(2)])),
+                ]],
+            ))
+            .build();
+
+        assert!(matches!(
+            expand(&header, &mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expand_with_invalid_local_alternate_alleles() {
+        let header = build_header();
+
+        let keys: Keys = [String::from(key::LOCAL_ALTERNATIVE_ALLELE)]
+            .into_iter()
+            .collect();
+
+        let mut record = RecordBuf::builder()
+            .set_reference_bases("A")
+            .set_alternate_bases(vec![String::from("C")].into())
+            .set_samples(Samples::new(
+                keys,
+                vec![vec![Some(Value::from(vec![Some(2)]))]],
+            ))
+            .build();
+
+        assert!(matches!(
+            expand(&header, &mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_compact() -> io::Result<()> {
+        let header = build_header();
+
+        let keys: Keys = [
+            String::from(key::GENOTYPE),
+            String::from(key::READ_DEPTHS),
+            String::from(key::ROUNDED_GENOTYPE_LIKELIHOODS),
+        ]
+        .into_iter()
+        .collect();
+
+        let original_record = RecordBuf::builder()
+            .set_reference_bases("A")
+            .set_alternate_bases(vec![String::from("C"), String::from("G")].into())
+            .set_samples(Samples::new(
+                keys,
+                vec![
+                    vec![
+                        Some(Value::Genotype("0/2".parse().unwrap())),
+                        Some(Value::from(vec![Some(3), Some(0), Some(8)])),
+                        Some(Value::from(vec![
+                            Some(10),
+                            Some(30),
+                            Some(40),
+                            Some(0),
+                            Some(50),
+                            Some(20),
+                        ])),
+                    ],
+                    vec![
+                        Some(Value::Genotype("0/0".parse().unwrap())),
+                        Some(Value::from(vec![Some(5), Some(0), Some(0)])),
+                        Some(Value::from(vec![
+                            Some(0),
+                            Some(30),
+                            Some(40),
+                            Some(30),
+                            Some(50),
+                            Some(40),
+                        ])),
+                    ],
+                ],
+            ))
+            .build();
+
+        let mut record = original_record.clone();
+        compact(&header, &mut record);
+
+        let expected_keys: Keys = [
+            String::from(key::GENOTYPE),
+            String::from(key::LOCAL_READ_DEPTHS),
+            String::from(key::LOCAL_ROUNDED_GENOTYPE_LIKELIHOODS),
+            String::from(key::LOCAL_ALTERNATIVE_ALLELE),
+        ]
+        .into_iter()
+        .collect();
+
+        let expected = Samples::new(
+            expected_keys,
+            vec![
+                vec![
+                    Some(Value::Genotype("0/2".parse().unwrap())),
+                    Some(Value::from(vec![Some(3), Some(8)])),
+                    Some(Value::from(vec![Some(10), Some(0), Some(20)])),
+                    Some(Value::from(vec![Some(2)])),
+                ],
+                vec![
+                    Some(Value::Genotype("0/0".parse().unwrap())),
+                    Some(Value::from(vec![Some(5)])),
+                    Some(Value::from(vec![Some(0)])),
+                    None,
+                ],
+            ],
+        );
+
+        assert_eq!(record.samples(), &expected);
+
+        // Compacting a compacted record does nothing.
+        let compacted_record = record.clone();
+        compact(&header, &mut record);
+        assert_eq!(record, compacted_record);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,300 @@
+//! Multiallelic variant record splitting and joining.
+//!
+//! Splitting decomposes a record with multiple alternate alleles into biallelic records, one per
+//! alternate allele. Joining is the inverse: it combines records at the same position with the
+//! same reference bases into a single multiallelic record.
+//!
+//! In both directions, INFO and FORMAT values are rewritten using the number definitions in the
+//! header:
+//!
+//!   * `Number=A` values have one value per alternate allele;
+//!   * `Number=R` values have one value per allele, including the reference allele; and
+//!   * `Number=G` values have one value per possible genotype, ordered as described in "Genotype
+//!     Ordering" (§ 1.6.2) of the VCF specification.
+//!
+//! The genotype (`GT`) allele indices are remapped to the new alleles. All other fields are
+//! copied as is.
+//!
+//! BCF records can be split or joined by first converting them to [`super::RecordBuf`]s using
+//! [`super::RecordBuf::try_from_variant_record`].
+
+mod join;
+mod split;
+
+pub use self::{join::join, split::split};
+
+use std::borrow::Cow;
+
+use crate::{
+    Header,
+    header::record::value::map::{format, info},
+    variant::record_buf::{
+        info::field::{Value as InfoValue, value::Array as InfoArray},
+        samples::sample::{Value as SampleValue, value::Array as SampleArray},
+    },
+};
+
+/// A header number definition, shared between INFO and FORMAT fields.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Number {
+    AlternateBases,
+    ReferenceAlternateBases,
+    Genotypes,
+    Other,
+}
+
+impl From<info::Number> for Number {
+    fn from(number: info::Number) -> Self {
+        match number {
+            info::Number::AlternateBases => Self::AlternateBases,
+            info::Number::ReferenceAlternateBases => Self::ReferenceAlternateBases,
+            info::Number::Samples => Self::Genotypes,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl From<format::Number> for Number {
+    fn from(number: format::Number) -> Self {
+        match number {
+            format::Number::AlternateBases => Self::AlternateBases,
+            format::Number::ReferenceAlternateBases => Self::ReferenceAlternateBases,
+            format::Number::Samples => Self::Genotypes,
+            _ => Self::Other,
+        }
+    }
+}
+
+fn info_number(header: &Header, key: &str) -> Number {
+    use crate::header::record::value::map::info::definition::definition;
+
+    header
+        .infos()
+        .get(key)
+        .map(|info| info.number())
+        .or_else(|| definition(header.file_format(), key).map(|(n, _, _)| n))
+        .map(Number::from)
+        .unwrap_or(Number::Other)
+}
+
+fn format_number(header: &Header, key: &str) -> Number {
+    use crate::header::record::value::map::format::definition::definition;
+
+    header
+        .formats()
+        .get(key)
+        .map(|format| format.number())
+        .or_else(|| definition(header.file_format(), key).map(|(n, _, _)| n))
+        .map(Number::from)
+        .unwrap_or(Number::Other)
+}
+
+/// An INFO or FORMAT array value.
+///
+/// INFO and FORMAT arrays have the same shape but are distinct types, so this abstracts over
+/// reading and building them.
+pub(super) trait Array: Clone + Sized {
+    type Value: Clone;
+
+    fn from_value(value: &Self::Value) -> Option<Cow<'_, Self>>;
+
+    fn into_value(self) -> Self::Value;
+
+    fn len(&self) -> usize;
+
+    /// Builds a new array from values selected from source arrays.
+    ///
+    /// Each source is either an array and an index into it or missing. The array type is
+    /// taken from the first source array; values of other types are treated as missing.
+    fn gather(sources: &[Option<(&Self, usize)>]) -> Option<Self>;
+}
+
+macro_rules! impl_array {
+    ($array:ident, $value:ident) => {
+        impl Array for $array {
+            type Value = $value;
+
+            fn from_value(value: &Self::Value) -> Option<Cow<'_, Self>> {
+                match value {
+                    $value::Integer(n) => Some(Cow::Owned(Self::Integer(vec![Some(*n)]))),
+                    $value::Float(n) => Some(Cow::Owned(Self::Float(vec![Some(*n)]))),
+                    $value::Character(c) => Some(Cow::Owned(Self::Character(vec![Some(*c)]))),
+                    $value::String(s) => Some(Cow::Owned(Self::String(vec![Some(s.clone())]))),
+                    $value::Array(array) => Some(Cow::Borrowed(array)),
+                    _ => None,
+                }
+            }
+
+            fn into_value(self) -> Self::Value {
+                $value::Array(self)
+            }
+
+            fn len(&self) -> usize {
+                match self {
+                    Self::Integer(values) => values.len(),
+                    Self::Float(values) => values.len(),
+                    Self::Character(values) => values.len(),
+                    Self::String(values) => values.len(),
+                }
+            }
+
+            fn gather(sources: &[Option<(&Self, usize)>]) -> Option<Self> {
+                let (first, _) = sources.iter().flatten().next()?;
+
+                let array = match first {
+                    Self::Integer(_) => Self::Integer(
+                        sources
+                            .iter()
+                            .map(|source| match source {
+                                Some((Self::Integer(values), i)) => values.get(*i).copied()?,
+                                _ => None,
+                            })
+                            .collect(),
+                    ),
+                    Self::Float(_) => Self::Float(
+                        sources
+                            .iter()
+                            .map(|source| match source {
+                                Some((Self::Float(values), i)) => values.get(*i).copied()?,
+                                _ => None,
+                            })
+                            .collect(),
+                    ),
+                    Self::Character(_) => Self::Character(
+                        sources
+                            .iter()
+                            .map(|source| match source {
+                                Some((Self::Character(values), i)) => values.get(*i).copied()?,
+                                _ => None,
+                            })
+                            .collect(),
+                    ),
+                    Self::String(_) => Self::String(
+                        sources
+                            .iter()
+                            .map(|source| match source {
+                                Some((Self::String(values), i)) => values.get(*i).cloned()?,
+                                _ => None,
+                            })
+                            .collect(),
+                    ),
+                };
+
+                Some(array)
+            }
+        }
+    };
+}
+
+impl_array!(InfoArray, InfoValue);
+
+impl_array!(SampleArray, SampleValue);
+
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+
+    let k = k.min(n - k);
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+/// Returns the number of genotypes for the given number of alleles and ploidy.
+pub(super) fn genotype_count(allele_count: usize, ploidy: usize) -> usize {
+    binomial(allele_count + ploidy - 1, ploidy)
+}
+
+/// Infers the ploidy from the number of `Number=G` values.
+pub(super) fn ploidy(allele_count: usize, len: usize) -> Option<usize> {
+    if allele_count == 0 {
+        return None;
+    }
+
+    let mut ploidy = 1;
+
+    loop {
+        let n = genotype_count(allele_count, ploidy);
+
+        if n == len {
+            return Some(ploidy);
+        } else if n > len || allele_count == 1 {
+            return None;
+        }
+
+        ploidy += 1;
+    }
+}
+
+/// Returns the index of a genotype, given its allele indices in ascending order.
+pub(super) fn genotype_index(alleles: &[usize]) -> usize {
+    alleles
+        .iter()
+        .enumerate()
+        .map(|(m, &a)| binomial(a + m, m + 1))
+        .sum()
+}
+
+/// Returns all genotypes, as ascending allele indices, in genotype order.
+pub(super) fn genotypes(allele_count: usize, ploidy: usize) -> Vec<Vec<usize>> {
+    fn push(alleles: &mut Vec<usize>, max: usize, ploidy: usize, dst: &mut Vec<Vec<usize>>) {
+        if alleles.len() == ploidy {
+            dst.push(alleles.clone());
+            return;
+        }
+
+        let start = alleles.last().copied().unwrap_or(0);
+
+        for a in start..max {
+            alleles.push(a);
+            push(alleles, max, ploidy, dst);
+            alleles.pop();
+        }
+    }
+
+    let mut dst = Vec::with_capacity(genotype_count(allele_count, ploidy));
+    push(
+        &mut Vec::with_capacity(ploidy),
+        allele_count,
+        ploidy,
+        &mut dst,
+    );
+    dst.sort_by_key(|alleles| genotype_index(alleles));
+    dst
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ploidy() {
+        assert_eq!(ploidy(2, 2), Some(1));
+        assert_eq!(ploidy(2, 3), Some(2));
+        assert_eq!(ploidy(3, 6), Some(2));
+        assert_eq!(ploidy(3, 10), Some(3));
+        assert_eq!(ploidy(3, 5), None);
+        assert_eq!(ploidy(1, 1), Some(1));
+        assert_eq!(ploidy(1, 2), None);
+    }
+
+    #[test]
+    fn test_genotype_index() {
+        assert_eq!(genotype_index(&[0, 0]), 0);
+        assert_eq!(genotype_index(&[0, 1]), 1);
+        assert_eq!(genotype_index(&[1, 1]), 2);
+        assert_eq!(genotype_index(&[0, 2]), 3);
+        assert_eq!(genotype_index(&[1, 2]), 4);
+        assert_eq!(genotype_index(&[2, 2]), 5);
+        assert_eq!(genotype_index(&[2]), 2);
+        assert_eq!(genotype_index(&[0, 0, 1]), 1);
+        assert_eq!(genotype_index(&[0, 1, 1]), 2);
+    }
+
+    #[test]
+    fn test_genotypes() {
+        assert_eq!(
+            genotypes(3, 2),
+            [[0, 0], [0, 1], [1, 1], [0, 2], [1, 2], [2, 2]]
+        );
+    }
+}
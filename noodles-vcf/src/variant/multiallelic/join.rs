@@ -0,0 +1,522 @@
+use std::io;
+
+use super::{Array, Number, format_number, genotype_index, genotypes, info_number, ploidy};
+use crate::{
+    Header,
+    variant::{
+        RecordBuf,
+        record::samples::keys::key,
+        record_buf::{
+            AlternateBases, Info, Samples,
+            info::field::value::Array as InfoArray,
+            samples::{
+                Keys,
+                sample::{
+                    Value,
+                    value::{Array as SampleArray, Genotype, genotype::Allele},
+                },
+            },
+        },
+    },
+};
+
+/// Joins variant records at the same position into a multiallelic record.
+///
+/// All records must have the same reference sequence name, variant start, reference bases, and
+/// number of samples. The alternate alleles of the joined record are the distinct alternate
+/// alleles of the input records, in order of appearance.
+///
+/// `Number=A`, `Number=R`, and `Number=G` INFO and FORMAT values are merged and reindexed to the
+/// joined alleles. Values that cannot be determined from any single input record, e.g., the
+/// likelihood of a genotype with alternate alleles from different records, are set to missing.
+/// Genotype (`GT`) alleles are remapped to the joined alleles; at each allele position, the first
+/// alternate allele takes precedence over the reference allele.
+///
+/// The IDs are the union of the IDs of the input records. All other fields and values that are
+/// not allele-specific are taken from the first record that has them.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_vcf::{
+///     self as vcf,
+///     variant::{RecordBuf, multiallelic},
+/// };
+///
+/// let header = vcf::Header::default();
+///
+/// let build_record = |allele: &str| {
+///     RecordBuf::builder()
+///         .set_reference_sequence_name("sq0")
+///         .set_variant_start(Position::MIN)
+///         .set_reference_bases("A")
+///         .set_alternate_bases(vec![String::from(allele)].into())
+///         .build()
+/// };
+///
+/// let records = [build_record("C"), build_record("G")];
+///
+/// let record = multiallelic::join(&header, &records)?;
+///
+/// assert_eq!(
+///     record.alternate_bases().as_ref(),
+///     [String::from("C"), String::from("G")]
+/// );
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn join(header: &Header, records: &[RecordBuf]) -> io::Result<RecordBuf> {
+    let (first, rest) = records
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no records to join"))?;
+
+    let sample_count = first.samples().values().count();
+
+    for record in rest {
+        if record.reference_sequence_name() != first.reference_sequence_name()
+            || record.variant_start() != first.variant_start()
+            || record.reference_bases() != first.reference_bases()
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "records do not have the same position and reference bases",
+            ));
+        }
+
+        if record.samples().values().count() != sample_count {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "records do not have the same number of samples",
+            ));
+        }
+    }
+
+    let mut alternate_bases: Vec<String> = Vec::new();
+
+    // For each record, the joined allele index of each of its alleles.
+    let allele_indices: Vec<Vec<usize>> = records
+        .iter()
+        .map(|record| {
+            let mut indices = vec![0];
+
+            for allele in record.alternate_bases().as_ref() {
+                let i = match alternate_bases.iter().position(|a| a == allele) {
+                    Some(i) => i,
+                    None => {
+                        alternate_bases.push(allele.clone());
+                        alternate_bases.len() - 1
+                    }
+                };
+
+                indices.push(i + 1);
+            }
+
+            indices
+        })
+        .collect();
+
+    let allele_count = alternate_bases.len() + 1;
+
+    let mut record = first.clone();
+
+    *record.alternate_bases_mut() = AlternateBases::from(alternate_bases);
+
+    *record.ids_mut() = records
+        .iter()
+        .flat_map(|record| record.ids().as_ref().iter().cloned())
+        .collect();
+
+    let info_keys: Vec<&String> = {
+        let mut keys = indexmap::IndexSet::new();
+        keys.extend(records.iter().flat_map(|record| record.info().keys()));
+        keys.into_iter().collect()
+    };
+
+    *record.info_mut() = info_keys
+        .into_iter()
+        .map(|key| {
+            let values: Vec<_> = records
+                .iter()
+                .map(|record| record.info().get(key))
+                .collect();
+
+            let value = join_values::<InfoArray>(
+                info_number(header, key),
+                &values,
+                &allele_indices,
+                allele_count,
+            );
+
+            (key.clone(), value)
+        })
+        .collect::<Info>();
+
+    let keys: Keys = records
+        .iter()
+        .flat_map(|record| record.format().as_ref().iter().cloned())
+        .collect();
+
+    let samples = (0..sample_count)
+        .map(|i| {
+            keys.as_ref()
+                .iter()
+                .map(|key| {
+                    let values: Vec<_> = records
+                        .iter()
+                        .map(|record| {
+                            record
+                                .samples()
+                                .get_index(i)
+                                .and_then(|sample| sample.get(key))
+                        })
+                        .collect();
+
+                    if key == key::GENOTYPE {
+                        join_genotypes(&values, &allele_indices).map(Value::Genotype)
+                    } else {
+                        join_values::<SampleArray>(
+                            format_number(header, key),
+                            &values,
+                            &allele_indices,
+                            allele_count,
+                        )
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    *record.samples_mut() = Samples::new(keys, samples);
+
+    Ok(record)
+}
+
+fn join_values<A>(
+    number: Number,
+    values: &[Option<Option<&A::Value>>],
+    allele_indices: &[Vec<usize>],
+    allele_count: usize,
+) -> Option<A::Value>
+where
+    A: Array,
+{
+    // (record index, array)
+    let arrays: Vec<_> = values
+        .iter()
+        .enumerate()
+        .filter_map(|(i, value)| value.flatten().and_then(A::from_value).map(|a| (i, a)))
+        .collect();
+
+    // The ploidy of `Number=G` values is inferred from the first array with a valid length.
+    // Arrays of another ploidy are skipped.
+    let value_ploidy = if number == Number::Genotypes {
+        let ploidy = arrays
+            .iter()
+            .find_map(|(i, array)| ploidy(allele_indices[*i].len(), array.len()))?;
+
+        Some(ploidy)
+    } else {
+        None
+    };
+
+    // Finds the first array with all of the given joined alleles and returns the local allele
+    // indices.
+    let find = |alleles: &[usize]| {
+        arrays.iter().find_map(|(i, array)| {
+            let indices = &allele_indices[*i];
+
+            if value_ploidy.is_some() && ploidy(indices.len(), array.len()) != value_ploidy {
+                return None;
+            }
+
+            let local_alleles: Option<Vec<_>> = alleles
+                .iter()
+                .map(|a| indices.iter().position(|b| b == a))
+                .collect();
+
+            local_alleles.map(|local_alleles| (array.as_ref(), local_alleles))
+        })
+    };
+
+    let sources: Vec<_> = match number {
+        Number::AlternateBases => (1..allele_count)
+            .map(|a| find(&[a]).map(|(array, local)| (array, local[0] - 1)))
+            .collect(),
+        Number::ReferenceAlternateBases => (0..allele_count)
+            .map(|a| find(&[a]).map(|(array, local)| (array, local[0])))
+            .collect(),
+        Number::Genotypes => {
+            // SAFETY: `value_ploidy` is set for `Number::Genotypes`.
+            let value_ploidy = value_ploidy.unwrap();
+
+            genotypes(allele_count, value_ploidy)
+                .into_iter()
+                .map(|alleles| {
+                    find(&alleles).map(|(array, mut local_alleles)| {
+                        local_alleles.sort_unstable();
+                        (array, genotype_index(&local_alleles))
+                    })
+                })
+                .collect()
+        }
+        Number::Other => {
+            return values
+                .iter()
+                .flatten()
+                .flatten()
+                .next()
+                .map(|v| (*v).clone());
+        }
+    };
+
+    A::gather(&sources).map(A::into_value)
+}
+
+fn join_genotypes(
+    values: &[Option<Option<&Value>>],
+    allele_indices: &[Vec<usize>],
+) -> Option<Genotype> {
+    let genotypes: Vec<_> = values
+        .iter()
+        .zip(allele_indices)
+        .filter_map(|(value, indices)| match value {
+            Some(Some(Value::Genotype(genotype))) => Some((genotype.as_ref(), indices)),
+            _ => None,
+        })
+        .collect();
+
+    let ploidy = genotypes.iter().map(|(alleles, _)| alleles.len()).max()?;
+
+    let genotype = (0..ploidy)
+        .map(|k| {
+            let alleles: Vec<_> = genotypes
+                .iter()
+                .filter_map(|(alleles, indices)| {
+                    alleles.get(k).map(|allele| {
+                        let position = allele
+                            .position()
+                            .and_then(|local| indices.get(local).copied());
+
+                        (position, allele.phasing())
+                    })
+                })
+                .collect();
+
+            // SAFETY: at least one genotype has `ploidy` alleles.
+            let (_, phasing) = alleles[0];
+
+            let position = alleles
+                .iter()
+                .map(|(position, _)| *position)
+                .find(|position| matches!(position, Some(n) if *n > 0))
+                .or_else(|| {
+                    alleles
+                        .iter()
+                        .map(|(position, _)| *position)
+                        .find(Option::is_some)
+                })
+                .flatten();
+
+            Allele::new(position, phasing)
+        })
+        .collect();
+
+    Some(genotype)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        header::record::value::{
+            Map,
+            map::{Format, Info as InfoMap},
+        },
+        variant::{multiallelic::split, record::info::field::key as info_key},
+    };
+
+    #[test]
+    fn test_join() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::variant::record_buf::info::field::Value as InfoValue;
+
+        let header = Header::builder()
+            .add_info(
+                info_key::ALLELE_COUNT,
+                Map::<InfoMap>::from(info_key::ALLELE_COUNT),
+            )
+            .add_format(key::GENOTYPE, Map::<Format>::from(key::GENOTYPE))
+            .add_format(key::READ_DEPTHS, Map::<Format>::from(key::READ_DEPTHS))
+            .add_format(
+                key::ROUNDED_GENOTYPE_LIKELIHOODS,
+                Map::<Format>::from(key::ROUNDED_GENOTYPE_LIKELIHOODS),
+            )
+            .add_sample_name("sample0")
+            .build();
+
+        let keys: Keys = [
+            String::from(key::GENOTYPE),
+            String::from(key::READ_DEPTHS),
+            String::from(key::ROUNDED_GENOTYPE_LIKELIHOODS),
+        ]
+        .into_iter()
+        .collect();
+
+        let builder = || {
+            RecordBuf::builder()
+                .set_reference_sequence_name("sq0")
+                .set_variant_start(noodles_core::Position::MIN)
+                .set_reference_bases("A")
+        };
+
+        let records = [
+            builder()
+                .set_ids([String::from("id0")].into_iter().collect())
+                .set_alternate_bases(vec![String::from("C")].into())
+                .set_info(
+                    [(
+                        String::from(info_key::ALLELE_COUNT),
+                        Some(InfoValue::from(vec![Some(1)])),
+                    )]
+                    .into_iter()
+                    .collect(),
+                )
+                .set_samples(Samples::new(
+                    keys.clone(),
+                    vec![vec![
+                        Some(Value::Genotype("0/1".parse()?)),
+                        Some(Value::from(vec![Some(3), Some(5)])),
+                        Some(Value::from(vec![Some(0), Some(1), Some(2)])),
+                    ]],
+                ))
+                .build(),
+            builder()
+                .set_ids([String::from("id1")].into_iter().collect())
+                .set_alternate_bases(vec![String::from("G")].into())
+                .set_info(
+                    [(
+                        String::from(info_key::ALLELE_COUNT),
+                        Some(InfoValue::from(vec![Some(2)])),
+                    )]
+                    .into_iter()
+                    .collect(),
+                )
+                .set_samples(Samples::new(
+                    keys.clone(),
+                    vec![vec![
+                        Some(Value::Genotype("1/0".parse()?)),
+                        Some(Value::from(vec![Some(3), Some(8)])),
+                        Some(Value::from(vec![Some(0), Some(3), Some(5)])),
+                    ]],
+                ))
+                .build(),
+        ];
+
+        let record = join(&header, &records)?;
+
+        assert_eq!(record.ids().as_ref().len(), 2);
+        assert_eq!(
+            record.alternate_bases().as_ref(),
+            [String::from("C"), String::from("G")]
+        );
+        assert_eq!(
+            record.info().get(info_key::ALLELE_COUNT),
+            Some(Some(&InfoValue::from(vec![Some(1), Some(2)])))
+        );
+        assert_eq!(
+            record.samples(),
+            &Samples::new(
+                keys,
+                vec![vec![
+                    Some(Value::Genotype("2/1".parse()?)),
+                    Some(Value::from(vec![Some(3), Some(5), Some(8)])),
+                    Some(Value::from(vec![
+                        Some(0),
+                        Some(1),
+                        Some(2),
+                        Some(3),
+                        None,
+                        Some(5),
+                    ])),
+                ]]
+            )
+        );
+
+        // Joining split records restores the INFO and FORMAT values.
+        let split_records = split(&header, &record);
+        let joined_record = join(&header, &split_records)?;
+        assert_eq!(joined_record.info(), record.info());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_with_genotypes_number_and_invalid_first_value()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_format(
+                key::ROUNDED_GENOTYPE_LIKELIHOODS,
+                Map::<Format>::from(key::ROUNDED_GENOTYPE_LIKELIHOODS),
+            )
+            .add_sample_name("sample0")
+            .build();
+
+        let keys: Keys = [String::from(key::ROUNDED_GENOTYPE_LIKELIHOODS)]
+            .into_iter()
+            .collect();
+
+        let build_record = |alternate_base: &str, value: Value| {
+            RecordBuf::builder()
+                .set_reference_sequence_name("sq0")
+                .set_variant_start(noodles_core::Position::MIN)
+                .set_reference_bases("A")
+                .set_alternate_bases(vec![String::from(alternate_base)].into())
+                .set_samples(Samples::new(keys.clone(), vec![vec![Some(value)]]))
+                .build()
+        };
+
+        // The first value has a length that does not match any ploidy.
+        let records = [
+            build_record("C", Value::from(vec![Some(0)])),
+            build_record("G", Value::from(vec![Some(0), Some(3), Some(5)])),
+        ];
+
+        let record = join(&header, &records)?;
+
+        assert_eq!(
+            record.samples(),
+            &Samples::new(
+                keys,
+                vec![vec![Some(Value::from(vec![
+                    Some(0),
+                    None,
+                    None,
+                    Some(3),
+                    None,
+                    Some(5),
+                ]))]]
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_join_with_invalid_records() {
+        let header = Header::default();
+
+        assert!(matches!(
+            join(&header, &[]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let records = [
+            RecordBuf::builder().set_reference_bases("A").build(),
+            RecordBuf::builder().set_reference_bases("C").build(),
+        ];
+
+        assert!(matches!(
+            join(&header, &records),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+}
@@ -0,0 +1,294 @@
+use std::mem;
+
+use super::{Array, Number, format_number, genotype_index, info_number, ploidy};
+use crate::{
+    Header,
+    variant::{
+        RecordBuf,
+        record::samples::keys::key,
+        record_buf::{
+            AlternateBases, Samples,
+            info::field::value::Array as InfoArray,
+            samples::sample::{Value, value::Array as SampleArray},
+        },
+    },
+};
+
+/// Splits a multiallelic variant record into biallelic records.
+///
+/// One record is created for each alternate allele, in order. `Number=A`, `Number=R`, and
+/// `Number=G` INFO and FORMAT values are subset to the alleles of each record. Genotype (`GT`)
+/// alleles that refer to the split alternate allele are set to 1, and those that refer to other
+/// alternate alleles are set to the reference allele (0).
+///
+/// The split records are not trimmed; use `variant::normalization::normalize` (`fasta` feature) to
+/// remove redundant bases.
+///
+/// Records with fewer than two alternate alleles are returned as is.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_vcf::{
+///     self as vcf,
+///     header::record::value::{map::Info, Map},
+///     variant::{
+///         multiallelic,
+///         record::info::field::key,
+///         record_buf::{info::field::Value, RecordBuf},
+///     },
+/// };
+///
+/// let header = vcf::Header::builder()
+///     .add_info(key::ALLELE_COUNT, Map::<Info>::from(key::ALLELE_COUNT))
+///     .build();
+///
+/// let record = RecordBuf::builder()
+///     .set_reference_sequence_name("sq0")
+///     .set_variant_start(Position::MIN)
+///     .set_reference_bases("A")
+///     .set_alternate_bases(vec![String::from("C"), String::from("G")].into())
+///     .set_info(
+///         [(String::from(key::ALLELE_COUNT), Some(Value::from(vec![Some(3), Some(5)])))]
+///             .into_iter()
+///             .collect(),
+///     )
+///     .build();
+///
+/// let records = multiallelic::split(&header, &record);
+/// assert_eq!(records.len(), 2);
+///
+/// assert_eq!(records[1].alternate_bases().as_ref(), [String::from("G")]);
+/// assert_eq!(
+///     records[1].info().get(key::ALLELE_COUNT),
+///     Some(Some(&Value::from(vec![Some(5)])))
+/// );
+/// ```
+pub fn split(header: &Header, record: &RecordBuf) -> Vec<RecordBuf> {
+    let alternate_bases = record.alternate_bases().as_ref();
+
+    if alternate_bases.len() < 2 {
+        return vec![record.clone()];
+    }
+
+    let allele_count = alternate_bases.len() + 1;
+
+    (1..allele_count)
+        .map(|i| split_allele(header, record, allele_count, i))
+        .collect()
+}
+
+fn split_allele(header: &Header, record: &RecordBuf, allele_count: usize, i: usize) -> RecordBuf {
+    let mut split_record = record.clone();
+
+    *split_record.alternate_bases_mut() =
+        AlternateBases::from(vec![record.alternate_bases().as_ref()[i - 1].clone()]);
+
+    for (key, value) in split_record.info_mut().as_mut() {
+        let number = info_number(header, key);
+        split_value::<InfoArray>(number, value, allele_count, i);
+    }
+
+    let (keys, mut samples) = mem::take(split_record.samples_mut()).into();
+
+    for sample in &mut samples {
+        for (key, value) in keys.as_ref().iter().zip(sample.iter_mut()) {
+            if key == key::GENOTYPE {
+                if let Some(Value::Genotype(genotype)) = value {
+                    for allele in genotype.as_mut() {
+                        if let Some(position) = allele.position_mut() {
+                            *position = usize::from(*position == i);
+                        }
+                    }
+                }
+            } else {
+                let number = format_number(header, key);
+                split_value::<SampleArray>(number, value, allele_count, i);
+            }
+        }
+    }
+
+    *split_record.samples_mut() = Samples::new(keys, samples);
+
+    split_record
+}
+
+fn split_value<A>(number: Number, value: &mut Option<A::Value>, allele_count: usize, i: usize)
+where
+    A: Array,
+{
+    let Some(array) = value.as_ref().and_then(|v| A::from_value(v)) else {
+        return;
+    };
+
+    let indices: Vec<_> = match number {
+        Number::AlternateBases => vec![i - 1],
+        Number::ReferenceAlternateBases => vec![0, i],
+        Number::Genotypes => match ploidy(allele_count, array.len()) {
+            Some(ploidy) => (0..=ploidy)
+                .map(|alternate_count| {
+                    let mut alleles = vec![0; ploidy - alternate_count];
+                    alleles.resize(ploidy, i);
+                    genotype_index(&alleles)
+                })
+                .collect(),
+            None => {
+                *value = None;
+                return;
+            }
+        },
+        Number::Other => return,
+    };
+
+    let sources: Vec<_> = indices
+        .into_iter()
+        .map(|j| Some((array.as_ref(), j)))
+        .collect();
+
+    *value = A::gather(&sources).map(A::into_value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        header::record::value::{
+            Map,
+            map::{Format, Info},
+        },
+        variant::{
+            record::{info::field::key as info_key, samples::series::value::genotype::Phasing},
+            record_buf::{
+                info::field::Value as InfoValue,
+                samples::{
+                    Keys,
+                    sample::value::{Genotype, genotype::Allele},
+                },
+            },
+        },
+    };
+
+    #[test]
+    fn test_split() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_info(
+                info_key::ALLELE_COUNT,
+                Map::<Info>::from(info_key::ALLELE_COUNT),
+            )
+            .add_info(
+                info_key::TOTAL_DEPTH,
+                Map::<Info>::from(info_key::TOTAL_DEPTH),
+            )
+            .add_format(key::GENOTYPE, Map::<Format>::from(key::GENOTYPE))
+            .add_format(key::READ_DEPTHS, Map::<Format>::from(key::READ_DEPTHS))
+            .add_format(
+                key::ROUNDED_GENOTYPE_LIKELIHOODS,
+                Map::<Format>::from(key::ROUNDED_GENOTYPE_LIKELIHOODS),
+            )
+            .add_sample_name("sample0")
+            .build();
+
+        let keys: Keys = [
+            String::from(key::GENOTYPE),
+            String::from(key::READ_DEPTHS),
+            String::from(key::ROUNDED_GENOTYPE_LIKELIHOODS),
+        ]
+        .into_iter()
+        .collect();
+
+        let record = RecordBuf::builder()
+            .set_reference_sequence_name("sq0")
+            .set_variant_start(noodles_core::Position::MIN)
+            .set_reference_bases("A")
+            .set_alternate_bases(vec![String::from("C"), String::from("G")].into())
+            .set_info(
+                [
+                    (
+                        String::from(info_key::ALLELE_COUNT),
+                        Some(InfoValue::from(vec![Some(1), Some(1)])),
+                    ),
+                    (
+                        String::from(info_key::TOTAL_DEPTH),
+                        Some(InfoValue::from(13)),
+                    ),
+                ]
+                .into_iter()
+                .collect(),
+            )
+            .set_samples(Samples::new(
+                keys.clone(),
+                vec![vec![
+                    Some(Value::Genotype("1/2".parse()?)),
+                    Some(Value::from(vec![Some(3), Some(5), Some(8)])),
+                    Some(Value::from(vec![
+                        Some(0),
+                        Some(1),
+                        Some(2),
+                        Some(3),
+                        Some(4),
+                        Some(5),
+                    ])),
+                ]],
+            ))
+            .build();
+
+        let records = split(&header, &record);
+        assert_eq!(records.len(), 2);
+
+        let genotype = |alleles: [usize; 2]| -> Genotype {
+            alleles
+                .into_iter()
+                .map(|i| Allele::new(Some(i), Phasing::Unphased))
+                .collect()
+        };
+
+        assert_eq!(records[0].alternate_bases().as_ref(), [String::from("C")]);
+        assert_eq!(
+            records[0].info().get(info_key::ALLELE_COUNT),
+            Some(Some(&InfoValue::from(vec![Some(1)])))
+        );
+        assert_eq!(
+            records[0].info().get(info_key::TOTAL_DEPTH),
+            Some(Some(&InfoValue::from(13)))
+        );
+        assert_eq!(
+            records[0].samples(),
+            &Samples::new(
+                keys.clone(),
+                vec![vec![
+                    Some(Value::Genotype(genotype([1, 0]))),
+                    Some(Value::from(vec![Some(3), Some(5)])),
+                    Some(Value::from(vec![Some(0), Some(1), Some(2)])),
+                ]]
+            )
+        );
+
+        assert_eq!(records[1].alternate_bases().as_ref(), [String::from("G")]);
+        assert_eq!(
+            records[1].samples(),
+            &Samples::new(
+                keys,
+                vec![vec![
+                    Some(Value::Genotype(genotype([0, 1]))),
+                    Some(Value::from(vec![Some(3), Some(8)])),
+                    Some(Value::from(vec![Some(0), Some(3), Some(5)])),
+                ]]
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_with_biallelic_record() {
+        let header = Header::default();
+
+        let record = RecordBuf::builder()
+            .set_reference_bases("A")
+            .set_alternate_bases(vec![String::from("C")].into())
+            .build();
+
+        assert_eq!(split(&header, &record), [record]);
+    }
+}
@@ -0,0 +1,428 @@
+//! Variant record normalization.
+//!
+//! A normalized variant is parsimonious, i.e., its alleles share no redundant leading or trailing
+//! bases, and left-aligned, i.e., its position cannot be shifted further left without changing
+//! the alleles. This is the normalization described by Tan et al. (2015) and implemented by
+//! `bcftools norm` and `vt normalize`.
+//!
+//! To normalize a [`crate::variant::Record`], first convert it to a [`RecordBuf`] using
+//! [`RecordBuf::try_from_variant_record`].
+
+use std::{error, fmt, io};
+
+use noodles_core::Position;
+use noodles_fasta::{self as fasta, record::Sequence};
+
+use super::RecordBuf;
+
+/// An error returned when a variant record fails to normalize.
+#[derive(Debug)]
+pub enum NormalizeError {
+    /// The variant start is missing.
+    MissingVariantStart,
+    /// The reference sequence is missing from the repository.
+    MissingReferenceSequence(String),
+    /// The reference sequence could not be read.
+    Io(io::Error),
+    /// The reference bases are past the end of the reference sequence.
+    ReferenceBasesOutOfBounds,
+    /// The reference bases do not match the reference sequence.
+    ReferenceBasesMismatch {
+        /// The bases in the reference sequence.
+        expected: String,
+        /// The reference bases in the record.
+        actual: String,
+    },
+}
+
+impl error::Error for NormalizeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for NormalizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingVariantStart => write!(f, "missing variant start"),
+            Self::MissingReferenceSequence(name) => {
+                write!(f, "missing reference sequence: {name}")
+            }
+            Self::Io(_) => write!(f, "I/O error"),
+            Self::ReferenceBasesOutOfBounds => write!(f, "reference bases out of bounds"),
+            Self::ReferenceBasesMismatch { expected, actual } => write!(
+                f,
+                "reference bases mismatch: expected {expected}, got {actual}"
+            ),
+        }
+    }
+}
+
+/// The changes made to a variant record by normalization.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Changes {
+    is_left_aligned: bool,
+    is_trimmed: bool,
+}
+
+impl Changes {
+    /// Returns whether the variant was shifted left.
+    pub fn is_left_aligned(&self) -> bool {
+        self.is_left_aligned
+    }
+
+    /// Returns whether redundant bases were removed from the alleles.
+    pub fn is_trimmed(&self) -> bool {
+        self.is_trimmed
+    }
+
+    /// Returns whether the record was changed.
+    pub fn is_changed(&self) -> bool {
+        self.is_left_aligned || self.is_trimmed
+    }
+}
+
+/// Normalizes a variant record against a reference sequence.
+///
+/// The reference bases are first verified against the reference sequence. Then, indels are
+/// left-aligned and shared leading and trailing bases are trimmed from all alleles, keeping one
+/// padding base when an allele would otherwise be empty.
+///
+/// Records with symbolic, breakend, overlapping deletion (`*`), or missing alternate alleles are
+/// verified but otherwise left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_fasta::{self as fasta, record::{Definition, Sequence}};
+/// use noodles_vcf::variant::{RecordBuf, normalization::normalize};
+///
+/// let repository = fasta::Repository::new(vec![fasta::Record::new(
+///     Definition::new("sq0", None),
+///     Sequence::from(b"GCACACAT".to_vec()),
+/// )]);
+///
+/// // An insertion of "CA" in the repeat, right-aligned.
+/// let mut record = RecordBuf::builder()
+///     .set_reference_sequence_name("sq0")
+///     .set_variant_start(Position::try_from(6)?)
+///     .set_reference_bases("C")
+///     .set_alternate_bases(vec![String::from("CAC")].into())
+///     .build();
+///
+/// let changes = normalize(&mut record, &repository)?;
+/// assert!(changes.is_left_aligned());
+///
+/// assert_eq!(record.variant_start(), Some(Position::MIN));
+/// assert_eq!(record.reference_bases(), "G");
+/// assert_eq!(record.alternate_bases().as_ref(), [String::from("GCA")]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn normalize(
+    record: &mut RecordBuf,
+    repository: &fasta::Repository,
+) -> Result<Changes, NormalizeError> {
+    let start = record
+        .variant_start()
+        .ok_or(NormalizeError::MissingVariantStart)?;
+
+    let reference_sequence_name = record.reference_sequence_name();
+
+    let sequence = repository
+        .get(reference_sequence_name.as_bytes())
+        .ok_or_else(|| NormalizeError::MissingReferenceSequence(reference_sequence_name.into()))?
+        .map_err(NormalizeError::Io)?;
+
+    verify_reference_bases(&sequence, start, record.reference_bases())?;
+
+    if !is_normalizable(record) {
+        return Ok(Changes::default());
+    }
+
+    let mut alleles: Vec<Vec<u8>> = Vec::with_capacity(record.alternate_bases().as_ref().len() + 1);
+    alleles.push(record.reference_bases().as_bytes().to_ascii_uppercase());
+
+    for allele in record.alternate_bases().as_ref() {
+        alleles.push(allele.as_bytes().to_ascii_uppercase());
+    }
+
+    let original_reference_bases_length = alleles[0].len();
+
+    let new_start = left_align(&sequence, start, &mut alleles);
+    let new_start = trim_leading_bases(new_start, &mut alleles);
+
+    let changes = Changes {
+        is_left_aligned: new_start < start,
+        is_trimmed: alleles[0].len() < original_reference_bases_length,
+    };
+
+    if changes.is_changed() {
+        let mut alleles = alleles
+            .into_iter()
+            .map(|allele| allele.into_iter().map(char::from).collect());
+
+        // SAFETY: `alleles` has at least one allele, the reference bases.
+        *record.reference_bases_mut() = alleles.next().unwrap();
+        *record.alternate_bases_mut().as_mut() = alleles.collect();
+        *record.variant_start_mut() = Some(new_start);
+    }
+
+    Ok(changes)
+}
+
+fn verify_reference_bases(
+    sequence: &Sequence,
+    start: Position,
+    reference_bases: &str,
+) -> Result<(), NormalizeError> {
+    if reference_bases.is_empty() {
+        return Ok(());
+    }
+
+    let end = usize::from(start) + reference_bases.len() - 1;
+    let end = Position::new(end).ok_or(NormalizeError::ReferenceBasesOutOfBounds)?;
+
+    let expected = sequence
+        .get(start..=end)
+        .ok_or(NormalizeError::ReferenceBasesOutOfBounds)?;
+
+    let is_match = expected
+        .iter()
+        .zip(reference_bases.as_bytes())
+        .all(|(a, b)| a.eq_ignore_ascii_case(b) || b.eq_ignore_ascii_case(&b'N'));
+
+    if is_match {
+        Ok(())
+    } else {
+        Err(NormalizeError::ReferenceBasesMismatch {
+            expected: expected.iter().copied().map(char::from).collect(),
+            actual: reference_bases.into(),
+        })
+    }
+}
+
+fn is_normalizable(record: &RecordBuf) -> bool {
+    fn is_bases(allele: &str) -> bool {
+        !allele.is_empty()
+            && allele
+                .bytes()
+                .all(|b| matches!(b.to_ascii_uppercase(), b'A' | b'C' | b'G' | b'T' | b'N'))
+    }
+
+    let alternate_bases = record.alternate_bases().as_ref();
+
+    is_bases(record.reference_bases())
+        && !alternate_bases.is_empty()
+        && alternate_bases.iter().all(|allele| is_bases(allele))
+}
+
+// Left-aligns alleles by repeatedly trimming a shared trailing base and, when an allele becomes
+// empty, extending all alleles by the preceding reference base. At the start of the reference
+// sequence, there is no preceding base, so alleles are instead extended by the following base.
+fn left_align(sequence: &Sequence, start: Position, alleles: &mut [Vec<u8>]) -> Position {
+    let mut start = start;
+
+    loop {
+        let mut is_changed = false;
+
+        let last_bases_match = alleles.iter().all(|allele| !allele.is_empty())
+            && alleles
+                .windows(2)
+                .all(|pair| pair[0].last() == pair[1].last());
+
+        if last_bases_match {
+            for allele in alleles.iter_mut() {
+                allele.pop();
+            }
+
+            is_changed = true;
+        }
+
+        if alleles.iter().any(|allele| allele.is_empty()) {
+            let Some(prev) = usize::from(start).checked_sub(1).and_then(Position::new) else {
+                let next = start.checked_add(alleles[0].len());
+
+                if let Some(base) = next.and_then(|position| sequence.get(position)) {
+                    for allele in alleles.iter_mut() {
+                        allele.push(base.to_ascii_uppercase());
+                    }
+                }
+
+                break;
+            };
+
+            let Some(base) = sequence.get(prev) else {
+                break;
+            };
+
+            for allele in alleles.iter_mut() {
+                allele.insert(0, base.to_ascii_uppercase());
+            }
+
+            start = prev;
+            is_changed = true;
+        }
+
+        if !is_changed {
+            break;
+        }
+    }
+
+    start
+}
+
+// Trims shared leading bases, keeping at least one base in each allele.
+fn trim_leading_bases(start: Position, alleles: &mut [Vec<u8>]) -> Position {
+    let min_len = alleles.iter().map(|allele| allele.len()).min().unwrap_or(0);
+
+    let n = (0..min_len.saturating_sub(1))
+        .take_while(|&i| alleles.windows(2).all(|pair| pair[0][i] == pair[1][i]))
+        .count();
+
+    for allele in alleles.iter_mut() {
+        allele.drain(..n);
+    }
+
+    // SAFETY: `start + n` is within the reference bases.
+    start.checked_add(n).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use fasta::record::Definition;
+
+    use super::*;
+
+    fn build_repository() -> fasta::Repository {
+        fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"GCACACATTTTG".to_vec()),
+        )])
+    }
+
+    fn build_record(start: usize, reference_bases: &str, alternate_bases: &[&str]) -> RecordBuf {
+        RecordBuf::builder()
+            .set_reference_sequence_name("sq0")
+            .set_variant_start(Position::new(start).unwrap())
+            .set_reference_bases(reference_bases)
+            .set_alternate_bases(
+                alternate_bases
+                    .iter()
+                    .map(|allele| String::from(*allele))
+                    .collect::<Vec<_>>()
+                    .into(),
+            )
+            .build()
+    }
+
+    fn alleles(record: &RecordBuf) -> (Option<usize>, &str, Vec<&str>) {
+        (
+            record.variant_start().map(usize::from),
+            record.reference_bases(),
+            record
+                .alternate_bases()
+                .as_ref()
+                .iter()
+                .map(|allele| allele.as_str())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_normalize() -> Result<(), NormalizeError> {
+        let repository = build_repository();
+
+        // deletion of "AC" in the repeat, right-aligned
+        let mut record = build_record(4, "CAC", &["C"]);
+        let changes = normalize(&mut record, &repository)?;
+        assert!(changes.is_left_aligned());
+        assert!(!changes.is_trimmed());
+        assert_eq!(alleles(&record), (Some(1), "GCA", vec!["G"]));
+
+        // deletion of "T" in a homopolymer
+        let mut record = build_record(10, "TT", &["T"]);
+        let changes = normalize(&mut record, &repository)?;
+        assert!(changes.is_left_aligned());
+        assert_eq!(alleles(&record), (Some(7), "AT", vec!["A"]));
+
+        // padded SNV
+        let mut record = build_record(2, "CAC", &["CGC"]);
+        let changes = normalize(&mut record, &repository)?;
+        assert!(!changes.is_left_aligned());
+        assert!(changes.is_trimmed());
+        assert_eq!(alleles(&record), (Some(3), "A", vec!["G"]));
+
+        // multiallelic
+        let mut record = build_record(7, "ATTT", &["ATT", "ATTTT"]);
+        let changes = normalize(&mut record, &repository)?;
+        assert!(!changes.is_left_aligned());
+        assert!(changes.is_trimmed());
+        assert_eq!(alleles(&record), (Some(7), "AT", vec!["A", "ATT"]));
+
+        // already normalized
+        let mut record = build_record(2, "C", &["T"]);
+        let changes = normalize(&mut record, &repository)?;
+        assert!(!changes.is_changed());
+        assert_eq!(alleles(&record), (Some(2), "C", vec!["T"]));
+
+        // symbolic allele
+        let mut record = build_record(2, "C", &["<DEL>"]);
+        let changes = normalize(&mut record, &repository)?;
+        assert!(!changes.is_changed());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_at_reference_sequence_start() -> Result<(), NormalizeError> {
+        let repository = build_repository();
+
+        // deletion of "G" at the start
+        let mut record = build_record(1, "GC", &["C"]);
+        let changes = normalize(&mut record, &repository)?;
+        assert!(!changes.is_changed());
+        assert_eq!(alleles(&record), (Some(1), "GC", vec!["C"]));
+
+        // insertion of "G" at the start
+        let mut record = build_record(1, "G", &["GG"]);
+        let changes = normalize(&mut record, &repository)?;
+        assert!(!changes.is_changed());
+        assert_eq!(alleles(&record), (Some(1), "G", vec!["GG"]));
+
+        // deletion of "CA" in the repeat, right-aligned, shifted to the start
+        let mut record = build_record(2, "CAC", &["C"]);
+        let changes = normalize(&mut record, &repository)?;
+        assert!(changes.is_left_aligned());
+        assert_eq!(alleles(&record), (Some(1), "GCA", vec!["G"]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_with_invalid_reference_bases() {
+        let repository = build_repository();
+
+        let mut record = build_record(2, "G", &["T"]);
+        assert!(matches!(
+            normalize(&mut record, &repository),
+            Err(NormalizeError::ReferenceBasesMismatch { .. })
+        ));
+
+        let mut record = build_record(12, "GA", &["G"]);
+        assert!(matches!(
+            normalize(&mut record, &repository),
+            Err(NormalizeError::ReferenceBasesOutOfBounds)
+        ));
+
+        let mut record = build_record(1, "G", &["T"]);
+        *record.reference_sequence_name_mut() = String::from("sq1");
+        assert!(matches!(
+            normalize(&mut record, &repository),
+            Err(NormalizeError::MissingReferenceSequence(_))
+        ));
+    }
+}
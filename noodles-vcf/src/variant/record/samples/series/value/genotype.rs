@@ -1,13 +1,174 @@
 //! Variant record samples genotype value.
 
 mod phasing;
+mod zygosity;
 
 use std::{fmt::Debug, io};
 
-pub use self::phasing::Phasing;
+pub use self::{phasing::Phasing, zygosity::Zygosity};
 
 /// A variant record samples genotype value.
 pub trait Genotype: Debug {
     /// Returns an iterator over allele position-phasing pairs.
     fn iter(&self) -> Box<dyn Iterator<Item = io::Result<(Option<usize>, Phasing)>> + '_>;
+
+    /// Returns the number of alleles, including missing alleles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::variant::{
+    ///     record::samples::series::value::Genotype as _,
+    ///     record_buf::samples::sample::value::Genotype,
+    /// };
+    ///
+    /// let genotype: Genotype = "0/.".parse()?;
+    /// assert_eq!((&genotype).ploidy()?, 2);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn ploidy(&self) -> io::Result<usize> {
+        self.iter().try_fold(0, |n, result| result.map(|_| n + 1))
+    }
+
+    /// Returns whether the genotype is phased.
+    ///
+    /// A genotype is phased if every allele after the first is phased. Haploid genotypes are
+    /// trivially phased.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::variant::{
+    ///     record::samples::series::value::Genotype as _,
+    ///     record_buf::samples::sample::value::Genotype,
+    /// };
+    ///
+    /// let genotype: Genotype = "0|1".parse()?;
+    /// assert!((&genotype).is_phased()?);
+    ///
+    /// let genotype: Genotype = "0/1".parse()?;
+    /// assert!(!(&genotype).is_phased()?);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn is_phased(&self) -> io::Result<bool> {
+        for result in self.iter().skip(1) {
+            let (_, phasing) = result?;
+
+            if phasing == Phasing::Unphased {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Returns the zygosity of the genotype.
+    ///
+    /// A genotype with some, but not all, alleles missing is a partial call, e.g., `./1` and `0/.`,
+    /// since the missing alleles may or may not match the called alleles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::variant::{
+    ///     record::samples::series::value::{Genotype as _, genotype::Zygosity},
+    ///     record_buf::samples::sample::value::Genotype,
+    /// };
+    ///
+    /// let genotype: Genotype = "0/1".parse()?;
+    /// assert_eq!((&genotype).zygosity()?, Zygosity::Heterozygous);
+    ///
+    /// let genotype: Genotype = "./.".parse()?;
+    /// assert_eq!((&genotype).zygosity()?, Zygosity::NoCall);
+    ///
+    /// let genotype: Genotype = "./1".parse()?;
+    /// assert_eq!((&genotype).zygosity()?, Zygosity::PartialCall);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn zygosity(&self) -> io::Result<Zygosity> {
+        let mut first_position = None;
+        let mut is_heterozygous = false;
+        let mut has_missing_allele = false;
+
+        for result in self.iter() {
+            let (position, _) = result?;
+
+            match (position, first_position) {
+                (None, _) => has_missing_allele = true,
+                (Some(position), None) => first_position = Some(position),
+                (Some(position), Some(first)) if position != first => is_heterozygous = true,
+                (Some(_), Some(_)) => {}
+            }
+        }
+
+        let zygosity = match first_position {
+            None => Zygosity::NoCall,
+            Some(_) if has_missing_allele => Zygosity::PartialCall,
+            Some(_) if is_heterozygous => Zygosity::Heterozygous,
+            Some(0) => Zygosity::HomozygousReference,
+            Some(_) => Zygosity::HomozygousAlternate,
+        };
+
+        Ok(zygosity)
+    }
+
+    /// Returns the number of alternate alleles.
+    ///
+    /// This returns `None` if any allele is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::variant::{
+    ///     record::samples::series::value::Genotype as _,
+    ///     record_buf::samples::sample::value::Genotype,
+    /// };
+    ///
+    /// let genotype: Genotype = "1/2".parse()?;
+    /// assert_eq!((&genotype).dosage()?, Some(2));
+    ///
+    /// let genotype: Genotype = "0/.".parse()?;
+    /// assert!((&genotype).dosage()?.is_none());
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn dosage(&self) -> io::Result<Option<usize>> {
+        let mut n = 0;
+
+        for result in self.iter() {
+            match result? {
+                (Some(0), _) => {}
+                (Some(_), _) => n += 1,
+                (None, _) => return Ok(None),
+            }
+        }
+
+        Ok(Some(n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::variant::record_buf::samples::sample::value::Genotype as GenotypeBuf;
+
+    #[test]
+    fn test_zygosity() -> Result<(), Box<dyn std::error::Error>> {
+        for (s, expected) in [
+            ("0/0", Zygosity::HomozygousReference),
+            ("0/1", Zygosity::Heterozygous),
+            ("1|2", Zygosity::Heterozygous),
+            ("1/1", Zygosity::HomozygousAlternate),
+            ("1", Zygosity::HomozygousAlternate),
+            ("./.", Zygosity::NoCall),
+            (".", Zygosity::NoCall),
+            ("./1", Zygosity::PartialCall),
+            ("0/.", Zygosity::PartialCall),
+            ("0/1/.", Zygosity::PartialCall),
+        ] {
+            let genotype: GenotypeBuf = s.parse()?;
+            assert_eq!((&genotype).zygosity()?, expected, "{s}");
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,14 @@
+/// A variant record samples series genotype value zygosity.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Zygosity {
+    /// All alleles are missing (e.g., `./.`).
+    NoCall,
+    /// Some, but not all, alleles are missing (e.g., `./1`, `0/.`).
+    PartialCall,
+    /// All alleles are the reference allele (e.g., `0/0`).
+    HomozygousReference,
+    /// The alleles differ (e.g., `0/1`, `1/2`).
+    Heterozygous,
+    /// All alleles are the same alternate allele (e.g., `1/1`).
+    HomozygousAlternate,
+}
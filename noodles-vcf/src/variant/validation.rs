@@ -0,0 +1,646 @@
+//! Variant record validation.
+
+mod diagnostic;
+
+pub use self::diagnostic::Diagnostic;
+
+use std::{collections::HashSet, io, mem};
+
+use noodles_core::Position;
+
+use super::{
+    Record,
+    multiallelic::genotype_count,
+    record::{
+        AlternateBases, Filters, Info, Samples,
+        info::field::Value as InfoValue,
+        samples::{
+            keys::key,
+            series::{Value as SampleValue, value::Array as SampleArray},
+        },
+    },
+};
+use crate::{
+    Header,
+    header::record::value::map::{format, info},
+    variant::record::info::field::value::Array as InfoArray,
+};
+
+const PASS: &str = "PASS";
+const DEFAULT_PLOIDY: usize = 2;
+
+/// A variant record validator.
+///
+/// This checks records against the definitions in a VCF header. Problems with a record are
+/// reported as [`Diagnostic`]s rather than errors, so a record can have more than one.
+///
+/// The validator checks
+///
+///   * that the reference sequence is defined and, if the contig has a length, that the variant
+///     ends within it;
+///   * that records are sorted, i.e., positions do not decrease within a reference sequence and
+///     a reference sequence is not revisited;
+///   * that filters are defined; and
+///   * that INFO and FORMAT keys are defined and their values have the defined type and number.
+///     `Number=A`, `Number=R`, and `Number=G` are relative to the number of alternate bases, and
+///     `Number=G` and `Number=P` use the ploidy of the sample genotype (`GT`), or diploid if
+///     there is none. INFO `Number=G` values are assumed to be diploid.
+///
+/// This works with any [`Record`], e.g., VCF and BCF records.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_vcf::{
+///     self as vcf,
+///     header::record::value::{map::Contig, Map},
+///     variant::{validation::{Diagnostic, Validator}, RecordBuf},
+/// };
+///
+/// let header = vcf::Header::builder()
+///     .add_contig("sq0", Map::<Contig>::new())
+///     .build();
+///
+/// let record = RecordBuf::builder()
+///     .set_reference_sequence_name("sq1")
+///     .set_variant_start(Position::MIN)
+///     .set_reference_bases("A")
+///     .build();
+///
+/// let mut validator = Validator::new(&header);
+/// let diagnostics = validator.validate(&record)?;
+///
+/// assert_eq!(diagnostics, [Diagnostic::UndefinedContig(String::from("sq1"))]);
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub struct Validator<'h> {
+    header: &'h Header,
+    previous: Option<(String, Option<Position>)>,
+    visited_reference_sequence_names: HashSet<String>,
+}
+
+impl<'h> Validator<'h> {
+    /// Creates a variant record validator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_vcf::{self as vcf, variant::validation::Validator};
+    /// let header = vcf::Header::default();
+    /// let validator = Validator::new(&header);
+    /// ```
+    pub fn new(header: &'h Header) -> Self {
+        Self {
+            header,
+            previous: None,
+            visited_reference_sequence_names: HashSet::new(),
+        }
+    }
+
+    /// Validates a record.
+    ///
+    /// Sort order is checked against the previously validated record.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the reference sequence name, position, filters, or samples of the
+    /// record cannot be read. Invalid INFO and sample values are reported as diagnostics.
+    pub fn validate<R>(&mut self, record: &R) -> io::Result<Vec<Diagnostic>>
+    where
+        R: Record + ?Sized,
+    {
+        let mut diagnostics = Vec::new();
+
+        self.validate_position(record, &mut diagnostics)?;
+        validate_filters(self.header, record, &mut diagnostics)?;
+        validate_info(self.header, record, &mut diagnostics);
+        validate_samples(self.header, record, &mut diagnostics)?;
+
+        Ok(diagnostics)
+    }
+
+    fn validate_position<R>(
+        &mut self,
+        record: &R,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> io::Result<()>
+    where
+        R: Record + ?Sized,
+    {
+        let name = record.reference_sequence_name(self.header)?;
+        let start = record.variant_start().transpose()?;
+
+        match self.header.contigs().get(name) {
+            Some(contig) => {
+                if let Some(length) = contig.length()
+                    && start.is_some()
+                {
+                    let end = record.variant_end(self.header)?;
+
+                    if usize::from(end) > length {
+                        diagnostics.push(Diagnostic::ContigLengthExceeded {
+                            name: name.into(),
+                            end,
+                            length,
+                        });
+                    }
+                }
+            }
+            None => diagnostics.push(Diagnostic::UndefinedContig(name.into())),
+        }
+
+        let is_unsorted = match &mut self.previous {
+            Some((previous_name, previous_start)) if previous_name == name => {
+                mem::replace(previous_start, start) > start
+            }
+            Some((previous_name, previous_start)) => {
+                let previous_name = mem::replace(previous_name, name.into());
+                self.visited_reference_sequence_names.insert(previous_name);
+                *previous_start = start;
+                self.visited_reference_sequence_names.contains(name)
+            }
+            None => {
+                self.previous = Some((name.into(), start));
+                false
+            }
+        };
+
+        if is_unsorted && let Some(start) = start {
+            diagnostics.push(Diagnostic::Unsorted {
+                name: name.into(),
+                start,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_filters<R>(
+    header: &Header,
+    record: &R,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> io::Result<()>
+where
+    R: Record + ?Sized,
+{
+    for result in record.filters().iter(header) {
+        let id = result?;
+
+        if id != PASS && !header.filters().contains_key(id) {
+            diagnostics.push(Diagnostic::UndefinedFilter(id.into()));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_info<R>(header: &Header, record: &R, diagnostics: &mut Vec<Diagnostic>)
+where
+    R: Record + ?Sized,
+{
+    let alternate_base_count = record.alternate_bases().len();
+    let info = record.info();
+
+    for result in info.iter(header) {
+        let (key, value) = match result {
+            Ok(field) => field,
+            Err(e) => {
+                diagnostics.push(Diagnostic::InvalidInfoField(e.to_string()));
+                continue;
+            }
+        };
+
+        let Some(definition) = header.infos().get(key) else {
+            diagnostics.push(Diagnostic::UndefinedInfo(key.into()));
+            continue;
+        };
+
+        let Some(value) = value else {
+            continue;
+        };
+
+        if !is_info_type(definition.ty(), &value) {
+            diagnostics.push(Diagnostic::InfoTypeMismatch {
+                key: key.into(),
+                expected: definition.ty(),
+            });
+
+            continue;
+        }
+
+        if let Some(expected) = info_len(definition.number(), alternate_base_count) {
+            let actual = info_value_len(&value);
+
+            if actual != expected {
+                diagnostics.push(Diagnostic::InfoNumberMismatch {
+                    key: key.into(),
+                    expected,
+                    actual,
+                });
+            }
+        }
+    }
+}
+
+fn is_info_type(ty: info::Type, value: &InfoValue<'_>) -> bool {
+    matches!(
+        (ty, value),
+        (info::Type::Integer, InfoValue::Integer(_))
+            | (info::Type::Integer, InfoValue::Array(InfoArray::Integer(_)))
+            | (info::Type::Float, InfoValue::Float(_))
+            | (info::Type::Float, InfoValue::Array(InfoArray::Float(_)))
+            | (info::Type::Flag, InfoValue::Flag)
+            | (info::Type::Character, InfoValue::Character(_))
+            | (
+                info::Type::Character,
+                InfoValue::Array(InfoArray::Character(_))
+            )
+            | (info::Type::String, InfoValue::String(_))
+            | (info::Type::String, InfoValue::Array(InfoArray::String(_)))
+    )
+}
+
+fn info_len(number: info::Number, alternate_base_count: usize) -> Option<usize> {
+    match number {
+        info::Number::Count(n) => Some(n),
+        info::Number::AlternateBases => Some(alternate_base_count),
+        info::Number::ReferenceAlternateBases => Some(alternate_base_count + 1),
+        info::Number::Samples => Some(genotype_count(alternate_base_count + 1, DEFAULT_PLOIDY)),
+        info::Number::Unknown => None,
+    }
+}
+
+fn info_value_len(value: &InfoValue<'_>) -> usize {
+    match value {
+        InfoValue::Flag => 0,
+        InfoValue::Array(InfoArray::Integer(values)) => values.len(),
+        InfoValue::Array(InfoArray::Float(values)) => values.len(),
+        InfoValue::Array(InfoArray::Character(values)) => values.len(),
+        InfoValue::Array(InfoArray::String(values)) => values.len(),
+        _ => 1,
+    }
+}
+
+fn validate_samples<R>(
+    header: &Header,
+    record: &R,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> io::Result<()>
+where
+    R: Record + ?Sized,
+{
+    let allele_count = record.alternate_bases().len() + 1;
+    let samples = record.samples()?;
+    let ploidies = sample_ploidies(header, &samples)?;
+
+    for result in samples.series() {
+        let series = result?;
+        let key = series.name(header)?;
+
+        let Some(definition) = header.formats().get(key) else {
+            diagnostics.push(Diagnostic::UndefinedFormat(key.into()));
+            continue;
+        };
+
+        for (i, result) in series.iter(header).enumerate() {
+            let value = match result {
+                Ok(Some(value)) => value,
+                Ok(None) => continue,
+                Err(e) => {
+                    diagnostics.push(Diagnostic::InvalidSampleValue {
+                        sample: i,
+                        key: key.into(),
+                        message: e.to_string(),
+                    });
+
+                    continue;
+                }
+            };
+
+            if !is_format_type(definition.ty(), &value) {
+                diagnostics.push(Diagnostic::FormatTypeMismatch {
+                    sample: i,
+                    key: key.into(),
+                    expected: definition.ty(),
+                });
+
+                continue;
+            }
+
+            let ploidy = ploidies.get(i).copied().flatten();
+
+            if let Some(expected) = format_len(definition.number(), allele_count, ploidy) {
+                let actual = sample_value_len(&value);
+
+                if actual != expected {
+                    diagnostics.push(Diagnostic::FormatNumberMismatch {
+                        sample: i,
+                        key: key.into(),
+                        expected,
+                        actual,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn sample_ploidies<S>(header: &Header, samples: &S) -> io::Result<Vec<Option<usize>>>
+where
+    S: Samples,
+{
+    let Some(series) = samples.select(header, key::GENOTYPE).transpose()? else {
+        return Ok(Vec::new());
+    };
+
+    Ok(series
+        .iter(header)
+        .map(|result| match result {
+            Ok(Some(SampleValue::Genotype(genotype))) => genotype.ploidy().ok(),
+            _ => None,
+        })
+        .collect())
+}
+
+fn is_format_type(ty: format::Type, value: &SampleValue<'_>) -> bool {
+    matches!(
+        (ty, value),
+        (format::Type::Integer, SampleValue::Integer(_))
+            | (
+                format::Type::Integer,
+                SampleValue::Array(SampleArray::Integer(_))
+            )
+            | (format::Type::Float, SampleValue::Float(_))
+            | (
+                format::Type::Float,
+                SampleValue::Array(SampleArray::Float(_))
+            )
+            | (format::Type::Character, SampleValue::Character(_))
+            | (
+                format::Type::Character,
+                SampleValue::Array(SampleArray::Character(_))
+            )
+            | (format::Type::String, SampleValue::String(_))
+            | (format::Type::String, SampleValue::Genotype(_))
+            | (
+                format::Type::String,
+                SampleValue::Array(SampleArray::String(_))
+            )
+    )
+}
+
+fn format_len(number: format::Number, allele_count: usize, ploidy: Option<usize>) -> Option<usize> {
+    match number {
+        format::Number::Count(n) => Some(n),
+        format::Number::AlternateBases => Some(allele_count - 1),
+        format::Number::ReferenceAlternateBases => Some(allele_count),
+        format::Number::Samples => match ploidy.unwrap_or(DEFAULT_PLOIDY) {
+            0 => None,
+            ploidy => Some(genotype_count(allele_count, ploidy)),
+        },
+        format::Number::Ploidy => ploidy,
+        format::Number::LocalAlternateBases
+        | format::Number::LocalReferenceAlternateBases
+        | format::Number::LocalSamples
+        | format::Number::BaseModifications
+        | format::Number::Unknown => None,
+    }
+}
+
+fn sample_value_len(value: &SampleValue<'_>) -> usize {
+    match value {
+        SampleValue::Array(SampleArray::Integer(values)) => values.len(),
+        SampleValue::Array(SampleArray::Float(values)) => values.len(),
+        SampleValue::Array(SampleArray::Character(values)) => values.len(),
+        SampleValue::Array(SampleArray::String(values)) => values.len(),
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        header::record::value::{
+            Map,
+            map::{Contig, Filter, Format, Info},
+        },
+        variant::{
+            RecordBuf,
+            record::info::field::key as info_key,
+            record_buf::{
+                Samples as SampleBufs, info::field::Value as InfoValueBuf,
+                samples::sample::Value as SampleValueBuf,
+            },
+        },
+    };
+
+    fn build_header() -> Header {
+        Header::builder()
+            .add_contig(
+                "sq0",
+                Map::<Contig>::builder().set_length(8).build().unwrap(),
+            )
+            .add_contig("sq1", Map::<Contig>::new())
+            .add_filter("q10", Map::<Filter>::new("Quality below 10"))
+            .add_info(
+                info_key::ALLELE_COUNT,
+                Map::<Info>::from(info_key::ALLELE_COUNT),
+            )
+            .add_info(
+                info_key::TOTAL_DEPTH,
+                Map::<Info>::from(info_key::TOTAL_DEPTH),
+            )
+            .add_format(key::GENOTYPE, Map::<Format>::from(key::GENOTYPE))
+            .add_format(
+                key::ROUNDED_GENOTYPE_LIKELIHOODS,
+                Map::<Format>::from(key::ROUNDED_GENOTYPE_LIKELIHOODS),
+            )
+            .add_sample_name("sample0")
+            .add_sample_name("sample1")
+            .build()
+    }
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+
+        let keys = [
+            String::from(key::GENOTYPE),
+            String::from(key::ROUNDED_GENOTYPE_LIKELIHOODS),
+        ]
+        .into_iter()
+        .collect();
+
+        let record = RecordBuf::builder()
+            .set_reference_sequence_name("sq0")
+            .set_variant_start(Position::MIN)
+            .set_reference_bases("A")
+            .set_alternate_bases(vec![String::from("C")].into())
+            .set_filters(["q10"].into_iter().map(String::from).collect())
+            .set_info(
+                [(
+                    String::from(info_key::ALLELE_COUNT),
+                    Some(InfoValueBuf::from(vec![Some(1)])),
+                )]
+                .into_iter()
+                .collect(),
+            )
+            .set_samples(SampleBufs::new(
+                keys,
+                vec![
+                    vec![
+                        Some(SampleValueBuf::Genotype("0/1".parse()?)),
+                        Some(SampleValueBuf::from(vec![Some(0), Some(1), Some(2)])),
+                    ],
+                    vec![
+                        Some(SampleValueBuf::Genotype("1".parse()?)),
+                        Some(SampleValueBuf::from(vec![Some(0), Some(1)])),
+                    ],
+                ],
+            ))
+            .build();
+
+        let mut validator = Validator::new(&header);
+        assert!(validator.validate(&record)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_with_invalid_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+
+        let keys = [
+            String::from(key::GENOTYPE),
+            String::from(key::ROUNDED_GENOTYPE_LIKELIHOODS),
+            String::from(key::READ_DEPTH),
+        ]
+        .into_iter()
+        .collect();
+
+        let record = RecordBuf::builder()
+            .set_reference_sequence_name("sq0")
+            .set_variant_start(Position::try_from(8)?)
+            .set_reference_bases("AC")
+            .set_alternate_bases(vec![String::from("A"), String::from("G")].into())
+            .set_filters(["q20"].into_iter().map(String::from).collect())
+            .set_info(
+                [
+                    (
+                        String::from(info_key::ALLELE_COUNT),
+                        Some(InfoValueBuf::from(vec![Some(1)])),
+                    ),
+                    (
+                        String::from(info_key::TOTAL_DEPTH),
+                        Some(InfoValueBuf::from(8.0)),
+                    ),
+                    (String::from("NDLS"), Some(InfoValueBuf::Flag)),
+                ]
+                .into_iter()
+                .collect(),
+            )
+            .set_samples(SampleBufs::new(
+                keys,
+                vec![
+                    vec![
+                        Some(SampleValueBuf::Genotype("0/1".parse()?)),
+                        Some(SampleValueBuf::from(vec![Some(0), Some(1), Some(2)])),
+                        None,
+                    ],
+                    vec![Some(SampleValueBuf::Genotype("1".parse()?)), None, None],
+                ],
+            ))
+            .build();
+
+        let mut validator = Validator::new(&header);
+        let diagnostics = validator.validate(&record)?;
+
+        assert_eq!(
+            diagnostics,
+            [
+                Diagnostic::ContigLengthExceeded {
+                    name: String::from("sq0"),
+                    end: Position::try_from(9)?,
+                    length: 8,
+                },
+                Diagnostic::UndefinedFilter(String::from("q20")),
+                Diagnostic::InfoNumberMismatch {
+                    key: String::from(info_key::ALLELE_COUNT),
+                    expected: 2,
+                    actual: 1,
+                },
+                Diagnostic::InfoTypeMismatch {
+                    key: String::from(info_key::TOTAL_DEPTH),
+                    expected: info::Type::Integer,
+                },
+                Diagnostic::UndefinedInfo(String::from("NDLS")),
+                Diagnostic::FormatNumberMismatch {
+                    sample: 0,
+                    key: String::from(key::ROUNDED_GENOTYPE_LIKELIHOODS),
+                    expected: 6,
+                    actual: 3,
+                },
+                Diagnostic::UndefinedFormat(String::from(key::READ_DEPTH)),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_with_vcf_record() -> io::Result<()> {
+        let header = build_header();
+
+        let record = crate::Record::try_from(&b"sq1\t1\t.\tA\t.\t.\tPASS\tDP=x"[..])?;
+
+        let mut validator = Validator::new(&header);
+        let diagnostics = validator.validate(&record)?;
+
+        assert!(matches!(diagnostics[..], [Diagnostic::InvalidInfoField(_)]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_sort_order() -> io::Result<()> {
+        let header = build_header();
+
+        let build_record = |name: &str, start: usize| {
+            RecordBuf::builder()
+                .set_reference_sequence_name(name)
+                .set_variant_start(Position::new(start).unwrap())
+                .set_reference_bases("A")
+                .build()
+        };
+
+        let mut validator = Validator::new(&header);
+
+        assert!(validator.validate(&build_record("sq0", 5))?.is_empty());
+        assert!(validator.validate(&build_record("sq0", 5))?.is_empty());
+
+        assert_eq!(
+            validator.validate(&build_record("sq0", 3))?,
+            [Diagnostic::Unsorted {
+                name: String::from("sq0"),
+                start: Position::new(3).unwrap(),
+            }]
+        );
+
+        assert!(validator.validate(&build_record("sq1", 1))?.is_empty());
+
+        assert_eq!(
+            validator.validate(&build_record("sq0", 8))?,
+            [Diagnostic::Unsorted {
+                name: String::from("sq0"),
+                start: Position::new(8).unwrap(),
+            }]
+        );
+
+        Ok(())
+    }
+}
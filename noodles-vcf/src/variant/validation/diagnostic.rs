@@ -0,0 +1,128 @@
+use std::fmt;
+
+use noodles_core::Position;
+
+use crate::header::record::value::map::{format, info};
+
+/// A record validation diagnostic.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Diagnostic {
+    /// The reference sequence is not defined in the header.
+    UndefinedContig(String),
+    /// The variant ends after the end of the reference sequence.
+    ContigLengthExceeded {
+        /// The reference sequence name.
+        name: String,
+        /// The variant end position.
+        end: Position,
+        /// The reference sequence length.
+        length: usize,
+    },
+    /// The record is out of order relative to the previous record.
+    Unsorted {
+        /// The reference sequence name.
+        name: String,
+        /// The variant start position.
+        start: Position,
+    },
+    /// A filter is not defined in the header.
+    UndefinedFilter(String),
+    /// An info field is invalid and could not be read.
+    InvalidInfoField(String),
+    /// An info field key is not defined in the header.
+    UndefinedInfo(String),
+    /// An info field value does not have the type defined in the header.
+    InfoTypeMismatch {
+        /// The info field key.
+        key: String,
+        /// The type defined in the header.
+        expected: info::Type,
+    },
+    /// An info field value does not have the number of values defined in the header.
+    InfoNumberMismatch {
+        /// The info field key.
+        key: String,
+        /// The expected number of values.
+        expected: usize,
+        /// The actual number of values.
+        actual: usize,
+    },
+    /// A sample field value is invalid and could not be read.
+    InvalidSampleValue {
+        /// The sample index.
+        sample: usize,
+        /// The format key.
+        key: String,
+        /// The error message.
+        message: String,
+    },
+    /// A format key is not defined in the header.
+    UndefinedFormat(String),
+    /// A sample field value does not have the type defined in the header.
+    FormatTypeMismatch {
+        /// The sample index.
+        sample: usize,
+        /// The format key.
+        key: String,
+        /// The type defined in the header.
+        expected: format::Type,
+    },
+    /// A sample field value does not have the number of values defined in the header.
+    FormatNumberMismatch {
+        /// The sample index.
+        sample: usize,
+        /// The format key.
+        key: String,
+        /// The expected number of values.
+        expected: usize,
+        /// The actual number of values.
+        actual: usize,
+    },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UndefinedContig(name) => write!(f, "undefined contig: {name}"),
+            Self::ContigLengthExceeded { name, end, length } => write!(
+                f,
+                "variant end ({end}) exceeds length of contig {name} ({length})"
+            ),
+            Self::Unsorted { name, start } => write!(f, "unsorted record: {name}:{start}"),
+            Self::UndefinedFilter(id) => write!(f, "undefined filter: {id}"),
+            Self::InvalidInfoField(message) => write!(f, "invalid info field: {message}"),
+            Self::UndefinedInfo(key) => write!(f, "undefined info field: {key}"),
+            Self::InfoTypeMismatch { key, expected } => {
+                write!(f, "info field {key}: expected type {expected}")
+            }
+            Self::InfoNumberMismatch {
+                key,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "info field {key}: expected {expected} values, got {actual}"
+            ),
+            Self::InvalidSampleValue {
+                sample,
+                key,
+                message,
+            } => write!(f, "sample {sample} field {key}: invalid value: {message}"),
+            Self::UndefinedFormat(key) => write!(f, "undefined format: {key}"),
+            Self::FormatTypeMismatch {
+                sample,
+                key,
+                expected,
+            } => write!(f, "sample {sample} field {key}: expected type {expected}"),
+            Self::FormatNumberMismatch {
+                sample,
+                key,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "sample {sample} field {key}: expected {expected} values, got {actual}"
+            ),
+        }
+    }
+}